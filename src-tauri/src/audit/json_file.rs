@@ -0,0 +1,211 @@
+// ============================================================================
+// AIRADCR Desktop - Sink d'audit JSON Lines (fichiers rotatifs)
+// ============================================================================
+// Un événement JSON par ligne dans audit.jsonl. Lorsque le fichier dépasse la
+// taille maximale, il est renommé audit.1.jsonl (les anciens sont décalés) et
+// seuls max_files fichiers archivés sont conservés. Les fichiers ne sont
+// lisibles que par le compte du service (0600 sous Unix).
+// ============================================================================
+
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+
+use super::{AuditEvent, AuditSink};
+use crate::config::JsonFileSinkConfig;
+
+const FILE_STEM: &str = "audit";
+
+/// Sink fichier JSON Lines avec rotation par taille
+pub struct JsonFileSink {
+    directory: PathBuf,
+    max_file_size: u64,
+    max_files: u32,
+    file: Option<File>,
+    current_size: u64,
+}
+
+impl JsonFileSink {
+    /// Crée le sink et son répertoire
+    pub fn new(config: &JsonFileSinkConfig) -> Result<Self, String> {
+        let directory = if config.directory.is_empty() {
+            dirs::data_local_dir()
+                .unwrap_or_else(|| PathBuf::from("."))
+                .join("AIRADCR")
+                .join("logs")
+                .join("audit")
+        } else {
+            PathBuf::from(&config.directory)
+        };
+
+        fs::create_dir_all(&directory)
+            .map_err(|e| format!("Erreur création répertoire audit {:?}: {}", directory, e))?;
+
+        Ok(Self {
+            directory,
+            max_file_size: config.max_file_size_mb.max(1) * 1024 * 1024,
+            max_files: config.max_files,
+            file: None,
+            current_size: 0,
+        })
+    }
+
+    /// Chemin du fichier courant
+    pub fn current_path(&self) -> PathBuf {
+        self.directory.join(format!("{}.jsonl", FILE_STEM))
+    }
+
+    fn archive_path(&self, index: u32) -> PathBuf {
+        self.directory.join(format!("{}.{}.jsonl", FILE_STEM, index))
+    }
+
+    fn open(&mut self) -> Result<(), String> {
+        let path = self.current_path();
+        let mut options = OpenOptions::new();
+        options.create(true).append(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+            options.mode(0o600);
+            // Fichier créé par une version antérieure avec les droits par défaut
+            if let Ok(metadata) = fs::metadata(&path) {
+                if metadata.permissions().mode() & 0o077 != 0 {
+                    let _ = fs::set_permissions(&path, fs::Permissions::from_mode(0o600));
+                }
+            }
+        }
+        let file = options
+            .open(&path)
+            .map_err(|e| format!("Erreur ouverture {:?}: {}", path, e))?;
+        self.current_size = file.metadata().map(|m| m.len()).unwrap_or(0);
+        self.file = Some(file);
+        Ok(())
+    }
+
+    /// Décale les archives (audit.N → audit.N+1) puis archive le fichier courant
+    fn rotate(&mut self) -> Result<(), String> {
+        self.file = None;
+
+        if self.max_files == 0 {
+            fs::remove_file(self.current_path())
+                .map_err(|e| format!("Erreur suppression fichier audit: {}", e))?;
+        } else {
+            let _ = fs::remove_file(self.archive_path(self.max_files));
+            for index in (1..self.max_files).rev() {
+                let from = self.archive_path(index);
+                if from.exists() {
+                    let _ = fs::rename(&from, self.archive_path(index + 1));
+                }
+            }
+            fs::rename(self.current_path(), self.archive_path(1))
+                .map_err(|e| format!("Erreur rotation fichier audit: {}", e))?;
+        }
+
+        self.current_size = 0;
+        Ok(())
+    }
+}
+
+impl AuditSink for JsonFileSink {
+    fn name(&self) -> &'static str {
+        "json_file"
+    }
+
+    fn write(&mut self, event: &AuditEvent) -> Result<(), String> {
+        let mut line = serde_json::to_string(event)
+            .map_err(|e| format!("Erreur sérialisation événement: {}", e))?;
+        line.push('\n');
+
+        if self.file.is_none() {
+            self.open()?;
+        }
+
+        if self.current_size > 0 && self.current_size + line.len() as u64 > self.max_file_size {
+            self.rotate()?;
+            self.open()?;
+        }
+
+        let file = self.file.as_mut().ok_or_else(|| "Fichier audit non ouvert".to_string())?;
+        if let Err(e) = file.write_all(line.as_bytes()) {
+            // Forcer la réouverture au prochain événement
+            self.file = None;
+            return Err(format!("Erreur écriture fichier audit: {}", e));
+        }
+        self.current_size += line.len() as u64;
+
+        Ok(())
+    }
+}
+
+// ============================================================================
+// Tests unitaires
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit::{AuditEventKind, AuditSeverity};
+
+    fn event(request_id: &str) -> AuditEvent {
+        AuditEvent {
+            timestamp: "2025-12-15T10:00:00+00:00".to_string(),
+            kind: AuditEventKind::KeyManagement,
+            severity: AuditSeverity::Notice,
+            event_type: "sqlcipher_key_rotated".to_string(),
+            request_id: request_id.to_string(),
+            ip_address: "local".to_string(),
+            method: "-".to_string(),
+            endpoint: "-".to_string(),
+            status_code: None,
+            result: None,
+            api_key_prefix: None,
+            user_agent: None,
+            duration_ms: None,
+            message: None,
+            user_id: None,
+        }
+    }
+
+    #[test]
+    fn test_write_and_rotate() {
+        let dir = std::env::temp_dir().join(format!("airadcr-audit-{}", uuid::Uuid::new_v4()));
+        let mut sink = JsonFileSink::new(&JsonFileSinkConfig {
+            enabled: true,
+            directory: dir.display().to_string(),
+            max_file_size_mb: 1,
+            max_files: 2,
+        })
+        .unwrap();
+        // Seuil abaissé : une ligne par fichier
+        sink.max_file_size = 64;
+
+        for id in ["e1", "e2", "e3", "e4"] {
+            sink.write(&event(id)).unwrap();
+        }
+
+        let read = |path: PathBuf| -> Vec<serde_json::Value> {
+            fs::read_to_string(path)
+                .unwrap()
+                .lines()
+                .map(|line| serde_json::from_str(line).unwrap())
+                .collect()
+        };
+        let current = read(sink.current_path());
+        assert_eq!(current.len(), 1);
+        assert_eq!(current[0]["request_id"], "e4");
+        assert_eq!(current[0]["kind"], "key_management");
+        assert_eq!(read(sink.archive_path(1))[0]["request_id"], "e3");
+        assert_eq!(read(sink.archive_path(2))[0]["request_id"], "e2");
+        // max_files = 2 : la plus ancienne archive est supprimée
+        assert!(!sink.archive_path(3).exists());
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            assert_eq!(fs::metadata(sink.current_path()).unwrap().permissions().mode() & 0o777, 0o600);
+            assert_eq!(fs::metadata(sink.archive_path(1)).unwrap().permissions().mode() & 0o777, 0o600);
+        }
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,243 @@
+// ============================================================================
+// AIRADCR Desktop - Sinks d'audit (SIEM)
+// ============================================================================
+// Transmet chaque entrée du journal d'accès API et les événements de sécurité
+// vers des destinations externes (syslog RFC 5424, fichiers JSON Lines).
+// Les événements passent par une file bornée consommée par un thread dédié :
+// un sink lent ou injoignable ne bloque jamais le traitement des requêtes.
+// ============================================================================
+
+pub mod syslog;
+pub mod json_file;
+
+use serde::Serialize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, SyncSender, TrySendError};
+use std::sync::OnceLock;
+use std::thread;
+use log::{info, warn, error};
+
+use crate::config::AuditConfig;
+
+/// Émetteur global vers le thread d'audit (initialisé une seule fois)
+static AUDIT_SENDER: OnceLock<SyncSender<AuditEvent>> = OnceLock::new();
+
+/// Nombre d'événements perdus (file pleine ou thread arrêté)
+static DROPPED_EVENTS: AtomicU64 = AtomicU64::new(0);
+
+/// Type d'événement d'audit
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditEventKind {
+    /// Entrée du journal d'accès API (une par requête HTTP)
+    Access,
+    /// Événement de sécurité (clé admin invalide, etc.)
+    Security,
+    /// Purge de rétention (règle appliquée, éléments supprimés)
+    Retention,
+    /// Droits des personnes RGPD (export, certificat d'effacement)
+    Gdpr,
+    /// Gestion des clés de chiffrement (rotation de la clé SQLCipher)
+    KeyManagement,
+}
+
+impl AuditEventKind {
+    /// Nom de l'événement, identique à la sérialisation JSON (tous les sinks)
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AuditEventKind::Access => "access",
+            AuditEventKind::Security => "security",
+            AuditEventKind::Retention => "retention",
+            AuditEventKind::Gdpr => "gdpr",
+            AuditEventKind::KeyManagement => "key_management",
+        }
+    }
+}
+
+/// Sévérité syslog (RFC 5424 §6.2.1)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditSeverity {
+    Error,
+    Warning,
+    Notice,
+    Informational,
+}
+
+impl AuditSeverity {
+    /// Code numérique RFC 5424
+    pub fn code(self) -> u8 {
+        match self {
+            AuditSeverity::Error => 3,
+            AuditSeverity::Warning => 4,
+            AuditSeverity::Notice => 5,
+            AuditSeverity::Informational => 6,
+        }
+    }
+
+    /// Sévérité déduite du résultat d'une requête (valeurs de access_logs.result)
+    pub fn from_result(result: &str) -> Self {
+        match result {
+            "success" => AuditSeverity::Informational,
+            "unauthorized" => AuditSeverity::Warning,
+            "error" => AuditSeverity::Error,
+            _ => AuditSeverity::Notice,
+        }
+    }
+}
+
+/// Événement d'audit transmis aux sinks
+#[derive(Debug, Clone, Serialize)]
+pub struct AuditEvent {
+    pub timestamp: String,
+    pub kind: AuditEventKind,
+    pub severity: AuditSeverity,
+    /// Identifiant court de l'événement (ex: "http_access", "invalid_admin_key")
+    pub event_type: String,
+    pub request_id: String,
+    pub ip_address: String,
+    pub method: String,
+    pub endpoint: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status_code: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_key_prefix: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_agent: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// Radiologue actif sur le poste (users.id)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
+}
+
+impl AuditEvent {
+    /// Résumé lisible utilisé comme MSG syslog
+    pub fn summary(&self) -> String {
+        match self.kind {
+            AuditEventKind::Access => format!(
+                "{} {} from {} -> {} {}",
+                self.method,
+                self.endpoint,
+                self.ip_address,
+                self.status_code.map(|c| c.to_string()).unwrap_or_else(|| "-".to_string()),
+                self.result.as_deref().unwrap_or("-"),
+            ),
+            AuditEventKind::Security => format!(
+                "{} on {} {} from {}{}",
+                self.event_type,
+                self.method,
+                self.endpoint,
+                self.ip_address,
+                self.message.as_ref().map(|m| format!(": {}", m)).unwrap_or_default(),
+            ),
+            AuditEventKind::Retention | AuditEventKind::Gdpr | AuditEventKind::KeyManagement => format!(
+                "{} {}{}",
+                self.event_type,
+                self.endpoint,
+                self.message.as_ref().map(|m| format!(": {}", m)).unwrap_or_default(),
+            ),
+        }
+    }
+}
+
+/// Destination d'audit (syslog, fichier JSON Lines, ...)
+pub trait AuditSink: Send {
+    /// Nom du sink pour les logs de diagnostic
+    fn name(&self) -> &'static str;
+
+    /// Écrit un événement (appelé uniquement depuis le thread d'audit)
+    fn write(&mut self, event: &AuditEvent) -> Result<(), String>;
+}
+
+/// Construit les sinks activés dans la configuration
+fn build_sinks(config: &AuditConfig) -> Vec<Box<dyn AuditSink>> {
+    let mut sinks: Vec<Box<dyn AuditSink>> = Vec::new();
+
+    if config.syslog.enabled {
+        match syslog::SyslogSink::new(&config.syslog) {
+            Ok(sink) => {
+                info!("[Audit] Sink syslog activé ({} → {})", config.syslog.transport, sink.destination());
+                sinks.push(Box::new(sink));
+            }
+            Err(e) => error!("[Audit] Impossible d'initialiser le sink syslog: {}", e),
+        }
+    }
+
+    if config.json_file.enabled {
+        match json_file::JsonFileSink::new(&config.json_file) {
+            Ok(sink) => {
+                info!("[Audit] Sink JSON Lines activé ({:?})", sink.current_path());
+                sinks.push(Box::new(sink));
+            }
+            Err(e) => error!("[Audit] Impossible d'initialiser le sink JSON Lines: {}", e),
+        }
+    }
+
+    sinks
+}
+
+/// Démarre le thread d'audit si au moins un sink est activé
+pub fn init(config: &AuditConfig) {
+    if AUDIT_SENDER.get().is_some() {
+        return;
+    }
+
+    let mut sinks = build_sinks(config);
+    if sinks.is_empty() {
+        info!("[Audit] Aucun sink externe configuré (audit SQLite uniquement)");
+        return;
+    }
+
+    let capacity = config.queue_capacity.max(1);
+    let (tx, rx) = mpsc::sync_channel::<AuditEvent>(capacity);
+
+    if AUDIT_SENDER.set(tx).is_err() {
+        return;
+    }
+
+    thread::spawn(move || {
+        info!("[Audit] Thread d'audit démarré ({} sink(s), file de {} événements)", sinks.len(), capacity);
+
+        for event in rx {
+            for sink in sinks.iter_mut() {
+                if let Err(e) = sink.write(&event) {
+                    warn!("[Audit] Échec écriture sink {}: {}", sink.name(), e);
+                }
+            }
+        }
+
+        info!("[Audit] Thread d'audit terminé");
+    });
+}
+
+/// Publie un événement sans jamais bloquer l'appelant
+/// Si la file est pleine, l'événement est compté comme perdu.
+pub fn emit(event: AuditEvent) {
+    let Some(sender) = AUDIT_SENDER.get() else {
+        return;
+    };
+
+    match sender.try_send(event) {
+        Ok(()) => {}
+        Err(TrySendError::Full(_)) => {
+            let dropped = DROPPED_EVENTS.fetch_add(1, Ordering::Relaxed) + 1;
+            // Éviter d'inonder les logs : un avertissement tous les 100 événements perdus
+            if dropped % 100 == 1 {
+                warn!("[Audit] File d'audit pleine, {} événement(s) perdu(s) au total", dropped);
+            }
+        }
+        Err(TrySendError::Disconnected(_)) => {
+            DROPPED_EVENTS.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Nombre total d'événements d'audit perdus depuis le démarrage
+pub fn dropped_events() -> u64 {
+    DROPPED_EVENTS.load(Ordering::Relaxed)
+}
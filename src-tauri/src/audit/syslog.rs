@@ -0,0 +1,336 @@
+// ============================================================================
+// AIRADCR Desktop - Sink d'audit Syslog (RFC 5424)
+// ============================================================================
+// Transports supportés :
+// - udp  : un datagramme par message (RFC 5426)
+// - tcp  : framing par comptage d'octets "LEN MSG" (RFC 6587 §3.4.1)
+// - unix : socket datagramme local (/dev/log), Unix uniquement
+// ============================================================================
+
+use std::io::Write;
+use std::net::{TcpStream, ToSocketAddrs, UdpSocket};
+use std::time::Duration;
+use chrono::SecondsFormat;
+
+use super::{AuditEvent, AuditSink};
+use crate::config::SyslogSinkConfig;
+
+/// Timeout réseau pour le transport TCP
+const TCP_TIMEOUT: Duration = Duration::from_secs(5);
+
+enum Transport {
+    Udp {
+        socket: UdpSocket,
+        address: String,
+    },
+    Tcp {
+        address: String,
+        stream: Option<TcpStream>,
+    },
+    #[cfg(unix)]
+    Unix {
+        socket: std::os::unix::net::UnixDatagram,
+        path: String,
+    },
+}
+
+/// Sink syslog RFC 5424
+pub struct SyslogSink {
+    transport: Transport,
+    facility: u8,
+    hostname: String,
+    app_name: String,
+    procid: String,
+    sd_id: String,
+}
+
+impl SyslogSink {
+    /// Crée le sink à partir de la configuration
+    pub fn new(config: &SyslogSinkConfig) -> Result<Self, String> {
+        if config.facility > 23 {
+            return Err(format!("Facility syslog invalide: {} (0-23)", config.facility));
+        }
+
+        let transport = match config.transport.to_lowercase().as_str() {
+            "udp" => {
+                let socket = UdpSocket::bind("0.0.0.0:0")
+                    .map_err(|e| format!("Erreur création socket UDP: {}", e))?;
+                Transport::Udp {
+                    socket,
+                    address: config.address.clone(),
+                }
+            }
+            "tcp" => Transport::Tcp {
+                address: config.address.clone(),
+                stream: None,
+            },
+            #[cfg(unix)]
+            "unix" => {
+                let socket = std::os::unix::net::UnixDatagram::unbound()
+                    .map_err(|e| format!("Erreur création socket Unix: {}", e))?;
+                Transport::Unix {
+                    socket,
+                    path: config.socket_path.clone(),
+                }
+            }
+            other => return Err(format!("Transport syslog non supporté: {}", other)),
+        };
+
+        Ok(Self {
+            transport,
+            facility: config.facility,
+            hostname: sanitize_header_field(&local_hostname(), 255),
+            app_name: sanitize_header_field(&config.app_name, 48),
+            procid: std::process::id().to_string(),
+            sd_id: sanitize_header_field(&config.sd_id, 32),
+        })
+    }
+
+    /// Destination lisible (pour les logs de démarrage)
+    pub fn destination(&self) -> &str {
+        match &self.transport {
+            Transport::Udp { address, .. } => address,
+            Transport::Tcp { address, .. } => address,
+            #[cfg(unix)]
+            Transport::Unix { path, .. } => path,
+        }
+    }
+
+    /// Formate un événement en message RFC 5424
+    pub fn format_message(&self, event: &AuditEvent) -> String {
+        let pri = self.facility as u16 * 8 + event.severity.code() as u16;
+
+        // TIME-SECFRAC limité à 6 chiffres par la RFC
+        let timestamp = chrono::DateTime::parse_from_rfc3339(&event.timestamp)
+            .map(|dt| dt.to_rfc3339_opts(SecondsFormat::Micros, true))
+            .unwrap_or_else(|_| "-".to_string());
+
+        let msgid = sanitize_header_field(&event.event_type, 32);
+
+        let mut params: Vec<(&str, String)> = vec![
+            ("kind", event.kind.as_str().to_string()),
+            ("request_id", event.request_id.clone()),
+            ("ip", event.ip_address.clone()),
+            ("method", event.method.clone()),
+            ("endpoint", event.endpoint.clone()),
+        ];
+        if let Some(code) = event.status_code {
+            params.push(("status", code.to_string()));
+        }
+        if let Some(result) = &event.result {
+            params.push(("result", result.clone()));
+        }
+        if let Some(prefix) = &event.api_key_prefix {
+            params.push(("api_key_prefix", prefix.clone()));
+        }
+        if let Some(duration) = event.duration_ms {
+            params.push(("duration_ms", duration.to_string()));
+        }
+        if let Some(user_id) = &event.user_id {
+            params.push(("user_id", user_id.clone()));
+        }
+
+        let structured_data = format!(
+            "[{}{}]",
+            self.sd_id,
+            params.iter()
+                .map(|(name, value)| format!(" {}=\"{}\"", name, escape_param_value(value)))
+                .collect::<String>()
+        );
+
+        format!(
+            "<{}>1 {} {} {} {} {} {} {}",
+            pri,
+            timestamp,
+            self.hostname,
+            self.app_name,
+            self.procid,
+            msgid,
+            structured_data,
+            event.summary(),
+        )
+    }
+
+    fn send(&mut self, message: &str) -> Result<(), String> {
+        match &mut self.transport {
+            Transport::Udp { socket, address } => {
+                let target = address
+                    .to_socket_addrs()
+                    .map_err(|e| format!("Adresse syslog invalide '{}': {}", address, e))?
+                    .next()
+                    .ok_or_else(|| format!("Adresse syslog non résolue: {}", address))?;
+                socket
+                    .send_to(message.as_bytes(), target)
+                    .map_err(|e| format!("Erreur envoi UDP: {}", e))?;
+                Ok(())
+            }
+            Transport::Tcp { address, stream } => {
+                let frame = format!("{} {}", message.len(), message);
+
+                // Une tentative sur la connexion existante, puis une reconnexion
+                for _ in 0..2 {
+                    if stream.is_none() {
+                        *stream = Some(connect_tcp(address)?);
+                    }
+                    if let Some(s) = stream.as_mut() {
+                        match s.write_all(frame.as_bytes()) {
+                            Ok(()) => return Ok(()),
+                            Err(_) => *stream = None,
+                        }
+                    }
+                }
+
+                Err(format!("Erreur envoi TCP vers {}", address))
+            }
+            #[cfg(unix)]
+            Transport::Unix { socket, path } => {
+                socket
+                    .send_to(message.as_bytes(), path.as_str())
+                    .map_err(|e| format!("Erreur envoi socket Unix {}: {}", path, e))?;
+                Ok(())
+            }
+        }
+    }
+}
+
+impl AuditSink for SyslogSink {
+    fn name(&self) -> &'static str {
+        "syslog"
+    }
+
+    fn write(&mut self, event: &AuditEvent) -> Result<(), String> {
+        let message = self.format_message(event);
+        self.send(&message)
+    }
+}
+
+fn connect_tcp(address: &str) -> Result<TcpStream, String> {
+    let target = address
+        .to_socket_addrs()
+        .map_err(|e| format!("Adresse syslog invalide '{}': {}", address, e))?
+        .next()
+        .ok_or_else(|| format!("Adresse syslog non résolue: {}", address))?;
+
+    let stream = TcpStream::connect_timeout(&target, TCP_TIMEOUT)
+        .map_err(|e| format!("Connexion TCP {} impossible: {}", address, e))?;
+    stream.set_write_timeout(Some(TCP_TIMEOUT)).ok();
+    Ok(stream)
+}
+
+/// Nom d'hôte local (sans dépendance système supplémentaire)
+fn local_hostname() -> String {
+    std::env::var("COMPUTERNAME")
+        .or_else(|_| std::env::var("HOSTNAME"))
+        .ok()
+        .or_else(|| {
+            std::fs::read_to_string("/etc/hostname")
+                .ok()
+                .map(|h| h.trim().to_string())
+        })
+        .filter(|h| !h.is_empty())
+        .unwrap_or_else(|| "-".to_string())
+}
+
+/// Champs d'en-tête : ASCII imprimable sans espace, longueur bornée, "-" si vide
+fn sanitize_header_field(value: &str, max_len: usize) -> String {
+    let cleaned: String = value
+        .chars()
+        .filter(|c| c.is_ascii_graphic())
+        .take(max_len)
+        .collect();
+    if cleaned.is_empty() {
+        "-".to_string()
+    } else {
+        cleaned
+    }
+}
+
+/// Échappe '"', '\' et ']' dans une valeur de paramètre structuré (RFC 5424 §6.3.3)
+fn escape_param_value(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '"' | '\\' | ']') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+// ============================================================================
+// Tests unitaires
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit::{AuditEventKind, AuditSeverity};
+
+    fn test_sink() -> SyslogSink {
+        let config = SyslogSinkConfig {
+            enabled: true,
+            transport: "udp".to_string(),
+            address: "127.0.0.1:514".to_string(),
+            socket_path: String::new(),
+            facility: 13,
+            app_name: "airadcr desktop".to_string(),
+            sd_id: "airadcr@32473".to_string(),
+        };
+        SyslogSink::new(&config).unwrap()
+    }
+
+    fn test_event() -> AuditEvent {
+        AuditEvent {
+            timestamp: "2025-12-15T10:00:00.123456789+00:00".to_string(),
+            kind: AuditEventKind::Access,
+            severity: AuditSeverity::Warning,
+            event_type: "http_access".to_string(),
+            request_id: "ab12cd34".to_string(),
+            ip_address: "10.0.0.5".to_string(),
+            method: "GET".to_string(),
+            endpoint: "/api-keys".to_string(),
+            status_code: Some(401),
+            result: Some("unauthorized".to_string()),
+            api_key_prefix: None,
+            user_agent: None,
+            duration_ms: Some(3),
+            message: Some("Invalid \"admin\" key]".to_string()),
+            user_id: None,
+        }
+    }
+
+    #[test]
+    fn test_rfc5424_header() {
+        let message = test_sink().format_message(&test_event());
+
+        // PRI = facility 13 * 8 + severity 4 = 108, TIME-SECFRAC tronqué à 6 chiffres
+        assert!(message.starts_with("<108>1 2025-12-15T10:00:00.123456Z "));
+        // Les espaces sont interdits dans APP-NAME
+        assert!(message.contains(" airadcrdesktop "));
+        assert!(message.contains(" http_access [airadcr@32473 kind=\"access\" request_id=\"ab12cd34\""));
+        assert!(message.contains(" status=\"401\" result=\"unauthorized\""));
+    }
+
+    #[test]
+    fn test_kind_matches_json_name() {
+        let mut event = test_event();
+        for kind in [
+            AuditEventKind::Access,
+            AuditEventKind::Security,
+            AuditEventKind::Retention,
+            AuditEventKind::Gdpr,
+            AuditEventKind::KeyManagement,
+        ] {
+            event.kind = kind;
+            let json = serde_json::to_value(&event).unwrap();
+            let expected = format!("kind=\"{}\"", json["kind"].as_str().unwrap());
+            assert!(test_sink().format_message(&event).contains(&expected), "{}", expected);
+        }
+        assert_eq!(AuditEventKind::KeyManagement.as_str(), "key_management");
+    }
+
+    #[test]
+    fn test_escape_param_value() {
+        assert_eq!(escape_param_value(r#"a"b\c]d"#), r#"a\"b\\c\]d"#);
+    }
+}
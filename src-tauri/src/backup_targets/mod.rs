@@ -0,0 +1,287 @@
+// ============================================================================
+// AIRADCR Desktop - Destinations distantes des backups
+// ============================================================================
+// Les backups locaux sont sur le même disque que la base. Après chaque backup,
+// les fichiers produits (archive exportée, ou backup + manifeste) sont copiés
+// vers les destinations activées : partage réseau monté, serveur SFTP,
+// stockage objet compatible S3. Chaque fichier déposé est relu et son
+// empreinte SHA-256 comparée à l'original. Chaque destination applique sa
+// propre rétention, d'après l'horodatage contenu dans le nom du fichier
+// (indépendant des horloges distantes). L'état des envois est exposé dans
+// /health/extended et /metrics.
+// ============================================================================
+
+pub mod network_share;
+pub mod sftp;
+pub mod s3;
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+use std::time::Instant;
+use log::{info, warn, error};
+
+use crate::config::BackupTargetsConfig;
+use crate::database::backup::sha256_file;
+
+/// Préfixes des fichiers gérés sur les destinations (les autres ne sont jamais touchés)
+const MANAGED_PREFIXES: [&str; 2] = ["airadcr_backup_", "airadcr_export_"];
+
+/// Destination distante de backups (partage réseau, SFTP, S3, ...)
+pub trait BackupTarget: Send {
+    /// Nom de la destination (logs, métriques)
+    fn name(&self) -> &'static str;
+
+    /// Emplacement lisible (sans secret)
+    fn destination(&self) -> String;
+
+    /// Rétention sur la destination en jours (0 = aucune purge)
+    fn retention_days(&self) -> u32;
+
+    /// Dépose un fichier local sous le nom donné
+    fn upload(&mut self, local: &Path, name: &str, sha256: &str) -> Result<(), String>;
+
+    /// Relit un fichier déposé : (taille, SHA-256 hex)
+    fn checksum(&mut self, name: &str) -> Result<(u64, String), String>;
+
+    /// Noms des fichiers présents sur la destination
+    fn list(&mut self) -> Result<Vec<String>, String>;
+
+    /// Supprime un fichier de la destination
+    fn delete(&mut self, name: &str) -> Result<(), String>;
+}
+
+/// État des envois vers une destination
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct TargetStatus {
+    pub name: String,
+    pub destination: String,
+    pub last_attempt_at: Option<String>,
+    pub last_success_at: Option<String>,
+    pub last_error: Option<String>,
+    /// Fichiers du dernier envoi réussi
+    pub last_files: Vec<String>,
+    pub last_duration_ms: u64,
+    pub uploads_ok: u64,
+    pub uploads_failed: u64,
+    /// Fichiers supprimés par la rétention de la destination
+    pub pruned_total: u64,
+}
+
+static STATUS: Lazy<RwLock<BTreeMap<&'static str, TargetStatus>>> = Lazy::new(|| RwLock::new(BTreeMap::new()));
+
+/// État de chaque destination configurée depuis le démarrage
+pub fn statuses() -> Vec<TargetStatus> {
+    STATUS
+        .read()
+        .map(|status| status.values().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// Construit les destinations activées dans la configuration
+pub fn build_targets(config: &BackupTargetsConfig) -> Vec<Box<dyn BackupTarget>> {
+    let mut targets: Vec<Box<dyn BackupTarget>> = Vec::new();
+
+    if config.network_share.enabled {
+        match network_share::NetworkShareTarget::new(&config.network_share) {
+            Ok(target) => targets.push(Box::new(target)),
+            Err(e) => record_failure("network_share", &config.network_share.path, e),
+        }
+    }
+
+    if config.sftp.enabled {
+        match sftp::SftpTarget::new(&config.sftp) {
+            Ok(target) => targets.push(Box::new(target)),
+            Err(e) => record_failure("sftp", &config.sftp.host, e),
+        }
+    }
+
+    if config.s3.enabled {
+        match s3::S3Target::new(&config.s3) {
+            Ok(target) => targets.push(Box::new(target)),
+            Err(e) => record_failure("s3", &config.s3.endpoint, e),
+        }
+    }
+
+    targets
+}
+
+/// Copie les fichiers d'un backup vers chaque destination activée.
+/// Retourne le nombre de destinations en échec.
+pub fn replicate(config: &BackupTargetsConfig, files: &[PathBuf]) -> usize {
+    let mut failures = 0;
+
+    for mut target in build_targets(config) {
+        let started = Instant::now();
+        let result = replicate_to(target.as_mut(), files);
+        let duration_ms = started.elapsed().as_millis() as u64;
+
+        match result {
+            Ok(pruned) => {
+                info!(
+                    "[Backup] {} fichier(s) déposé(s) et vérifié(s) sur {} ({}) en {}ms",
+                    files.len(), target.name(), target.destination(), duration_ms
+                );
+                update_status(target.as_ref(), |status| {
+                    status.last_success_at = status.last_attempt_at.clone();
+                    status.last_error = None;
+                    status.last_files = files.iter().filter_map(|f| file_name(f)).collect();
+                    status.last_duration_ms = duration_ms;
+                    status.uploads_ok += 1;
+                    status.pruned_total += pruned as u64;
+                });
+            }
+            Err(e) => {
+                error!("[Backup] Échec envoi vers {} ({}): {}", target.name(), target.destination(), e);
+                failures += 1;
+                update_status(target.as_ref(), |status| {
+                    status.last_error = Some(e.clone());
+                    status.last_duration_ms = duration_ms;
+                    status.uploads_failed += 1;
+                });
+            }
+        }
+    }
+
+    failures
+}
+
+/// Dépose, vérifie puis applique la rétention. Retourne le nombre de fichiers purgés.
+fn replicate_to(target: &mut dyn BackupTarget, files: &[PathBuf]) -> Result<usize, String> {
+    update_status(target, |status| status.last_attempt_at = Some(Utc::now().to_rfc3339()));
+
+    let mut uploaded = Vec::new();
+    for file in files {
+        let name = file_name(file).ok_or_else(|| format!("Nom de fichier invalide: {:?}", file))?;
+        let size = std::fs::metadata(file)
+            .map_err(|e| format!("Lecture {:?}: {}", file, e))?
+            .len();
+        let sha256 = sha256_file(file)?;
+
+        target.upload(file, &name, &sha256)?;
+
+        let (remote_size, remote_sha256) = target.checksum(&name)?;
+        if remote_size != size || remote_sha256 != sha256 {
+            return Err(format!(
+                "Vérification de {} échouée ({} octets, {} ≠ {} octets, {})",
+                name, remote_size, remote_sha256, size, sha256
+            ));
+        }
+        uploaded.push(name);
+    }
+
+    apply_retention(target, &uploaded)
+}
+
+/// Supprime de la destination les fichiers gérés plus anciens que sa rétention
+fn apply_retention(target: &mut dyn BackupTarget, keep: &[String]) -> Result<usize, String> {
+    if target.retention_days() == 0 {
+        return Ok(0);
+    }
+
+    let cutoff = Utc::now() - chrono::Duration::days(target.retention_days() as i64);
+    let expired: Vec<String> = target
+        .list()?
+        .into_iter()
+        .filter(|name| !keep.contains(name))
+        .filter(|name| timestamp_of(name).map(|ts| ts < cutoff).unwrap_or(false))
+        .collect();
+
+    let mut pruned = 0;
+    for name in expired {
+        match target.delete(&name) {
+            Ok(()) => pruned += 1,
+            Err(e) => warn!("[Backup] Rétention {}: suppression de {} impossible: {}", target.name(), name, e),
+        }
+    }
+
+    if pruned > 0 {
+        info!("[Backup] {} ancien(s) fichier(s) supprimé(s) de {}", pruned, target.name());
+    }
+    Ok(pruned)
+}
+
+/// Horodatage d'un fichier géré (`airadcr_backup_YYYYMMDD_HHMMSS...`)
+fn timestamp_of(name: &str) -> Option<DateTime<Utc>> {
+    let rest = MANAGED_PREFIXES.iter().find_map(|prefix| name.strip_prefix(prefix))?;
+    let stamp = rest.get(..15)?;
+    NaiveDateTime::parse_from_str(stamp, "%Y%m%d_%H%M%S")
+        .ok()
+        .map(|ts| ts.and_utc())
+}
+
+fn file_name(path: &Path) -> Option<String> {
+    path.file_name().and_then(|n| n.to_str()).map(|n| n.to_string())
+}
+
+fn update_status<F: FnOnce(&mut TargetStatus)>(target: &dyn BackupTarget, update: F) {
+    if let Ok(mut status) = STATUS.write() {
+        let entry = status.entry(target.name()).or_insert_with(|| TargetStatus {
+            name: target.name().to_string(),
+            ..Default::default()
+        });
+        entry.destination = target.destination();
+        update(entry);
+    }
+}
+
+/// Destination mal configurée : visible dans l'état sans tentative d'envoi
+fn record_failure(name: &'static str, destination: &str, error: String) {
+    error!("[Backup] Destination {} inutilisable: {}", name, error);
+    if let Ok(mut status) = STATUS.write() {
+        let entry = status.entry(name).or_insert_with(|| TargetStatus {
+            name: name.to_string(),
+            ..Default::default()
+        });
+        entry.destination = destination.to_string();
+        entry.last_attempt_at = Some(Utc::now().to_rfc3339());
+        entry.last_error = Some(error);
+        entry.uploads_failed += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::NetworkShareTargetConfig;
+
+    #[test]
+    fn test_network_share_upload_verify_and_retention() {
+        let root = std::env::temp_dir().join(format!("airadcr-targets-{}", uuid::Uuid::new_v4()));
+        let local = root.join("local");
+        std::fs::create_dir_all(&local).unwrap();
+
+        let share = root.join("share");
+        std::fs::create_dir_all(&share).unwrap();
+        std::fs::write(share.join("airadcr_export_20200101_000000.tar.zst.age"), b"old").unwrap();
+        std::fs::write(share.join("notes.txt"), b"not managed").unwrap();
+
+        let backup = local.join(format!("airadcr_backup_{}.db", Utc::now().format("%Y%m%d_%H%M%S")));
+        std::fs::write(&backup, b"backup content").unwrap();
+
+        let mut target = network_share::NetworkShareTarget::new(&NetworkShareTargetConfig {
+            enabled: true,
+            path: share.to_string_lossy().to_string(),
+            retention_days: 30,
+        })
+        .unwrap();
+
+        let pruned = replicate_to(&mut target, std::slice::from_ref(&backup)).unwrap();
+        assert_eq!(pruned, 1);
+
+        let mut remaining = target.list().unwrap();
+        remaining.sort();
+        assert_eq!(remaining, vec![file_name(&backup).unwrap(), "notes.txt".to_string()]);
+        assert_eq!(
+            std::fs::read(share.join(file_name(&backup).unwrap())).unwrap(),
+            b"backup content"
+        );
+
+        assert!(timestamp_of("airadcr_backup_premigration_v7_to_v8_20200101_000000.db").is_none());
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
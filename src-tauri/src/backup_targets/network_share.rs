@@ -0,0 +1,85 @@
+// ============================================================================
+// AIRADCR Desktop - Destination de backup : partage réseau monté
+// ============================================================================
+// Copie vers un répertoire accessible par le système de fichiers (chemin UNC,
+// lecteur réseau, montage SMB/NFS). Le fichier est écrit sous un nom
+// temporaire puis renommé : un backup partiel n'apparaît jamais sous son nom
+// définitif.
+// ============================================================================
+
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::BackupTarget;
+use crate::config::NetworkShareTargetConfig;
+
+/// Destination partage réseau
+pub struct NetworkShareTarget {
+    directory: PathBuf,
+    retention_days: u32,
+}
+
+impl NetworkShareTarget {
+    pub fn new(config: &NetworkShareTargetConfig) -> Result<Self, String> {
+        if config.path.trim().is_empty() {
+            return Err("Chemin du partage réseau non configuré".to_string());
+        }
+
+        Ok(Self {
+            directory: PathBuf::from(config.path.trim()),
+            retention_days: config.retention_days,
+        })
+    }
+}
+
+impl BackupTarget for NetworkShareTarget {
+    fn name(&self) -> &'static str {
+        "network_share"
+    }
+
+    fn destination(&self) -> String {
+        self.directory.display().to_string()
+    }
+
+    fn retention_days(&self) -> u32 {
+        self.retention_days
+    }
+
+    fn upload(&mut self, local: &Path, name: &str, _sha256: &str) -> Result<(), String> {
+        // Partage non monté : erreur explicite plutôt qu'un répertoire local créé par erreur
+        if !self.directory.is_dir() {
+            return Err(format!("Partage inaccessible: {:?}", self.directory));
+        }
+
+        let partial = self.directory.join(format!("{}.partial", name));
+        fs::copy(local, &partial).map_err(|e| format!("Copie vers {:?}: {}", partial, e))?;
+        fs::rename(&partial, self.directory.join(name)).map_err(|e| {
+            let _ = fs::remove_file(&partial);
+            format!("Publication de {}: {}", name, e)
+        })
+    }
+
+    fn checksum(&mut self, name: &str) -> Result<(u64, String), String> {
+        let path = self.directory.join(name);
+        let mut file = fs::File::open(&path).map_err(|e| format!("Relecture {:?}: {}", path, e))?;
+        let mut hasher = Sha256::new();
+        let size = std::io::copy(&mut file, &mut hasher).map_err(|e| format!("Relecture {:?}: {}", path, e))?;
+        Ok((size, hex::encode(hasher.finalize())))
+    }
+
+    fn list(&mut self) -> Result<Vec<String>, String> {
+        let entries = fs::read_dir(&self.directory)
+            .map_err(|e| format!("Lecture de {:?}: {}", self.directory, e))?;
+
+        Ok(entries
+            .flatten()
+            .filter(|entry| entry.path().is_file())
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .collect())
+    }
+
+    fn delete(&mut self, name: &str) -> Result<(), String> {
+        fs::remove_file(self.directory.join(name)).map_err(|e| e.to_string())
+    }
+}
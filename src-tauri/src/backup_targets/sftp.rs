@@ -0,0 +1,151 @@
+// ============================================================================
+// AIRADCR Desktop - Destination de backup : serveur SFTP
+// ============================================================================
+// Authentification par clé privée uniquement. La clé d'hôte du serveur est
+// épinglée par son empreinte SHA-256 (`ssh-keygen -l -f`) : sans empreinte
+// configurée, ou si elle ne correspond pas, aucune donnée n'est envoyée.
+// ============================================================================
+
+use base64::Engine;
+use sha2::{Digest, Sha256};
+use ssh2::{HashType, RenameFlags, Session, Sftp};
+use std::io::Write;
+use std::net::{TcpStream, ToSocketAddrs};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use super::BackupTarget;
+use crate::config::SftpTargetConfig;
+
+/// Délai de connexion TCP et des opérations SSH
+const SFTP_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Destination SFTP (connexion ouverte au premier envoi)
+pub struct SftpTarget {
+    config: SftpTargetConfig,
+    connection: Option<(Session, Sftp)>,
+}
+
+impl SftpTarget {
+    pub fn new(config: &SftpTargetConfig) -> Result<Self, String> {
+        if config.host.trim().is_empty() || config.username.trim().is_empty() {
+            return Err("Hôte ou utilisateur SFTP non configuré".to_string());
+        }
+        if config.private_key_path.trim().is_empty() {
+            return Err("Clé privée SFTP non configurée (private_key_path)".to_string());
+        }
+        if normalize_fingerprint(&config.host_key_sha256).is_empty() {
+            return Err("Empreinte de la clé d'hôte SFTP non configurée (host_key_sha256)".to_string());
+        }
+
+        Ok(Self {
+            config: config.clone(),
+            connection: None,
+        })
+    }
+
+    /// Chemin distant (séparateur "/" quel que soit l'OS du poste)
+    fn remote_path(&self, name: &str) -> PathBuf {
+        PathBuf::from(format!("{}/{}", self.config.remote_dir.trim_end_matches('/'), name))
+    }
+
+    /// Ouvre (ou réutilise) la session SFTP après vérification de la clé d'hôte
+    fn sftp(&mut self) -> Result<&Sftp, String> {
+        if self.connection.is_none() {
+            let address = (self.config.host.as_str(), self.config.port)
+                .to_socket_addrs()
+                .map_err(|e| format!("Résolution de {}: {}", self.config.host, e))?
+                .next()
+                .ok_or_else(|| format!("Adresse introuvable pour {}", self.config.host))?;
+            let tcp = TcpStream::connect_timeout(&address, SFTP_TIMEOUT)
+                .map_err(|e| format!("Connexion à {}: {}", address, e))?;
+
+            let mut session = Session::new().map_err(|e| format!("Session SSH: {}", e))?;
+            session.set_timeout(SFTP_TIMEOUT.as_millis() as u32);
+            session.set_tcp_stream(tcp);
+            session.handshake().map_err(|e| format!("Négociation SSH: {}", e))?;
+
+            let fingerprint = session
+                .host_key_hash(HashType::Sha256)
+                .map(|hash| base64::engine::general_purpose::STANDARD_NO_PAD.encode(hash))
+                .ok_or_else(|| "Clé d'hôte SFTP indisponible".to_string())?;
+            if fingerprint != normalize_fingerprint(&self.config.host_key_sha256) {
+                return Err(format!(
+                    "Clé d'hôte SFTP inattendue (SHA256:{}) : envoi refusé",
+                    fingerprint
+                ));
+            }
+
+            session
+                .userauth_pubkey_file(&self.config.username, None, Path::new(&self.config.private_key_path), None)
+                .map_err(|e| format!("Authentification SFTP: {}", e))?;
+            let sftp = session.sftp().map_err(|e| format!("Canal SFTP: {}", e))?;
+            self.connection = Some((session, sftp));
+        }
+
+        Ok(&self.connection.as_ref().expect("connexion SFTP ouverte").1)
+    }
+}
+
+/// Empreinte sans préfixe "SHA256:" ni remplissage base64
+fn normalize_fingerprint(value: &str) -> String {
+    value.trim().trim_start_matches("SHA256:").trim_end_matches('=').to_string()
+}
+
+impl BackupTarget for SftpTarget {
+    fn name(&self) -> &'static str {
+        "sftp"
+    }
+
+    fn destination(&self) -> String {
+        format!("sftp://{}@{}:{}/{}", self.config.username, self.config.host, self.config.port, self.config.remote_dir)
+    }
+
+    fn retention_days(&self) -> u32 {
+        self.config.retention_days
+    }
+
+    fn upload(&mut self, local: &Path, name: &str, _sha256: &str) -> Result<(), String> {
+        let partial = self.remote_path(&format!("{}.partial", name));
+        let target = self.remote_path(name);
+        let sftp = self.sftp()?;
+
+        let mut source = std::fs::File::open(local).map_err(|e| format!("Lecture {:?}: {}", local, e))?;
+        let mut remote = sftp.create(&partial).map_err(|e| format!("Création de {:?}: {}", partial, e))?;
+        std::io::copy(&mut source, &mut remote).map_err(|e| format!("Envoi de {}: {}", name, e))?;
+        remote.flush().map_err(|e| format!("Envoi de {}: {}", name, e))?;
+        drop(remote);
+
+        // Certains serveurs refusent d'écraser : l'ancienne version est retirée d'abord
+        let _ = sftp.unlink(&target);
+        sftp.rename(&partial, &target, Some(RenameFlags::OVERWRITE | RenameFlags::ATOMIC))
+            .map_err(|e| format!("Publication de {}: {}", name, e))
+    }
+
+    fn checksum(&mut self, name: &str) -> Result<(u64, String), String> {
+        let path = self.remote_path(name);
+        let mut remote = self.sftp()?.open(&path).map_err(|e| format!("Relecture de {:?}: {}", path, e))?;
+
+        let mut hasher = Sha256::new();
+        let size = std::io::copy(&mut remote, &mut hasher).map_err(|e| format!("Relecture de {}: {}", name, e))?;
+        Ok((size, hex::encode(hasher.finalize())))
+    }
+
+    fn list(&mut self) -> Result<Vec<String>, String> {
+        let directory = PathBuf::from(&self.config.remote_dir);
+        let entries = self.sftp()?
+            .readdir(&directory)
+            .map_err(|e| format!("Lecture de {:?}: {}", directory, e))?;
+
+        Ok(entries
+            .into_iter()
+            .filter(|(_, stat)| stat.is_file())
+            .filter_map(|(path, _)| path.file_name().and_then(|n| n.to_str()).map(|n| n.to_string()))
+            .collect())
+    }
+
+    fn delete(&mut self, name: &str) -> Result<(), String> {
+        let path = self.remote_path(name);
+        self.sftp()?.unlink(&path).map_err(|e| e.to_string())
+    }
+}
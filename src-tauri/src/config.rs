@@ -5,6 +5,7 @@
 // ============================================================================
 
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
 use std::sync::OnceLock;
@@ -108,6 +109,682 @@ impl Default for TeoHubConfig {
     }
 }
 
+/// Configuration d'un sink d'audit syslog (RFC 5424)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyslogSinkConfig {
+    /// Activer l'envoi des événements d'audit vers syslog
+    #[serde(default)]
+    pub enabled: bool,
+    
+    /// Transport : "udp", "tcp" ou "unix"
+    #[serde(default = "default_syslog_transport")]
+    pub transport: String,
+    
+    /// Adresse host:port du collecteur (udp/tcp)
+    #[serde(default = "default_syslog_address")]
+    pub address: String,
+    
+    /// Chemin du socket local (transport unix)
+    #[serde(default = "default_syslog_socket_path")]
+    pub socket_path: String,
+    
+    /// Facility syslog (0-23, défaut: 13 = log audit)
+    #[serde(default = "default_syslog_facility")]
+    pub facility: u8,
+    
+    /// APP-NAME de l'en-tête RFC 5424
+    #[serde(default = "default_syslog_app_name")]
+    pub app_name: String,
+    
+    /// SD-ID des données structurées (nom@numéro d'entreprise privé)
+    #[serde(default = "default_syslog_sd_id")]
+    pub sd_id: String,
+}
+
+fn default_syslog_transport() -> String { "udp".to_string() }
+fn default_syslog_address() -> String { "127.0.0.1:514".to_string() }
+fn default_syslog_socket_path() -> String { "/dev/log".to_string() }
+fn default_syslog_facility() -> u8 { 13 }
+fn default_syslog_app_name() -> String { "airadcr-desktop".to_string() }
+fn default_syslog_sd_id() -> String { "airadcr@32473".to_string() }
+
+impl Default for SyslogSinkConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            transport: default_syslog_transport(),
+            address: default_syslog_address(),
+            socket_path: default_syslog_socket_path(),
+            facility: default_syslog_facility(),
+            app_name: default_syslog_app_name(),
+            sd_id: default_syslog_sd_id(),
+        }
+    }
+}
+
+/// Configuration d'un sink d'audit fichier JSON Lines rotatif
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsonFileSinkConfig {
+    /// Activer l'écriture des événements d'audit en JSON Lines
+    #[serde(default)]
+    pub enabled: bool,
+    
+    /// Répertoire des fichiers (vide = <données locales>/AIRADCR/logs/audit)
+    #[serde(default)]
+    pub directory: String,
+    
+    /// Taille maximale d'un fichier avant rotation (Mo)
+    #[serde(default = "default_json_file_max_size_mb")]
+    pub max_file_size_mb: u64,
+    
+    /// Nombre de fichiers archivés conservés
+    #[serde(default = "default_json_file_max_files")]
+    pub max_files: u32,
+}
+
+fn default_json_file_max_size_mb() -> u64 { 10 }
+fn default_json_file_max_files() -> u32 { 10 }
+
+impl Default for JsonFileSinkConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            directory: String::new(),
+            max_file_size_mb: default_json_file_max_size_mb(),
+            max_files: default_json_file_max_files(),
+        }
+    }
+}
+
+/// Configuration des sinks d'audit externes (SIEM)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditConfig {
+    /// Capacité de la file d'événements (au-delà, les événements sont perdus)
+    #[serde(default = "default_audit_queue_capacity")]
+    pub queue_capacity: usize,
+    
+    /// Sink syslog RFC 5424
+    #[serde(default)]
+    pub syslog: SyslogSinkConfig,
+    
+    /// Sink fichiers JSON Lines
+    #[serde(default)]
+    pub json_file: JsonFileSinkConfig,
+}
+
+fn default_audit_queue_capacity() -> usize { 1024 }
+
+impl Default for AuditConfig {
+    fn default() -> Self {
+        Self {
+            queue_capacity: default_audit_queue_capacity(),
+            syslog: SyslogSinkConfig::default(),
+            json_file: JsonFileSinkConfig::default(),
+        }
+    }
+}
+
+/// Configuration des métriques Prometheus
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricsConfig {
+    /// Buckets de latence HTTP en secondes
+    #[serde(default = "default_latency_buckets_secs")]
+    pub http_latency_buckets_secs: Vec<f64>,
+    
+    /// Buckets de latence des appels TÉO Hub en secondes
+    #[serde(default = "default_teo_latency_buckets_secs")]
+    pub teo_latency_buckets_secs: Vec<f64>,
+    
+    /// Buckets de latence des injections de texte en secondes
+    #[serde(default = "default_latency_buckets_secs")]
+    pub injection_latency_buckets_secs: Vec<f64>,
+}
+
+fn default_latency_buckets_secs() -> Vec<f64> {
+    vec![0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0]
+}
+
+fn default_teo_latency_buckets_secs() -> Vec<f64> {
+    // Appels réseau avec retry : jusqu'au timeout TÉO (30s par défaut)
+    vec![0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0]
+}
+
+impl Default for MetricsConfig {
+    fn default() -> Self {
+        Self {
+            http_latency_buckets_secs: default_latency_buckets_secs(),
+            teo_latency_buckets_secs: default_teo_latency_buckets_secs(),
+            injection_latency_buckets_secs: default_latency_buckets_secs(),
+        }
+    }
+}
+
+/// Configuration du traçage distribué (export OTLP)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TracingConfig {
+    /// Activer l'export des spans vers un collecteur OpenTelemetry
+    #[serde(default)]
+    pub enabled: bool,
+    
+    /// Endpoint OTLP/gRPC du collecteur (défaut: http://localhost:4317)
+    #[serde(default = "default_otlp_endpoint")]
+    pub otlp_endpoint: String,
+    
+    /// Nom du service (attribut service.name)
+    #[serde(default = "default_tracing_service_name")]
+    pub service_name: String,
+    
+    /// Proportion des traces racines échantillonnées (0.0 - 1.0)
+    #[serde(default = "default_tracing_sample_ratio")]
+    pub sample_ratio: f64,
+}
+
+fn default_otlp_endpoint() -> String { "http://localhost:4317".to_string() }
+fn default_tracing_service_name() -> String { "airadcr-desktop".to_string() }
+fn default_tracing_sample_ratio() -> f64 { 1.0 }
+
+impl Default for TracingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            otlp_endpoint: default_otlp_endpoint(),
+            service_name: default_tracing_service_name(),
+            sample_ratio: default_tracing_sample_ratio(),
+        }
+    }
+}
+
+/// Configuration des sondes de disponibilité (/health/ready)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthConfig {
+    /// Espace disque minimal dans le répertoire de données (Mo)
+    #[serde(default = "default_min_free_disk_mb")]
+    pub min_free_disk_mb: u64,
+    
+    /// Âge maximal du dernier backup avant état dégradé (heures)
+    #[serde(default = "default_backup_max_age_hours")]
+    pub backup_max_age_hours: u64,
+    
+    /// Durée de cache du résultat TÉO Hub (secondes)
+    #[serde(default = "default_teo_cache_secs")]
+    pub teo_cache_secs: u64,
+    
+    /// TÉO Hub injoignable = service non prêt
+    #[serde(default)]
+    pub require_teo_hub: bool,
+    
+    /// SpeechMike déconnecté = service non prêt
+    #[serde(default)]
+    pub require_speechmike: bool,
+}
+
+fn default_min_free_disk_mb() -> u64 { 500 }
+fn default_backup_max_age_hours() -> u64 { 36 }
+fn default_teo_cache_secs() -> u64 { 60 }
+
+impl Default for HealthConfig {
+    fn default() -> Self {
+        Self {
+            min_free_disk_mb: default_min_free_disk_mb(),
+            backup_max_age_hours: default_backup_max_age_hours(),
+            teo_cache_secs: default_teo_cache_secs(),
+            require_teo_hub: false,
+            require_speechmike: false,
+        }
+    }
+}
+
+/// Archives de backup exportables hors poste (chiffrées age/X25519 pour la DSI)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupExportConfig {
+    /// Produire une archive à chaque backup automatique
+    #[serde(default)]
+    pub enabled: bool,
+    
+    /// Clés publiques age (`age1...`) autorisées à ouvrir les archives
+    #[serde(default)]
+    pub recipients: Vec<String>,
+    
+    /// Niveau de compression zstd (1 = rapide, 19 = maximal)
+    #[serde(default = "default_export_compression_level")]
+    pub compression_level: i32,
+}
+
+fn default_export_compression_level() -> i32 { 10 }
+
+impl Default for BackupExportConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            recipients: Vec::new(),
+            compression_level: default_export_compression_level(),
+        }
+    }
+}
+
+/// Destination distante : partage réseau monté (chemin UNC ou lecteur réseau)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NetworkShareTargetConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    
+    /// Répertoire de destination (ex: `\\nas\backups\airadcr`, `Z:\airadcr`)
+    #[serde(default)]
+    pub path: String,
+    
+    /// Rétention sur la destination en jours
+    #[serde(default = "default_target_retention_days")]
+    pub retention_days: u32,
+}
+
+impl Default for NetworkShareTargetConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            path: String::new(),
+            retention_days: default_target_retention_days(),
+        }
+    }
+}
+
+/// Destination distante : serveur SFTP (authentification par clé)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SftpTargetConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    
+    #[serde(default)]
+    pub host: String,
+    
+    #[serde(default = "default_sftp_port")]
+    pub port: u16,
+    
+    #[serde(default)]
+    pub username: String,
+    
+    /// Clé privée SSH (OpenSSH/PEM)
+    #[serde(default)]
+    pub private_key_path: String,
+    
+    /// Empreinte SHA-256 attendue de la clé d'hôte (format `ssh-keygen -l`, "SHA256:...")
+    #[serde(default)]
+    pub host_key_sha256: String,
+    
+    /// Répertoire distant (doit exister)
+    #[serde(default = "default_sftp_remote_dir")]
+    pub remote_dir: String,
+    
+    /// Rétention sur la destination en jours
+    #[serde(default = "default_target_retention_days")]
+    pub retention_days: u32,
+}
+
+fn default_sftp_port() -> u16 { 22 }
+fn default_sftp_remote_dir() -> String { "airadcr-backups".to_string() }
+
+impl Default for SftpTargetConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            host: String::new(),
+            port: default_sftp_port(),
+            username: String::new(),
+            private_key_path: String::new(),
+            host_key_sha256: String::new(),
+            remote_dir: default_sftp_remote_dir(),
+            retention_days: default_target_retention_days(),
+        }
+    }
+}
+
+/// Destination distante : stockage objet compatible S3 (AWS, MinIO, ...)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct S3TargetConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    
+    /// URL du service (ex: https://s3.eu-west-3.amazonaws.com, http://minio:9000)
+    #[serde(default)]
+    pub endpoint: String,
+    
+    #[serde(default = "default_s3_region")]
+    pub region: String,
+    
+    #[serde(default)]
+    pub bucket: String,
+    
+    /// Préfixe des objets dans le bucket
+    #[serde(default = "default_s3_prefix")]
+    pub prefix: String,
+    
+    #[serde(default)]
+    pub access_key_id: String,
+    
+    /// Clé secrète : déplacée dans le keychain OS au chargement ; à défaut,
+    /// variable d'environnement AIRADCR_S3_SECRET_ACCESS_KEY
+    #[serde(default)]
+    pub secret_access_key: String,
+    
+    /// Adressage `endpoint/bucket/objet` (MinIO) plutôt que `bucket.endpoint/objet`
+    #[serde(default = "default_s3_path_style")]
+    pub path_style: bool,
+    
+    /// Délai maximal d'une requête (secondes)
+    #[serde(default = "default_s3_timeout_secs")]
+    pub timeout_secs: u64,
+    
+    /// Rétention sur la destination en jours
+    #[serde(default = "default_target_retention_days")]
+    pub retention_days: u32,
+}
+
+fn default_s3_region() -> String { "us-east-1".to_string() }
+fn default_s3_prefix() -> String { "airadcr/".to_string() }
+fn default_s3_path_style() -> bool { true }
+fn default_s3_timeout_secs() -> u64 { 300 }
+
+impl Default for S3TargetConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            endpoint: String::new(),
+            region: default_s3_region(),
+            bucket: String::new(),
+            prefix: default_s3_prefix(),
+            access_key_id: String::new(),
+            secret_access_key: String::new(),
+            path_style: default_s3_path_style(),
+            timeout_secs: default_s3_timeout_secs(),
+            retention_days: default_target_retention_days(),
+        }
+    }
+}
+
+/// Destinations distantes des backups (copie hors du disque de la base)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BackupTargetsConfig {
+    #[serde(default)]
+    pub network_share: NetworkShareTargetConfig,
+    
+    #[serde(default)]
+    pub sftp: SftpTargetConfig,
+    
+    #[serde(default)]
+    pub s3: S3TargetConfig,
+}
+
+fn default_target_retention_days() -> u32 { 30 }
+
+/// Configuration de l'accès concurrent à la base (pool de lecture, WAL)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DatabaseConfig {
+    /// Nombre maximal de connexions de lecture (l'écriture passe par une connexion dédiée)
+    #[serde(default = "default_db_read_pool_size")]
+    pub read_pool_size: u32,
+    
+    /// Attente maximale sur un verrou SQLite avant SQLITE_BUSY (millisecondes)
+    #[serde(default = "default_db_busy_timeout_ms")]
+    pub busy_timeout_ms: u64,
+    
+    /// Attente maximale d'une connexion libre dans le pool (secondes)
+    #[serde(default = "default_db_pool_timeout_secs")]
+    pub pool_timeout_secs: u64,
+}
+
+fn default_db_read_pool_size() -> u32 { 4 }
+fn default_db_busy_timeout_ms() -> u64 { 5000 }
+fn default_db_pool_timeout_secs() -> u64 { 10 }
+
+impl Default for DatabaseConfig {
+    fn default() -> Self {
+        Self {
+            read_pool_size: default_db_read_pool_size(),
+            busy_timeout_ms: default_db_busy_timeout_ms(),
+            pool_timeout_secs: default_db_pool_timeout_secs(),
+        }
+    }
+}
+
+/// Rotation planifiée de la clé SQLCipher ([key_rotation])
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeyRotationConfig {
+    /// Rotation automatique quand la clé atteint `interval_days`
+    #[serde(default = "default_key_rotation_enabled")]
+    pub enabled: bool,
+    
+    /// Âge maximal de la clé en jours (politique de sécurité : annuel)
+    #[serde(default = "default_key_rotation_interval_days")]
+    pub interval_days: u32,
+    
+    /// Heure locale (0-23) à laquelle une rotation due est lancée : le serveur
+    /// HTTP répond 503 pendant la rotation
+    #[serde(default = "default_key_rotation_hour")]
+    pub hour: u32,
+}
+
+fn default_key_rotation_enabled() -> bool { true }
+fn default_key_rotation_interval_days() -> u32 { 365 }
+fn default_key_rotation_hour() -> u32 { 3 }
+
+impl Default for KeyRotationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_key_rotation_enabled(),
+            interval_days: default_key_rotation_interval_days(),
+            hour: default_key_rotation_hour(),
+        }
+    }
+}
+
+/// Séquestre de la clé SQLCipher pour la reprise après sinistre ([key_escrow])
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct KeyEscrowConfig {
+    /// Séquestrer la clé active pour les clés publiques ci-dessous (au démarrage
+    /// et après chaque rotation)
+    #[serde(default)]
+    pub enabled: bool,
+    
+    /// Clés publiques age (`age1...`) des administrateurs habilités à la reprise
+    #[serde(default)]
+    pub recipients: Vec<String>,
+}
+
+/// Stockage des secrets : clé SQLCipher, token TÉO, clés HMAC ([keystore])
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeystoreConfig {
+    /// "auto" (keychain OS, fichier chiffré s'il est indisponible), "os" ou "file"
+    #[serde(default = "default_keystore_backend")]
+    pub backend: String,
+    
+    /// Keystore fichier (vide = <données locales>/airadcr-desktop/keystore.enc)
+    #[serde(default)]
+    pub file_path: String,
+    
+    /// Variable d'environnement contenant la passphrase du keystore fichier ;
+    /// absente ou vide : clé liée à la machine (/etc/machine-id)
+    #[serde(default = "default_keystore_passphrase_env")]
+    pub passphrase_env: String,
+}
+
+fn default_keystore_backend() -> String { "auto".to_string() }
+fn default_keystore_passphrase_env() -> String { "AIRADCR_KEYSTORE_PASSPHRASE".to_string() }
+
+impl Default for KeystoreConfig {
+    fn default() -> Self {
+        Self {
+            backend: default_keystore_backend(),
+            file_path: String::new(),
+            passphrase_env: default_keystore_passphrase_env(),
+        }
+    }
+}
+
+/// Configuration des verrous de rapports (bail renouvelé par heartbeat)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportLockConfig {
+    /// Verrouillage automatique à l'ouverture d'un rapport
+    #[serde(default = "default_locks_enabled")]
+    pub enabled: bool,
+    
+    /// Durée du bail sans heartbeat (secondes)
+    #[serde(default = "default_lock_lease_secs")]
+    pub lease_secs: u64,
+}
+
+fn default_locks_enabled() -> bool { true }
+fn default_lock_lease_secs() -> u64 { 300 }
+
+impl Default for ReportLockConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_locks_enabled(),
+            lease_secs: default_lock_lease_secs(),
+        }
+    }
+}
+
+/// Configuration de la validation des rapports structurés (JSON Schema par modalité)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportSchemaConfig {
+    /// Valider le contenu structuré à la réception (POST /pending-report)
+    #[serde(default = "default_report_schemas_enabled")]
+    pub enabled: bool,
+    
+    /// Rejeter (400) un rapport non conforme ; false = avertissement seul
+    /// (log `[Schemas]`), le rapport est accepté
+    #[serde(default = "default_report_schemas_enforce")]
+    pub enforce: bool,
+    
+    /// Répertoire des schémas personnalisés (<MODALITÉ>.json, default.json) ;
+    /// défaut : <config>/airadcr-desktop/schemas
+    #[serde(default)]
+    pub directory: Option<String>,
+}
+
+fn default_report_schemas_enabled() -> bool { true }
+fn default_report_schemas_enforce() -> bool { false }
+
+impl Default for ReportSchemaConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_report_schemas_enabled(),
+            enforce: default_report_schemas_enforce(),
+            directory: None,
+        }
+    }
+}
+
+/// Configuration du détecteur de données nominatives dans les valeurs
+/// (appliqué avant tout envoi hors du poste : TÉO Hub…)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PiiConfig {
+    /// Analyser les valeurs des payloads sortants
+    #[serde(default = "default_pii_enabled")]
+    pub enabled: bool,
+    
+    /// Détecteurs actifs : nir, birth_date, phone, email, ipp, name
+    #[serde(default = "default_pii_detectors")]
+    pub detectors: Vec<String>,
+    
+    /// Action par défaut sur une détection : reject, redact ou tokenise
+    #[serde(default = "default_pii_action")]
+    pub default_action: String,
+    
+    /// Action par champ (chemin JSON sans indices, ex: "sections[].content",
+    /// ou nom du champ, ex: "conclusion")
+    #[serde(default)]
+    pub field_actions: HashMap<String, String>,
+    
+    /// Expressions régulières d'IPP propres à l'établissement
+    #[serde(default)]
+    pub ipp_patterns: Vec<String>,
+    
+    /// Fichiers de noms (un nom par ligne) à détecter dans le texte
+    #[serde(default)]
+    pub name_dictionaries: Vec<String>,
+    
+    /// Politique Patient-Safe (règles sur les clés, destinations) ;
+    /// défaut : <config>/airadcr-desktop/patient_safe.toml
+    #[serde(default)]
+    pub policy_file: Option<String>,
+}
+
+fn default_pii_enabled() -> bool { true }
+fn default_pii_detectors() -> Vec<String> {
+    ["nir", "birth_date", "phone", "email", "ipp", "name"]
+        .iter()
+        .map(|d| d.to_string())
+        .collect()
+}
+fn default_pii_action() -> String { "reject".to_string() }
+
+impl Default for PiiConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_pii_enabled(),
+            detectors: default_pii_detectors(),
+            default_action: default_pii_action(),
+            field_actions: HashMap::new(),
+            ipp_patterns: Vec::new(),
+            name_dictionaries: Vec::new(),
+            policy_file: None,
+        }
+    }
+}
+
+/// Règle de rétention des rapports ([[retention.rules]])
+/// Les critères absents correspondent à toutes les valeurs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetentionRuleConfig {
+    /// Identifiant de la règle (repris dans le rapport de purge)
+    pub id: String,
+    
+    /// Origine du rapport (ex: "tauri_local", "teo_hub")
+    #[serde(default)]
+    pub source_type: Option<String>,
+    
+    /// Modalité (ex: "CT", "MR")
+    #[serde(default)]
+    pub modality: Option<String>,
+    
+    /// Statut : pending, retrieved ou expired
+    #[serde(default)]
+    pub status: Option<String>,
+    
+    /// Durée de conservation depuis la création, en heures
+    pub max_age_hours: u32,
+}
+
+/// Politique de rétention et de purge des données locales
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetentionConfig {
+    /// Effacement sécurisé (PRAGMA secure_delete) lors des purges
+    #[serde(default = "default_retention_secure_delete")]
+    pub secure_delete: bool,
+    
+    /// Mode simulation : le cycle de nettoyage produit le rapport de purge
+    /// sans rien supprimer
+    #[serde(default)]
+    pub dry_run: bool,
+    
+    /// Règles par source_type / modality / status, évaluées dans l'ordre
+    /// (la première règle correspondant au rapport s'applique)
+    #[serde(default)]
+    pub rules: Vec<RetentionRuleConfig>,
+}
+
+fn default_retention_secure_delete() -> bool { true }
+
+impl Default for RetentionConfig {
+    fn default() -> Self {
+        Self {
+            secure_delete: default_retention_secure_delete(),
+            dry_run: false,
+            rules: Vec::new(),
+        }
+    }
+}
+
 /// Configuration de l'application
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppConfig {
@@ -143,6 +820,26 @@ pub struct AppConfig {
     #[serde(default = "default_backup_retention_days")]
     pub backup_retention_days: u32,
     
+    /// Archives de backup exportables (hors poste)
+    #[serde(default)]
+    pub backup_export: BackupExportConfig,
+    
+    /// Destinations distantes des backups (partage réseau, SFTP, S3)
+    #[serde(default)]
+    pub backup_targets: BackupTargetsConfig,
+    
+    /// Rotation planifiée de la clé SQLCipher
+    #[serde(default)]
+    pub key_rotation: KeyRotationConfig,
+    
+    /// Séquestre de la clé SQLCipher (reprise après sinistre)
+    #[serde(default)]
+    pub key_escrow: KeyEscrowConfig,
+    
+    /// Stockage des secrets (keychain OS ou keystore fichier chiffré)
+    #[serde(default)]
+    pub keystore: KeystoreConfig,
+    
     /// Intervalle de cleanup en secondes (défaut: 3600 = 1h)
     #[serde(default = "default_cleanup_interval_secs")]
     pub cleanup_interval_secs: u64,
@@ -151,9 +848,45 @@ pub struct AppConfig {
     #[serde(default)]
     pub disable_api_auth: bool,
     
+    /// Accès concurrent à la base (pool, WAL, busy timeout)
+    #[serde(default)]
+    pub database: DatabaseConfig,
+    
     /// Configuration TÉO Hub Client
     #[serde(default)]
     pub teo_hub: TeoHubConfig,
+    
+    /// Sinks d'audit externes (syslog, JSON Lines)
+    #[serde(default)]
+    pub audit: AuditConfig,
+    
+    /// Métriques Prometheus (buckets des histogrammes)
+    #[serde(default)]
+    pub metrics: MetricsConfig,
+    
+    /// Traçage distribué (spans OTLP)
+    #[serde(default)]
+    pub tracing: TracingConfig,
+    
+    /// Sondes de disponibilité
+    #[serde(default)]
+    pub health: HealthConfig,
+    
+    /// Verrous de rapports (édition concurrente)
+    #[serde(default)]
+    pub locks: ReportLockConfig,
+    
+    /// Schémas de validation des rapports structurés
+    #[serde(default)]
+    pub report_schemas: ReportSchemaConfig,
+    
+    /// Détection des données nominatives dans les valeurs sortantes
+    #[serde(default)]
+    pub pii: PiiConfig,
+    
+    /// Rétention des rapports et purge sécurisée
+    #[serde(default)]
+    pub retention: RetentionConfig,
 }
 
 fn default_http_port() -> u16 { 8741 }
@@ -177,9 +910,23 @@ impl Default for AppConfig {
             iframe_url: default_iframe_url(),
             backup_enabled: default_backup_enabled(),
             backup_retention_days: default_backup_retention_days(),
+            backup_export: BackupExportConfig::default(),
+            backup_targets: BackupTargetsConfig::default(),
+            key_rotation: KeyRotationConfig::default(),
+            key_escrow: KeyEscrowConfig::default(),
+            keystore: KeystoreConfig::default(),
             cleanup_interval_secs: default_cleanup_interval_secs(),
             disable_api_auth: false,
+            database: DatabaseConfig::default(),
             teo_hub: TeoHubConfig::default(),
+            audit: AuditConfig::default(),
+            metrics: MetricsConfig::default(),
+            tracing: TracingConfig::default(),
+            health: HealthConfig::default(),
+            locks: ReportLockConfig::default(),
+            report_schemas: ReportSchemaConfig::default(),
+            pii: PiiConfig::default(),
+            retention: RetentionConfig::default(),
         }
     }
 }
@@ -199,6 +946,9 @@ impl AppConfig {
                 if let Ok(mut config) = toml::from_str::<AppConfig>(&content) {
                         let mut needs_save = false;
                         
+                        // 🔐 Backend des secrets, avant la migration du token ci-dessous
+                        crate::database::keychain::init(&config.keystore);
+                        
                         // Migration automatique : corriger l'ancienne URL sans ?tori=true
                         if config.iframe_url == "https://airadcr.com/app" || config.iframe_url == "https://airadcr.com" {
                             info!("[Config] Migration: ancienne iframe_url détectée, mise à jour vers ?tori=true");
@@ -234,6 +984,18 @@ impl AppConfig {
                             }
                         }
                         
+                        // 🔐 Même migration pour la clé secrète de la destination de backup S3
+                        if !config.backup_targets.s3.secret_access_key.is_empty() {
+                            info!("[Config] Migration: clé secrète S3 → keychain OS");
+                            if let Err(e) = crate::database::keychain::store_s3_secret(&config.backup_targets.s3.secret_access_key) {
+                                warn!("[Config] Erreur migration clé secrète S3 vers keychain: {}", e);
+                            } else {
+                                config.backup_targets.s3.secret_access_key = String::new();
+                                needs_save = true;
+                                info!("[Config] Clé secrète S3 migrée vers keychain OS, supprimée du fichier TOML");
+                            }
+                        }
+                        
                         if needs_save {
                             let _ = config.save();
                         }
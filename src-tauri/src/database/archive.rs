@@ -0,0 +1,189 @@
+// ============================================================================
+// AIRADCR Desktop - Archives de backup exportables (hors poste)
+// ============================================================================
+// Format : age( zstd( tar[ manifest.json, pending_reports.key, pending_reports.db ] ) )
+// La base contenue est chiffrée par SQLCipher avec une clé à usage unique
+// (pending_reports.key, hex), elle-même protégée par age (X25519) à
+// destination des clés publiques de la DSI : aucune copie en clair n'est
+// écrite sur disque, ni à l'export ni à l'import. L'archive reste lisible si
+// le poste (et son keychain) est perdu, et la clé SQLCipher du poste ne
+// suffit pas à l'ouvrir. Lisible avec les outils standards :
+// `age -d -i cle.txt archive | zstd -d | tar x` puis `sqlcipher` avec la clé.
+// ============================================================================
+
+use std::fs;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+use super::backup::BackupManifest;
+
+/// Extension des archives exportées
+pub const ARCHIVE_EXTENSION: &str = "tar.zst.age";
+
+/// Entrées de l'archive
+const MANIFEST_ENTRY: &str = "manifest.json";
+const DATABASE_KEY_ENTRY: &str = "pending_reports.key";
+pub const DATABASE_ENTRY: &str = "pending_reports.db";
+
+/// Indique si un fichier est une archive exportée (d'après son nom)
+pub fn is_archive(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|n| n.to_str())
+        .map(|n| n.ends_with(&format!(".{}", ARCHIVE_EXTENSION)))
+        .unwrap_or(false)
+}
+
+/// Valide les clés publiques age (`age1...`) des destinataires
+pub fn parse_recipients(keys: &[String]) -> Result<Vec<Box<dyn age::Recipient + Send>>, String> {
+    if keys.is_empty() {
+        return Err("Aucune clé publique age configurée ([backup_export] recipients)".to_string());
+    }
+
+    keys.iter()
+        .map(|key| {
+            age::x25519::Recipient::from_str(key.trim())
+                .map(|recipient| Box::new(recipient) as Box<dyn age::Recipient + Send>)
+                .map_err(|e| format!("Clé publique age invalide '{}': {}", key, e))
+        })
+        .collect()
+}
+
+/// Charge les clés privées age (fichier d'identité `AGE-SECRET-KEY-1...`)
+pub(super) fn load_identities(identity_file: &Path) -> Result<Vec<age::x25519::Identity>, String> {
+    let file = age::IdentityFile::from_file(identity_file.to_string_lossy().to_string())
+        .map_err(|e| format!("Lecture de la clé privée age {:?}: {}", identity_file, e))?;
+
+    let identities: Vec<_> = file
+        .into_identities()
+        .into_iter()
+        .map(|entry| {
+            let age::IdentityFileEntry::Native(identity) = entry;
+            identity
+        })
+        .collect();
+
+    if identities.is_empty() {
+        return Err(format!("Aucune clé privée age dans {:?}", identity_file));
+    }
+    Ok(identities)
+}
+
+/// Écrit l'archive chiffrée (fichier temporaire puis renommage) ; `database`
+/// est chiffrée par SQLCipher avec `database_key`
+pub fn write_archive(
+    dest: &Path,
+    manifest: &BackupManifest,
+    database: &Path,
+    database_key: &str,
+    recipients: Vec<Box<dyn age::Recipient + Send>>,
+    compression_level: i32,
+) -> Result<(), String> {
+    let partial = dest.with_extension("partial");
+
+    let result = (|| -> Result<(), String> {
+        let encryptor = age::Encryptor::with_recipients(recipients)
+            .ok_or_else(|| "Aucun destinataire age".to_string())?;
+        let output = BufWriter::new(
+            fs::File::create(&partial).map_err(|e| format!("Création de l'archive: {}", e))?,
+        );
+        let encrypted = encryptor
+            .wrap_output(output)
+            .map_err(|e| format!("Chiffrement age: {}", e))?;
+        let compressed = zstd::Encoder::new(encrypted, compression_level)
+            .map_err(|e| format!("Compression zstd: {}", e))?;
+
+        let mut tar = tar::Builder::new(compressed);
+        let manifest_json = serde_json::to_vec_pretty(manifest)
+            .map_err(|e| format!("Sérialisation du manifeste: {}", e))?;
+        let mut header = tar::Header::new_gnu();
+        header.set_size(manifest_json.len() as u64);
+        header.set_mode(0o600);
+        header.set_mtime(chrono::Utc::now().timestamp().max(0) as u64);
+        header.set_cksum();
+        tar.append_data(&mut header, MANIFEST_ENTRY, manifest_json.as_slice())
+            .map_err(|e| format!("Écriture du manifeste: {}", e))?;
+        let mut header = tar::Header::new_gnu();
+        header.set_size(database_key.len() as u64);
+        header.set_mode(0o600);
+        header.set_mtime(chrono::Utc::now().timestamp().max(0) as u64);
+        header.set_cksum();
+        tar.append_data(&mut header, DATABASE_KEY_ENTRY, database_key.as_bytes())
+            .map_err(|e| format!("Écriture de la clé de la base: {}", e))?;
+        tar.append_path_with_name(database, DATABASE_ENTRY)
+            .map_err(|e| format!("Écriture de la base: {}", e))?;
+
+        let compressed = tar.into_inner().map_err(|e| format!("Finalisation tar: {}", e))?;
+        let encrypted = compressed.finish().map_err(|e| format!("Finalisation zstd: {}", e))?;
+        let mut output = encrypted.finish().map_err(|e| format!("Finalisation age: {}", e))?;
+        output.flush().map_err(|e| format!("Écriture de l'archive: {}", e))?;
+        Ok(())
+    })();
+
+    match result {
+        Ok(()) => fs::rename(&partial, dest).map_err(|e| format!("Publication de l'archive: {}", e)),
+        Err(e) => {
+            let _ = fs::remove_file(&partial);
+            Err(e)
+        }
+    }
+}
+
+/// Déchiffre l'archive avec la clé privée age, écrit la base (toujours chiffrée
+/// par SQLCipher) dans `database_out` et retourne le manifeste et la clé de la base
+pub fn read_archive(source: &Path, identity_file: &Path, database_out: &Path) -> Result<(BackupManifest, String), String> {
+    let identities = load_identities(identity_file)?;
+    let input = BufReader::new(
+        fs::File::open(source).map_err(|e| format!("Ouverture de l'archive: {}", e))?,
+    );
+
+    let decrypted = match age::Decryptor::new(input).map_err(|e| format!("Archive age invalide: {}", e))? {
+        age::Decryptor::Recipients(decryptor) => decryptor
+            .decrypt(identities.iter().map(|i| i as &dyn age::Identity))
+            .map_err(|e| format!("Déchiffrement age: {}", e))?,
+        age::Decryptor::Passphrase(_) => {
+            return Err("Archive chiffrée par phrase secrète : format non pris en charge".to_string())
+        }
+    };
+    let decompressed = zstd::Decoder::new(decrypted).map_err(|e| format!("Décompression zstd: {}", e))?;
+
+    let mut tar = tar::Archive::new(decompressed);
+    let mut manifest = None;
+    let mut database_key = None;
+    let mut database_found = false;
+
+    for entry in tar.entries().map_err(|e| format!("Lecture tar: {}", e))? {
+        let mut entry = entry.map_err(|e| format!("Lecture tar: {}", e))?;
+        let name = entry.path().map_err(|e| format!("Lecture tar: {}", e))?.to_string_lossy().to_string();
+
+        match name.as_str() {
+            MANIFEST_ENTRY => {
+                let mut content = String::new();
+                entry.read_to_string(&mut content).map_err(|e| format!("Lecture du manifeste: {}", e))?;
+                manifest = Some(
+                    serde_json::from_str::<BackupManifest>(&content)
+                        .map_err(|e| format!("Manifeste invalide: {}", e))?,
+                );
+            }
+            DATABASE_KEY_ENTRY => {
+                let mut content = String::new();
+                entry.read_to_string(&mut content).map_err(|e| format!("Lecture de la clé de la base: {}", e))?;
+                database_key = Some(content.trim().to_string());
+            }
+            DATABASE_ENTRY => {
+                let mut output = fs::File::create(database_out)
+                    .map_err(|e| format!("Extraction de la base: {}", e))?;
+                std::io::copy(&mut entry, &mut output).map_err(|e| format!("Extraction de la base: {}", e))?;
+                database_found = true;
+            }
+            other => log::warn!("[Backup] Entrée inattendue ignorée dans l'archive: {}", other),
+        }
+    }
+
+    if !database_found {
+        return Err("Archive incomplète: base absente".to_string());
+    }
+    let manifest = manifest.ok_or_else(|| "Archive incomplète: manifeste absent".to_string())?;
+    let database_key = database_key.ok_or_else(|| "Archive incomplète: clé de la base absente".to_string())?;
+    Ok((manifest, database_key))
+}
@@ -1,11 +1,46 @@
 // ============================================================================
 // AIRADCR Desktop - Système de Backup SQLite
 // ============================================================================
+// Les backups passent par l'API de backup SQLite sur une connexion chiffrée :
+// la copie est un instantané cohérent même pendant les écritures du serveur.
+// Chaque backup est rouvert avec la clé SQLCipher (integrity_check) puis
+// accompagné d'un manifeste JSON (version, schéma, lignes, SHA-256).
+// Les archives exportées (voir `archive`) en sont dérivées pour la DSI et
+// sont re-chiffrées avec la clé du poste lors d'une restauration. Aucune copie
+// en clair n'est écrite sur disque : la base intermédiaire est chiffrée avec
+// une clé SQLCipher à usage unique, transportée dans l'archive age.
+// ============================================================================
 
-use std::fs::{self, File};
-use std::io::{Read, Write};
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
 use chrono::{Utc, Duration};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use rusqlite::Connection;
+
+use super::{archive, keychain, migrations, restore};
+use super::Database;
+use crate::config::BackupExportConfig;
+
+/// Version du format de manifeste
+const MANIFEST_FORMAT: u32 = 1;
+
+/// Manifeste d'un backup, écrit à côté du fichier (`<nom>.manifest.json`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupManifest {
+    pub format: u32,
+    pub backup_file: String,
+    pub created_at: String,
+    pub app_version: String,
+    /// Version du schéma (`PRAGMA user_version`) contenue dans le backup
+    pub schema_version: u32,
+    pub encrypted: bool,
+    pub size_bytes: u64,
+    pub sha256: String,
+    /// Nombre de lignes par table, lu dans le backup lui-même
+    pub row_counts: BTreeMap<String, i64>,
+}
 
 /// Gère les backups automatiques de la base de données SQLite
 pub struct BackupManager {
@@ -14,6 +49,21 @@ pub struct BackupManager {
     retention_days: u32,
 }
 
+/// Chemin du manifeste associé à un fichier de backup
+pub fn manifest_path(backup_path: &Path) -> PathBuf {
+    backup_path.with_extension("manifest.json")
+}
+
+/// Empreinte SHA-256 (hex) d'un fichier
+pub fn sha256_file(path: &Path) -> Result<String, String> {
+    let mut file = fs::File::open(path)
+        .map_err(|e| format!("Erreur ouverture {:?}: {}", path, e))?;
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher)
+        .map_err(|e| format!("Erreur lecture {:?}: {}", path, e))?;
+    Ok(hex::encode(hasher.finalize()))
+}
+
 impl BackupManager {
     /// Crée un nouveau gestionnaire de backup
     pub fn new(db_path: PathBuf, retention_days: u32) -> Self {
@@ -29,45 +79,81 @@ impl BackupManager {
         }
     }
     
-    /// Crée un backup de la base de données
-    pub fn create_backup(&self) -> Result<PathBuf, String> {
+    /// Répertoire des backups
+    pub fn backup_dir(&self) -> &Path {
+        &self.backup_dir
+    }
+    
+    /// Crée un backup cohérent de la base via l'API de backup SQLite.
+    ///
+    /// La copie est faite depuis une connexion chiffrée (le serveur continue
+    /// d'écrire), vérifiée avec la même clé, puis publiée avec son manifeste.
+    pub fn create_backup(&self, db: &Database) -> Result<BackupInfo, String> {
         // Créer le répertoire de backup s'il n'existe pas
         fs::create_dir_all(&self.backup_dir)
             .map_err(|e| format!("Erreur création répertoire backup: {}", e))?;
         
         // Générer le nom du fichier avec timestamp
-        let timestamp = Utc::now().format("%Y%m%d_%H%M%S");
-        let backup_filename = format!("airadcr_backup_{}.db", timestamp);
+        let created_at = Utc::now();
+        let backup_filename = format!("airadcr_backup_{}.db", created_at.format("%Y%m%d_%H%M%S"));
         let backup_path = self.backup_dir.join(&backup_filename);
+        let partial_path = backup_path.with_extension("db.partial");
         
-        // Copier le fichier de base de données
-        let mut source = File::open(&self.db_path)
-            .map_err(|e| format!("Erreur ouverture source: {}", e))?;
+        // Copie en ligne (instantané de lecture, sans bloquer l'écriture)
+        let _ = fs::remove_file(&partial_path);
+        db.backup_to(&partial_path)
+            .map_err(|e| format!("Erreur backup SQLite: {}", e))?;
         
-        let mut content = Vec::new();
-        source.read_to_end(&mut content)
-            .map_err(|e| format!("Erreur lecture source: {}", e))?;
+        // Vérifier la copie avec la clé et relever le contenu pour le manifeste
+        let (schema_version, row_counts) = match Self::inspect_backup(db, &partial_path) {
+            Ok(summary) => summary,
+            Err(e) => {
+                let _ = fs::remove_file(&partial_path);
+                return Err(e);
+            }
+        };
         
-        let mut dest = File::create(&backup_path)
-            .map_err(|e| format!("Erreur création backup: {}", e))?;
+        fs::rename(&partial_path, &backup_path)
+            .map_err(|e| format!("Erreur publication backup: {}", e))?;
         
-        dest.write_all(&content)
-            .map_err(|e| format!("Erreur écriture backup: {}", e))?;
+        let size_bytes = fs::metadata(&backup_path)
+            .map(|m| m.len())
+            .map_err(|e| format!("Erreur lecture taille backup: {}", e))?;
+        let manifest = BackupManifest {
+            format: MANIFEST_FORMAT,
+            backup_file: backup_filename.clone(),
+            created_at: created_at.to_rfc3339(),
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            schema_version,
+            encrypted: db.is_encrypted(),
+            size_bytes,
+            sha256: sha256_file(&backup_path)?,
+            row_counts,
+        };
         
-        println!("✅ [Backup] Créé: {:?} ({} bytes)", backup_path, content.len());
+        write_manifest(&backup_path, &manifest)?;
         
-        // Vérifier l'intégrité du backup
-        self.verify_backup(&backup_path)?;
+        println!("✅ [Backup] Créé: {:?} ({} bytes, schéma v{})", backup_path, size_bytes, schema_version);
         
-        Ok(backup_path)
+        Ok(BackupInfo {
+            filename: backup_filename,
+            path: backup_path.to_string_lossy().to_string(),
+            size_bytes,
+            created_at: Some(manifest.created_at.clone()),
+            manifest: Some(manifest),
+        })
     }
     
-    /// Vérifie l'intégrité d'un fichier backup
-    fn verify_backup(&self, backup_path: &PathBuf) -> Result<(), String> {
-        // Ouvrir le backup avec SQLite et vérifier l'intégrité
-        let conn = rusqlite::Connection::open(backup_path)
+    /// Ouvre une copie avec la clé de la base, vérifie son intégrité et
+    /// retourne (version du schéma, nombre de lignes par table)
+    fn inspect_backup(db: &Database, path: &Path) -> Result<(u32, BTreeMap<String, i64>), String> {
+        let conn = db.open_keyed_readonly(path)
             .map_err(|e| format!("Erreur ouverture backup pour vérification: {}", e))?;
-        
+        Self::inspect_connection(&conn)
+    }
+    
+    /// Vérifie l'intégrité d'une base ouverte et relève son contenu
+    fn inspect_connection(conn: &Connection) -> Result<(u32, BTreeMap<String, i64>), String> {
         let integrity: String = conn
             .query_row("PRAGMA integrity_check", [], |row| row.get(0))
             .map_err(|e| format!("Erreur vérification intégrité: {}", e))?;
@@ -76,8 +162,164 @@ impl BackupManager {
             return Err(format!("Backup corrompu: {}", integrity));
         }
         
+        let schema_version: u32 = conn
+            .query_row("PRAGMA user_version", [], |row| row.get(0))
+            .map_err(|e| format!("Erreur lecture version du schéma: {}", e))?;
+        
+        // Tables applicatives (hors tables internes SQLite et FTS5)
+        let tables: Vec<String> = conn
+            .prepare(
+                "SELECT name FROM sqlite_master
+                 WHERE type = 'table' AND name NOT LIKE 'sqlite_%' AND name NOT LIKE 'pending_reports_fts%'
+                 ORDER BY name",
+            )
+            .and_then(|mut stmt| stmt.query_map([], |row| row.get(0))?.collect())
+            .map_err(|e| format!("Erreur lecture des tables: {}", e))?;
+        
+        let mut row_counts = BTreeMap::new();
+        for table in tables {
+            let count: i64 = conn
+                .query_row(&format!("SELECT COUNT(*) FROM \"{}\"", table), [], |row| row.get(0))
+                .map_err(|e| format!("Erreur comptage {}: {}", table, e))?;
+            row_counts.insert(table, count);
+        }
+        
+        Ok((schema_version, row_counts))
+    }
+    
+    /// Vérifie un backup : empreinte du manifeste (si présent) puis ouverture
+    /// avec la clé de la base et `PRAGMA integrity_check`
+    pub fn verify_backup(&self, db: &Database, backup_path: &Path) -> Result<Option<BackupManifest>, String> {
+        let manifest = read_manifest(backup_path);
+        
+        if let Some(manifest) = &manifest {
+            let checksum = sha256_file(backup_path)?;
+            if checksum != manifest.sha256 {
+                return Err(format!(
+                    "Empreinte du backup invalide: {} (manifeste: {})",
+                    checksum, manifest.sha256
+                ));
+            }
+        }
+        
+        Self::inspect_backup(db, backup_path)?;
+        
         println!("✅ [Backup] Intégrité vérifiée: OK");
-        Ok(())
+        Ok(manifest)
+    }
+    
+    /// Exporte un backup vérifié en archive compressée, chiffrée pour les
+    /// clés publiques age de la DSI (base chiffrée par une clé à usage unique
+    /// contenue dans l'archive)
+    pub fn export_archive(
+        &self,
+        db: &Database,
+        backup: &BackupInfo,
+        config: &BackupExportConfig,
+    ) -> Result<PathBuf, String> {
+        let recipients = archive::parse_recipients(&config.recipients)?;
+        let source = backup.manifest.clone()
+            .ok_or_else(|| format!("Manifeste absent pour {}", backup.filename))?;
+        
+        let stem = backup.filename.trim_end_matches(".db");
+        let archive_path = self.backup_dir.join(format!(
+            "{}.{}",
+            stem.replacen("airadcr_backup_", "airadcr_export_", 1),
+            archive::ARCHIVE_EXTENSION
+        ));
+        let export_path = self.backup_dir.join(format!("{}.export.partial", stem));
+        
+        // Copie éphémère chiffrée avec une clé à usage unique (jamais en clair
+        // sur disque), supprimée quel que soit le résultat
+        let export_key = keychain::generate_encryption_key();
+        let result = (|| -> Result<(), String> {
+            let _ = fs::remove_file(&export_path);
+            let conn = Connection::open(&backup.path)
+                .map_err(|e| format!("Erreur ouverture backup: {}", e))?;
+            if let Some(key) = &db.encryption_key() {
+                super::apply_sqlcipher_key(&conn, key)
+                    .map_err(|e| format!("Erreur clé backup: {}", e))?;
+            }
+            export_database(&conn, &export_path, &export_key)?;
+            drop(conn);
+            
+            let exported = open_with_key(&export_path, &export_key)
+                .map_err(|e| format!("Erreur ouverture export: {}", e))?;
+            let (schema_version, row_counts) = Self::inspect_connection(&exported)?;
+            drop(exported);
+            
+            if row_counts != source.row_counts {
+                return Err("Export incohérent avec le manifeste du backup".to_string());
+            }
+            
+            let manifest = BackupManifest {
+                format: MANIFEST_FORMAT,
+                backup_file: archive::DATABASE_ENTRY.to_string(),
+                created_at: source.created_at.clone(),
+                app_version: source.app_version.clone(),
+                schema_version,
+                encrypted: true,
+                size_bytes: fs::metadata(&export_path).map(|m| m.len()).unwrap_or(0),
+                sha256: sha256_file(&export_path)?,
+                row_counts,
+            };
+            
+            archive::write_archive(&archive_path, &manifest, &export_path, &export_key, recipients, config.compression_level)
+        })();
+        
+        let _ = fs::remove_file(&export_path);
+        result?;
+        
+        println!("📦 [Backup] Archive exportée: {:?}", archive_path);
+        Ok(archive_path)
+    }
+    
+    /// Importe une archive exportée : déchiffrement avec la clé privée age,
+    /// contrôle du manifeste puis re-chiffrement avec la clé de la base locale.
+    /// Retourne le chemin de la base re-chiffrée et vérifiée (à côté de l'archive).
+    fn import_archive(&self, db: &Database, archive_path: &Path, identity_file: &Path) -> Result<PathBuf, String> {
+        let import_path = archive_path.with_extension("import.partial");
+        let keyed_path = archive_path.with_extension("restore.partial");
+        let _ = fs::remove_file(&keyed_path);
+        
+        let result = (|| -> Result<BackupManifest, String> {
+            let (manifest, import_key) = archive::read_archive(archive_path, identity_file, &import_path)?;
+            
+            let checksum = sha256_file(&import_path)?;
+            if checksum != manifest.sha256 {
+                return Err(format!("Empreinte de la base archivée invalide: {} (manifeste: {})", checksum, manifest.sha256));
+            }
+            if manifest.schema_version > super::migrations::latest_version() {
+                return Err(format!(
+                    "Archive au schéma v{} plus récent que l'application (v{})",
+                    manifest.schema_version,
+                    super::migrations::latest_version()
+                ));
+            }
+            
+            let imported = open_with_key(&import_path, &import_key)
+                .map_err(|e| format!("Erreur ouverture base archivée: {}", e))?;
+            export_database(&imported, &keyed_path, db.encryption_key().as_deref().unwrap_or(""))?;
+            Ok(manifest)
+        })();
+        
+        let _ = fs::remove_file(&import_path);
+        
+        let verified = result.and_then(|manifest| {
+            let (schema_version, row_counts) = Self::inspect_backup(db, &keyed_path)?;
+            if schema_version != manifest.schema_version || row_counts != manifest.row_counts {
+                return Err("Base restaurée incohérente avec le manifeste de l'archive".to_string());
+            }
+            Ok(())
+        });
+        
+        if let Err(e) = verified {
+            let _ = fs::remove_file(&keyed_path);
+            return Err(e);
+        }
+        
+        println!("✅ [Backup] Archive importée et re-chiffrée: {:?}", archive_path);
+        Ok(keyed_path)
     }
     
     /// Nettoie les backups anciens selon la rétention configurée
@@ -101,7 +343,9 @@ impl BackupManager {
             
             // Vérifier si c'est un fichier de backup
             if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
-                if !name.starts_with("airadcr_backup_") || !name.ends_with(".db") {
+                let is_backup = name.starts_with("airadcr_backup_") && name.ends_with(".db");
+                let is_export = name.starts_with("airadcr_export_") && archive::is_archive(&path);
+                if !is_backup && !is_export {
                     continue;
                 }
             }
@@ -113,6 +357,7 @@ impl BackupManager {
                     
                     if modified_time < cutoff {
                         if fs::remove_file(&path).is_ok() {
+                            let _ = fs::remove_file(manifest_path(&path));
                             println!("🗑️ [Backup] Supprimé ancien backup: {:?}", path);
                             deleted_count += 1;
                         }
@@ -157,6 +402,7 @@ impl BackupManager {
                                 path: path.to_string_lossy().to_string(),
                                 size_bytes: size,
                                 created_at: created,
+                                manifest: read_manifest(&path),
                             });
                         }
                     }
@@ -170,28 +416,52 @@ impl BackupManager {
         backups
     }
     
-    /// Restaure un backup spécifique
-    pub fn restore_backup(&self, backup_filename: &str) -> Result<(), String> {
+    /// Restaure un backup spécifique, ou une archive exportée (la clé privée
+    /// age de la DSI est alors requise). Le backup est vérifié avant la mise
+    /// en pause des accès ; le remplacement et le retour arrière éventuel sont
+    /// décrits dans `restore`.
+    pub fn restore_backup(
+        &self,
+        db: &Database,
+        backup_filename: &str,
+        identity_file: Option<&Path>,
+        progress: &dyn Fn(restore::RestoreProgress),
+    ) -> Result<restore::RestoreReport, String> {
         let backup_path = self.backup_dir.join(backup_filename);
+        let notify = |step, message: String| restore::notify(progress, step, backup_filename, message);
         
-        if !backup_path.exists() {
-            return Err(format!("Backup non trouvé: {}", backup_filename));
-        }
-        
-        // Vérifier l'intégrité avant restauration
-        self.verify_backup(&backup_path)?;
+        notify(restore::RestoreStep::Verifying, "Vérification du backup (empreinte, intégrité, clé, schéma)".to_string());
+        let verified = (|| -> Result<PathBuf, String> {
+            if !backup_path.exists() {
+                return Err(format!("Backup non trouvé: {}", backup_filename));
+            }
+            
+            if archive::is_archive(&backup_path) {
+                let identity_file = identity_file
+                    .ok_or_else(|| "Clé privée age requise pour restaurer une archive exportée".to_string())?;
+                return self.import_archive(db, &backup_path, identity_file);
+            }
+            
+            self.verify_backup(db, &backup_path)?;
+            let conn = db.open_keyed_readonly(&backup_path)
+                .map_err(|e| format!("Erreur ouverture backup: {}", e))?;
+            migrations::ensure_supported(&conn).map_err(|e| e.to_string())?;
+            Ok(backup_path.clone())
+        })();
         
-        // Créer un backup de sécurité avant restauration
-        let safety_backup = self.db_path.with_extension("db.before_restore");
-        fs::copy(&self.db_path, &safety_backup)
-            .map_err(|e| format!("Erreur création backup de sécurité: {}", e))?;
+        let source_path = verified.inspect_err(|e| {
+            notify(restore::RestoreStep::Failed, e.clone());
+        })?;
         
-        // Copier le backup vers la base principale
-        fs::copy(&backup_path, &self.db_path)
-            .map_err(|e| format!("Erreur restauration: {}", e))?;
+        let result = restore::replace_database(db, &source_path, backup_filename, progress);
+        if source_path != backup_path {
+            let _ = fs::remove_file(&source_path);
+        }
         
-        println!("✅ [Backup] Restauré depuis: {}", backup_filename);
-        Ok(())
+        if result.is_ok() {
+            println!("✅ [Backup] Restauré depuis: {}", backup_filename);
+        }
+        result
     }
 }
 
@@ -202,4 +472,131 @@ pub struct BackupInfo {
     pub path: String,
     pub size_bytes: u64,
     pub created_at: Option<String>,
+    /// Manifeste (absent pour les backups antérieurs au format avec manifeste)
+    pub manifest: Option<BackupManifest>,
+}
+
+/// Ouvre une base avec une clé SQLCipher explicite (lecture-écriture :
+/// requis pour l'ATTACH de `export_database`)
+fn open_with_key(path: &Path, key: &str) -> rusqlite::Result<Connection> {
+    let conn = Connection::open(path)?;
+    super::apply_sqlcipher_key(&conn, key)?;
+    Ok(conn)
+}
+
+/// Copie complète de la base principale de `conn` vers `target` (ATTACH +
+/// `sqlcipher_export`), chiffrée avec `key` (chaîne vide = en clair).
+/// `sqlcipher_export` ne reporte pas `user_version` : il est recopié ici.
+fn export_database(conn: &Connection, target: &Path, key: &str) -> Result<(), String> {
+    // La clé est interpolée dans l'instruction ATTACH : hex uniquement
+    if !key.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err("Clé d'export invalide (hexadécimal attendu)".to_string());
+    }
+    
+    let schema_version: u32 = conn
+        .query_row("PRAGMA user_version", [], |row| row.get(0))
+        .map_err(|e| format!("Erreur lecture version du schéma: {}", e))?;
+    conn.execute_batch(&format!(
+        "ATTACH DATABASE '{}' AS export KEY '{}';
+         SELECT sqlcipher_export('export');
+         PRAGMA export.user_version = {};
+         DETACH DATABASE export;",
+        target.to_string_lossy().replace('\'', "''"),
+        key,
+        schema_version
+    ))
+    .map_err(|e| format!("Erreur export chiffré: {}", e))
+}
+
+/// Écrit le manifeste à côté du backup
+pub fn write_manifest(backup_path: &Path, manifest: &BackupManifest) -> Result<(), String> {
+    let manifest_json = serde_json::to_string_pretty(manifest)
+        .map_err(|e| format!("Erreur sérialisation manifeste: {}", e))?;
+    fs::write(manifest_path(backup_path), manifest_json)
+        .map_err(|e| format!("Erreur écriture manifeste: {}", e))
+}
+
+/// Lit le manifeste d'un backup (None s'il est absent ou illisible)
+pub fn read_manifest(backup_path: &Path) -> Option<BackupManifest> {
+    fs::read_to_string(manifest_path(backup_path))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_online_backup_with_manifest_and_checksum() {
+        let dir = std::env::temp_dir().join(format!("airadcr-backup-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let db = Database::new_in_memory().unwrap();
+        db.add_api_key("k-backup", "airadcr_", "hash", "Backup").unwrap();
+        let keys = db.count_active_api_keys().unwrap();
+
+        let manager = BackupManager::new(dir.join("pending_reports.db"), 7);
+        let backup = manager.create_backup(&db).unwrap();
+        let manifest = backup.manifest.clone().unwrap();
+        let backup_path = PathBuf::from(&backup.path);
+
+        assert_eq!(manifest.schema_version, super::super::migrations::latest_version());
+        assert_eq!(manifest.row_counts.get("api_keys").copied(), Some(keys));
+        assert!(!manifest.row_counts.keys().any(|t| t.starts_with("sqlite_")));
+        assert_eq!(manager.list_backups().len(), 1);
+        assert!(manager.verify_backup(&db, &backup_path).unwrap().is_some());
+
+        // Fichier modifié après coup : l'empreinte ne correspond plus
+        let mut bytes = fs::read(&backup_path).unwrap();
+        let last = bytes.len() - 1;
+        bytes[last] ^= 0xFF;
+        fs::write(&backup_path, bytes).unwrap();
+        assert!(manager.verify_backup(&db, &backup_path).is_err());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_export_archive_roundtrip_requires_it_key() {
+        use age::secrecy::ExposeSecret;
+
+        let dir = std::env::temp_dir().join(format!("airadcr-export-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let db = Database::new_in_memory().unwrap();
+        db.add_api_key("k-export", "airadcr_", "hash", "Export").unwrap();
+        let manager = BackupManager::new(dir.join("pending_reports.db"), 7);
+        let backup = manager.create_backup(&db).unwrap();
+
+        let it_key = age::x25519::Identity::generate();
+        let config = BackupExportConfig {
+            enabled: true,
+            recipients: vec![it_key.to_public().to_string()],
+            ..Default::default()
+        };
+        let archive_path = manager.export_archive(&db, &backup, &config).unwrap();
+        assert!(archive::is_archive(&archive_path));
+        assert_eq!(fs::read_dir(dir.join("backups")).unwrap().count(), 3);
+
+        // Base de l'archive chiffrée par la clé à usage unique, jamais en clair
+        let extracted = dir.join("extracted.db");
+        let it_key_file = dir.join("it.txt");
+        fs::write(&it_key_file, it_key.to_string().expose_secret()).unwrap();
+        let (_, export_key) = archive::read_archive(&archive_path, &it_key_file, &extracted).unwrap();
+        assert!(!fs::read(&extracted).unwrap().starts_with(b"SQLite format 3"));
+        assert!(open_with_key(&extracted, &export_key).is_ok());
+
+        // Une autre clé privée ne déchiffre pas l'archive
+        let other_key_file = dir.join("other.txt");
+        fs::write(&other_key_file, age::x25519::Identity::generate().to_string().expose_secret()).unwrap();
+        assert!(manager.import_archive(&db, &archive_path, &other_key_file).is_err());
+
+        let restored = manager.import_archive(&db, &archive_path, &it_key_file).unwrap();
+        let (schema_version, row_counts) = BackupManager::inspect_backup(&db, &restored).unwrap();
+        assert_eq!(schema_version, super::super::migrations::latest_version());
+        assert_eq!(Some(&row_counts), backup.manifest.as_ref().map(|m| &m.row_counts));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
 }
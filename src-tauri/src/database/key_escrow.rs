@@ -0,0 +1,574 @@
+// ============================================================================
+// AIRADCR Desktop - Séquestre de la clé SQLCipher (reprise après sinistre)
+// ============================================================================
+// Sans la clé du keychain, la base et tous ses backups sont illisibles. La clé
+// active est donc séquestrée dans le répertoire des backups (et répliquée
+// avec eux), chiffrée avec age :
+// - pour les clés publiques des administrateurs (`[key_escrow] recipients`),
+//   automatiquement au démarrage et après chaque rotation ;
+// - par une passphrase de reprise saisie par un administrateur (à refaire
+//   après chaque rotation, la passphrase n'étant pas conservée).
+// Fichiers : `airadcr_key_escrow_<empreinte>.age` / `.passphrase.age`, où
+// l'empreinte identifie la clé sans la révéler. Les séquestres des clés
+// précédentes sont conservés : ils ouvrent les copies distantes plus anciennes.
+// La reprise déchiffre un séquestre, vérifie que la clé ouvre le backup à
+// restaurer, puis l'installe comme nouvelle version de la clé du poste
+// (rotation de déclencheur `recovery`). Keychain effacé sur le poste même :
+// la base ne s'ouvre plus, la reprise se fait donc avant son ouverture
+// (`recover_offline`, option `--recover-key` au lancement).
+// ============================================================================
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use log::{info, warn};
+
+use super::archive;
+use super::backup::BackupManager;
+use super::key_rotation::{self, audit_key_event, RotationReport, RotationTrigger};
+use super::{keychain, Database};
+use crate::audit::AuditSeverity;
+use crate::config::KeyEscrowConfig;
+
+/// Préfixe des fichiers de séquestre (jamais purgés par la rétention des backups)
+pub const ESCROW_PREFIX: &str = "airadcr_key_escrow_";
+/// Version du contenu chiffré
+const FORMAT_VERSION: u32 = 1;
+/// Longueur minimale de la passphrase de reprise
+const MIN_PASSPHRASE_LEN: usize = 12;
+
+/// Mode de chiffrement d'un séquestre
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EscrowMethod {
+    /// Clés publiques age des administrateurs
+    Recipients,
+    /// Passphrase de reprise
+    Passphrase,
+}
+
+impl EscrowMethod {
+    fn as_str(self) -> &'static str {
+        match self {
+            EscrowMethod::Recipients => "recipients",
+            EscrowMethod::Passphrase => "passphrase",
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            EscrowMethod::Recipients => "age",
+            EscrowMethod::Passphrase => "passphrase.age",
+        }
+    }
+}
+
+/// Contenu déchiffré d'un séquestre
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct EscrowPayload {
+    format: u32,
+    key_fingerprint: String,
+    key_version: u32,
+    key: String,
+    created_at: String,
+    app_version: String,
+}
+
+/// Séquestre présent dans le répertoire des backups
+#[derive(Debug, Clone, Serialize)]
+pub struct EscrowFile {
+    pub path: String,
+    pub method: String,
+    pub key_fingerprint: String,
+    /// Séquestre de la clé active
+    pub current: bool,
+    pub modified_at: Option<String>,
+}
+
+/// État du séquestre (panneau de debug)
+#[derive(Debug, Clone, Serialize)]
+pub struct EscrowStatus {
+    pub enabled: bool,
+    pub recipients: usize,
+    pub key_fingerprint: Option<String>,
+    /// La clé active est séquestrée pour les clés publiques / par passphrase
+    pub recipients_escrow: bool,
+    pub passphrase_escrow: bool,
+    pub files: Vec<EscrowFile>,
+}
+
+/// Résultat d'une reprise
+#[derive(Debug, Clone, Serialize)]
+pub struct RecoveryReport {
+    pub key_fingerprint: String,
+    /// Version de la clé sur le poste d'origine
+    pub escrowed_version: u32,
+    pub escrowed_at: String,
+    /// La clé restaurée était déjà la clé active du poste
+    pub already_active: bool,
+    pub rotation: Option<RotationReport>,
+}
+
+/// Empreinte publique d'une clé SQLCipher (ne permet pas de la retrouver)
+pub fn key_fingerprint(key: &str) -> String {
+    let digest = Sha256::digest(format!("airadcr-key-escrow:{}", key).as_bytes());
+    hex::encode(&digest[..8])
+}
+
+fn escrow_dir(db: &Database) -> PathBuf {
+    BackupManager::new(db.path().to_path_buf(), 0).backup_dir().to_path_buf()
+}
+
+fn escrow_path(dir: &Path, fingerprint: &str, method: EscrowMethod) -> PathBuf {
+    dir.join(format!("{}{}.{}", ESCROW_PREFIX, fingerprint, method.extension()))
+}
+
+fn payload(key: String, version: u32) -> EscrowPayload {
+    EscrowPayload {
+        format: FORMAT_VERSION,
+        key_fingerprint: key_fingerprint(&key),
+        key_version: version,
+        key,
+        created_at: Utc::now().to_rfc3339(),
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+    }
+}
+
+fn current_payload(db: &Database) -> Result<EscrowPayload, String> {
+    let key = db
+        .encryption_key()
+        .ok_or_else(|| "Séquestre impossible sur une base en mémoire".to_string())?;
+    Ok(payload(key, keychain::current_db_key_version()))
+}
+
+/// Séquestre la clé active pour les clés publiques configurées
+pub fn escrow_with_recipients(db: &Database, config: &KeyEscrowConfig) -> Result<PathBuf, String> {
+    escrow_payload_with_recipients(db, config, current_payload(db))
+}
+
+/// Vérifie qu'une clé remplacée est séquestrée (clés publiques ou passphrase),
+/// sinon la séquestre pour les clés publiques configurées
+pub(super) fn ensure_escrowed(db: &Database, key: &str, version: u32) -> bool {
+    let fingerprint = key_fingerprint(key);
+    if escrow_files(&escrow_dir(db), None).iter().any(|f| f.key_fingerprint == fingerprint) {
+        return true;
+    }
+    let config = &crate::config::get_config().key_escrow;
+    config.enabled && escrow_payload_with_recipients(db, config, Ok(payload(key.to_string(), version))).is_ok()
+}
+
+fn escrow_payload_with_recipients(
+    db: &Database,
+    config: &KeyEscrowConfig,
+    payload: Result<EscrowPayload, String>,
+) -> Result<PathBuf, String> {
+    let result = (|| {
+        if config.recipients.is_empty() {
+            return Err("Aucune clé publique age configurée ([key_escrow] recipients)".to_string());
+        }
+        let recipients = archive::parse_recipients(&config.recipients)?;
+        let encryptor = age::Encryptor::with_recipients(recipients)
+            .ok_or_else(|| "Aucun destinataire age".to_string())?;
+        let payload = payload?;
+        let path = escrow_path(&escrow_dir(db), &payload.key_fingerprint, EscrowMethod::Recipients);
+        write_escrow(&path, &payload, encryptor)?;
+        Ok((path, payload))
+    })();
+    audit_escrow(result, EscrowMethod::Recipients, config.recipients.len())
+}
+
+/// Séquestre la clé active avec une passphrase de reprise
+pub fn escrow_with_passphrase(db: &Database, passphrase: &str) -> Result<PathBuf, String> {
+    let result = (|| {
+        if passphrase.chars().count() < MIN_PASSPHRASE_LEN {
+            return Err(format!(
+                "Passphrase de reprise trop courte ({} caractères minimum)",
+                MIN_PASSPHRASE_LEN
+            ));
+        }
+        let encryptor = age::Encryptor::with_user_passphrase(age::secrecy::Secret::new(passphrase.to_string()));
+        let payload = current_payload(db)?;
+        let path = escrow_path(&escrow_dir(db), &payload.key_fingerprint, EscrowMethod::Passphrase);
+        write_escrow(&path, &payload, encryptor)?;
+        Ok((path, payload))
+    })();
+    audit_escrow(result, EscrowMethod::Passphrase, 0)
+}
+
+fn audit_escrow(
+    result: Result<(PathBuf, EscrowPayload), String>,
+    method: EscrowMethod,
+    recipients: usize,
+) -> Result<PathBuf, String> {
+    match result {
+        Ok((path, payload)) => {
+            info!(
+                "[KeyEscrow] Clé SQLCipher v{} ({}) séquestrée ({}) : {:?}",
+                payload.key_version, payload.key_fingerprint, method.as_str(), path
+            );
+            audit_key_event(
+                "key_escrowed",
+                AuditSeverity::Notice,
+                format!(
+                    "key v{} ({}) escrowed by {} ({} recipient(s))",
+                    payload.key_version, payload.key_fingerprint, method.as_str(), recipients
+                ),
+            );
+            Ok(path)
+        }
+        Err(e) => {
+            warn!("[KeyEscrow] Séquestre ({}) impossible: {}", method.as_str(), e);
+            audit_key_event("key_escrow_failed", AuditSeverity::Error, format!("{}: {}", method.as_str(), e));
+            Err(e)
+        }
+    }
+}
+
+/// Écrit le séquestre chiffré (fichier temporaire puis renommage)
+fn write_escrow(path: &Path, payload: &EscrowPayload, encryptor: age::Encryptor) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Création du répertoire des backups: {}", e))?;
+    }
+    let partial = path.with_extension("partial");
+
+    let result = (|| -> Result<(), String> {
+        let content = serde_json::to_vec(payload).map_err(|e| format!("Sérialisation du séquestre: {}", e))?;
+        let output = BufWriter::new(
+            fs::File::create(&partial).map_err(|e| format!("Création du séquestre: {}", e))?,
+        );
+        let mut writer = encryptor
+            .wrap_output(output)
+            .map_err(|e| format!("Chiffrement age: {}", e))?;
+        writer.write_all(&content).map_err(|e| format!("Écriture du séquestre: {}", e))?;
+        let mut output = writer.finish().map_err(|e| format!("Finalisation age: {}", e))?;
+        output.flush().map_err(|e| format!("Écriture du séquestre: {}", e))
+    })();
+
+    match result {
+        Ok(()) => fs::rename(&partial, path).map_err(|e| format!("Publication du séquestre: {}", e)),
+        Err(e) => {
+            let _ = fs::remove_file(&partial);
+            Err(e)
+        }
+    }
+}
+
+/// Déchiffre un séquestre (clé privée age ou passphrase selon le fichier)
+fn read_escrow(path: &Path, identity_file: Option<&Path>, passphrase: Option<&str>) -> Result<EscrowPayload, String> {
+    let input = BufReader::new(fs::File::open(path).map_err(|e| format!("Ouverture du séquestre {:?}: {}", path, e))?);
+
+    let mut reader = match age::Decryptor::new(input).map_err(|e| format!("Séquestre age invalide: {}", e))? {
+        age::Decryptor::Recipients(decryptor) => {
+            let identity_file = identity_file
+                .ok_or_else(|| "Séquestre chiffré pour des clés publiques : clé privée age requise".to_string())?;
+            let identities = archive::load_identities(identity_file)?;
+            decryptor
+                .decrypt(identities.iter().map(|i| i as &dyn age::Identity))
+                .map_err(|e| format!("Déchiffrement age: {}", e))?
+        }
+        age::Decryptor::Passphrase(decryptor) => {
+            let passphrase = passphrase
+                .filter(|p| !p.is_empty())
+                .ok_or_else(|| "Séquestre chiffré par passphrase : passphrase de reprise requise".to_string())?;
+            decryptor
+                .decrypt(&age::secrecy::Secret::new(passphrase.to_string()), None)
+                .map_err(|e| format!("Déchiffrement age (passphrase incorrecte ?): {}", e))?
+        }
+    };
+
+    let mut content = Vec::new();
+    reader.read_to_end(&mut content).map_err(|e| format!("Lecture du séquestre: {}", e))?;
+    let payload: EscrowPayload =
+        serde_json::from_slice(&content).map_err(|e| format!("Séquestre invalide: {}", e))?;
+
+    if payload.format != FORMAT_VERSION {
+        return Err(format!("Format de séquestre non supporté: {}", payload.format));
+    }
+    // La clé est injectée dans PRAGMA rekey : 256 bits en hexadécimal uniquement
+    if payload.key.len() != 64 || !payload.key.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err("Séquestre invalide: clé mal formée".to_string());
+    }
+    if key_fingerprint(&payload.key) != payload.key_fingerprint {
+        return Err("Séquestre invalide: empreinte de clé incohérente".to_string());
+    }
+    Ok(payload)
+}
+
+/// Reprise sur un nouveau poste : déchiffre le séquestre, vérifie que la clé
+/// ouvre `verify_path` (backup à restaurer) puis l'installe dans le keychain
+pub fn recover(
+    db: &Database,
+    escrow: &Path,
+    identity_file: Option<&Path>,
+    passphrase: Option<&str>,
+    verify_path: &Path,
+) -> Result<RecoveryReport, String> {
+    let result = (|| {
+        let payload = read_escrow(escrow, identity_file, passphrase)?;
+        if !key_rotation::opens_with(verify_path, &payload.key) {
+            return Err(format!("La clé du séquestre n'ouvre pas {:?}", verify_path));
+        }
+
+        let already_active = db.encryption_key().as_deref() == Some(payload.key.as_str());
+        let rotation = if already_active {
+            None
+        } else {
+            Some(key_rotation::rotate_to(db, RotationTrigger::Recovery, Some(&payload.key))?)
+        };
+
+        Ok(RecoveryReport {
+            key_fingerprint: payload.key_fingerprint,
+            escrowed_version: payload.key_version,
+            escrowed_at: payload.created_at,
+            already_active,
+            rotation,
+        })
+    })();
+
+    audit_recovery(&result, escrow);
+    result
+}
+
+/// Reprise avant l'ouverture de la base (keychain effacé) : déchiffre le
+/// séquestre, vérifie que la clé ouvre `verify_path` (la base du poste ou un
+/// backup) puis l'installe comme nouvelle version active. Aucun re-chiffrement.
+pub fn recover_offline(
+    escrow: &Path,
+    identity_file: Option<&Path>,
+    passphrase: Option<&str>,
+    verify_path: &Path,
+) -> Result<RecoveryReport, String> {
+    recover_with(escrow, identity_file, passphrase, verify_path, &|key| {
+        let version = keychain::current_db_key_version();
+        if keychain::get_db_key_version(version).as_deref() == Some(key) {
+            return Ok(true);
+        }
+        // Jamais d'écrasement : la clé restaurée prend la version suivante
+        keychain::store_db_key_version(version + 1, key)?;
+        keychain::activate_db_key_version(version + 1)?;
+        Ok(false)
+    })
+}
+
+/// Déchiffre et vérifie un séquestre puis installe la clé avec `install`
+/// (retourne vrai si la clé était déjà active)
+fn recover_with(
+    escrow: &Path,
+    identity_file: Option<&Path>,
+    passphrase: Option<&str>,
+    verify_path: &Path,
+    install: &dyn Fn(&str) -> Result<bool, String>,
+) -> Result<RecoveryReport, String> {
+    let payload = read_escrow(escrow, identity_file, passphrase)?;
+    if !key_rotation::opens_with(verify_path, &payload.key) {
+        return Err(format!("La clé du séquestre n'ouvre pas {:?}", verify_path));
+    }
+    let already_active = install(&payload.key)?;
+
+    Ok(RecoveryReport {
+        key_fingerprint: payload.key_fingerprint,
+        escrowed_version: payload.key_version,
+        escrowed_at: payload.created_at,
+        already_active,
+        rotation: None,
+    })
+}
+
+/// Journalise et audite une reprise (après `audit::init` pour `recover_offline`)
+pub fn audit_recovery(result: &Result<RecoveryReport, String>, escrow: &Path) {
+    match &result {
+        Ok(report) => {
+            info!(
+                "[KeyEscrow] Clé {} (v{} séquestrée le {}) restaurée depuis {:?}",
+                report.key_fingerprint, report.escrowed_version, report.escrowed_at, escrow
+            );
+            audit_key_event(
+                "key_recovered",
+                AuditSeverity::Warning,
+                format!(
+                    "key {} (escrowed v{} at {}) recovered from {:?}{}",
+                    report.key_fingerprint,
+                    report.escrowed_version,
+                    report.escrowed_at,
+                    escrow.file_name().unwrap_or_default(),
+                    if report.already_active { ", already active" } else { "" }
+                ),
+            );
+        }
+        Err(e) => {
+            warn!("[KeyEscrow] Reprise depuis {:?} impossible: {}", escrow, e);
+            audit_key_event(
+                "key_recovery_failed",
+                AuditSeverity::Error,
+                format!("{:?}: {}", escrow.file_name().unwrap_or_default(), e),
+            );
+        }
+    }
+}
+
+/// Séquestres présents dans le répertoire des backups
+fn escrow_files(dir: &Path, current: Option<&str>) -> Vec<EscrowFile> {
+    let mut files: Vec<EscrowFile> = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter_map(|entry| {
+                    let path = entry.path();
+                    let name = path.file_name()?.to_str()?.to_string();
+                    let rest = name.strip_prefix(ESCROW_PREFIX)?;
+                    let (fingerprint, method) = if let Some(fp) = rest.strip_suffix(".passphrase.age") {
+                        (fp, EscrowMethod::Passphrase)
+                    } else {
+                        (rest.strip_suffix(".age")?, EscrowMethod::Recipients)
+                    };
+                    let modified_at = entry
+                        .metadata()
+                        .and_then(|m| m.modified())
+                        .ok()
+                        .map(|t| chrono::DateTime::<Utc>::from(t).to_rfc3339());
+                    Some(EscrowFile {
+                        path: path.to_string_lossy().to_string(),
+                        method: method.as_str().to_string(),
+                        key_fingerprint: fingerprint.to_string(),
+                        current: current == Some(fingerprint),
+                        modified_at,
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort_by(|a, b| b.modified_at.cmp(&a.modified_at));
+    files
+}
+
+/// État du séquestre de la clé active
+pub fn status(db: &Database, config: &KeyEscrowConfig) -> EscrowStatus {
+    let key_fingerprint = db.encryption_key().map(|key| key_fingerprint(&key));
+    let files = escrow_files(&escrow_dir(db), key_fingerprint.as_deref());
+    let has = |method: EscrowMethod| files.iter().any(|f| f.current && f.method == method.as_str());
+
+    EscrowStatus {
+        enabled: config.enabled,
+        recipients: config.recipients.len(),
+        recipients_escrow: has(EscrowMethod::Recipients),
+        passphrase_escrow: has(EscrowMethod::Passphrase),
+        key_fingerprint,
+        files,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::apply_sqlcipher_key;
+    use age::secrecy::ExposeSecret;
+    use rusqlite::Connection;
+
+    #[test]
+    fn test_escrow_and_recover_key() {
+        let dir = std::env::temp_dir().join(format!("airadcr-escrow-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let key = "ab".repeat(32);
+
+        let backup = dir.join("airadcr_backup_20200101_000000.db");
+        let conn = Connection::open(&backup).unwrap();
+        apply_sqlcipher_key(&conn, &key).unwrap();
+        conn.execute_batch("CREATE TABLE t (x INTEGER);").unwrap();
+        drop(conn);
+
+        let identity = age::x25519::Identity::generate();
+        let identity_file = dir.join("admin.key");
+        fs::write(&identity_file, identity.to_string().expose_secret()).unwrap();
+        let recipients = archive::parse_recipients(&[identity.to_public().to_string()]).unwrap();
+
+        let payload = EscrowPayload {
+            format: FORMAT_VERSION,
+            key_fingerprint: key_fingerprint(&key),
+            key_version: 3,
+            key: key.clone(),
+            created_at: Utc::now().to_rfc3339(),
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+        };
+        let path = escrow_path(&dir, &payload.key_fingerprint, EscrowMethod::Recipients);
+        write_escrow(&path, &payload, age::Encryptor::with_recipients(recipients).unwrap()).unwrap();
+        assert!(!fs::read(&path).unwrap().windows(key.len()).any(|w| w == key.as_bytes()));
+
+        let recovered = read_escrow(&path, Some(&identity_file), None).unwrap();
+        assert_eq!(recovered.key, key);
+        assert_eq!(recovered.key_version, 3);
+        assert!(key_rotation::opens_with(&backup, &recovered.key));
+        assert!(read_escrow(&path, None, Some("passphrase")).is_err());
+
+        let other = age::x25519::Identity::generate();
+        let other_file = dir.join("other.key");
+        fs::write(&other_file, other.to_string().expose_secret()).unwrap();
+        assert!(read_escrow(&path, Some(&other_file), None).is_err());
+
+        let files = escrow_files(&dir, Some(&payload.key_fingerprint));
+        assert_eq!(files.len(), 1);
+        assert!(files[0].current);
+        assert_eq!(files[0].method, "recipients");
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_recover_wiped_key_before_opening() {
+        use super::super::keystore::{FileKeystore, KeystoreSecret};
+        use std::cell::RefCell;
+
+        let dir = std::env::temp_dir().join(format!("airadcr-escrow-wiped-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let key = "cd".repeat(32);
+
+        let db_path = dir.join("pending_reports.db");
+        let conn = Connection::open(&db_path).unwrap();
+        apply_sqlcipher_key(&conn, &key).unwrap();
+        conn.execute_batch("CREATE TABLE t (x INTEGER); INSERT INTO t VALUES (42);").unwrap();
+        drop(conn);
+
+        let identity = age::x25519::Identity::generate();
+        let identity_file = dir.join("admin.key");
+        fs::write(&identity_file, identity.to_string().expose_secret()).unwrap();
+        let recipients = archive::parse_recipients(&[identity.to_public().to_string()]).unwrap();
+        let escrowed = payload(key.clone(), 4);
+        let escrow = escrow_path(&dir, &escrowed.key_fingerprint, EscrowMethod::Recipients);
+        write_escrow(&escrow, &escrowed, age::Encryptor::with_recipients(recipients).unwrap()).unwrap();
+
+        // Keychain effacé : keystore vide, une clé générée n'ouvre pas la base
+        let store = RefCell::new(
+            FileKeystore::open_with_cost(&dir.join("keystore.enc"), &KeystoreSecret::passphrase("wiped"), 256, 1)
+                .unwrap(),
+        );
+        assert!(store.borrow().get("sqlcipher-encryption-key").is_none());
+        assert!(!key_rotation::opens_with(&db_path, &keychain::generate_encryption_key()));
+
+        let install = |key: &str| {
+            store.borrow_mut().set("sqlcipher-encryption-key", key)?;
+            Ok(false)
+        };
+
+        // Séquestre d'une autre clé : refusé, rien n'est installé
+        let other = payload("ef".repeat(32), 1);
+        let other_escrow = escrow_path(&dir, &other.key_fingerprint, EscrowMethod::Recipients);
+        let recipients = archive::parse_recipients(&[identity.to_public().to_string()]).unwrap();
+        write_escrow(&other_escrow, &other, age::Encryptor::with_recipients(recipients).unwrap()).unwrap();
+        assert!(recover_with(&other_escrow, Some(&identity_file), None, &db_path, &install).is_err());
+        assert!(store.borrow().get("sqlcipher-encryption-key").is_none());
+
+        let report = recover_with(&escrow, Some(&identity_file), None, &db_path, &install).unwrap();
+        assert_eq!(report.escrowed_version, 4);
+        assert!(report.rotation.is_none());
+
+        let restored = store.borrow().get("sqlcipher-encryption-key").unwrap();
+        let conn = Connection::open(&db_path).unwrap();
+        apply_sqlcipher_key(&conn, &restored).unwrap();
+        let x: i64 = conn.query_row("SELECT x FROM t", [], |row| row.get(0)).unwrap();
+        assert_eq!(x, 42);
+        drop(conn);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,578 @@
+// ============================================================================
+// AIRADCR Desktop - Rotation de la clé SQLCipher
+// ============================================================================
+// Politique de sécurité : rotation annuelle et après tout départ d'un membre
+// du personnel. La rotation entière se déroule en mode maintenance (comme
+// une restauration) : deux rotations (manuelle, planifiée, reprise) ne
+// peuvent pas se croiser sur la clé en attente. Déroulement, repris au
+// démarrage s'il est interrompu :
+//   1. la nouvelle clé est stockée dans le keychain sous une entrée versionnée
+//      (`sqlcipher-encryption-key.vN`) ; la version active reste l'ancienne ;
+//   2. accès suspendus, journal repassé en mode DELETE puis `PRAGMA rekey`
+//      sur la base (transactionnel) ;
+//   3. la nouvelle version devient active et la base est rouverte (WAL) ;
+//   4. les backups locaux (et l'instantané de restauration) sont re-chiffrés
+//      avec la nouvelle clé, manifestes mis à jour ; ceux qui ne peuvent pas
+//      l'être sont mis en quarantaine (`.quarantine`), jamais supprimés ;
+//   5. l'ancienne clé est supprimée du keychain, seulement si elle est
+//      séquestrée (les copies distantes et la quarantaine en dépendent).
+// `PRAGMA rekey` ne passe jamais par le WAL : aucun arrêt ne peut laisser des
+// pages chiffrées avec la nouvelle clé en attente de checkpoint. Un arrêt
+// pendant le rekey laisse un journal de rollback, rejoué à l'ouverture avec
+// l'ancienne clé. Arrêt entre 2 et 3 : la base ne s'ouvre qu'avec la clé en
+// attente, que `resume_interrupted` active. Arrêt avant 2 ou avant 5 :
+// `finish_pending` supprime la clé inutilisée ou termine le re-chiffrement
+// des backups. Les archives exportées (age) ne dépendent pas de la clé du poste.
+// ============================================================================
+
+use chrono::{DateTime, Local, Timelike, Utc};
+use rusqlite::{Connection, OpenFlags};
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use log::{info, warn, error};
+
+use super::backup::{self, BackupManager};
+use super::key_escrow;
+use super::queries::KeyRotationEntry;
+use super::restore::{self, Maintenance};
+use super::{apply_sqlcipher_key, keychain, Database};
+use crate::audit::{self, AuditEvent, AuditEventKind, AuditSeverity};
+use crate::config::KeyRotationConfig;
+
+/// Origine d'une rotation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RotationTrigger {
+    /// Commande d'administration (départ d'un membre du personnel, incident)
+    Manual,
+    /// Politique `[key_rotation]` (clé plus ancienne que `interval_days`)
+    Scheduled,
+    /// Clé restaurée depuis un séquestre (voir `database::key_escrow`)
+    Recovery,
+}
+
+impl RotationTrigger {
+    fn as_str(self) -> &'static str {
+        match self {
+            RotationTrigger::Manual => "manual",
+            RotationTrigger::Scheduled => "scheduled",
+            RotationTrigger::Recovery => "recovery",
+        }
+    }
+}
+
+/// Résultat d'une rotation
+#[derive(Debug, Clone, Serialize)]
+pub struct RotationReport {
+    pub from_version: u32,
+    pub to_version: u32,
+    pub rotated_at: String,
+    pub backups_reencrypted: usize,
+    pub backups_invalidated: usize,
+    pub duration_ms: u64,
+}
+
+/// État de la clé SQLCipher (panneau de debug)
+#[derive(Debug, Clone, Serialize)]
+pub struct KeyStatus {
+    pub current_version: u32,
+    /// Mise en service de la clé courante (dernière rotation ou création)
+    pub in_use_since: Option<String>,
+    pub scheduled: bool,
+    pub interval_days: u32,
+    pub next_rotation_at: Option<String>,
+    pub history: Vec<KeyRotationEntry>,
+}
+
+/// Indique si un fichier de base s'ouvre avec la clé donnée
+pub(super) fn opens_with(path: &Path, key: &str) -> bool {
+    Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .map(|conn| apply_sqlcipher_key(&conn, key).is_ok())
+        .unwrap_or(false)
+}
+
+/// Rotation interrompue après `PRAGMA rekey` : si la base s'ouvre avec la clé
+/// en attente, celle-ci devient la version active et est retournée
+pub fn resume_interrupted(db_path: &Path) -> Option<String> {
+    let current = keychain::current_db_key_version();
+    let pending = keychain::get_db_key_version(current + 1)?;
+    if !opens_with(db_path, &pending) {
+        return None;
+    }
+
+    warn!(
+        "[KeyRotation] Rotation v{} → v{} interrompue après re-chiffrement de la base : activation de la nouvelle clé",
+        current, current + 1
+    );
+    if let Err(e) = keychain::activate_db_key_version(current + 1) {
+        error!("[KeyRotation] Activation de la clé v{} impossible: {}", current + 1, e);
+        return None;
+    }
+    Some(pending)
+}
+
+/// Termine une rotation interrompue (au démarrage) : clé en attente jamais
+/// appliquée, ou backups pas encore re-chiffrés avec l'ancienne clé conservée
+pub fn finish_pending(db: &Database) {
+    let Some(current_key) = db.encryption_key() else { return };
+    // Une rotation en cours détient la clé en attente : rien à reprendre
+    let Ok(_maintenance) = Maintenance::begin("reprise de rotation de clé") else { return };
+    let version = keychain::current_db_key_version();
+
+    // La base s'ouvre avec la version active : la version suivante n'a jamais servi
+    if keychain::get_db_key_version(version + 1).is_some() {
+        warn!("[KeyRotation] Clé v{} inutilisée (rotation interrompue avant re-chiffrement) : supprimée", version + 1);
+        let _ = keychain::delete_db_key_version(version + 1);
+    }
+
+    if version > 1 {
+        if let Some(old_key) = keychain::get_db_key_version(version - 1) {
+            warn!("[KeyRotation] Clé v{} encore présente : reprise du re-chiffrement des backups", version - 1);
+            let (reencrypted, invalidated) = reencrypt_backups(&backup_files(db.path()), &old_key, &current_key);
+            info!(
+                "[KeyRotation] Reprise terminée : {} backup(s) re-chiffré(s), {} en quarantaine",
+                reencrypted, invalidated
+            );
+            let retired = retire_key(db, version - 1, &old_key);
+            audit_key_event(
+                "key_rotation_resumed",
+                AuditSeverity::Notice,
+                format!(
+                    "v{} -> v{}: {} backup(s) re-encrypted, {} quarantined, old key {} {}",
+                    version - 1,
+                    version,
+                    reencrypted,
+                    invalidated,
+                    key_escrow::key_fingerprint(&old_key),
+                    if retired { "deleted" } else { "kept (not escrowed)" }
+                ),
+            );
+        }
+    }
+}
+
+/// Rotation complète de la clé SQLCipher (base, keychain, backups locaux)
+pub fn rotate(db: &Database, trigger: RotationTrigger) -> Result<RotationReport, String> {
+    rotate_to(db, trigger, None)
+}
+
+/// Rotation vers une clé fournie (clé restaurée) ou générée (`None`)
+pub(super) fn rotate_to(db: &Database, trigger: RotationTrigger, key: Option<&str>) -> Result<RotationReport, String> {
+    let started = Instant::now();
+    // Tenu jusqu'à la suppression de l'ancienne clé : la clé en attente
+    // (v{n+1}) n'existe que sous ce verrou
+    let _maintenance = Maintenance::begin("rotation de clé")?;
+    let old_key = db
+        .encryption_key()
+        .ok_or_else(|| "Rotation impossible sur une base en mémoire".to_string())?;
+    let from_version = keychain::current_db_key_version();
+    let to_version = from_version + 1;
+
+    info!("[KeyRotation] Rotation de la clé SQLCipher v{} → v{} ({})", from_version, to_version, trigger.as_str());
+    let new_key = match key {
+        Some(key) => {
+            keychain::store_db_key_version(to_version, key)?;
+            key.to_string()
+        }
+        None => keychain::stage_db_key_version(to_version)?,
+    };
+
+    if let Err(e) = rekey_database(db, &new_key, &|| keychain::activate_db_key_version(to_version)) {
+        if !opens_with(db.path(), &new_key) {
+            // Base inchangée : la clé en attente est abandonnée
+            let _ = keychain::delete_db_key_version(to_version);
+            audit_key_event(
+                "key_rotation_failed",
+                AuditSeverity::Error,
+                format!("v{} -> v{} ({}): {}", from_version, to_version, trigger.as_str(), e),
+            );
+            return Err(e);
+        }
+        error!("[KeyRotation] Base re-chiffrée avec la clé v{} malgré l'erreur: {}", to_version, e);
+    }
+
+    let (backups_reencrypted, backups_invalidated) =
+        reencrypt_backups(&backup_files(db.path()), &old_key, &new_key);
+    let old_fingerprint = key_escrow::key_fingerprint(&old_key);
+    let old_key_retired = retire_key(db, from_version, &old_key);
+
+    // Les `.db` déjà répliqués restent chiffrés avec l'ancienne clé
+    let targets = &crate::config::get_config().backup_targets;
+    if targets.network_share.enabled || targets.sftp.enabled || targets.s3.enabled {
+        warn!(
+            "[KeyRotation] Backups répliqués antérieurs chiffrés avec la clé v{} ({}) : lisibles uniquement avec son séquestre",
+            from_version, old_fingerprint
+        );
+    }
+
+    let report = RotationReport {
+        from_version,
+        to_version,
+        rotated_at: Utc::now().to_rfc3339(),
+        backups_reencrypted,
+        backups_invalidated,
+        duration_ms: started.elapsed().as_millis() as u64,
+    };
+
+    if let Err(e) = db.insert_key_rotation(&KeyRotationEntry {
+        rotated_at: report.rotated_at.clone(),
+        from_version,
+        to_version,
+        trigger: trigger.as_str().to_string(),
+        backups_reencrypted: backups_reencrypted as i64,
+        backups_invalidated: backups_invalidated as i64,
+    }) {
+        warn!("[KeyRotation] Enregistrement de la rotation impossible: {}", e);
+    }
+
+    audit_key_event(
+        "key_rotation",
+        AuditSeverity::Notice,
+        format!(
+            "v{} -> v{} ({}): {} backup(s) re-encrypted, {} quarantined; replicated copies need old key {} ({})",
+            from_version,
+            to_version,
+            trigger.as_str(),
+            backups_reencrypted,
+            backups_invalidated,
+            old_fingerprint,
+            if old_key_retired { "deleted, escrowed" } else { "kept, not escrowed" }
+        ),
+    );
+    info!(
+        "[KeyRotation] Clé v{} active ({} backup(s) re-chiffré(s), {} en quarantaine) en {}ms",
+        to_version, backups_reencrypted, backups_invalidated, report.duration_ms
+    );
+    Ok(report)
+}
+
+/// Supprime une version de clé remplacée si elle est séquestrée ; sinon elle
+/// reste dans le keychain (reprise par `finish_pending` au démarrage suivant)
+fn retire_key(db: &Database, version: u32, key: &str) -> bool {
+    let fingerprint = key_escrow::key_fingerprint(key);
+    if !key_escrow::ensure_escrowed(db, key, version) {
+        warn!(
+            "[KeyRotation] Clé v{} ({}) non séquestrée : conservée dans le keychain",
+            version, fingerprint
+        );
+        audit_key_event(
+            "key_retirement_deferred",
+            AuditSeverity::Warning,
+            format!("v{} ({}) kept: no escrow", version, fingerprint),
+        );
+        return false;
+    }
+    match keychain::delete_db_key_version(version) {
+        Ok(()) => true,
+        Err(e) => {
+            warn!("[KeyRotation] Suppression de la clé v{} impossible: {}", version, e);
+            false
+        }
+    }
+}
+
+/// `PRAGMA rekey` sur un fichier fermé par ailleurs, en journal de rollback :
+/// en WAL, les pages re-chiffrées attendraient un checkpoint
+fn rekey_file(conn: &Connection, new_key: &str) -> rusqlite::Result<()> {
+    let mode: String = conn.query_row("PRAGMA journal_mode = DELETE", [], |row| row.get(0))?;
+    if !mode.eq_ignore_ascii_case("delete") {
+        return Err(rusqlite::Error::SqliteFailure(
+            rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_BUSY),
+            Some(format!("journal {} : mode DELETE impossible", mode)),
+        ));
+    }
+    conn.execute_batch(&format!("PRAGMA rekey = '{}';", new_key))
+}
+
+/// Re-chiffre la base avec `new_key`, accès suspendus (appelant en mode
+/// maintenance). `on_switched` est appelé avant la réouverture, une fois la
+/// base lisible avec la nouvelle clé ; `resume` remet le journal en WAL.
+fn rekey_database(db: &Database, new_key: &str, on_switched: &dyn Fn() -> Result<(), String>) -> Result<(), String> {
+    let old_key = db
+        .encryption_key()
+        .ok_or_else(|| "Rotation impossible sur une base en mémoire".to_string())?;
+
+    restore::wait_for_instances(db)?;
+    db.suspend(restore::DRAIN_TIMEOUT)
+        .map_err(|e| format!("Fermeture de la base impossible: {}", e))?;
+
+    let rekeyed = Connection::open(db.path()).and_then(|conn| {
+        apply_sqlcipher_key(&conn, &old_key)?;
+        rekey_file(&conn, new_key)
+    });
+
+    // État réel du fichier, quelle que soit l'issue de PRAGMA rekey
+    let switched = opens_with(db.path(), new_key);
+    if switched {
+        db.set_encryption_key(new_key.to_string());
+        if let Err(e) = on_switched() {
+            error!("[KeyRotation] Activation de la nouvelle clé: {} (reprise à la prochaine ouverture)", e);
+        }
+    } else if !opens_with(db.path(), &old_key) {
+        return Err("Base illisible avec l'ancienne et la nouvelle clé après PRAGMA rekey".to_string());
+    }
+
+    db.resume().map_err(|e| format!("Réouverture de la base: {}", e))?;
+
+    match (switched, rekeyed) {
+        (true, _) => Ok(()),
+        (false, Err(e)) => Err(format!("PRAGMA rekey: {}", e)),
+        (false, Ok(())) => Err("Base toujours chiffrée avec l'ancienne clé après PRAGMA rekey".to_string()),
+    }
+}
+
+/// Copies de la base chiffrées avec la clé du poste : backups locaux
+/// (y compris pré-migration) et instantané de restauration
+fn backup_files(db_path: &Path) -> Vec<PathBuf> {
+    let manager = BackupManager::new(db_path.to_path_buf(), 0);
+    let mut files: Vec<PathBuf> = fs::read_dir(manager.backup_dir())
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| path.is_file())
+                .filter(|path| {
+                    path.file_name()
+                        .and_then(|n| n.to_str())
+                        .map(|n| n.starts_with("airadcr_backup_") && n.ends_with(".db"))
+                        .unwrap_or(false)
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let snapshot = db_path.with_extension("db.before_restore");
+    if snapshot.is_file() {
+        files.push(snapshot);
+    }
+    files
+}
+
+/// Chemin de quarantaine d'une copie (ignorée par la liste et la rétention)
+fn quarantine_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(".quarantine");
+    PathBuf::from(name)
+}
+
+/// Re-chiffre des copies de la base : (re-chiffrées, mises en quarantaine).
+/// Une copie illisible avec l'ancienne clé (clé plus ancienne, copie rapatriée
+/// par un administrateur) est renommée, jamais supprimée.
+fn reencrypt_backups(files: &[PathBuf], old_key: &str, new_key: &str) -> (usize, usize) {
+    let mut reencrypted = 0;
+    let mut invalidated = 0;
+
+    for path in files {
+        // Déjà re-chiffrée (reprise d'une rotation interrompue)
+        if opens_with(path, new_key) {
+            continue;
+        }
+
+        match reencrypt_file(path, old_key, new_key) {
+            Ok(()) => reencrypted += 1,
+            Err(e) => {
+                let quarantine = quarantine_path(path);
+                warn!("[KeyRotation] Backup {:?} non re-chiffrable ({}) : mis en quarantaine {:?}", path, e, quarantine);
+                if let Err(e) = fs::rename(path, &quarantine) {
+                    error!("[KeyRotation] Mise en quarantaine de {:?} impossible: {}", path, e);
+                }
+                let manifest = backup::manifest_path(path);
+                if manifest.exists() {
+                    let _ = fs::rename(&manifest, quarantine_path(&manifest));
+                }
+                invalidated += 1;
+            }
+        }
+    }
+
+    (reencrypted, invalidated)
+}
+
+/// `PRAGMA rekey` sur une copie, vérification puis mise à jour du manifeste.
+/// La date de modification est conservée (rétention des backups).
+fn reencrypt_file(path: &Path, old_key: &str, new_key: &str) -> Result<(), String> {
+    let modified = fs::metadata(path).and_then(|m| m.modified()).ok();
+
+    let conn = Connection::open(path).map_err(|e| e.to_string())?;
+    apply_sqlcipher_key(&conn, old_key).map_err(|e| format!("ancienne clé refusée: {}", e))?;
+    rekey_file(&conn, new_key).map_err(|e| format!("PRAGMA rekey: {}", e))?;
+    drop(conn);
+
+    let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY).map_err(|e| e.to_string())?;
+    apply_sqlcipher_key(&conn, new_key).map_err(|e| format!("nouvelle clé refusée: {}", e))?;
+    let check: String = conn
+        .query_row("PRAGMA quick_check", [], |row| row.get(0))
+        .map_err(|e| e.to_string())?;
+    if check != "ok" {
+        return Err(format!("vérification: {}", check));
+    }
+    drop(conn);
+
+    if let Some(mut manifest) = backup::read_manifest(path) {
+        manifest.size_bytes = fs::metadata(path).map_err(|e| e.to_string())?.len();
+        manifest.sha256 = backup::sha256_file(path)?;
+        backup::write_manifest(path, &manifest)?;
+    }
+
+    if let Some(modified) = modified {
+        let _ = fs::File::options().write(true).open(path).and_then(|file| file.set_modified(modified));
+    }
+    Ok(())
+}
+
+/// Prochaine rotation planifiée (None si la politique est désactivée)
+fn next_rotation_at(in_use_since: Option<&str>, config: &KeyRotationConfig) -> Option<DateTime<Utc>> {
+    if !config.enabled || config.interval_days == 0 {
+        return None;
+    }
+    let since = in_use_since
+        .and_then(|since| DateTime::parse_from_rfc3339(since).ok())
+        .map(|since| since.with_timezone(&Utc))
+        .unwrap_or_else(Utc::now);
+    Some(since + chrono::Duration::days(config.interval_days as i64))
+}
+
+/// Indique si la rotation planifiée doit être lancée maintenant
+pub fn is_due(db: &Database, config: &KeyRotationConfig, now: DateTime<Local>) -> bool {
+    if now.hour() != config.hour {
+        return false;
+    }
+    let in_use_since = db.key_in_use_since().ok().flatten();
+    next_rotation_at(in_use_since.as_deref(), config)
+        .map(|next| next <= now.with_timezone(&Utc))
+        .unwrap_or(false)
+}
+
+/// État de la clé et historique des rotations
+pub fn status(db: &Database, config: &KeyRotationConfig) -> Result<KeyStatus, String> {
+    let in_use_since = db.key_in_use_since().map_err(|e| e.to_string())?;
+    let history = db.list_key_rotations(20).map_err(|e| e.to_string())?;
+
+    Ok(KeyStatus {
+        current_version: keychain::current_db_key_version(),
+        next_rotation_at: next_rotation_at(in_use_since.as_deref(), config).map(|next| next.to_rfc3339()),
+        in_use_since,
+        scheduled: config.enabled,
+        interval_days: config.interval_days,
+        history,
+    })
+}
+
+/// Transmet un événement de gestion de clé aux sinks d'audit
+pub(crate) fn audit_key_event(event_type: &str, severity: AuditSeverity, message: String) {
+    audit::emit(AuditEvent {
+        timestamp: Utc::now().to_rfc3339(),
+        kind: AuditEventKind::KeyManagement,
+        severity,
+        event_type: event_type.to_string(),
+        request_id: crate::telemetry::generate_request_id(),
+        ip_address: "local".to_string(),
+        method: "-".to_string(),
+        endpoint: "sqlcipher-key".to_string(),
+        status_code: None,
+        result: None,
+        api_key_prefix: None,
+        user_agent: None,
+        duration_ms: None,
+        message: Some(message),
+        user_id: None,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rekey_database_and_reencrypt_backups() {
+        let dir = std::env::temp_dir().join(format!("airadcr-rotation-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let db_path = dir.join("pending_reports.db");
+        let conn = Connection::open(&db_path).unwrap();
+        apply_sqlcipher_key(&conn, "k-old").unwrap();
+        super::super::schema::initialize(&conn).unwrap();
+        let db = Database::with_read_pool(conn, db_path.clone(), db_path.clone(), Some("k-old".to_string()), None)
+            .unwrap();
+        db.add_api_key("k-rotation", "airadcr_", "hash", "Rotation").unwrap();
+        let keys = db.count_active_api_keys().unwrap();
+
+        let manager = BackupManager::new(db_path.clone(), 7);
+        let backup = manager.create_backup(&db).unwrap();
+        let backup_path = PathBuf::from(&backup.path);
+        let unreadable = manager.backup_dir().join("airadcr_backup_20200101_000000.db");
+        fs::write(&unreadable, vec![0x5au8; 4096]).unwrap();
+
+        let activated = std::cell::Cell::new(false);
+        rekey_database(&db, "k-new", &|| {
+            activated.set(true);
+            Ok(())
+        })
+        .unwrap();
+        assert!(activated.get());
+        assert!(opens_with(&db_path, "k-new"));
+        assert!(!opens_with(&db_path, "k-old"));
+        assert_eq!(db.count_active_api_keys().unwrap(), keys);
+
+        let (reencrypted, invalidated) = reencrypt_backups(&backup_files(&db_path), "k-old", "k-new");
+        assert_eq!((reencrypted, invalidated), (1, 1));
+        assert!(!unreadable.exists());
+        assert!(quarantine_path(&unreadable).exists());
+        assert!(opens_with(&backup_path, "k-new"));
+        assert!(manager.verify_backup(&db, &backup_path).unwrap().is_some());
+
+        // Reprise : les copies déjà re-chiffrées ne sont pas retouchées
+        assert_eq!(reencrypt_backups(&backup_files(&db_path), "k-old", "k-new"), (0, 0));
+
+        drop(db);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_rekey_leaves_nothing_to_checkpoint() {
+        let dir = std::env::temp_dir().join(format!("airadcr-rekey-wal-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let wal = |path: &Path| PathBuf::from(format!("{}-wal", path.display()));
+
+        // Base en WAL avec des pages non reportées (arrêt brutal simulé par copie)
+        let live = dir.join("live.db");
+        let conn = Connection::open(&live).unwrap();
+        apply_sqlcipher_key(&conn, "k-old").unwrap();
+        super::super::pool::enable_wal(&conn).unwrap();
+        conn.execute_batch("PRAGMA wal_autocheckpoint = 0; CREATE TABLE t (x INTEGER); INSERT INTO t VALUES (1), (2);")
+            .unwrap();
+        let crashed = dir.join("pending_reports.db");
+        fs::copy(&live, &crashed).unwrap();
+        fs::copy(wal(&live), wal(&crashed)).unwrap();
+        drop(conn);
+
+        let conn = Connection::open(&crashed).unwrap();
+        apply_sqlcipher_key(&conn, "k-old").unwrap();
+        rekey_file(&conn, "k-new").unwrap();
+
+        // Arrêt juste après le rekey : seul le fichier de base est conservé
+        let killed = dir.join("killed.db");
+        fs::copy(&crashed, &killed).unwrap();
+        assert!(!wal(&crashed).exists());
+        drop(conn);
+
+        let conn = Connection::open(&killed).unwrap();
+        apply_sqlcipher_key(&conn, "k-new").unwrap();
+        let count: i64 = conn.query_row("SELECT count(*) FROM t", [], |row| row.get(0)).unwrap();
+        assert_eq!(count, 2);
+        drop(conn);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_next_rotation_follows_interval() {
+        let config = KeyRotationConfig { enabled: true, interval_days: 365, hour: 3 };
+        let next = next_rotation_at(Some("2025-01-01T00:00:00+00:00"), &config).unwrap();
+        assert_eq!(next.to_rfc3339(), "2026-01-01T00:00:00+00:00");
+
+        let disabled = KeyRotationConfig { enabled: false, ..config };
+        assert!(next_rotation_at(Some("2025-01-01T00:00:00+00:00"), &disabled).is_none());
+    }
+}
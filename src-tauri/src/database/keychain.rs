@@ -5,38 +5,165 @@
 // - Windows : Credential Manager
 // - macOS   : Keychain
 // - Linux   : Secret Service (GNOME Keyring / KWallet)
+// Sans keychain OS (Linux headless), repli sur un keystore fichier chiffré
+// (voir `database::keystore`).
 // ============================================================================
 
-use log::{info, warn};
+use log::{info, warn, error};
 use rand::Rng;
+use serde::Serialize;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+use super::key_rotation::audit_key_event;
+use super::keystore::{FileKeystore, KeystoreSecret};
+use crate::audit::AuditSeverity;
+use crate::config::KeystoreConfig;
 
 const SERVICE_NAME: &str = "airadcr-desktop";
 const DB_KEY_ENTRY: &str = "sqlcipher-encryption-key";
+/// Version active de la clé SQLCipher (absente = version 1, entrée historique)
+const DB_KEY_VERSION_ENTRY: &str = "sqlcipher-encryption-key-version";
 const TEO_TOKEN_ENTRY: &str = "teo-hub-api-token";
+const S3_SECRET_ENTRY: &str = "s3-secret-access-key";
+const PSEUDONYM_KEY_ENTRY: &str = "pseudonym-hmac-key";
+const SIGNING_KEY_ENTRY: &str = "gdpr-signing-key";
 
 /// Génère une clé de chiffrement aléatoire de 64 caractères hex (256 bits)
-fn generate_encryption_key() -> String {
+pub(super) fn generate_encryption_key() -> String {
     let bytes: Vec<u8> = (0..32).map(|_| rand::thread_rng().gen::<u8>()).collect();
     hex::encode(bytes)
 }
 
-/// Récupère ou crée la clé de chiffrement SQLCipher depuis le keychain OS
-pub fn get_or_create_db_encryption_key() -> Result<String, String> {
-    match get_keychain_value(DB_KEY_ENTRY) {
+/// Indique si `path` est une base existante qui n'est pas en clair (SQLCipher)
+fn is_encrypted_db(path: &Path) -> bool {
+    let mut header = [0u8; 16];
+    std::fs::File::open(path)
+        .and_then(|mut file| file.read_exact(&mut header))
+        .map(|_| &header != b"SQLite format 3\0")
+        .unwrap_or(false)
+}
+
+/// Récupère ou crée la clé de chiffrement SQLCipher (version active) depuis le keychain OS.
+/// Aucune clé n'est générée si `db_path` est déjà une base chiffrée.
+pub fn get_or_create_db_encryption_key(db_path: &Path) -> Result<String, String> {
+    let version = current_db_key_version();
+    match get_keychain_value(&db_key_entry(version)) {
         Ok(key) if !key.is_empty() => {
             info!("[Keychain] Clé de chiffrement SQLCipher récupérée depuis le keychain OS");
             Ok(key)
         }
-        _ => {
+        lookup => {
+            // Keychain effacé ou repli sur un keystore vide : une nouvelle clé
+            // n'ouvrirait pas la base existante
+            if is_encrypted_db(db_path) {
+                let backend = keystore().active.name();
+                let reason = lookup.err().unwrap_or_else(|| "entrée vide".to_string());
+                error!(
+                    "[Keychain] Clé SQLCipher v{} absente du keystore {} ({}) alors que la base chiffrée {:?} existe",
+                    version, backend, reason, db_path
+                );
+                return Err(format!(
+                    "Clé SQLCipher v{} absente du keystore {} alors que la base chiffrée existe : \
+                     restaurer la clé depuis un séquestre",
+                    version, backend
+                ));
+            }
+
             // Première utilisation : générer et stocker la clé
             let new_key = generate_encryption_key();
-            set_keychain_value(DB_KEY_ENTRY, &new_key)?;
+            set_keychain_value(&db_key_entry(current_db_key_version()), &new_key)?;
             info!("[Keychain] Nouvelle clé de chiffrement SQLCipher créée et stockée dans le keychain OS");
             Ok(new_key)
         }
     }
 }
 
+// ============================================================================
+// Versions de la clé SQLCipher (rotation : voir `database::key_rotation`)
+// ============================================================================
+// Chaque version a sa propre entrée : la nouvelle clé est stockée avant
+// `PRAGMA rekey`, puis la version active est basculée. Un arrêt entre les deux
+// laisse les deux clés disponibles.
+
+/// Entrée keychain d'une version de la clé SQLCipher (v1 = entrée historique)
+fn db_key_entry(version: u32) -> String {
+    if version <= 1 {
+        DB_KEY_ENTRY.to_string()
+    } else {
+        format!("{}.v{}", DB_KEY_ENTRY, version)
+    }
+}
+
+/// Version active de la clé SQLCipher
+pub fn current_db_key_version() -> u32 {
+    get_keychain_value(DB_KEY_VERSION_ENTRY)
+        .ok()
+        .and_then(|value| value.trim().parse().ok())
+        .unwrap_or(1)
+}
+
+/// Clé SQLCipher d'une version donnée (None si absente du keychain)
+pub fn get_db_key_version(version: u32) -> Option<String> {
+    get_keychain_value(&db_key_entry(version))
+        .ok()
+        .filter(|key| !key.is_empty())
+}
+
+/// Génère et stocke la clé d'une nouvelle version, sans l'activer
+pub fn stage_db_key_version(version: u32) -> Result<String, String> {
+    let new_key = generate_encryption_key();
+    set_keychain_value(&db_key_entry(version), &new_key)?;
+    info!("[Keychain] Clé SQLCipher v{} générée (en attente d'activation)", version);
+    Ok(new_key)
+}
+
+/// Stocke une clé existante (restaurée d'un séquestre) sous une nouvelle version, sans l'activer
+pub fn store_db_key_version(version: u32, key: &str) -> Result<(), String> {
+    set_keychain_value(&db_key_entry(version), key)?;
+    info!("[Keychain] Clé SQLCipher v{} restaurée (en attente d'activation)", version);
+    Ok(())
+}
+
+/// Active une version de la clé SQLCipher (après `PRAGMA rekey`)
+pub fn activate_db_key_version(version: u32) -> Result<(), String> {
+    set_keychain_value(DB_KEY_VERSION_ENTRY, &version.to_string())?;
+    info!("[Keychain] Clé SQLCipher v{} active", version);
+    Ok(())
+}
+
+/// Supprime une version de la clé SQLCipher du keychain
+pub fn delete_db_key_version(version: u32) -> Result<(), String> {
+    delete_keychain_value(&db_key_entry(version))
+}
+
+/// Récupère ou crée la clé HMAC de pseudonymisation des identifiants patient
+pub fn get_or_create_pseudonym_key() -> Result<String, String> {
+    match get_keychain_value(PSEUDONYM_KEY_ENTRY) {
+        Ok(key) if !key.is_empty() => Ok(key),
+        _ => {
+            let new_key = generate_encryption_key();
+            set_keychain_value(PSEUDONYM_KEY_ENTRY, &new_key)?;
+            info!("[Keychain] Nouvelle clé de pseudonymisation créée et stockée dans le keychain OS");
+            Ok(new_key)
+        }
+    }
+}
+
+/// Récupère ou crée la clé HMAC de signature des exports et certificats RGPD
+pub fn get_or_create_signing_key() -> Result<String, String> {
+    match get_keychain_value(SIGNING_KEY_ENTRY) {
+        Ok(key) if !key.is_empty() => Ok(key),
+        _ => {
+            let new_key = generate_encryption_key();
+            set_keychain_value(SIGNING_KEY_ENTRY, &new_key)?;
+            info!("[Keychain] Nouvelle clé de signature RGPD créée et stockée dans le keychain OS");
+            Ok(new_key)
+        }
+    }
+}
+
 /// Stocke le token TEO Hub dans le keychain OS
 pub fn store_teo_token(token: &str) -> Result<(), String> {
     if token.is_empty() {
@@ -66,11 +193,362 @@ pub fn delete_teo_token() -> Result<(), String> {
     delete_keychain_value(TEO_TOKEN_ENTRY)
 }
 
+/// Stocke la clé secrète de la destination de backup S3 dans le keychain OS
+pub fn store_s3_secret(secret: &str) -> Result<(), String> {
+    if secret.is_empty() {
+        return Ok(());
+    }
+    set_keychain_value(S3_SECRET_ENTRY, secret)?;
+    info!("[Keychain] Clé secrète S3 stockée dans le keychain OS");
+    Ok(())
+}
+
+/// Récupère la clé secrète S3 depuis le keychain OS
+pub fn get_s3_secret() -> Result<Option<String>, String> {
+    match get_keychain_value(S3_SECRET_ENTRY) {
+        Ok(secret) if !secret.is_empty() => Ok(Some(secret)),
+        Ok(_) => Ok(None),
+        Err(e) => {
+            warn!("[Keychain] Clé secrète S3 non trouvée dans le keychain: {}", e);
+            Ok(None)
+        }
+    }
+}
+
+// ============================================================================
+// Backends : keychain OS ou keystore fichier chiffré ([keystore])
+// ============================================================================
+// "auto" : keychain OS s'il répond, sinon fichier chiffré, uniquement sur
+// Linux sans service de stockage (PlatformFailure / NoStorageAccess) ; un
+// keychain en erreur n'est jamais contourné. Un keystore fichier qui détient
+// déjà la clé SQLCipher reste utilisé. "os" / "file" : backend imposé ; les entrées détenues par l'autre
+// backend y sont migrées au démarrage puis supprimées de l'ancien.
+
+/// Backend de stockage des entrées
+enum Keystore {
+    Os,
+    File(Mutex<FileKeystore>),
+    /// Backend choisi mais inutilisable (passphrase absente, fichier illisible)
+    Unavailable(String),
+}
+
+impl Keystore {
+    fn name(&self) -> &'static str {
+        match self {
+            Keystore::Os => "os",
+            Keystore::File(_) => "file",
+            Keystore::Unavailable(_) => "unavailable",
+        }
+    }
+
+    fn get(&self, entry_name: &str) -> Result<String, String> {
+        match self {
+            Keystore::Os => get_os_value(entry_name),
+            Keystore::File(file) => lock_file(file)
+                .get(entry_name)
+                .ok_or_else(|| format!("Entrée '{}' absente du keystore fichier", entry_name)),
+            Keystore::Unavailable(e) => Err(e.clone()),
+        }
+    }
+
+    fn set(&self, entry_name: &str, value: &str) -> Result<(), String> {
+        match self {
+            Keystore::Os => set_os_value(entry_name, value),
+            Keystore::File(file) => lock_file(file).set(entry_name, value),
+            Keystore::Unavailable(e) => Err(e.clone()),
+        }
+    }
+
+    fn delete(&self, entry_name: &str) -> Result<(), String> {
+        match self {
+            Keystore::Os => delete_os_value(entry_name),
+            Keystore::File(file) => lock_file(file).delete(entry_name),
+            Keystore::Unavailable(e) => Err(e.clone()),
+        }
+    }
+
+    /// Indique si le backend détient la clé SQLCipher active
+    fn holds_db_key(&self) -> bool {
+        self.get(&db_key_entry(self.db_key_version()))
+            .map(|key| !key.is_empty())
+            .unwrap_or(false)
+    }
+
+    fn db_key_version(&self) -> u32 {
+        self.get(DB_KEY_VERSION_ENTRY)
+            .ok()
+            .and_then(|value| value.trim().parse().ok())
+            .unwrap_or(1)
+    }
+
+    fn holds(&self, entry_name: &str) -> bool {
+        self.get(entry_name).map(|value| !value.is_empty()).unwrap_or(false)
+    }
+}
+
+fn lock_file(file: &Mutex<FileKeystore>) -> std::sync::MutexGuard<'_, FileKeystore> {
+    file.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Backend actif et backend secondaire (source d'une migration, état)
+struct KeystoreState {
+    configured: String,
+    active: Keystore,
+    secondary: Option<Keystore>,
+    os_available: bool,
+    file_path: PathBuf,
+    migration: Option<String>,
+}
+
+static KEYSTORE: OnceLock<KeystoreState> = OnceLock::new();
+
+/// Sélectionne le backend (au chargement de la configuration, avant tout accès)
+pub fn init(config: &KeystoreConfig) {
+    if KEYSTORE.get().is_some() {
+        warn!("[Keychain] Backend déjà sélectionné, configuration [keystore] ignorée");
+        return;
+    }
+    let _ = KEYSTORE.set(select_backend(config));
+}
+
+fn keystore() -> &'static KeystoreState {
+    if let Some(state) = KEYSTORE.get() {
+        return state;
+    }
+    // Le chargement de la configuration appelle `init` (fichier présent)
+    let config = crate::config::get_config();
+    KEYSTORE.get_or_init(|| select_backend(&config.keystore))
+}
+
+/// Chemin du keystore fichier (défaut : à côté de la base)
+fn keystore_file_path(config: &KeystoreConfig) -> PathBuf {
+    if !config.file_path.is_empty() {
+        return PathBuf::from(&config.file_path);
+    }
+    dirs::data_local_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("airadcr-desktop")
+        .join("keystore.enc")
+}
+
+fn open_file_keystore(path: &Path, passphrase_env: &str) -> Keystore {
+    match KeystoreSecret::from_env(passphrase_env).and_then(|secret| FileKeystore::open(path, &secret)) {
+        Ok(file) => Keystore::File(Mutex::new(file)),
+        Err(e) => {
+            error!("[Keychain] Keystore fichier {:?} inutilisable: {}", path, e);
+            Keystore::Unavailable(format!("Keystore fichier inutilisable: {}", e))
+        }
+    }
+}
+
+/// Réponse du keychain OS au démarrage
+enum OsKeychain {
+    /// Le keychain répond (une entrée absente compte comme disponible)
+    Available,
+    /// Aucun service de stockage (Linux sans Secret Service)
+    Missing(String),
+    /// Keychain présent mais en erreur (verrouillé, accès refusé, ...)
+    Failing(String),
+}
+
+fn probe_os_keychain() -> OsKeychain {
+    match keyring::Entry::new(SERVICE_NAME, DB_KEY_VERSION_ENTRY).and_then(|entry| entry.get_password()) {
+        Ok(_) | Err(keyring::Error::NoEntry) => OsKeychain::Available,
+        Err(e @ (keyring::Error::PlatformFailure(_) | keyring::Error::NoStorageAccess(_))) => {
+            OsKeychain::Missing(e.to_string())
+        }
+        Err(e) => OsKeychain::Failing(e.to_string()),
+    }
+}
+
+fn select_backend(config: &KeystoreConfig) -> KeystoreState {
+    let configured = config.backend.to_lowercase();
+    let file_path = keystore_file_path(config);
+    let probe = probe_os_keychain();
+    let os_available = matches!(probe, OsKeychain::Available);
+    let open_file = || open_file_keystore(&file_path, &config.passphrase_env);
+
+    let (active, secondary, migrate) = match configured.as_str() {
+        "os" => {
+            if !os_available {
+                error!("[Keychain] Keychain OS indisponible alors que [keystore] backend = \"os\" : utiliser \"auto\" ou \"file\"");
+            }
+            (Keystore::Os, file_path.exists().then(open_file), true)
+        }
+        "file" => (open_file(), os_available.then_some(Keystore::Os), true),
+        other => {
+            if other != "auto" {
+                warn!("[Keychain] Backend [keystore] inconnu '{}', utilisation de \"auto\"", other);
+            }
+            // Repli automatique seulement sur Linux sans service de stockage :
+            // ailleurs, un keychain en erreur n'est pas contourné
+            match (file_path.exists().then(open_file), probe) {
+                (Some(file), _) if file.holds_db_key() => (file, os_available.then_some(Keystore::Os), false),
+                (file, OsKeychain::Available) => (Keystore::Os, file, false),
+                (file, OsKeychain::Missing(e)) if cfg!(target_os = "linux") => {
+                    warn!("[Keychain] Keychain OS indisponible ({}) : repli sur le keystore fichier {:?}", e, file_path);
+                    (file.unwrap_or_else(open_file), None, false)
+                }
+                (file, OsKeychain::Missing(e) | OsKeychain::Failing(e)) => {
+                    error!(
+                        "[Keychain] Keychain OS en erreur ({}) : pas de repli automatique, [keystore] backend = \"file\" pour l'imposer",
+                        e
+                    );
+                    (Keystore::Unavailable(format!("Keychain OS en erreur: {}", e)), file, false)
+                }
+            }
+        }
+    };
+
+    let mut migration = None;
+    if let Some(source) = secondary.as_ref().filter(|source| migrate && source.holds_db_key()) {
+        if active.holds_db_key() {
+            warn!(
+                "[Keychain] Les backends {} et {} détiennent chacun une clé SQLCipher : aucune migration",
+                source.name(), active.name()
+            );
+        } else {
+            match migrate_entries(source, &active) {
+                Ok(count) => {
+                    let message = format!("{} -> {}: {} entry(ies) migrated", source.name(), active.name(), count);
+                    info!("[Keychain] Migration du keystore {} → {} : {} entrée(s)", source.name(), active.name(), count);
+                    migration = Some(message);
+                }
+                Err(e) => error!("[Keychain] Migration du keystore {} → {} échouée: {}", source.name(), active.name(), e),
+            }
+        }
+    }
+
+    info!("[Keychain] Backend actif : {} (configuré : {})", active.name(), configured);
+    KeystoreState {
+        configured,
+        active,
+        secondary,
+        os_available,
+        file_path,
+        migration,
+    }
+}
+
+/// Entrées connues d'un backend (toutes versions de la clé SQLCipher comprises)
+fn known_entries(store: &Keystore) -> Vec<String> {
+    let mut names: Vec<String> = [DB_KEY_VERSION_ENTRY, TEO_TOKEN_ENTRY, S3_SECRET_ENTRY, PSEUDONYM_KEY_ENTRY, SIGNING_KEY_ENTRY]
+        .iter()
+        .map(|name| name.to_string())
+        .collect();
+    names.extend((1..=store.db_key_version() + 1).map(db_key_entry));
+    names
+}
+
+/// Copie les entrées vers le nouveau backend, les relit, puis les supprime de l'ancien
+fn migrate_entries(from: &Keystore, to: &Keystore) -> Result<usize, String> {
+    let names: Vec<String> = known_entries(from)
+        .into_iter()
+        .filter(|name| from.holds(name))
+        .collect();
+
+    for name in &names {
+        let value = from.get(name)?;
+        to.set(name, &value)?;
+        if to.get(name)? != value {
+            return Err(format!("Relecture de l'entrée '{}' incorrecte après migration", name));
+        }
+    }
+
+    for name in &names {
+        from.delete(name)?;
+    }
+    if let Keystore::File(file) = from {
+        let mut file = lock_file(file);
+        if file.is_empty() {
+            file.remove_file()?;
+        }
+    }
+    Ok(names.len())
+}
+
+/// État du stockage des secrets (panneau de debug, diagnostic)
+#[derive(Debug, Clone, Serialize)]
+pub struct KeystoreStatus {
+    /// Valeur de `[keystore] backend` : auto, os ou file
+    pub configured: String,
+    /// Backend utilisé : os, file ou unavailable
+    pub active: String,
+    pub error: Option<String>,
+    pub os_available: bool,
+    pub file_path: String,
+    pub file_exists: bool,
+    /// Secret du keystore fichier : passphrase ou machine
+    pub file_secret_source: Option<String>,
+    /// Backends détenant la clé SQLCipher active / le token TÉO
+    pub db_key_backends: Vec<String>,
+    pub teo_token_backends: Vec<String>,
+    /// Migration effectuée au démarrage
+    pub migration: Option<String>,
+}
+
+pub fn keystore_status() -> KeystoreStatus {
+    let state = keystore();
+    let stores: Vec<&Keystore> = std::iter::once(&state.active).chain(state.secondary.as_ref()).collect();
+    let holders = |check: &dyn Fn(&Keystore) -> bool| -> Vec<String> {
+        stores.iter().filter(|store| check(store)).map(|store| store.name().to_string()).collect()
+    };
+    let file_secret_source = stores.iter().find_map(|store| match store {
+        Keystore::File(file) => Some(lock_file(file).secret_source().as_str().to_string()),
+        _ => None,
+    });
+
+    KeystoreStatus {
+        configured: state.configured.clone(),
+        active: state.active.name().to_string(),
+        error: match &state.active {
+            Keystore::Unavailable(e) => Some(e.clone()),
+            _ => None,
+        },
+        os_available: state.os_available,
+        file_path: state.file_path.to_string_lossy().to_string(),
+        file_exists: state.file_path.exists(),
+        file_secret_source,
+        db_key_backends: holders(&|store| store.holds_db_key()),
+        teo_token_backends: holders(&|store| store.holds(TEO_TOKEN_ENTRY)),
+        migration: state.migration.clone(),
+    }
+}
+
+/// Trace le backend retenu dans l'audit (après l'initialisation des sinks)
+pub fn audit_keystore_startup() {
+    let state = keystore();
+    if let Some(migration) = &state.migration {
+        audit_key_event("keystore_migrated", AuditSeverity::Notice, migration.clone());
+    }
+    if let Keystore::Unavailable(e) = &state.active {
+        audit_key_event("keystore_unavailable", AuditSeverity::Error, e.clone());
+    } else if matches!(state.active, Keystore::File(_)) {
+        audit_key_event(
+            "keystore_file_backend",
+            AuditSeverity::Notice,
+            format!("secrets stored in encrypted file {:?}", state.file_path),
+        );
+    }
+}
+
 // ============================================================================
 // Opérations bas niveau sur le keychain
 // ============================================================================
 
 fn get_keychain_value(entry_name: &str) -> Result<String, String> {
+    keystore().active.get(entry_name)
+}
+
+fn set_keychain_value(entry_name: &str, value: &str) -> Result<(), String> {
+    keystore().active.set(entry_name, value)
+}
+
+fn delete_keychain_value(entry_name: &str) -> Result<(), String> {
+    keystore().active.delete(entry_name)
+}
+
+fn get_os_value(entry_name: &str) -> Result<String, String> {
     let entry = keyring::Entry::new(SERVICE_NAME, entry_name)
         .map_err(|e| format!("Erreur création entrée keychain: {}", e))?;
     
@@ -78,7 +556,7 @@ fn get_keychain_value(entry_name: &str) -> Result<String, String> {
         .map_err(|e| format!("Erreur lecture keychain '{}': {}", entry_name, e))
 }
 
-fn set_keychain_value(entry_name: &str, value: &str) -> Result<(), String> {
+fn set_os_value(entry_name: &str, value: &str) -> Result<(), String> {
     let entry = keyring::Entry::new(SERVICE_NAME, entry_name)
         .map_err(|e| format!("Erreur création entrée keychain: {}", e))?;
     
@@ -86,8 +564,7 @@ fn set_keychain_value(entry_name: &str, value: &str) -> Result<(), String> {
         .map_err(|e| format!("Erreur écriture keychain '{}': {}", entry_name, e))
 }
 
-#[allow(dead_code)]
-fn delete_keychain_value(entry_name: &str) -> Result<(), String> {
+fn delete_os_value(entry_name: &str) -> Result<(), String> {
     let entry = keyring::Entry::new(SERVICE_NAME, entry_name)
         .map_err(|e| format!("Erreur création entrée keychain: {}", e))?;
     
@@ -102,3 +579,54 @@ fn delete_keychain_value(entry_name: &str) -> Result<(), String> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migrate_entries_between_backends() {
+        let dir = std::env::temp_dir().join(format!("airadcr-keychain-{}", uuid::Uuid::new_v4()));
+        let secret = KeystoreSecret::passphrase("migration");
+        let open = |name: &str| {
+            Keystore::File(Mutex::new(FileKeystore::open_with_cost(&dir.join(name), &secret, 256, 1).unwrap()))
+        };
+        let source = open("source.enc");
+        let target = open("target.enc");
+
+        source.set(DB_KEY_VERSION_ENTRY, "2").unwrap();
+        source.set(&db_key_entry(2), "k-v2").unwrap();
+        source.set(TEO_TOKEN_ENTRY, "t-teo").unwrap();
+        assert!(source.holds_db_key());
+        assert!(!target.holds_db_key());
+
+        assert_eq!(migrate_entries(&source, &target).unwrap(), 3);
+        assert!(target.holds_db_key());
+        assert_eq!(target.get(TEO_TOKEN_ENTRY).unwrap(), "t-teo");
+        assert!(!source.holds_db_key());
+        assert!(!dir.join("source.enc").exists());
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_encrypted_database_detection() {
+        let dir = std::env::temp_dir().join(format!("airadcr-keychain-db-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let plain = dir.join("plain.db");
+        rusqlite::Connection::open(&plain).unwrap().execute_batch("CREATE TABLE t (x INTEGER);").unwrap();
+        let encrypted = dir.join("pending_reports.db");
+        let conn = rusqlite::Connection::open(&encrypted).unwrap();
+        super::super::apply_sqlcipher_key(&conn, "k-test").unwrap();
+        conn.execute_batch("CREATE TABLE t (x INTEGER);").unwrap();
+        drop(conn);
+
+        // Pas de nouvelle clé pour une base chiffrée existante
+        assert!(is_encrypted_db(&encrypted));
+        assert!(!is_encrypted_db(&plain));
+        assert!(!is_encrypted_db(&dir.join("absent.db")));
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
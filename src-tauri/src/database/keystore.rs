@@ -0,0 +1,372 @@
+// ============================================================================
+// AIRADCR Desktop - Keystore fichier chiffré (repli sans keychain OS)
+// ============================================================================
+// Postes Linux sans Secret Service (kiosques, conteneurs CI) : les entrées du
+// keychain sont stockées dans un fichier JSON chiffré (XChaCha20-Poly1305).
+// La clé du fichier est dérivée par Argon2id :
+// - d'une passphrase fournie par variable d'environnement, ou
+// - d'un secret lié à la machine (`/etc/machine-id`).
+// Le fichier est remplacé de façon atomique, avec les droits 0600 (Unix).
+// Le secret machine empêche seulement d'ouvrir une copie du fichier sur un
+// autre poste : la passphrase reste la protection recommandée.
+// ============================================================================
+
+use argon2::{Algorithm, Argon2, Params, Version};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use log::{info, warn};
+
+/// Version du format de fichier
+const FORMAT_VERSION: u32 = 1;
+/// Données associées authentifiées (lie le chiffré au format)
+const AAD: &[u8] = b"airadcr-desktop keystore v1";
+/// Coût Argon2id par défaut : 64 Mio, 3 passes (dérivation une fois par démarrage)
+const DEFAULT_MEMORY_KIB: u32 = 64 * 1024;
+const DEFAULT_ITERATIONS: u32 = 3;
+/// Fichiers lus pour le secret lié à la machine
+const MACHINE_ID_FILES: [&str; 2] = ["/etc/machine-id", "/var/lib/dbus/machine-id"];
+
+/// Origine du secret dont la clé du fichier est dérivée
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SecretSource {
+    Passphrase,
+    Machine,
+}
+
+impl SecretSource {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SecretSource::Passphrase => "passphrase",
+            SecretSource::Machine => "machine",
+        }
+    }
+
+    fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "passphrase" => Ok(SecretSource::Passphrase),
+            "machine" => Ok(SecretSource::Machine),
+            other => Err(format!("Source de secret du keystore inconnue: {}", other)),
+        }
+    }
+}
+
+/// Secret d'ouverture du keystore
+#[derive(Clone)]
+pub struct KeystoreSecret {
+    source: SecretSource,
+    value: String,
+}
+
+impl KeystoreSecret {
+    pub fn passphrase(passphrase: &str) -> Self {
+        Self {
+            source: SecretSource::Passphrase,
+            value: passphrase.to_string(),
+        }
+    }
+
+    /// Secret lié à la machine (identifiant systemd/D-Bus du poste)
+    pub fn machine() -> Result<Self, String> {
+        let machine_id = MACHINE_ID_FILES
+            .iter()
+            .filter_map(|path| fs::read_to_string(path).ok())
+            .map(|id| id.trim().to_string())
+            .find(|id| !id.is_empty())
+            .ok_or_else(|| {
+                "Aucun secret machine disponible (/etc/machine-id absent) : fournir une passphrase de keystore".to_string()
+            })?;
+        Ok(Self {
+            source: SecretSource::Machine,
+            value: format!("airadcr-desktop:{}", machine_id),
+        })
+    }
+
+    /// Passphrase de la variable d'environnement si définie, sinon secret machine
+    pub fn from_env(variable: &str) -> Result<Self, String> {
+        match std::env::var(variable) {
+            Ok(passphrase) if !passphrase.is_empty() => Ok(Self::passphrase(&passphrase)),
+            _ => Self::machine(),
+        }
+    }
+}
+
+/// Paramètres de dérivation stockés en clair dans le fichier
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct KdfParams {
+    algorithm: String,
+    memory_kib: u32,
+    iterations: u32,
+    parallelism: u32,
+    salt: String,
+}
+
+/// Contenu du fichier sur disque
+#[derive(Debug, Serialize, Deserialize)]
+struct KeystoreFile {
+    format: u32,
+    secret_source: String,
+    kdf: KdfParams,
+    nonce: String,
+    ciphertext: String,
+}
+
+/// Keystore fichier ouvert (entrées déchiffrées en mémoire)
+pub struct FileKeystore {
+    path: PathBuf,
+    source: SecretSource,
+    kdf: KdfParams,
+    key: [u8; 32],
+    entries: BTreeMap<String, String>,
+}
+
+impl FileKeystore {
+    /// Ouvre le keystore, ou en prépare un vide (écrit à la première entrée)
+    pub fn open(path: &Path, secret: &KeystoreSecret) -> Result<Self, String> {
+        Self::open_with_cost(path, secret, DEFAULT_MEMORY_KIB, DEFAULT_ITERATIONS)
+    }
+
+    /// Ouverture avec un coût Argon2 explicite (tests)
+    pub(super) fn open_with_cost(path: &Path, secret: &KeystoreSecret, memory_kib: u32, iterations: u32) -> Result<Self, String> {
+        if !path.exists() {
+            let kdf = new_kdf_params(memory_kib, iterations);
+            return Ok(Self {
+                path: path.to_path_buf(),
+                source: secret.source,
+                key: derive_key(&secret.value, &kdf)?,
+                kdf,
+                entries: BTreeMap::new(),
+            });
+        }
+
+        restrict_permissions(path);
+        let content = fs::read_to_string(path)
+            .map_err(|e| format!("Erreur lecture keystore {:?}: {}", path, e))?;
+        let file: KeystoreFile = serde_json::from_str(&content)
+            .map_err(|e| format!("Keystore {:?} illisible: {}", path, e))?;
+        if file.format != FORMAT_VERSION {
+            return Err(format!("Format de keystore non supporté: {}", file.format));
+        }
+
+        // Un keystore lié à la machine passe sous passphrase dès qu'elle est fournie
+        let stored_source = SecretSource::parse(&file.secret_source)?;
+        let opening_secret = match (stored_source, secret.source) {
+            (SecretSource::Machine, SecretSource::Passphrase) => KeystoreSecret::machine()?,
+            (SecretSource::Passphrase, SecretSource::Machine) => {
+                return Err("Keystore protégé par passphrase : passphrase absente".to_string());
+            }
+            _ => secret.clone(),
+        };
+
+        let key = derive_key(&opening_secret.value, &file.kdf)?;
+        let nonce = hex::decode(&file.nonce).map_err(|e| format!("Nonce du keystore invalide: {}", e))?;
+        if nonce.len() != 24 {
+            return Err("Nonce du keystore invalide".to_string());
+        }
+        let ciphertext = BASE64
+            .decode(&file.ciphertext)
+            .map_err(|e| format!("Contenu du keystore invalide: {}", e))?;
+        let plaintext = XChaCha20Poly1305::new((&key).into())
+            .decrypt(XNonce::from_slice(&nonce), Payload { msg: &ciphertext, aad: AAD })
+            .map_err(|_| format!(
+                "Déchiffrement du keystore {:?} impossible (secret {} incorrect)",
+                path, stored_source.as_str()
+            ))?;
+        let entries = serde_json::from_slice(&plaintext)
+            .map_err(|e| format!("Contenu du keystore invalide: {}", e))?;
+
+        let mut keystore = Self {
+            path: path.to_path_buf(),
+            source: stored_source,
+            kdf: file.kdf,
+            key,
+            entries,
+        };
+        if stored_source != secret.source {
+            keystore.rewrap(secret)?;
+            info!("[Keystore] Keystore re-chiffré avec la passphrase (n'est plus lié à la machine)");
+        }
+        Ok(keystore)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn secret_source(&self) -> SecretSource {
+        self.source
+    }
+
+    pub fn get(&self, name: &str) -> Option<String> {
+        self.entries.get(name).cloned()
+    }
+
+    pub fn set(&mut self, name: &str, value: &str) -> Result<(), String> {
+        self.entries.insert(name.to_string(), value.to_string());
+        self.save()
+    }
+
+    pub fn delete(&mut self, name: &str) -> Result<(), String> {
+        if self.entries.remove(name).is_some() {
+            self.save()?;
+        }
+        Ok(())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Supprime le fichier (keystore vidé après migration vers le keychain OS)
+    pub fn remove_file(&mut self) -> Result<(), String> {
+        self.entries.clear();
+        if self.path.exists() {
+            fs::remove_file(&self.path)
+                .map_err(|e| format!("Erreur suppression keystore {:?}: {}", self.path, e))?;
+            info!("[Keystore] Fichier {:?} supprimé", self.path);
+        }
+        Ok(())
+    }
+
+    /// Re-dérive la clé du fichier depuis un autre secret (nouveau sel)
+    fn rewrap(&mut self, secret: &KeystoreSecret) -> Result<(), String> {
+        let kdf = new_kdf_params(self.kdf.memory_kib, self.kdf.iterations);
+        self.key = derive_key(&secret.value, &kdf)?;
+        self.kdf = kdf;
+        self.source = secret.source;
+        self.save()
+    }
+
+    /// Chiffre et remplace le fichier (fichier temporaire 0600 puis renommage)
+    fn save(&self) -> Result<(), String> {
+        let plaintext = serde_json::to_vec(&self.entries)
+            .map_err(|e| format!("Erreur sérialisation keystore: {}", e))?;
+        let mut nonce = [0u8; 24];
+        rand::thread_rng().fill_bytes(&mut nonce);
+        let ciphertext = XChaCha20Poly1305::new((&self.key).into())
+            .encrypt(XNonce::from_slice(&nonce), Payload { msg: &plaintext, aad: AAD })
+            .map_err(|_| "Erreur chiffrement keystore".to_string())?;
+
+        let file = KeystoreFile {
+            format: FORMAT_VERSION,
+            secret_source: self.source.as_str().to_string(),
+            kdf: self.kdf.clone(),
+            nonce: hex::encode(nonce),
+            ciphertext: BASE64.encode(ciphertext),
+        };
+        let content = serde_json::to_string_pretty(&file)
+            .map_err(|e| format!("Erreur sérialisation keystore: {}", e))?;
+
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Erreur création répertoire keystore: {}", e))?;
+        }
+        let tmp_path = self.path.with_extension("tmp");
+        let mut tmp = create_private_file(&tmp_path)?;
+        tmp.write_all(content.as_bytes())
+            .and_then(|_| tmp.sync_all())
+            .map_err(|e| format!("Erreur écriture keystore {:?}: {}", tmp_path, e))?;
+        drop(tmp);
+        fs::rename(&tmp_path, &self.path)
+            .map_err(|e| format!("Erreur remplacement keystore {:?}: {}", self.path, e))
+    }
+}
+
+fn new_kdf_params(memory_kib: u32, iterations: u32) -> KdfParams {
+    let mut salt = [0u8; 16];
+    rand::thread_rng().fill_bytes(&mut salt);
+    KdfParams {
+        algorithm: "argon2id".to_string(),
+        memory_kib,
+        iterations,
+        parallelism: 1,
+        salt: hex::encode(salt),
+    }
+}
+
+/// Dérive la clé XChaCha20-Poly1305 du fichier (Argon2id)
+fn derive_key(secret: &str, kdf: &KdfParams) -> Result<[u8; 32], String> {
+    if kdf.algorithm != "argon2id" {
+        return Err(format!("Dérivation de clé non supportée: {}", kdf.algorithm));
+    }
+    let salt = hex::decode(&kdf.salt).map_err(|e| format!("Sel du keystore invalide: {}", e))?;
+    let params = Params::new(kdf.memory_kib, kdf.iterations, kdf.parallelism, Some(32))
+        .map_err(|e| format!("Paramètres Argon2 invalides: {}", e))?;
+    let mut key = [0u8; 32];
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(secret.as_bytes(), &salt, &mut key)
+        .map_err(|e| format!("Erreur dérivation Argon2: {}", e))?;
+    Ok(key)
+}
+
+/// Crée un fichier lisible par le seul propriétaire
+fn create_private_file(path: &Path) -> Result<fs::File, String> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options
+        .open(path)
+        .map_err(|e| format!("Erreur création {:?}: {}", path, e))
+}
+
+/// Retire les droits du groupe et des autres utilisateurs sur un keystore existant
+fn restrict_permissions(path: &Path) {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        if let Ok(metadata) = fs::metadata(path) {
+            if metadata.permissions().mode() & 0o077 != 0 {
+                warn!("[Keystore] Droits trop larges sur {:?} : ramenés à 0600", path);
+                let _ = fs::set_permissions(path, fs::Permissions::from_mode(0o600));
+            }
+        }
+    }
+    #[cfg(not(unix))]
+    let _ = path;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_keystore_round_trip_and_wrong_passphrase() {
+        let dir = std::env::temp_dir().join(format!("airadcr-keystore-{}", uuid::Uuid::new_v4()));
+        let path = dir.join("keystore.enc");
+        let secret = KeystoreSecret::passphrase("correct horse");
+
+        let mut keystore = FileKeystore::open_with_cost(&path, &secret, 256, 1).unwrap();
+        assert!(!path.exists());
+        keystore.set("sqlcipher-encryption-key", "k-db").unwrap();
+        keystore.set("teo-hub-api-token", "t-teo").unwrap();
+        keystore.delete("teo-hub-api-token").unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(!content.contains("k-db"));
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            assert_eq!(fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o600);
+        }
+
+        let reopened = FileKeystore::open_with_cost(&path, &secret, 256, 1).unwrap();
+        assert_eq!(reopened.get("sqlcipher-encryption-key").as_deref(), Some("k-db"));
+        assert_eq!(reopened.get("teo-hub-api-token"), None);
+        assert_eq!(reopened.secret_source(), SecretSource::Passphrase);
+
+        let wrong = KeystoreSecret::passphrase("wrong");
+        assert!(FileKeystore::open_with_cost(&path, &wrong, 256, 1).is_err());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,289 @@
+// ============================================================================
+// AIRADCR Desktop - Migrations versionnées du schéma
+// ============================================================================
+// La version du schéma est suivie par `PRAGMA user_version`. À l'ouverture,
+// les migrations en attente (déclarées dans `schema::MIGRATIONS`) sont
+// appliquées une par une, chacune dans sa propre transaction, après une
+// sauvegarde vérifiée de la base. Une base créée par une version plus récente
+// de l'application est refusée plutôt que modifiée.
+// ============================================================================
+
+use chrono::Utc;
+use log::{info, warn};
+use rusqlite::{params, Connection, OpenFlags, Result as SqlResult};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use super::schema::MIGRATIONS;
+
+/// Préfixe des sauvegardes pré-migration (même répertoire et même préfixe
+/// que les backups automatiques, donc soumises à la même rétention)
+const PRE_MIGRATION_PREFIX: &str = "airadcr_backup_premigration_";
+
+/// Étape numérotée du schéma
+pub struct Migration {
+    pub version: u32,
+    pub name: &'static str,
+    pub apply: fn(&Connection) -> SqlResult<()>,
+}
+
+/// Migration déjà appliquée (historique `schema_migrations`)
+#[derive(Debug, Clone, Serialize)]
+pub struct AppliedMigration {
+    pub version: u32,
+    pub name: String,
+    pub applied_at: String,
+    pub duration_ms: i64,
+}
+
+/// Migration connue de l'application mais pas encore appliquée
+#[derive(Debug, Clone, Serialize)]
+pub struct PendingMigration {
+    pub version: u32,
+    pub name: &'static str,
+}
+
+/// Diagnostic "migrations status" (panneau de debug)
+#[derive(Debug, Clone, Serialize)]
+pub struct MigrationStatus {
+    pub current_version: u32,
+    pub latest_version: u32,
+    pub up_to_date: bool,
+    pub applied: Vec<AppliedMigration>,
+    pub pending: Vec<PendingMigration>,
+    pub pre_migration_backups: Vec<String>,
+}
+
+fn migration_error(code: i32, message: String) -> rusqlite::Error {
+    rusqlite::Error::SqliteFailure(rusqlite::ffi::Error::new(code), Some(message))
+}
+
+/// Dernière version de schéma connue de cette version de l'application
+pub fn latest_version() -> u32 {
+    MIGRATIONS.last().map(|m| m.version).unwrap_or(0)
+}
+
+/// Version du schéma enregistrée dans la base (`PRAGMA user_version`)
+pub fn current_version(conn: &Connection) -> SqlResult<u32> {
+    conn.query_row("PRAGMA user_version", [], |row| row.get(0))
+}
+
+/// Refuse une base dont le schéma est plus récent que celui de l'application
+/// (retour arrière de version) : la modifier risquerait de la corrompre.
+pub fn ensure_supported(conn: &Connection) -> SqlResult<u32> {
+    let current = current_version(conn)?;
+    let latest = latest_version();
+
+    if current > latest {
+        return Err(migration_error(
+            rusqlite::ffi::SQLITE_CANTOPEN,
+            format!(
+                "Schéma de base v{} créé par une version plus récente d'AIRADCR Desktop (schéma supporté : v{}). \
+                 Mettez à jour l'application ou restaurez un backup compatible.",
+                current, latest
+            ),
+        ));
+    }
+
+    Ok(current)
+}
+
+/// Applique les migrations en attente, chacune dans sa transaction.
+/// Une migration en échec est annulée et les suivantes ne sont pas tentées.
+pub fn apply_pending(conn: &Connection) -> SqlResult<Vec<u32>> {
+    let current = ensure_supported(conn)?;
+
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS schema_migrations (
+            version INTEGER PRIMARY KEY,
+            name TEXT NOT NULL,
+            applied_at TEXT NOT NULL,
+            duration_ms INTEGER NOT NULL
+        )",
+        [],
+    )?;
+
+    let mut applied = Vec::new();
+
+    for migration in MIGRATIONS.iter().filter(|m| m.version > current) {
+        let started = Instant::now();
+        let tx = conn.unchecked_transaction()?;
+
+        (migration.apply)(&tx).map_err(|e| {
+            warn!("[Migrations] Échec v{} ({}) : {}", migration.version, migration.name, e);
+            e
+        })?;
+
+        tx.execute(
+            "INSERT OR REPLACE INTO schema_migrations (version, name, applied_at, duration_ms)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![
+                migration.version,
+                migration.name,
+                Utc::now().to_rfc3339(),
+                started.elapsed().as_millis() as i64
+            ],
+        )?;
+        tx.execute_batch(&format!("PRAGMA user_version = {};", migration.version))?;
+        tx.commit()?;
+
+        info!("[Migrations] v{} ({}) appliquée", migration.version, migration.name);
+        applied.push(migration.version);
+    }
+
+    Ok(applied)
+}
+
+/// Répertoire des backups, à côté du fichier de base (`None` pour une base en mémoire)
+fn backup_dir(db_path: &Path) -> Option<PathBuf> {
+    db_path
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .map(|p| p.join("backups"))
+}
+
+/// Sauvegarde vérifiée de la base avant d'appliquer les migrations en attente.
+///
+/// La copie est rouverte avec la clé SQLCipher et passe `PRAGMA integrity_check`.
+/// Retourne `None` si le schéma est déjà à jour. En cas d'échec la migration
+/// ne doit pas être lancée.
+pub fn backup_before_migration(
+    conn: &Connection,
+    db_path: &Path,
+    encryption_key: &str,
+) -> SqlResult<Option<PathBuf>> {
+    let current = ensure_supported(conn)?;
+    let latest = latest_version();
+
+    if current >= latest {
+        return Ok(None);
+    }
+
+    let dir = backup_dir(db_path).ok_or_else(|| {
+        migration_error(rusqlite::ffi::SQLITE_CANTOPEN, "Répertoire de backup introuvable".to_string())
+    })?;
+    std::fs::create_dir_all(&dir).map_err(|e| {
+        migration_error(rusqlite::ffi::SQLITE_CANTOPEN, format!("Création du répertoire de backup: {}", e))
+    })?;
+
+    let backup_path = dir.join(format!(
+        "{}v{}_to_v{}_{}.db",
+        PRE_MIGRATION_PREFIX,
+        current,
+        latest,
+        Utc::now().format("%Y%m%d_%H%M%S")
+    ));
+
+    // Aucune transaction n'est ouverte : après report du journal WAL dans le
+    // fichier principal, la copie du seul fichier .db est cohérente
+    conn.execute_batch("PRAGMA wal_checkpoint(TRUNCATE);")?;
+    std::fs::copy(db_path, &backup_path).map_err(|e| {
+        migration_error(rusqlite::ffi::SQLITE_CANTOPEN, format!("Copie pré-migration: {}", e))
+    })?;
+
+    let verified = Connection::open_with_flags(&backup_path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .and_then(|copy| {
+            super::apply_sqlcipher_key(&copy, encryption_key)?;
+            copy.query_row("PRAGMA integrity_check", [], |row| row.get::<_, String>(0))
+        });
+
+    match verified {
+        Ok(result) if result == "ok" => {
+            info!("[Migrations] Backup pré-migration v{} → v{} : {:?}", current, latest, backup_path);
+            Ok(Some(backup_path))
+        }
+        Ok(result) => {
+            let _ = std::fs::remove_file(&backup_path);
+            Err(migration_error(
+                rusqlite::ffi::SQLITE_CORRUPT,
+                format!("Backup pré-migration invalide (integrity_check: {})", result),
+            ))
+        }
+        Err(e) => {
+            let _ = std::fs::remove_file(&backup_path);
+            Err(e)
+        }
+    }
+}
+
+/// État des migrations : version courante, historique, migrations en attente
+/// et backups pré-migration présents sur disque
+pub fn status(conn: &Connection, db_path: &Path) -> SqlResult<MigrationStatus> {
+    let current_version = current_version(conn)?;
+    let latest_version = latest_version();
+
+    let mut stmt = conn.prepare(
+        "SELECT version, name, applied_at, duration_ms FROM schema_migrations ORDER BY version",
+    )?;
+    let applied = stmt
+        .query_map([], |row| {
+            Ok(AppliedMigration {
+                version: row.get(0)?,
+                name: row.get(1)?,
+                applied_at: row.get(2)?,
+                duration_ms: row.get(3)?,
+            })
+        })?
+        .collect::<SqlResult<Vec<_>>>()?;
+
+    let pending = MIGRATIONS
+        .iter()
+        .filter(|m| m.version > current_version)
+        .map(|m| PendingMigration { version: m.version, name: m.name })
+        .collect();
+
+    let mut pre_migration_backups: Vec<String> = backup_dir(db_path)
+        .and_then(|dir| std::fs::read_dir(dir).ok())
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter_map(|entry| entry.file_name().into_string().ok())
+                .filter(|name| name.starts_with(PRE_MIGRATION_PREFIX))
+                .collect()
+        })
+        .unwrap_or_default();
+    pre_migration_backups.sort_by(|a, b| b.cmp(a));
+
+    Ok(MigrationStatus {
+        current_version,
+        latest_version,
+        up_to_date: current_version == latest_version,
+        applied,
+        pending,
+        pre_migration_backups,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_legacy_adoption_and_newer_schema_refused() {
+        // Versions strictement croissantes et contiguës à partir de 1
+        for (index, migration) in MIGRATIONS.iter().enumerate() {
+            assert_eq!(migration.version as usize, index + 1);
+        }
+
+        let conn = Connection::open_in_memory().unwrap();
+        super::super::schema::initialize(&conn).unwrap();
+        assert_eq!(current_version(&conn).unwrap(), latest_version());
+        assert!(apply_pending(&conn).unwrap().is_empty());
+
+        // Base antérieure au suivi de version : tables présentes, user_version = 0
+        conn.execute_batch("PRAGMA user_version = 0; DELETE FROM schema_migrations;").unwrap();
+        let applied = apply_pending(&conn).unwrap();
+        assert_eq!(applied.len(), MIGRATIONS.len());
+
+        let status = status(&conn, Path::new(":memory:")).unwrap();
+        assert!(status.up_to_date);
+        assert_eq!(status.applied.len(), MIGRATIONS.len());
+        assert!(status.pending.is_empty());
+
+        // Base issue d'une version plus récente : refusée, non modifiée
+        conn.execute_batch(&format!("PRAGMA user_version = {};", latest_version() + 1)).unwrap();
+        assert!(apply_pending(&conn).is_err());
+        assert_eq!(current_version(&conn).unwrap(), latest_version() + 1);
+    }
+}
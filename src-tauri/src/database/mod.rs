@@ -3,21 +3,48 @@
 // ============================================================================
 // Ce module gère le stockage local des rapports en attente et des clés API.
 // La base est stockée dans le répertoire AppData de l'application.
+// Écritures sérialisées sur une connexion dédiée, lectures via un pool
+// (journal WAL) : voir `pool`.
 // ============================================================================
 
 pub mod schema;
+pub mod migrations;
 pub mod queries;
 pub mod backup;
+pub mod archive;
 pub mod keychain;
+pub mod keystore;
+pub mod pool;
+pub mod restore;
+pub mod key_rotation;
+pub mod key_escrow;
 
 use rusqlite::{Connection, Result as SqlResult};
-use std::sync::Mutex;
-use std::path::PathBuf;
+use std::sync::{Arc, Mutex, RwLock};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 use log::{info, warn, error};
 
+/// Connexions ouvertes sur la base
+struct Connections {
+    /// Connexion d'écriture (SQLite n'accepte qu'un écrivain à la fois)
+    writer: Mutex<Connection>,
+    /// Pool de connexions de lecture (`query_only`)
+    readers: r2d2::Pool<pool::ReadConnectionManager>,
+}
+
 /// Structure principale de la base de données thread-safe
 pub struct Database {
-    conn: Mutex<Connection>,
+    /// Connexions courantes (None pendant une restauration : voir `restore`)
+    connections: Mutex<Option<Arc<Connections>>>,
+    /// Cible des connexions (fichier, ou URI de la base en mémoire partagée)
+    target: PathBuf,
+    db_path: PathBuf,
+    /// Clé SQLCipher de la base (None = base en mémoire non chiffrée ;
+    /// remplacée lors d'une rotation : voir `key_rotation`)
+    encryption_key: RwLock<Option<String>>,
+    /// Instance ouverte sur la base du poste (comptée pour la restauration)
+    _instance: Option<restore::InstanceGuard>,
 }
 
 /// Applique la clé de chiffrement SQLCipher sur une connexion ouverte
@@ -28,12 +55,18 @@ fn apply_sqlcipher_key(conn: &Connection, encryption_key: &str) -> SqlResult<()>
     // Vérifier que le chiffrement fonctionne en lisant une table système
     conn.execute_batch("SELECT count(*) FROM sqlite_master;")?;
     
+    // Fichiers temporaires (tris, fusions de l'index FTS5) gardés en mémoire
+    conn.execute_batch("PRAGMA temp_store = MEMORY;")?;
+    
     Ok(())
 }
 
 impl Database {
     /// Crée ou ouvre la base de données chiffrée avec SQLCipher
     pub fn new(app_data_dir: PathBuf) -> SqlResult<Self> {
+        // Refus pendant une restauration (la base est en cours de remplacement)
+        let instance = restore::InstanceGuard::acquire()?;
+        
         // Créer le répertoire si nécessaire
         std::fs::create_dir_all(&app_data_dir).ok();
         
@@ -41,7 +74,7 @@ impl Database {
         info!("[Database] Chemin: {:?}", db_path);
         
         // Récupérer la clé de chiffrement depuis le keychain OS
-        let encryption_key = keychain::get_or_create_db_encryption_key()
+        let mut encryption_key = keychain::get_or_create_db_encryption_key(&db_path)
             .map_err(|e| {
                 error!("[Database] Erreur keychain: {}", e);
                 rusqlite::Error::SqliteFailure(
@@ -51,7 +84,7 @@ impl Database {
             })?;
         
         let db_exists = db_path.exists();
-        let conn = Connection::open(&db_path)?;
+        let mut conn = Connection::open(&db_path)?;
         
         if db_exists {
             // Base existante : essayer d'abord avec la clé SQLCipher
@@ -60,17 +93,25 @@ impl Database {
                     info!("[Database] Base chiffrée SQLCipher ouverte avec succès");
                 }
                 Err(_) => {
-                    // La base existante n'est probablement pas chiffrée (migration)
-                    warn!("[Database] Base non chiffrée détectée, migration vers SQLCipher...");
                     drop(conn);
-                    Self::migrate_to_encrypted(&db_path, &encryption_key)?;
-                    let conn = Connection::open(&db_path)?;
-                    apply_sqlcipher_key(&conn, &encryption_key)?;
-                    schema::initialize(&conn)?;
-                    info!("[Database] Migration SQLCipher terminée avec succès");
-                    return Ok(Self {
-                        conn: Mutex::new(conn),
-                    });
+                    
+                    // Rotation interrompue après `PRAGMA rekey` : la base est déjà
+                    // chiffrée avec la nouvelle clé (activée par `resume_interrupted`)
+                    if let Some(rotated_key) = key_rotation::resume_interrupted(&db_path) {
+                        encryption_key = rotated_key;
+                        conn = Connection::open(&db_path)?;
+                        apply_sqlcipher_key(&conn, &encryption_key)?;
+                    } else {
+                        // La base existante n'est probablement pas chiffrée (migration)
+                        warn!("[Database] Base non chiffrée détectée, migration vers SQLCipher...");
+                        Self::migrate_to_encrypted(&db_path, &encryption_key)?;
+                        let conn = Connection::open(&db_path)?;
+                        apply_sqlcipher_key(&conn, &encryption_key)?;
+                        migrations::backup_before_migration(&conn, &db_path, &encryption_key)?;
+                        schema::initialize(&conn)?;
+                        info!("[Database] Migration SQLCipher terminée avec succès");
+                        return Self::with_read_pool(conn, db_path.clone(), db_path, Some(encryption_key), Some(instance));
+                    }
                 }
             }
         } else {
@@ -79,16 +120,139 @@ impl Database {
             info!("[Database] Nouvelle base SQLCipher créée");
         }
         
-        // Initialiser le schéma
+        // Base existante : refuser un schéma plus récent, sauvegarder avant migration
+        if db_exists {
+            migrations::backup_before_migration(&conn, &db_path, &encryption_key)?;
+        }
+        
+        // Initialiser le schéma (migrations versionnées)
         schema::initialize(&conn)?;
         
         info!("[Database] Base initialisée avec succès (chiffrée AES-256)");
         
+        Self::with_read_pool(conn, db_path.clone(), db_path, Some(encryption_key), Some(instance))
+    }
+    
+    /// Construit la base à partir de la connexion d'écriture (schéma à jour)
+    fn with_read_pool(
+        writer: Connection,
+        db_path: PathBuf,
+        target: PathBuf,
+        encryption_key: Option<String>,
+        instance: Option<restore::InstanceGuard>,
+    ) -> SqlResult<Self> {
+        let connections = Self::connect(writer, &target, encryption_key.clone())?;
+        
         Ok(Self {
-            conn: Mutex::new(conn),
+            connections: Mutex::new(Some(Arc::new(connections))),
+            target,
+            db_path,
+            encryption_key: RwLock::new(encryption_key),
+            _instance: instance,
         })
     }
     
+    /// Active WAL sur la connexion d'écriture et crée le pool de lecture
+    fn connect(writer: Connection, target: &Path, encryption_key: Option<String>) -> SqlResult<Connections> {
+        let config = &crate::config::get_config().database;
+        let busy_timeout = Duration::from_millis(config.busy_timeout_ms);
+        
+        writer.busy_timeout(busy_timeout)?;
+        let journal_mode = pool::enable_wal(&writer)?;
+        
+        let readers = pool::build_read_pool(
+            pool::ConnectionSettings {
+                target: target.to_path_buf(),
+                encryption_key,
+                busy_timeout,
+            },
+            config.read_pool_size,
+            Duration::from_secs(config.pool_timeout_secs),
+        )?;
+        
+        info!(
+            "[Database] Journal {} — pool de lecture: {} connexion(s) max, busy_timeout {}ms",
+            journal_mode, config.read_pool_size, config.busy_timeout_ms
+        );
+        
+        Ok(Connections {
+            writer: Mutex::new(writer),
+            readers,
+        })
+    }
+    
+    /// Rouvre la base du fichier `db_path` (après une restauration) : clé,
+    /// contrôle de version, sauvegarde pré-migration, migrations, pool
+    fn open_connections(&self) -> SqlResult<Connections> {
+        let encryption_key = self.encryption_key().ok_or_else(|| {
+            unavailable_error("Base en mémoire : réouverture impossible")
+        })?;
+        
+        let conn = Connection::open(&self.db_path)?;
+        apply_sqlcipher_key(&conn, &encryption_key)?;
+        migrations::backup_before_migration(&conn, &self.db_path, &encryption_key)?;
+        schema::initialize(&conn)?;
+        
+        Self::connect(conn, &self.target, Some(encryption_key))
+    }
+    
+    /// Ferme toutes les connexions après la fin des requêtes en cours (au plus
+    /// `timeout`). Les appels suivants échouent jusqu'à `resume`.
+    fn suspend(&self, timeout: Duration) -> SqlResult<()> {
+        let mut current = match self.slot()?.take() {
+            Some(connections) => connections,
+            None => return Ok(()),
+        };
+        let deadline = Instant::now() + timeout;
+        
+        loop {
+            match Arc::try_unwrap(current) {
+                Ok(connections) => {
+                    // Journal WAL reporté dans le fichier de base avant fermeture
+                    if let Ok(writer) = connections.writer.lock() {
+                        if let Err(e) = writer.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(())) {
+                            warn!("[Database] Checkpoint avant fermeture impossible: {}", e);
+                        }
+                    }
+                    drop(connections);
+                    info!("[Database] Connexions fermées");
+                    return Ok(());
+                }
+                Err(shared) if Instant::now() >= deadline => {
+                    *self.slot()? = Some(shared);
+                    return Err(rusqlite::Error::SqliteFailure(
+                        rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_BUSY),
+                        Some("Requêtes en cours : fermeture de la base impossible".to_string()),
+                    ));
+                }
+                Err(shared) => {
+                    current = shared;
+                    std::thread::sleep(Duration::from_millis(50));
+                }
+            }
+        }
+    }
+    
+    /// Rouvre les connexions fermées par `suspend` ; `connect` remet le journal
+    /// en WAL (repassé en DELETE pour un `PRAGMA rekey`)
+    fn resume(&self) -> SqlResult<()> {
+        let connections = self.open_connections()?;
+        *self.slot()? = Some(Arc::new(connections));
+        info!("[Database] Connexions rouvertes");
+        Ok(())
+    }
+    
+    fn slot(&self) -> SqlResult<std::sync::MutexGuard<'_, Option<Arc<Connections>>>> {
+        self.connections.lock().map_err(|_| rusqlite::Error::ExecuteReturnedResults)
+    }
+    
+    /// Connexions courantes (erreur pendant une restauration)
+    fn current(&self) -> SqlResult<Arc<Connections>> {
+        self.slot()?
+            .clone()
+            .ok_or_else(|| unavailable_error("Base indisponible (restauration en cours)"))
+    }
+    
     /// Migre une base non chiffrée vers SQLCipher
     fn migrate_to_encrypted(db_path: &std::path::Path, encryption_key: &str) -> SqlResult<()> {
         let backup_path = db_path.with_extension("db.unencrypted.bak");
@@ -132,24 +296,162 @@ impl Database {
     /// Crée une base en mémoire (pour les tests) — non chiffrée
     #[allow(dead_code)]
     pub fn new_in_memory() -> SqlResult<Self> {
-        let conn = Connection::open_in_memory()?;
+        // Cache partagé : le pool de lecture voit la même base que l'écrivain
+        let target = PathBuf::from(format!(
+            "file:airadcr-{}?mode=memory&cache=shared",
+            uuid::Uuid::new_v4()
+        ));
+        let conn = pool::ConnectionSettings {
+            target: target.clone(),
+            encryption_key: None,
+            busy_timeout: Duration::ZERO,
+        }
+        .open()?;
         schema::initialize(&conn)?;
         
-        Ok(Self {
-            conn: Mutex::new(conn),
-        })
+        Self::with_read_pool(conn, PathBuf::from(":memory:"), target, None, None)
+    }
+    
+    /// Chemin du fichier de base de données
+    pub fn path(&self) -> &std::path::Path {
+        &self.db_path
+    }
+    
+    /// État des migrations du schéma (diagnostic)
+    pub fn migration_status(&self) -> SqlResult<migrations::MigrationStatus> {
+        self.with_read_connection(|conn| migrations::status(conn, &self.db_path))
+    }
+    
+    /// Indique si la base est chiffrée par SQLCipher
+    pub fn is_encrypted(&self) -> bool {
+        self.encryption_key().is_some()
+    }
+    
+    /// Clé SQLCipher courante
+    fn encryption_key(&self) -> Option<String> {
+        self.encryption_key.read().ok().and_then(|key| key.clone())
+    }
+    
+    /// Remplace la clé utilisée pour les prochaines connexions (rotation)
+    fn set_encryption_key(&self, key: String) {
+        if let Ok(mut current) = self.encryption_key.write() {
+            *current = Some(key);
+        }
     }
     
-    /// Exécute une opération avec la connexion
-    pub fn with_connection<F, T>(&self, f: F) -> SqlResult<T>
+    /// Ouvre en lecture seule une copie de la base (backup) avec la clé de la base
+    pub fn open_keyed_readonly(&self, path: &std::path::Path) -> SqlResult<Connection> {
+        let conn = Connection::open_with_flags(path, rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+        if let Some(key) = &self.encryption_key() {
+            apply_sqlcipher_key(&conn, key)?;
+        }
+        Ok(conn)
+    }
+    
+    /// Copie cohérente de la base vers `dest` via l'API de backup SQLite.
+    ///
+    /// La source est une connexion du pool de lecture : la copie se fait en une
+    /// seule étape, dans un instantané WAL, sans bloquer la connexion d'écriture.
+    /// La destination est chiffrée avec la même clé SQLCipher.
+    pub fn backup_to(&self, dest: &std::path::Path) -> SqlResult<()> {
+        let mut target = Connection::open(dest)?;
+        if let Some(key) = &self.encryption_key() {
+            apply_sqlcipher_key(&target, key)?;
+        }
+        
+        self.with_read_connection(|source| {
+            use rusqlite::backup::{Backup, StepResult};
+            
+            // Toutes les pages en une étape : une seule transaction de lecture
+            match Backup::new(source, &mut target)?.step(-1)? {
+                StepResult::Done => Ok(()),
+                _ => Err(rusqlite::Error::SqliteFailure(
+                    rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_BUSY),
+                    Some("Backup interrompu (base occupée)".to_string()),
+                )),
+            }
+        })?;
+        
+        // Fichier autonome : pas de -wal/-shm à côté du backup
+        target.execute_batch("PRAGMA journal_mode = DELETE;")?;
+        Ok(())
+    }
+    
+    /// Exécute une écriture (ou une lecture qui doit voir une écriture en
+    /// cours) sur la connexion d'écriture, sérialisée par le mutex.
+    /// Chaque appel ouvre un span "db.query" localisé sur la méthode appelante.
+    #[track_caller]
+    pub fn with_write_connection<F, T>(&self, f: F) -> SqlResult<T>
     where
         F: FnOnce(&Connection) -> SqlResult<T>,
     {
-        let conn = self.conn.lock().map_err(|_| {
+        let _entered = query_span(std::panic::Location::caller(), "write").entered();
+        
+        let connections = self.current()?;
+        let conn = connections.writer.lock().map_err(|_| {
             rusqlite::Error::ExecuteReturnedResults
         })?;
         f(&conn)
     }
+    
+    /// Exécute une lecture sur une connexion du pool : ne bloque ni
+    /// l'écrivain ni les autres lecteurs (WAL)
+    #[track_caller]
+    pub fn with_read_connection<F, T>(&self, f: F) -> SqlResult<T>
+    where
+        F: FnOnce(&Connection) -> SqlResult<T>,
+    {
+        let _entered = query_span(std::panic::Location::caller(), "read").entered();
+        
+        let connections = self.current()?;
+        let conn = connections.readers.get().map_err(pool::pool_error)?;
+        f(&conn)
+    }
+    
+    /// Exécute plusieurs écritures en une transaction sur la connexion
+    /// d'écriture : tout est annulé si `f` échoue.
+    #[track_caller]
+    pub fn with_write_transaction<F, T>(&self, f: F) -> SqlResult<T>
+    where
+        F: FnOnce(&Connection) -> SqlResult<T>,
+    {
+        self.with_write_connection(|conn| {
+            let tx = conn.unchecked_transaction()?;
+            let result = f(&tx)?;
+            tx.commit()?;
+            Ok(result)
+        })
+    }
+    
+    /// État du pool de lecture : (connexions ouvertes, connexions inactives)
+    pub fn read_pool_state(&self) -> (u32, u32) {
+        match self.current() {
+            Ok(connections) => {
+                let state = connections.readers.state();
+                (state.connections, state.idle_connections)
+            }
+            Err(_) => (0, 0),
+        }
+    }
+}
+
+/// Erreur d'une base fermée ou non rouvrable
+fn unavailable_error(message: &str) -> rusqlite::Error {
+    rusqlite::Error::SqliteFailure(
+        rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_CANTOPEN),
+        Some(message.to_string()),
+    )
+}
+
+/// Span "db.query" d'un accès à la base (chemin de lecture ou d'écriture)
+fn query_span(caller: &'static std::panic::Location<'static>, path: &'static str) -> tracing::Span {
+    tracing::info_span!(
+        "db.query",
+        db.system = "sqlite",
+        db.path = path,
+        code.filepath = caller.file(),
+        code.lineno = caller.line(),
+    )
 }
 
 // ============================================================================
@@ -174,7 +476,7 @@ impl Database {
         created_at: &str,
         expires_at: &str,
     ) -> SqlResult<()> {
-        self.with_connection(|conn| {
+        self.with_write_connection(|conn| {
             queries::insert_pending_report(
                 conn,
                 id,
@@ -196,89 +498,239 @@ impl Database {
     
     /// Récupère un rapport par son technical_id
     pub fn get_pending_report(&self, technical_id: &str) -> SqlResult<Option<queries::PendingReport>> {
-        self.with_connection(|conn| {
+        self.with_read_connection(|conn| {
             queries::get_pending_report_by_tid(conn, technical_id)
         })
     }
     
     /// Marque un rapport comme récupéré
     pub fn mark_as_retrieved(&self, technical_id: &str) -> SqlResult<bool> {
-        self.with_connection(|conn| {
+        self.with_write_connection(|conn| {
             queries::mark_as_retrieved(conn, technical_id)
         })
     }
     
     /// Supprime un rapport
     pub fn delete_pending_report(&self, technical_id: &str) -> SqlResult<bool> {
-        self.with_connection(|conn| {
+        self.with_write_connection(|conn| {
             queries::delete_pending_report(conn, technical_id)
         })
     }
     
     /// Nettoie les rapports expirés
     pub fn cleanup_expired_reports(&self) -> SqlResult<usize> {
-        self.with_connection(|conn| {
+        self.with_write_connection(|conn| {
             queries::cleanup_expired_reports(conn)
         })
     }
     
+    /// Supprime les verrous expirés ou orphelins
+    pub fn cleanup_stale_report_locks(&self) -> SqlResult<usize> {
+        self.with_write_connection(|conn| {
+            queries::cleanup_stale_report_locks(conn)
+        })
+    }
+    
     /// Valide une clé API
     pub fn validate_api_key(&self, key_prefix: &str, key_hash: &str) -> SqlResult<bool> {
-        self.with_connection(|conn| {
+        self.with_read_connection(|conn| {
             queries::validate_api_key(conn, key_prefix, key_hash)
         })
     }
     
+    /// Identité de la clé API active correspondant au préfixe et au hash
+    pub fn find_api_key(&self, key_prefix: &str, key_hash: &str) -> SqlResult<Option<queries::ApiKeyIdentity>> {
+        self.with_read_connection(|conn| {
+            queries::find_api_key(conn, key_prefix, key_hash)
+        })
+    }
+    
     /// Ajoute une clé API (pour l'administration)
     pub fn add_api_key(&self, id: &str, key_prefix: &str, key_hash: &str, name: &str) -> SqlResult<()> {
-        self.with_connection(|conn| {
+        self.with_write_connection(|conn| {
             queries::add_api_key(conn, id, key_prefix, key_hash, name)
         })
     }
     
     /// Liste toutes les clés API
     pub fn list_api_keys(&self) -> SqlResult<Vec<(String, String, String, bool, String)>> {
-        self.with_connection(|conn| {
+        self.with_read_connection(|conn| {
             queries::list_api_keys(conn)
         })
     }
     
     /// Révoque une clé API (soft-delete)
     pub fn revoke_api_key(&self, key_prefix: &str) -> SqlResult<bool> {
-        self.with_connection(|conn| {
+        self.with_write_connection(|conn| {
             queries::revoke_api_key(conn, key_prefix)
         })
     }
     
-    /// Recherche un rapport par identifiants RIS
-    pub fn find_pending_report_by_identifiers(
+    /// Recherche les rapports candidats pour des identifiants RIS (classés par score)
+    pub fn find_report_candidates(
         &self,
-        patient_id: Option<&str>,
-        accession_number: Option<&str>,
-        exam_uid: Option<&str>,
-    ) -> SqlResult<Option<queries::PendingReport>> {
-        self.with_connection(|conn| {
-            queries::find_pending_report_by_identifiers(conn, patient_id, accession_number, exam_uid)
+        identifiers: &queries::ReportIdentifiers,
+        mode: queries::MatchMode,
+        preferred_user: Option<&str>,
+        limit: i64,
+    ) -> SqlResult<Vec<queries::MatchCandidate>> {
+        self.with_read_connection(|conn| {
+            queries::find_report_candidates(conn, identifiers, mode, preferred_user, limit)
+        })
+    }
+    
+    /// Recherche plein texte dans le contenu structuré des rapports
+    pub fn search_reports(&self, query: &str, modality: Option<&str>, limit: i64) -> SqlResult<Vec<queries::SearchHit>> {
+        self.with_read_connection(|conn| {
+            queries::search_reports(conn, query, modality, limit)
+        })
+    }
+    
+    /// Crée un modèle de rapport
+    pub fn insert_report_template(
+        &self,
+        id: &str,
+        fields: &queries::ReportTemplateFields,
+        created_by: Option<&str>,
+    ) -> SqlResult<()> {
+        self.with_write_connection(|conn| {
+            queries::insert_report_template(conn, id, fields, created_by)
+        })
+    }
+    
+    /// Remplace les champs d'un modèle de rapport
+    pub fn update_report_template(&self, id: &str, fields: &queries::ReportTemplateFields) -> SqlResult<bool> {
+        self.with_write_connection(|conn| {
+            queries::update_report_template(conn, id, fields)
+        })
+    }
+    
+    /// Récupère un modèle de rapport par id ou par nom
+    pub fn get_report_template(&self, id_or_name: &str) -> SqlResult<Option<queries::ReportTemplate>> {
+        self.with_read_connection(|conn| {
+            queries::get_report_template(conn, id_or_name)
+        })
+    }
+    
+    /// Liste les modèles de rapports
+    pub fn list_report_templates(
+        &self,
+        modality: Option<&str>,
+        body_part: Option<&str>,
+        language: Option<&str>,
+    ) -> SqlResult<Vec<queries::ReportTemplate>> {
+        self.with_read_connection(|conn| {
+            queries::list_report_templates(conn, modality, body_part, language)
+        })
+    }
+    
+    /// Supprime un modèle de rapport (par identifiant ou nom)
+    pub fn delete_report_template(&self, id_or_name: &str) -> SqlResult<bool> {
+        self.with_write_connection(|conn| {
+            queries::delete_report_template(conn, id_or_name)
+        })
+    }
+    
+    /// Attribue un rapport à un radiologue (None = retire l'attribution)
+    pub fn assign_pending_report(&self, technical_id: &str, user_id: Option<&str>) -> SqlResult<bool> {
+        self.with_write_connection(|conn| {
+            queries::assign_pending_report(conn, technical_id, user_id)
+        })
+    }
+    
+    /// Liste de travail triée par priorité, échéance et ancienneté
+    pub fn list_worklist(&self, filter: &queries::WorklistFilter) -> SqlResult<Vec<queries::WorklistItem>> {
+        self.with_read_connection(|conn| {
+            queries::list_worklist(conn, filter)
+        })
+    }
+    
+    /// Définit la priorité et l'échéance de lecture d'un rapport
+    pub fn set_report_schedule(&self, technical_id: &str, priority: &str, due_at: Option<&str>) -> SqlResult<bool> {
+        self.with_write_connection(|conn| {
+            queries::set_report_schedule(conn, technical_id, priority, due_at)
+        })
+    }
+    
+    /// Change la priorité d'un rapport
+    pub fn set_report_priority(&self, technical_id: &str, priority: &str) -> SqlResult<bool> {
+        self.with_write_connection(|conn| {
+            queries::set_report_priority(conn, technical_id, priority)
+        })
+    }
+    
+    /// Verrou actif d'un rapport
+    pub fn get_report_lock(&self, technical_id: &str) -> SqlResult<Option<queries::ReportLock>> {
+        self.with_read_connection(|conn| {
+            queries::get_report_lock(conn, technical_id)
+        })
+    }
+    
+    /// Prend ou renouvelle le verrou d'un rapport
+    pub fn acquire_report_lock(
+        &self,
+        technical_id: &str,
+        holder_id: &str,
+        holder_name: Option<&str>,
+        workstation: &str,
+        lease_secs: i64,
+    ) -> SqlResult<queries::LockAcquisition> {
+        self.with_write_connection(|conn| {
+            queries::acquire_report_lock(conn, technical_id, holder_id, holder_name, workstation, lease_secs)
+        })
+    }
+    
+    /// Prolonge le bail d'un verrou détenu
+    pub fn heartbeat_report_lock(
+        &self,
+        technical_id: &str,
+        holder_id: &str,
+        workstation: &str,
+        lease_secs: i64,
+    ) -> SqlResult<Option<queries::ReportLock>> {
+        self.with_write_connection(|conn| {
+            queries::heartbeat_report_lock(conn, technical_id, holder_id, workstation, lease_secs)
+        })
+    }
+    
+    /// Libère un verrou (None = libération forcée)
+    pub fn release_report_lock(&self, technical_id: &str, holder: Option<(&str, &str)>) -> SqlResult<bool> {
+        self.with_write_connection(|conn| {
+            queries::release_report_lock(conn, technical_id, holder)
+        })
+    }
+    
+    /// Liste les verrous actifs
+    pub fn list_report_locks(&self) -> SqlResult<Vec<queries::ReportLock>> {
+        self.with_read_connection(|conn| {
+            queries::list_report_locks(conn)
         })
     }
     
     /// Liste tous les rapports (pour Debug Panel)
     pub fn list_all_pending_reports(&self) -> SqlResult<Vec<queries::PendingReportSummary>> {
-        self.with_connection(|conn| {
+        self.with_read_connection(|conn| {
             queries::list_all_pending_reports(conn)
         })
     }
     
+    /// Valeurs d'une colonne d'identifiant (ré-identification d'un pseudonyme)
+    pub fn list_report_identifiers(&self, column: &str) -> SqlResult<Vec<(String, String)>> {
+        self.with_read_connection(|conn| {
+            queries::list_report_identifiers(conn, column)
+        })
+    }
+    
     /// Récupère les statistiques de la base
     pub fn get_database_stats(&self) -> SqlResult<queries::DatabaseStats> {
-        self.with_connection(|conn| {
+        self.with_read_connection(|conn| {
             queries::get_database_stats(conn)
         })
     }
     
     /// Liste les clés API (version simplifiée pour Debug Panel)
     pub fn list_api_keys_summary(&self) -> SqlResult<Vec<queries::ApiKeySummary>> {
-        self.with_connection(|conn| {
+        self.with_read_connection(|conn| {
             queries::list_api_keys_summary(conn)
         })
     }
@@ -301,8 +753,9 @@ impl Database {
         request_id: &str,
         duration_ms: i64,
         error_message: Option<&str>,
+        user_id: Option<&str>,
     ) -> SqlResult<i64> {
-        self.with_connection(|conn| {
+        self.with_write_connection(|conn| {
             queries::insert_access_log(
                 conn,
                 timestamp,
@@ -316,38 +769,239 @@ impl Database {
                 request_id,
                 duration_ms,
                 error_message,
+                user_id,
             )
         })
     }
     
     /// Liste les logs d'accès récents
     pub fn list_access_logs(&self, limit: i64, offset: i64) -> SqlResult<Vec<queries::AccessLogSummary>> {
-        self.with_connection(|conn| {
+        self.with_read_connection(|conn| {
             queries::list_access_logs(conn, limit, offset)
         })
     }
     
     /// Récupère les statistiques des logs d'accès
     pub fn get_access_logs_stats(&self) -> SqlResult<queries::AccessLogsStats> {
-        self.with_connection(|conn| {
+        self.with_read_connection(|conn| {
             queries::get_access_logs_stats(conn)
         })
     }
     
     /// Nettoie les vieux logs d'accès
     pub fn cleanup_old_access_logs(&self, days: i64) -> SqlResult<usize> {
-        self.with_connection(|conn| {
+        self.with_write_connection(|conn| {
             queries::cleanup_old_access_logs(conn, days)
         })
     }
     
+    // =========================================================================
+    // Rétention et purge sécurisée
+    // =========================================================================
+    
+    /// Liste les rapports évalués par les règles de rétention
+    pub fn list_retention_candidates(&self) -> SqlResult<Vec<queries::RetentionCandidate>> {
+        self.with_read_connection(|conn| {
+            queries::list_retention_candidates(conn)
+        })
+    }
+    
+    /// Supprime des rapports (PRAGMA secure_delete si demandé)
+    pub fn purge_pending_reports(&self, technical_ids: &[String], secure_delete: bool) -> SqlResult<usize> {
+        self.with_write_connection(|conn| {
+            queries::purge_pending_reports(conn, technical_ids, secure_delete)
+        })
+    }
+    
+    /// Nombre de logs d'accès antérieurs à une date
+    pub fn count_access_logs_before(&self, cutoff: &str) -> SqlResult<i64> {
+        self.with_read_connection(|conn| {
+            queries::count_access_logs_before(conn, cutoff)
+        })
+    }
+    
+    /// Supprime les logs d'accès antérieurs à une date
+    pub fn purge_access_logs_before(&self, cutoff: &str, secure_delete: bool) -> SqlResult<usize> {
+        self.with_write_connection(|conn| {
+            queries::purge_access_logs_before(conn, cutoff, secure_delete)
+        })
+    }
+    
+    /// Enregistre une purge dans le journal de rétention
+    pub fn insert_retention_purge(
+        &self,
+        purged_at: &str,
+        rule_id: &str,
+        target: &str,
+        item_count: i64,
+        technical_ids: &[String],
+        secure_delete: bool,
+    ) -> SqlResult<i64> {
+        self.with_write_connection(|conn| {
+            queries::insert_retention_purge(conn, purged_at, rule_id, target, item_count, technical_ids, secure_delete)
+        })
+    }
+    
+    /// Liste les dernières purges
+    pub fn list_retention_purges(&self, limit: i64) -> SqlResult<Vec<queries::RetentionPurgeEntry>> {
+        self.with_read_connection(|conn| {
+            queries::list_retention_purges(conn, limit)
+        })
+    }
+    
+    // =========================================================================
+    // Droits des personnes (RGPD)
+    // =========================================================================
+    
+    /// Rapports d'une personne (patient_id et/ou accession_number)
+    pub fn find_subject_reports(
+        &self,
+        patient_id: Option<&str>,
+        accession_number: Option<&str>,
+    ) -> SqlResult<Vec<queries::PendingReport>> {
+        self.with_read_connection(|conn| {
+            queries::find_subject_reports(conn, patient_id, accession_number)
+        })
+    }
+    
+    /// Logs d'accès référençant une des valeurs
+    pub fn find_access_logs_referencing(&self, needles: &[String]) -> SqlResult<Vec<queries::AccessLogEntry>> {
+        self.with_read_connection(|conn| {
+            queries::find_access_logs_referencing(conn, needles)
+        })
+    }
+    
+    /// Efface ou anonymise les enregistrements d'une personne
+    pub fn erase_subject_records(
+        &self,
+        technical_ids: &[String],
+        access_log_ids: &[i64],
+        anonymise: bool,
+        secure_delete: bool,
+    ) -> SqlResult<queries::SubjectErasure> {
+        self.with_write_connection(|conn| {
+            queries::erase_subject_records(conn, technical_ids, access_log_ids, anonymise, secure_delete)
+        })
+    }
+    
+    /// Enregistre un certificat d'effacement
+    pub fn insert_erasure_certificate(
+        &self,
+        certificate_id: &str,
+        issued_at: &str,
+        mode: &str,
+        certificate: &str,
+    ) -> SqlResult<()> {
+        self.with_write_connection(|conn| {
+            queries::insert_erasure_certificate(conn, certificate_id, issued_at, mode, certificate)
+        })
+    }
+    
+    /// Liste les certificats d'effacement
+    pub fn list_erasure_certificates(&self, limit: i64) -> SqlResult<Vec<queries::ErasureCertificateEntry>> {
+        self.with_read_connection(|conn| {
+            queries::list_erasure_certificates(conn, limit)
+        })
+    }
+    
+    /// Enregistre une rotation de la clé SQLCipher
+    pub fn insert_key_rotation(&self, entry: &queries::KeyRotationEntry) -> SqlResult<()> {
+        self.with_write_connection(|conn| {
+            queries::insert_key_rotation(conn, entry)
+        })
+    }
+    
+    /// Liste les rotations de la clé SQLCipher
+    pub fn list_key_rotations(&self, limit: i64) -> SqlResult<Vec<queries::KeyRotationEntry>> {
+        self.with_read_connection(|conn| {
+            queries::list_key_rotations(conn, limit)
+        })
+    }
+    
+    /// Date de mise en service de la clé SQLCipher courante
+    pub fn key_in_use_since(&self) -> SqlResult<Option<String>> {
+        self.with_read_connection(|conn| {
+            queries::key_in_use_since(conn)
+        })
+    }
+    
+    // =========================================================================
+    // Profils radiologues
+    // =========================================================================
+    
+    /// Crée un profil radiologue
+    pub fn create_user(&self, id: &str, username: &str, display_name: &str, rpps: Option<&str>) -> SqlResult<()> {
+        self.with_write_connection(|conn| {
+            queries::create_user(conn, id, username, display_name, rpps)
+        })
+    }
+    
+    /// Liste les profils radiologues
+    pub fn list_users(&self, active_only: bool) -> SqlResult<Vec<queries::UserProfile>> {
+        self.with_read_connection(|conn| {
+            queries::list_users(conn, active_only)
+        })
+    }
+    
+    /// Récupère un profil par id ou username
+    pub fn find_user(&self, id_or_username: &str) -> SqlResult<Option<queries::UserProfile>> {
+        self.with_read_connection(|conn| {
+            queries::find_user(conn, id_or_username)
+        })
+    }
+    
+    /// Désactive un profil
+    pub fn deactivate_user(&self, user_id: &str) -> SqlResult<bool> {
+        self.with_write_connection(|conn| {
+            queries::deactivate_user(conn, user_id)
+        })
+    }
+    
+    /// Identifiant du radiologue actif
+    pub fn get_active_user_id(&self) -> SqlResult<Option<String>> {
+        self.with_read_connection(|conn| {
+            queries::get_active_user_id(conn)
+        })
+    }
+    
+    /// Profil du radiologue actif
+    pub fn get_active_user(&self) -> SqlResult<Option<queries::UserProfile>> {
+        self.with_read_connection(|conn| {
+            match queries::get_active_user_id(conn)? {
+                Some(id) => queries::find_user(conn, &id),
+                None => Ok(None),
+            }
+        })
+    }
+    
+    /// Change le radiologue actif
+    pub fn set_active_user(&self, user_id: Option<&str>) -> SqlResult<()> {
+        self.with_write_connection(|conn| {
+            queries::set_active_user(conn, user_id)
+        })
+    }
+    
+    /// Liste les préférences d'un radiologue
+    pub fn list_user_preferences(&self, user_id: &str) -> SqlResult<Vec<queries::UserPreference>> {
+        self.with_read_connection(|conn| {
+            queries::list_user_preferences(conn, user_id)
+        })
+    }
+    
+    /// Enregistre une préférence d'un radiologue
+    pub fn set_user_preference(&self, user_id: &str, pref_key: &str, value: &str) -> SqlResult<()> {
+        self.with_write_connection(|conn| {
+            queries::set_user_preference(conn, user_id, pref_key, value)
+        })
+    }
+    
     // =========================================================================
     // Méthodes pour métriques Prometheus (Phase 2)
     // =========================================================================
     
     /// Compte les rapports en attente
     pub fn count_pending_reports(&self) -> SqlResult<i64> {
-        self.with_connection(|conn| {
+        self.with_read_connection(|conn| {
             conn.query_row(
                 "SELECT COUNT(*) FROM pending_reports WHERE status = 'pending'",
                 [],
@@ -358,7 +1012,7 @@ impl Database {
     
     /// Compte les clés API actives
     pub fn count_active_api_keys(&self) -> SqlResult<i64> {
-        self.with_connection(|conn| {
+        self.with_read_connection(|conn| {
             conn.query_row(
                 "SELECT COUNT(*) FROM api_keys WHERE is_active = 1",
                 [],
@@ -367,9 +1021,21 @@ impl Database {
         })
     }
     
+    /// Vérifie l'accès effectif à la base (clé SQLCipher appliquée)
+    /// Retourne la version SQLCipher, ou None si la base n'est pas chiffrée
+    pub fn check_access(&self) -> SqlResult<Option<String>> {
+        self.with_read_connection(|conn| {
+            conn.query_row("SELECT count(*) FROM sqlite_master", [], |row| row.get::<_, i64>(0))?;
+            let cipher_version: Option<String> = conn
+                .query_row("PRAGMA cipher_version", [], |row| row.get(0))
+                .ok();
+            Ok(cipher_version.filter(|v| !v.is_empty()))
+        })
+    }
+    
     /// Récupère la taille de la base de données
     pub fn get_database_size(&self) -> SqlResult<u64> {
-        self.with_connection(|conn| {
+        self.with_read_connection(|conn| {
             let page_count: i64 = conn.query_row("PRAGMA page_count", [], |row| row.get(0))?;
             let page_size: i64 = conn.query_row("PRAGMA page_size", [], |row| row.get(0))?;
             Ok((page_count * page_size) as u64)
@@ -0,0 +1,172 @@
+// ============================================================================
+// AIRADCR Desktop - Connexions SQLCipher (pool de lecture, WAL)
+// ============================================================================
+// Une seule connexion écrit (SQLite n'accepte qu'un écrivain à la fois) ; les
+// lectures passent par un pool r2d2 de connexions chiffrées en lecture seule
+// (`query_only`). En mode WAL, les lectures ne bloquent pas l'écriture et
+// inversement : une requête longue sur access_logs ne retarde plus le
+// stockage d'un rapport.
+// ============================================================================
+
+use rusqlite::{Connection, OpenFlags, Result as SqlResult};
+use scheduled_thread_pool::ScheduledThreadPool;
+use std::path::PathBuf;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+/// Thread de maintenance r2d2 partagé par tous les pools : les commandes
+/// Tauri ouvrent une `Database` par appel, sans recréer de threads à chaque fois
+static MAINTENANCE_THREADS: OnceLock<Arc<ScheduledThreadPool>> = OnceLock::new();
+
+/// Paramètres appliqués à chaque connexion ouverte sur la base
+#[derive(Debug, Clone)]
+pub struct ConnectionSettings {
+    /// Fichier de base, ou URI `file:...?mode=memory&cache=shared` pour les tests
+    pub target: PathBuf,
+    /// Clé SQLCipher (None = base en mémoire non chiffrée)
+    pub encryption_key: Option<String>,
+    pub busy_timeout: Duration,
+}
+
+impl ConnectionSettings {
+    fn flags(&self) -> OpenFlags {
+        OpenFlags::SQLITE_OPEN_READ_WRITE
+            | OpenFlags::SQLITE_OPEN_CREATE
+            | OpenFlags::SQLITE_OPEN_URI
+            | OpenFlags::SQLITE_OPEN_NO_MUTEX
+    }
+
+    /// Ouvre une connexion, applique la clé puis les réglages de concurrence
+    pub fn open(&self) -> SqlResult<Connection> {
+        let conn = Connection::open_with_flags(&self.target, self.flags())?;
+        if let Some(key) = &self.encryption_key {
+            super::apply_sqlcipher_key(&conn, key)?;
+        }
+        conn.busy_timeout(self.busy_timeout)?;
+        Ok(conn)
+    }
+}
+
+/// Passe la base en journal WAL (connexion d'écriture, une fois à l'ouverture).
+/// Retourne le mode effectif ("memory" pour une base en mémoire).
+pub fn enable_wal(conn: &Connection) -> SqlResult<String> {
+    let mode: String = conn.query_row("PRAGMA journal_mode = WAL", [], |row| row.get(0))?;
+    // NORMAL suffit en WAL : aucune corruption possible, seule la dernière
+    // transaction peut être perdue en cas de coupure de courant
+    conn.execute_batch("PRAGMA synchronous = NORMAL;")?;
+    Ok(mode)
+}
+
+/// Gestionnaire r2d2 des connexions de lecture
+pub struct ReadConnectionManager {
+    settings: ConnectionSettings,
+}
+
+impl ReadConnectionManager {
+    pub fn new(settings: ConnectionSettings) -> Self {
+        Self { settings }
+    }
+}
+
+impl r2d2::ManageConnection for ReadConnectionManager {
+    type Connection = Connection;
+    type Error = rusqlite::Error;
+
+    fn connect(&self) -> SqlResult<Connection> {
+        let conn = self.settings.open()?;
+        // Garde-fou : une écriture sur le chemin de lecture échoue au lieu
+        // de concurrencer la connexion d'écriture
+        conn.execute_batch("PRAGMA query_only = ON;")?;
+        Ok(conn)
+    }
+
+    fn is_valid(&self, conn: &mut Connection) -> SqlResult<()> {
+        conn.execute_batch("SELECT 1;")
+    }
+
+    fn has_broken(&self, _conn: &mut Connection) -> bool {
+        false
+    }
+}
+
+/// Construit le pool de lecture (connexions ouvertes à la demande)
+pub fn build_read_pool(
+    settings: ConnectionSettings,
+    max_size: u32,
+    checkout_timeout: Duration,
+) -> SqlResult<r2d2::Pool<ReadConnectionManager>> {
+    r2d2::Pool::builder()
+        .max_size(max_size.max(1))
+        .min_idle(Some(0))
+        .connection_timeout(checkout_timeout)
+        .test_on_check_out(false)
+        .thread_pool(Arc::clone(MAINTENANCE_THREADS.get_or_init(|| {
+            Arc::new(
+                ScheduledThreadPool::builder()
+                    .num_threads(1)
+                    .thread_name_pattern("airadcr-db-pool-{}")
+                    .build(),
+            )
+        })))
+        .build(ReadConnectionManager::new(settings))
+        .map_err(pool_error)
+}
+
+/// Convertit une erreur du pool (délai d'attente dépassé, ouverture impossible)
+pub fn pool_error(e: r2d2::Error) -> rusqlite::Error {
+    rusqlite::Error::SqliteFailure(
+        rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_BUSY),
+        Some(format!("Pool de connexions: {}", e)),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::Database;
+
+    #[test]
+    fn test_read_pool_sees_writes_and_rejects_writes() {
+        let db = Database::new_in_memory().unwrap();
+        db.add_api_key("k-pool", "airadcr_", "hash", "Pool").unwrap();
+
+        // Lecture par le pool : même base que la connexion d'écriture
+        assert!(db.validate_api_key("airadcr_", "hash").unwrap());
+
+        // Chemin de lecture protégé contre les écritures
+        let keys = db.count_active_api_keys().unwrap();
+        let write_on_reader = db.with_read_connection(|conn| {
+            conn.execute("DELETE FROM api_keys", [])
+        });
+        assert!(write_on_reader.is_err());
+        assert_eq!(db.count_active_api_keys().unwrap(), keys);
+
+        let (connections, _idle) = db.read_pool_state();
+        assert!(connections >= 1);
+    }
+
+    #[test]
+    fn test_write_transaction_rolls_back_on_error() {
+        use super::super::queries;
+
+        let db = Database::new_in_memory().unwrap();
+        let store = |fail: bool| {
+            db.with_write_transaction(|conn| {
+                queries::insert_pending_report(
+                    conn, "id-tx", "TID-TX", None, None, None, None, "{}", "teo_hub",
+                    None, None, None, "2024-01-01T00:00:00Z", "2999-01-01T00:00:00Z",
+                )?;
+                if fail {
+                    conn.execute("UPDATE missing_table SET x = 1", [])?;
+                }
+                queries::set_report_schedule(conn, "TID-TX", "stat", None)
+            })
+        };
+
+        // Échec après l'insertion : le rapport n'est pas conservé
+        assert!(store(true).is_err());
+        assert!(db.get_pending_report("TID-TX").unwrap().is_none());
+
+        assert!(store(false).unwrap());
+        assert_eq!(db.get_pending_report("TID-TX").unwrap().unwrap().priority, "stat");
+    }
+}
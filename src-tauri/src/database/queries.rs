@@ -30,6 +30,41 @@ pub struct PendingReport {
     pub created_at: String,
     pub expires_at: String,
     pub retrieved_at: Option<String>,
+    // Radiologue attribué (users.id)
+    pub assigned_to: Option<String>,
+    // Liste de travail
+    pub priority: String,
+    pub due_at: Option<String>,
+}
+
+/// Colonnes lues par `row_to_pending_report` (dans cet ordre)
+const PENDING_REPORT_COLUMNS: &str =
+    "id, technical_id, patient_id, exam_uid, accession_number, study_instance_uid,
+     structured_data, source_type, ai_modules, modality, metadata, status, created_at, expires_at, retrieved_at,
+     assigned_to, priority, due_at";
+
+/// Construit un PendingReport à partir d'une ligne SELECT PENDING_REPORT_COLUMNS
+fn row_to_pending_report(row: &rusqlite::Row) -> SqlResult<PendingReport> {
+    Ok(PendingReport {
+        id: row.get(0)?,
+        technical_id: row.get(1)?,
+        patient_id: row.get(2)?,
+        exam_uid: row.get(3)?,
+        accession_number: row.get(4)?,
+        study_instance_uid: row.get(5)?,
+        structured_data: row.get(6)?,
+        source_type: row.get(7)?,
+        ai_modules: row.get(8)?,
+        modality: row.get(9)?,
+        metadata: row.get(10)?,
+        status: row.get(11)?,
+        created_at: row.get(12)?,
+        expires_at: row.get(13)?,
+        retrieved_at: row.get(14)?,
+        assigned_to: row.get(15)?,
+        priority: row.get(16)?,
+        due_at: row.get(17)?,
+    })
 }
 
 // ============================================================================
@@ -53,12 +88,33 @@ pub fn insert_pending_report(
     created_at: &str,
     expires_at: &str,
 ) -> SqlResult<()> {
-    // Utiliser INSERT OR REPLACE pour écraser un rapport existant avec le même technical_id
+    // Un rapport existant avec le même technical_id est écrasé. Pas d'INSERT OR
+    // REPLACE : sa suppression implicite ne déclenche pas le trigger FTS de
+    // suppression, l'ancien texte resterait indexé. Le trigger de mise à jour
+    // réindexe structured_data.
     conn.execute(
-        "INSERT OR REPLACE INTO pending_reports 
+        "INSERT INTO pending_reports 
          (id, technical_id, patient_id, exam_uid, accession_number, study_instance_uid,
           structured_data, source_type, ai_modules, modality, metadata, status, created_at, expires_at)
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, 'pending', ?12, ?13)",
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, 'pending', ?12, ?13)
+         ON CONFLICT(technical_id) DO UPDATE SET
+            id = excluded.id,
+            patient_id = excluded.patient_id,
+            exam_uid = excluded.exam_uid,
+            accession_number = excluded.accession_number,
+            study_instance_uid = excluded.study_instance_uid,
+            structured_data = excluded.structured_data,
+            source_type = excluded.source_type,
+            ai_modules = excluded.ai_modules,
+            modality = excluded.modality,
+            metadata = excluded.metadata,
+            status = 'pending',
+            created_at = excluded.created_at,
+            expires_at = excluded.expires_at,
+            retrieved_at = NULL,
+            assigned_to = NULL,
+            priority = 'routine',
+            due_at = NULL",
         params![id, technical_id, patient_id, exam_uid, accession_number, study_instance_uid,
                 structured_data, source_type, ai_modules, modality, metadata, created_at, expires_at],
     )?;
@@ -68,32 +124,14 @@ pub fn insert_pending_report(
 
 /// Récupère un rapport par son technical_id (uniquement si non expiré)
 pub fn get_pending_report_by_tid(conn: &Connection, technical_id: &str) -> SqlResult<Option<PendingReport>> {
-    let mut stmt = conn.prepare(
-        "SELECT id, technical_id, patient_id, exam_uid, accession_number, study_instance_uid,
-                structured_data, source_type, ai_modules, modality, metadata, status, created_at, expires_at, retrieved_at
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {}
          FROM pending_reports
-         WHERE technical_id = ?1 AND status != 'expired' AND expires_at > datetime('now')"
-    )?;
+         WHERE technical_id = ?1 AND status != 'expired' AND expires_at > datetime('now')",
+        PENDING_REPORT_COLUMNS
+    ))?;
     
-    let result = stmt.query_row([technical_id], |row| {
-        Ok(PendingReport {
-            id: row.get(0)?,
-            technical_id: row.get(1)?,
-            patient_id: row.get(2)?,
-            exam_uid: row.get(3)?,
-            accession_number: row.get(4)?,
-            study_instance_uid: row.get(5)?,
-            structured_data: row.get(6)?,
-            source_type: row.get(7)?,
-            ai_modules: row.get(8)?,
-            modality: row.get(9)?,
-            metadata: row.get(10)?,
-            status: row.get(11)?,
-            created_at: row.get(12)?,
-            expires_at: row.get(13)?,
-            retrieved_at: row.get(14)?,
-        })
-    });
+    let result = stmt.query_row([technical_id], row_to_pending_report);
     
     match result {
         Ok(report) => Ok(Some(report)),
@@ -119,6 +157,7 @@ pub fn delete_pending_report(conn: &Connection, technical_id: &str) -> SqlResult
         "DELETE FROM pending_reports WHERE technical_id = ?1",
         [technical_id],
     )?;
+    conn.execute("DELETE FROM report_locks WHERE technical_id = ?1", [technical_id])?;
     
     Ok(rows > 0)
 }
@@ -134,91 +173,721 @@ pub fn cleanup_expired_reports(conn: &Connection) -> SqlResult<usize> {
         println!("🧹 [Database] {} rapport(s) expiré(s) supprimé(s)", rows);
     }
     
+    cleanup_stale_report_locks(conn)?;
+    
     Ok(rows)
 }
 
-/// Recherche un rapport par identifiants RIS (patient_id, accession_number, exam_uid)
-/// Retourne le premier rapport correspondant non expiré
-pub fn find_pending_report_by_identifiers(
-    conn: &Connection,
-    patient_id: Option<&str>,
-    accession_number: Option<&str>,
-    exam_uid: Option<&str>,
-) -> SqlResult<Option<PendingReport>> {
-    // Construire la requête dynamiquement selon les paramètres fournis
-    let mut conditions = Vec::new();
-    let mut param_values: Vec<String> = Vec::new();
-    
-    if let Some(acc) = accession_number {
-        if !acc.is_empty() {
-            conditions.push("accession_number = ?");
-            param_values.push(acc.to_string());
-        }
-    }
+/// Supprime les verrous expirés ou orphelins
+pub fn cleanup_stale_report_locks(conn: &Connection) -> SqlResult<usize> {
+    conn.execute(
+        "DELETE FROM report_locks
+         WHERE expires_at <= ?1 OR technical_id NOT IN (SELECT technical_id FROM pending_reports)",
+        [Utc::now().to_rfc3339()],
+    )
+}
+
+/// Attribue un rapport à un radiologue (None = retire l'attribution)
+pub fn assign_pending_report(conn: &Connection, technical_id: &str, user_id: Option<&str>) -> SqlResult<bool> {
+    let rows = conn.execute(
+        "UPDATE pending_reports SET assigned_to = ?1 WHERE technical_id = ?2",
+        params![user_id, technical_id],
+    )?;
     
-    if let Some(exam) = exam_uid {
-        if !exam.is_empty() {
-            conditions.push("exam_uid = ?");
-            param_values.push(exam.to_string());
+    Ok(rows > 0)
+}
+
+// ============================================================================
+// Appariement RIS (find-report / open-report)
+// ============================================================================
+
+/// Mode d'appariement des identifiants RIS
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchMode {
+    /// Tous les identifiants fournis doivent correspondre (ET)
+    Strict,
+    /// Au moins un identifiant correspond, candidats classés par score (OU)
+    Ranked,
+}
+
+impl MatchMode {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "strict" => Some(MatchMode::Strict),
+            "ranked" => Some(MatchMode::Ranked),
+            _ => None,
         }
     }
     
-    if let Some(pat) = patient_id {
-        if !pat.is_empty() {
-            conditions.push("patient_id = ?");
-            param_values.push(pat.to_string());
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MatchMode::Strict => "strict",
+            MatchMode::Ranked => "ranked",
         }
     }
+}
+
+/// Identifiants RIS d'une recherche (valeurs vides ignorées)
+#[derive(Debug, Clone, Default)]
+pub struct ReportIdentifiers<'a> {
+    pub accession_number: Option<&'a str>,
+    pub study_instance_uid: Option<&'a str>,
+    pub exam_uid: Option<&'a str>,
+    pub patient_id: Option<&'a str>,
+}
+
+/// Poids de chaque identifiant dans le score : les identifiants d'examen
+/// l'emportent sur l'identifiant patient (commun à tous ses examens)
+pub const MATCH_WEIGHTS: [(&str, u32); 4] = [
+    ("accession_number", 40),
+    ("study_instance_uid", 40),
+    ("exam_uid", 30),
+    ("patient_id", 10),
+];
+
+/// Nombre maximal de candidats renvoyés
+pub const MAX_MATCH_CANDIDATES: i64 = 10;
+
+/// Rapport candidat avec son score d'appariement
+#[derive(Debug, Clone)]
+pub struct MatchCandidate {
+    pub report: PendingReport,
+    pub score: u32,
+    /// Identifiants ayant correspondu (noms de colonnes)
+    pub matched_on: Vec<&'static str>,
+}
+
+impl<'a> ReportIdentifiers<'a> {
+    /// Identifiants fournis, dans l'ordre de MATCH_WEIGHTS
+    fn provided(&self) -> Vec<(&'static str, &'a str)> {
+        let values = [self.accession_number, self.study_instance_uid, self.exam_uid, self.patient_id];
+        MATCH_WEIGHTS
+            .iter()
+            .zip(values)
+            .filter_map(|((column, _), value)| value.filter(|v| !v.is_empty()).map(|v| (*column, v)))
+            .collect()
+    }
     
-    if conditions.is_empty() {
-        return Ok(None);
+    pub fn is_empty(&self) -> bool {
+        self.provided().is_empty()
     }
+}
+
+/// Vrai si les deux meilleurs candidats ont le même score
+pub fn is_ambiguous_match(candidates: &[MatchCandidate]) -> bool {
+    matches!(candidates, [first, second, ..] if first.score == second.score)
+}
+
+/// Recherche les rapports non expirés correspondant aux identifiants RIS,
+/// classés par score décroissant, puis attribution (`preferred_user`, puis
+/// non attribués), puis du plus récent au plus ancien
+pub fn find_report_candidates(
+    conn: &Connection,
+    identifiers: &ReportIdentifiers,
+    mode: MatchMode,
+    preferred_user: Option<&str>,
+    limit: i64,
+) -> SqlResult<Vec<MatchCandidate>> {
+    let provided = identifiers.provided();
+    if provided.is_empty() {
+        return Ok(Vec::new());
+    }
+    
+    // ?1..?n = identifiants fournis, puis radiologue préféré et limite
+    let conditions: Vec<String> = provided
+        .iter()
+        .enumerate()
+        .map(|(i, (column, _))| format!("{} = ?{}", column, i + 1))
+        .collect();
+    let score_expr: Vec<String> = provided
+        .iter()
+        .enumerate()
+        .map(|(i, (column, _))| {
+            let weight = MATCH_WEIGHTS.iter().find(|(c, _)| c == column).map_or(0, |(_, w)| *w);
+            format!("CASE WHEN {} = ?{} THEN {} ELSE 0 END", column, i + 1, weight)
+        })
+        .collect();
+    let joiner = match mode {
+        MatchMode::Strict => " AND ",
+        MatchMode::Ranked => " OR ",
+    };
     
-    let where_clause = conditions.join(" OR ");
     let sql = format!(
-        "SELECT id, technical_id, patient_id, exam_uid, accession_number, study_instance_uid,
-                structured_data, source_type, ai_modules, modality, metadata, status, created_at, expires_at, retrieved_at
+        "SELECT {}, ({}) AS match_score
          FROM pending_reports
          WHERE ({}) AND status != 'expired' AND expires_at > datetime('now')
-         ORDER BY created_at DESC
-         LIMIT 1",
-        where_clause
+         ORDER BY match_score DESC,
+                  CASE
+                    WHEN assigned_to = ?{} THEN 0
+                    WHEN assigned_to IS NULL THEN 1
+                    ELSE 2
+                  END,
+                  created_at DESC
+         LIMIT ?{}",
+        PENDING_REPORT_COLUMNS,
+        score_expr.join(" + "),
+        conditions.join(joiner),
+        provided.len() + 1,
+        provided.len() + 2,
     );
     
     let mut stmt = conn.prepare(&sql)?;
     
-    // Convertir les paramètres en références pour rusqlite
+    let limit = limit.clamp(1, MAX_MATCH_CANDIDATES);
+    let mut params: Vec<&dyn rusqlite::ToSql> = provided.iter()
+        .map(|(_, value)| value as &dyn rusqlite::ToSql)
+        .collect();
+    params.push(&preferred_user);
+    params.push(&limit);
+    
+    let rows = stmt.query_map(params.as_slice(), |row| {
+        let report = row_to_pending_report(row)?;
+        let score: u32 = row.get("match_score")?;
+        Ok((report, score))
+    })?;
+    
+    let mut candidates = Vec::new();
+    for row in rows {
+        let (report, score) = row?;
+        let matched_on = provided
+            .iter()
+            .filter(|(column, value)| {
+                let field = match *column {
+                    "accession_number" => &report.accession_number,
+                    "study_instance_uid" => &report.study_instance_uid,
+                    "exam_uid" => &report.exam_uid,
+                    _ => &report.patient_id,
+                };
+                field.as_deref() == Some(*value)
+            })
+            .map(|(column, _)| *column)
+            .collect();
+        candidates.push(MatchCandidate { report, score, matched_on });
+    }
+    
+    Ok(candidates)
+}
+
+// ============================================================================
+// Liste de travail (worklist)
+// ============================================================================
+
+/// Priorités de lecture, de la plus urgente à la moins urgente
+pub const REPORT_PRIORITIES: &[&str] = &["stat", "urgent", "routine"];
+
+/// Critères de sélection de la liste de travail
+#[derive(Debug, Clone, Default)]
+pub struct WorklistFilter {
+    /// Radiologue : ses rapports attribués puis les rapports non attribués
+    pub user_id: Option<String>,
+    /// Exclure les rapports non attribués
+    pub assigned_only: bool,
+    pub modality: Option<String>,
+    pub priority: Option<String>,
+    /// Rapport à exclure (ex: rapport en cours de lecture)
+    pub exclude_tid: Option<String>,
+    pub limit: i64,
+}
+
+/// Entrée de la liste de travail
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct WorklistItem {
+    pub technical_id: String,
+    pub accession_number: Option<String>,
+    pub modality: Option<String>,
+    pub priority: String,
+    pub due_at: Option<String>,
+    pub overdue: bool,
+    pub assigned_to: Option<String>,
+    pub created_at: String,
+    // Verrou actif (holder_id, poste)
+    pub locked_by: Option<String>,
+    pub locked_workstation: Option<String>,
+}
+
+/// Liste les rapports à lire, triés par priorité (STAT > urgent > routine),
+/// puis par échéance, puis par ancienneté
+pub fn list_worklist(conn: &Connection, filter: &WorklistFilter) -> SqlResult<Vec<WorklistItem>> {
+    let now = Utc::now().to_rfc3339();
+    let mut conditions = vec![
+        "p.status = 'pending'".to_string(),
+        "p.expires_at > datetime('now')".to_string(),
+    ];
+    let mut param_values: Vec<String> = vec![now];
+    
+    match (&filter.user_id, filter.assigned_only) {
+        (Some(user_id), true) => {
+            conditions.push("p.assigned_to = ?".to_string());
+            param_values.push(user_id.clone());
+        }
+        (Some(user_id), false) => {
+            conditions.push("(p.assigned_to = ? OR p.assigned_to IS NULL)".to_string());
+            param_values.push(user_id.clone());
+        }
+        (None, true) => conditions.push("p.assigned_to IS NOT NULL".to_string()),
+        (None, false) => {}
+    }
+    
+    if let Some(modality) = filter.modality.as_ref().filter(|m| !m.is_empty()) {
+        conditions.push("p.modality = ?".to_string());
+        param_values.push(modality.clone());
+    }
+    
+    if let Some(priority) = filter.priority.as_ref().filter(|p| !p.is_empty()) {
+        conditions.push("p.priority = ?".to_string());
+        param_values.push(priority.clone());
+    }
+    
+    if let Some(tid) = filter.exclude_tid.as_ref().filter(|t| !t.is_empty()) {
+        conditions.push("p.technical_id != ?".to_string());
+        param_values.push(tid.clone());
+    }
+    
+    let sql = format!(
+        "SELECT p.technical_id, p.accession_number, p.modality, p.priority, p.due_at,
+                (p.due_at IS NOT NULL AND p.due_at < ?1) AS overdue,
+                p.assigned_to, p.created_at, l.holder_id, l.workstation
+         FROM pending_reports p
+         LEFT JOIN report_locks l ON l.technical_id = p.technical_id AND l.expires_at > ?1
+         WHERE {}
+         ORDER BY CASE p.priority WHEN 'stat' THEN 0 WHEN 'urgent' THEN 1 ELSE 2 END,
+                  p.due_at IS NULL,
+                  p.due_at ASC,
+                  p.created_at ASC
+         LIMIT {}",
+        conditions.join(" AND "),
+        filter.limit.clamp(1, 500)
+    );
+    
+    let mut stmt = conn.prepare(&sql)?;
     let params: Vec<&dyn rusqlite::ToSql> = param_values.iter()
         .map(|s| s as &dyn rusqlite::ToSql)
         .collect();
     
-    let result = stmt.query_row(params.as_slice(), |row| {
-        Ok(PendingReport {
-            id: row.get(0)?,
-            technical_id: row.get(1)?,
-            patient_id: row.get(2)?,
-            exam_uid: row.get(3)?,
-            accession_number: row.get(4)?,
-            study_instance_uid: row.get(5)?,
-            structured_data: row.get(6)?,
-            source_type: row.get(7)?,
-            ai_modules: row.get(8)?,
-            modality: row.get(9)?,
-            metadata: row.get(10)?,
-            status: row.get(11)?,
-            created_at: row.get(12)?,
-            expires_at: row.get(13)?,
-            retrieved_at: row.get(14)?,
+    let items = stmt.query_map(params.as_slice(), |row| {
+        Ok(WorklistItem {
+            technical_id: row.get(0)?,
+            accession_number: row.get(1)?,
+            modality: row.get(2)?,
+            priority: row.get(3)?,
+            due_at: row.get(4)?,
+            overdue: row.get(5)?,
+            assigned_to: row.get(6)?,
+            created_at: row.get(7)?,
+            locked_by: row.get(8)?,
+            locked_workstation: row.get(9)?,
+        })
+    })?
+    .collect::<SqlResult<Vec<_>>>()?;
+    
+    Ok(items)
+}
+
+/// Définit la priorité et l'échéance de lecture d'un rapport
+pub fn set_report_schedule(
+    conn: &Connection,
+    technical_id: &str,
+    priority: &str,
+    due_at: Option<&str>,
+) -> SqlResult<bool> {
+    let rows = conn.execute(
+        "UPDATE pending_reports SET priority = ?1, due_at = ?2 WHERE technical_id = ?3",
+        params![priority, due_at, technical_id],
+    )?;
+    
+    Ok(rows > 0)
+}
+
+/// Change la priorité d'un rapport (l'échéance est conservée)
+pub fn set_report_priority(conn: &Connection, technical_id: &str, priority: &str) -> SqlResult<bool> {
+    let rows = conn.execute(
+        "UPDATE pending_reports SET priority = ?1 WHERE technical_id = ?2",
+        params![priority, technical_id],
+    )?;
+    
+    Ok(rows > 0)
+}
+
+// ============================================================================
+// Recherche plein texte (FTS5)
+// ============================================================================
+
+/// Résultat de recherche plein texte (sans identifiant patient)
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct SearchHit {
+    pub technical_id: String,
+    pub accession_number: Option<String>,
+    pub modality: Option<String>,
+    pub status: String,
+    pub priority: String,
+    pub created_at: String,
+    pub assigned_to: Option<String>,
+    /// Extrait du champ le plus pertinent, termes entourés de <mark>…</mark>
+    pub snippet: String,
+    /// Score BM25 (plus petit = plus pertinent)
+    pub rank: f64,
+}
+
+/// Convertit une saisie utilisateur en requête FTS5 : chaque terme devient une
+/// phrase entre guillemets (les "-" de "BI-RADS" ne sont pas des opérateurs),
+/// un "*" final est conservé comme recherche par préfixe. Termes combinés en ET.
+pub fn build_fts_query(input: &str) -> Option<String> {
+    let terms: Vec<String> = input
+        .split_whitespace()
+        .filter_map(|term| {
+            let (term, prefix) = match term.strip_suffix('*') {
+                Some(stem) => (stem, true),
+                None => (term, false),
+            };
+            let term = term.replace('"', "");
+            if term.is_empty() {
+                return None;
+            }
+            Some(format!("\"{}\"{}", term, if prefix { "*" } else { "" }))
         })
-    });
+        .collect();
+    
+    if terms.is_empty() {
+        None
+    } else {
+        Some(terms.join(" "))
+    }
+}
+
+/// Recherche dans le contenu structuré des rapports non expirés,
+/// classés par pertinence (BM25)
+pub fn search_reports(
+    conn: &Connection,
+    query: &str,
+    modality: Option<&str>,
+    limit: i64,
+) -> SqlResult<Vec<SearchHit>> {
+    let fts_query = match build_fts_query(query) {
+        Some(q) => q,
+        None => return Ok(Vec::new()),
+    };
+    
+    let mut stmt = conn.prepare(
+        "SELECT p.technical_id, p.accession_number, p.modality, p.status, p.priority, p.created_at, p.assigned_to,
+                snippet(pending_reports_fts, -1, '<mark>', '</mark>', '…', 16),
+                bm25(pending_reports_fts)
+         FROM pending_reports_fts
+         JOIN pending_reports p ON p.technical_id = pending_reports_fts.technical_id
+         WHERE pending_reports_fts MATCH ?1
+           AND p.status != 'expired' AND p.expires_at > ?2
+           AND (?3 IS NULL OR p.modality = ?3)
+         ORDER BY bm25(pending_reports_fts)
+         LIMIT ?4"
+    )?;
+    
+    let rows = stmt.query_map(
+        params![fts_query, Utc::now().to_rfc3339(), modality, limit.clamp(1, 100)],
+        |row| {
+            Ok(SearchHit {
+                technical_id: row.get(0)?,
+                accession_number: row.get(1)?,
+                modality: row.get(2)?,
+                status: row.get(3)?,
+                priority: row.get(4)?,
+                created_at: row.get(5)?,
+                assigned_to: row.get(6)?,
+                snippet: row.get::<_, Option<String>>(7)?.unwrap_or_default(),
+                rank: row.get(8)?,
+            })
+        },
+    )?;
+    
+    rows.collect()
+}
+
+// ============================================================================
+// Modèles de rapports (comptes rendus types)
+// ============================================================================
+
+/// Modèle de rapport ; `content` = rapport structuré JSON avec placeholders {{nom}}
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ReportTemplate {
+    pub id: String,
+    pub name: String,
+    pub modality: Option<String>,
+    pub body_part: Option<String>,
+    pub language: String,
+    pub content: String,
+    pub created_by: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// Champs modifiables d'un modèle
+#[derive(Debug, Clone)]
+pub struct ReportTemplateFields<'a> {
+    pub name: &'a str,
+    pub modality: Option<&'a str>,
+    pub body_part: Option<&'a str>,
+    pub language: &'a str,
+    pub content: &'a str,
+}
+
+const REPORT_TEMPLATE_COLUMNS: &str =
+    "id, name, modality, body_part, language, content, created_by, created_at, updated_at";
+
+fn row_to_report_template(row: &rusqlite::Row) -> SqlResult<ReportTemplate> {
+    Ok(ReportTemplate {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        modality: row.get(2)?,
+        body_part: row.get(3)?,
+        language: row.get(4)?,
+        content: row.get(5)?,
+        created_by: row.get(6)?,
+        created_at: row.get(7)?,
+        updated_at: row.get(8)?,
+    })
+}
+
+/// Crée un modèle de rapport
+pub fn insert_report_template(
+    conn: &Connection,
+    id: &str,
+    fields: &ReportTemplateFields,
+    created_by: Option<&str>,
+) -> SqlResult<()> {
+    let now = Utc::now().to_rfc3339();
+    conn.execute(
+        "INSERT INTO report_templates (id, name, modality, body_part, language, content, created_by, created_at, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?8)",
+        params![id, fields.name, fields.modality, fields.body_part, fields.language, fields.content, created_by, now],
+    )?;
+    Ok(())
+}
+
+/// Remplace les champs d'un modèle (false si introuvable)
+pub fn update_report_template(conn: &Connection, id: &str, fields: &ReportTemplateFields) -> SqlResult<bool> {
+    let rows = conn.execute(
+        "UPDATE report_templates
+         SET name = ?2, modality = ?3, body_part = ?4, language = ?5, content = ?6, updated_at = ?7
+         WHERE id = ?1",
+        params![id, fields.name, fields.modality, fields.body_part, fields.language, fields.content, Utc::now().to_rfc3339()],
+    )?;
+    Ok(rows > 0)
+}
+
+/// Récupère un modèle par id ou par nom
+pub fn get_report_template(conn: &Connection, id_or_name: &str) -> SqlResult<Option<ReportTemplate>> {
+    let result = conn.query_row(
+        &format!(
+            "SELECT {} FROM report_templates WHERE id = ?1 OR name = ?1 ORDER BY id = ?1 DESC LIMIT 1",
+            REPORT_TEMPLATE_COLUMNS
+        ),
+        [id_or_name],
+        row_to_report_template,
+    );
     
     match result {
-        Ok(report) => Ok(Some(report)),
+        Ok(template) => Ok(Some(template)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Liste les modèles (filtres optionnels), triés par modalité puis nom
+pub fn list_report_templates(
+    conn: &Connection,
+    modality: Option<&str>,
+    body_part: Option<&str>,
+    language: Option<&str>,
+) -> SqlResult<Vec<ReportTemplate>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM report_templates
+         WHERE (?1 IS NULL OR modality = ?1)
+           AND (?2 IS NULL OR body_part = ?2 COLLATE NOCASE)
+           AND (?3 IS NULL OR language = ?3)
+         ORDER BY modality, name",
+        REPORT_TEMPLATE_COLUMNS
+    ))?;
+    
+    let rows = stmt.query_map(params![modality, body_part, language], row_to_report_template)?;
+    rows.collect()
+}
+
+/// Supprime un modèle
+pub fn delete_report_template(conn: &Connection, id_or_name: &str) -> SqlResult<bool> {
+    // Même résolution que get_report_template : l'id prime sur un nom identique
+    let rows = conn.execute(
+        "DELETE FROM report_templates WHERE id = (
+            SELECT id FROM report_templates WHERE id = ?1 OR name = ?1 ORDER BY id = ?1 DESC LIMIT 1
+         )",
+        [id_or_name],
+    )?;
+    Ok(rows > 0)
+}
+
+// ============================================================================
+// Verrous de rapports (bail renouvelé par heartbeat)
+// ============================================================================
+
+/// Verrou d'édition d'un rapport
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ReportLock {
+    pub technical_id: String,
+    pub holder_id: String,
+    pub holder_name: Option<String>,
+    pub workstation: String,
+    pub acquired_at: String,
+    pub heartbeat_at: String,
+    pub expires_at: String,
+}
+
+/// Résultat d'une tentative de verrouillage
+#[derive(Debug, Clone)]
+pub enum LockAcquisition {
+    /// Verrou obtenu (ou renouvelé par son détenteur)
+    Acquired(ReportLock),
+    /// Verrou détenu par un autre client
+    Held(ReportLock),
+}
+
+fn row_to_report_lock(row: &rusqlite::Row) -> SqlResult<ReportLock> {
+    Ok(ReportLock {
+        technical_id: row.get(0)?,
+        holder_id: row.get(1)?,
+        holder_name: row.get(2)?,
+        workstation: row.get(3)?,
+        acquired_at: row.get(4)?,
+        heartbeat_at: row.get(5)?,
+        expires_at: row.get(6)?,
+    })
+}
+
+/// Verrou actif (non expiré) d'un rapport
+pub fn get_report_lock(conn: &Connection, technical_id: &str) -> SqlResult<Option<ReportLock>> {
+    let result = conn.query_row(
+        "SELECT technical_id, holder_id, holder_name, workstation, acquired_at, heartbeat_at, expires_at
+         FROM report_locks
+         WHERE technical_id = ?1 AND expires_at > ?2",
+        params![technical_id, Utc::now().to_rfc3339()],
+        row_to_report_lock,
+    );
+    
+    match result {
+        Ok(lock) => Ok(Some(lock)),
         Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
         Err(e) => Err(e),
     }
 }
 
+/// Prend (ou renouvelle) le verrou d'un rapport pour `lease_secs` secondes
+pub fn acquire_report_lock(
+    conn: &Connection,
+    technical_id: &str,
+    holder_id: &str,
+    holder_name: Option<&str>,
+    workstation: &str,
+    lease_secs: i64,
+) -> SqlResult<LockAcquisition> {
+    // IMMEDIATE : verrou d'écriture pris avant la lecture, deux clients ne
+    // peuvent pas constater simultanément l'absence de verrou
+    let tx = rusqlite::Transaction::new_unchecked(conn, rusqlite::TransactionBehavior::Immediate)?;
+    
+    if let Some(existing) = get_report_lock(&tx, technical_id)? {
+        if existing.holder_id != holder_id || existing.workstation != workstation {
+            return Ok(LockAcquisition::Held(existing));
+        }
+    }
+    
+    let now = Utc::now();
+    let now_str = now.to_rfc3339();
+    let expires_at = (now + chrono::Duration::seconds(lease_secs)).to_rfc3339();
+    
+    // acquired_at conservé lorsque le détenteur renouvelle un verrou encore valide
+    tx.execute(
+        "INSERT INTO report_locks (technical_id, holder_id, holder_name, workstation, acquired_at, heartbeat_at, expires_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?5, ?6)
+         ON CONFLICT(technical_id) DO UPDATE SET
+            acquired_at = CASE
+                WHEN report_locks.holder_id = excluded.holder_id
+                 AND report_locks.workstation = excluded.workstation
+                 AND report_locks.expires_at > excluded.heartbeat_at
+                THEN report_locks.acquired_at ELSE excluded.acquired_at END,
+            holder_id = excluded.holder_id,
+            holder_name = excluded.holder_name,
+            workstation = excluded.workstation,
+            heartbeat_at = excluded.heartbeat_at,
+            expires_at = excluded.expires_at",
+        params![technical_id, holder_id, holder_name, workstation, now_str, expires_at],
+    )?;
+    
+    let lock = get_report_lock(&tx, technical_id)?
+        .ok_or(rusqlite::Error::QueryReturnedNoRows)?;
+    tx.commit()?;
+    
+    Ok(LockAcquisition::Acquired(lock))
+}
+
+/// Prolonge le bail d'un verrou détenu par `holder_id` sur `workstation`
+/// (None si le verrou a expiré ou appartient à un autre client)
+pub fn heartbeat_report_lock(
+    conn: &Connection,
+    technical_id: &str,
+    holder_id: &str,
+    workstation: &str,
+    lease_secs: i64,
+) -> SqlResult<Option<ReportLock>> {
+    let now = Utc::now();
+    let rows = conn.execute(
+        "UPDATE report_locks SET heartbeat_at = ?1, expires_at = ?2
+         WHERE technical_id = ?3 AND holder_id = ?4 AND workstation = ?5 AND expires_at > ?1",
+        params![
+            now.to_rfc3339(),
+            (now + chrono::Duration::seconds(lease_secs)).to_rfc3339(),
+            technical_id,
+            holder_id,
+            workstation,
+        ],
+    )?;
+    
+    if rows == 0 {
+        return Ok(None);
+    }
+    get_report_lock(conn, technical_id)
+}
+
+/// Libère un verrou : par son détenteur (`holder` = (holder_id, workstation))
+/// ou sans condition (`holder` = None, libération forcée par un administrateur)
+pub fn release_report_lock(
+    conn: &Connection,
+    technical_id: &str,
+    holder: Option<(&str, &str)>,
+) -> SqlResult<bool> {
+    let rows = match holder {
+        Some((holder_id, workstation)) => conn.execute(
+            "DELETE FROM report_locks WHERE technical_id = ?1 AND holder_id = ?2 AND workstation = ?3",
+            params![technical_id, holder_id, workstation],
+        )?,
+        None => conn.execute(
+            "DELETE FROM report_locks WHERE technical_id = ?1",
+            [technical_id],
+        )?,
+    };
+    
+    Ok(rows > 0)
+}
+
+/// Liste les verrous actifs
+pub fn list_report_locks(conn: &Connection) -> SqlResult<Vec<ReportLock>> {
+    let mut stmt = conn.prepare(
+        "SELECT technical_id, holder_id, holder_name, workstation, acquired_at, heartbeat_at, expires_at
+         FROM report_locks
+         WHERE expires_at > ?1
+         ORDER BY acquired_at DESC"
+    )?;
+    
+    let locks = stmt.query_map([Utc::now().to_rfc3339()], row_to_report_lock)?
+        .collect::<SqlResult<Vec<_>>>()?;
+    
+    Ok(locks)
+}
+
 // ============================================================================
 // Opérations sur les clés API
 // ============================================================================
@@ -234,6 +903,24 @@ pub fn validate_api_key(conn: &Connection, key_prefix: &str, key_hash: &str) ->
     Ok(count > 0)
 }
 
+/// Identité d'une clé API active (sans hash)
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct ApiKeyIdentity {
+    pub id: String,
+    pub name: String,
+}
+
+/// Clé API active correspondant au préfixe et au hash (None si inconnue ou révoquée)
+pub fn find_api_key(conn: &Connection, key_prefix: &str, key_hash: &str) -> SqlResult<Option<ApiKeyIdentity>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, name FROM api_keys WHERE key_prefix = ?1 AND key_hash = ?2 AND is_active = 1 LIMIT 1"
+    )?;
+    let mut rows = stmt.query_map(params![key_prefix, key_hash], |row| {
+        Ok(ApiKeyIdentity { id: row.get(0)?, name: row.get(1)? })
+    })?;
+    rows.next().transpose()
+}
+
 /// Ajoute une nouvelle clé API
 pub fn add_api_key(
     conn: &Connection,
@@ -309,6 +996,12 @@ pub struct PendingReportSummary {
     pub source_type: String,
     pub created_at: String,
     pub expires_at: String,
+    pub assigned_to: Option<String>,
+    pub priority: String,
+    pub due_at: Option<String>,
+    // Verrou actif (holder_id, poste)
+    pub locked_by: Option<String>,
+    pub locked_workstation: Option<String>,
 }
 
 /// Structure simplifiée pour affichage des clés API
@@ -324,13 +1017,15 @@ pub struct ApiKeySummary {
 /// Liste tous les rapports en attente (pour le Debug Panel)
 pub fn list_all_pending_reports(conn: &Connection) -> SqlResult<Vec<PendingReportSummary>> {
     let mut stmt = conn.prepare(
-        "SELECT technical_id, accession_number, patient_id, modality, status, source_type, created_at, expires_at
-         FROM pending_reports
-         ORDER BY created_at DESC
+        "SELECT p.technical_id, p.accession_number, p.patient_id, p.modality, p.status, p.source_type,
+                p.created_at, p.expires_at, p.assigned_to, p.priority, p.due_at, l.holder_id, l.workstation
+         FROM pending_reports p
+         LEFT JOIN report_locks l ON l.technical_id = p.technical_id AND l.expires_at > ?1
+         ORDER BY p.created_at DESC
          LIMIT 100"
     )?;
     
-    let reports = stmt.query_map([], |row| {
+    let reports = stmt.query_map([Utc::now().to_rfc3339()], |row| {
         Ok(PendingReportSummary {
             technical_id: row.get(0)?,
             accession_number: row.get(1)?,
@@ -340,6 +1035,11 @@ pub fn list_all_pending_reports(conn: &Connection) -> SqlResult<Vec<PendingRepor
             source_type: row.get(5)?,
             created_at: row.get(6)?,
             expires_at: row.get(7)?,
+            assigned_to: row.get(8)?,
+            priority: row.get(9)?,
+            due_at: row.get(10)?,
+            locked_by: row.get(11)?,
+            locked_workstation: row.get(12)?,
         })
     })?
     .collect::<SqlResult<Vec<_>>>()?;
@@ -347,6 +1047,29 @@ pub fn list_all_pending_reports(conn: &Connection) -> SqlResult<Vec<PendingRepor
     Ok(reports)
 }
 
+/// Colonnes d'identifiants patient / examen de pending_reports
+pub const IDENTIFIER_COLUMNS: [&str; 4] = ["patient_id", "accession_number", "exam_uid", "study_instance_uid"];
+
+/// Valeurs d'une colonne d'identifiant (technical_id, valeur), rapports expirés compris
+pub fn list_report_identifiers(conn: &Connection, column: &str) -> SqlResult<Vec<(String, String)>> {
+    if !IDENTIFIER_COLUMNS.contains(&column) {
+        return Err(rusqlite::Error::InvalidColumnName(column.to_string()));
+    }
+    
+    let mut stmt = conn.prepare(&format!(
+        "SELECT technical_id, {column} FROM pending_reports
+         WHERE {column} IS NOT NULL AND {column} != ''
+         ORDER BY created_at DESC",
+        column = column
+    ))?;
+    
+    let rows = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<SqlResult<Vec<_>>>()?;
+    
+    Ok(rows)
+}
+
 /// Récupère les statistiques de la base de données
 pub fn get_database_stats(conn: &Connection) -> SqlResult<DatabaseStats> {
     let total_reports: i64 = conn.query_row(
@@ -434,6 +1157,7 @@ pub struct AccessLog {
     pub request_id: String,
     pub duration_ms: i64,
     pub error_message: Option<String>,
+    pub user_id: Option<String>,
 }
 
 /// Structure simplifiée pour l'affichage dans le Debug Panel
@@ -447,6 +1171,7 @@ pub struct AccessLogSummary {
     pub status_code: i32,
     pub result: String,
     pub duration_ms: i64,
+    pub user_id: Option<String>,
 }
 
 /// Statistiques des logs d'accès
@@ -476,15 +1201,16 @@ pub fn insert_access_log(
     request_id: &str,
     duration_ms: i64,
     error_message: Option<&str>,
+    user_id: Option<&str>,
 ) -> SqlResult<i64> {
     conn.execute(
         "INSERT INTO access_logs 
          (timestamp, ip_address, method, endpoint, status_code, result, 
-          api_key_prefix, user_agent, request_id, duration_ms, error_message)
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+          api_key_prefix, user_agent, request_id, duration_ms, error_message, user_id)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
         params![
             timestamp, ip_address, method, endpoint, status_code, result,
-            api_key_prefix, user_agent, request_id, duration_ms, error_message
+            api_key_prefix, user_agent, request_id, duration_ms, error_message, user_id
         ],
     )?;
     
@@ -494,7 +1220,7 @@ pub fn insert_access_log(
 /// Liste les logs d'accès récents (avec pagination)
 pub fn list_access_logs(conn: &Connection, limit: i64, offset: i64) -> SqlResult<Vec<AccessLogSummary>> {
     let mut stmt = conn.prepare(
-        "SELECT id, timestamp, ip_address, method, endpoint, status_code, result, duration_ms
+        "SELECT id, timestamp, ip_address, method, endpoint, status_code, result, duration_ms, user_id
          FROM access_logs
          ORDER BY timestamp DESC
          LIMIT ?1 OFFSET ?2"
@@ -510,6 +1236,7 @@ pub fn list_access_logs(conn: &Connection, limit: i64, offset: i64) -> SqlResult
             status_code: row.get(5)?,
             result: row.get(6)?,
             duration_ms: row.get(7)?,
+            user_id: row.get(8)?,
         })
     })?
     .collect::<SqlResult<Vec<_>>>()?;
@@ -599,7 +1326,594 @@ pub fn cleanup_old_access_logs(conn: &Connection, days: i64) -> SqlResult<usize>
 }
 
 // ============================================================================
-// Tests unitaires
+// Rétention et purge sécurisée
+// ============================================================================
+
+/// Rapport candidat à la purge (champs évalués par les règles de rétention)
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct RetentionCandidate {
+    pub technical_id: String,
+    pub source_type: String,
+    pub modality: Option<String>,
+    pub status: String,
+    pub created_at: String,
+    pub expires_at: String,
+}
+
+/// Entrée du journal des purges
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct RetentionPurgeEntry {
+    pub id: i64,
+    pub purged_at: String,
+    pub rule_id: String,
+    pub target: String,
+    pub item_count: i64,
+    pub technical_ids: Vec<String>,
+    pub secure_delete: bool,
+}
+
+/// Liste les rapports évalués par les règles de rétention
+pub fn list_retention_candidates(conn: &Connection) -> SqlResult<Vec<RetentionCandidate>> {
+    let mut stmt = conn.prepare(
+        "SELECT technical_id, COALESCE(source_type, 'tauri_local'), modality,
+                COALESCE(status, 'pending'), created_at, expires_at
+         FROM pending_reports ORDER BY created_at",
+    )?;
+    
+    let rows = stmt.query_map([], |row| {
+        Ok(RetentionCandidate {
+            technical_id: row.get(0)?,
+            source_type: row.get(1)?,
+            modality: row.get(2)?,
+            status: row.get(3)?,
+            created_at: row.get(4)?,
+            expires_at: row.get(5)?,
+        })
+    })?;
+    
+    rows.collect()
+}
+
+/// Exécute `f` avec PRAGMA secure_delete activé si demandé
+/// (pages libérées écrasées : les données purgées ne restent pas dans le fichier),
+/// puis restaure le réglage précédent de la connexion. En mode sécurisé, le
+/// WAL est ensuite reporté et tronqué : il garderait les anciennes pages.
+fn with_secure_delete<F, T>(conn: &Connection, secure_delete: bool, f: F) -> SqlResult<T>
+where
+    F: FnOnce(&Connection) -> SqlResult<T>,
+{
+    let previous: i64 = conn.query_row("PRAGMA secure_delete", [], |row| row.get(0))?;
+    conn.execute_batch(&format!("PRAGMA secure_delete = {};", if secure_delete { 1 } else { previous }))?;
+    
+    let result = f(conn);
+    
+    conn.execute_batch(&format!("PRAGMA secure_delete = {};", previous))?;
+    let result = result?;
+    
+    if secure_delete {
+        let busy: i64 = conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |row| row.get(0))?;
+        if busy != 0 {
+            log::warn!("[Database] WAL non tronqué après suppression sécurisée (lecteurs actifs) : reporté au prochain checkpoint");
+        }
+    }
+    Ok(result)
+}
+
+/// Fusionne les segments de l'index plein texte : FTS5 n'écrit qu'un marqueur
+/// à la suppression, les termes supprimés restaient dans `pending_reports_fts_data`
+/// (option `secure-delete` indisponible avant SQLite 3.42)
+pub(crate) fn scrub_full_text_index(conn: &Connection) -> SqlResult<()> {
+    conn.execute("INSERT INTO pending_reports_fts (pending_reports_fts) VALUES ('optimize')", [])?;
+    Ok(())
+}
+
+/// Supprime des rapports (index plein texte et verrous compris) en une transaction
+pub fn purge_pending_reports(conn: &Connection, technical_ids: &[String], secure_delete: bool) -> SqlResult<usize> {
+    if technical_ids.is_empty() {
+        return Ok(0);
+    }
+    
+    with_secure_delete(conn, secure_delete, |conn| {
+        let tx = conn.unchecked_transaction()?;
+        let mut rows = 0;
+        for technical_id in technical_ids {
+            rows += tx.execute("DELETE FROM pending_reports WHERE technical_id = ?1", [technical_id])?;
+            tx.execute("DELETE FROM report_locks WHERE technical_id = ?1", [technical_id])?;
+        }
+        if secure_delete {
+            scrub_full_text_index(&tx)?;
+        }
+        tx.commit()?;
+        Ok(rows)
+    })
+}
+
+/// Nombre de logs d'accès antérieurs à une date (RFC 3339)
+pub fn count_access_logs_before(conn: &Connection, cutoff: &str) -> SqlResult<i64> {
+    conn.query_row(
+        "SELECT COUNT(*) FROM access_logs WHERE timestamp < ?1",
+        [cutoff],
+        |row| row.get(0),
+    )
+}
+
+/// Supprime les logs d'accès antérieurs à une date (RFC 3339)
+pub fn purge_access_logs_before(conn: &Connection, cutoff: &str, secure_delete: bool) -> SqlResult<usize> {
+    with_secure_delete(conn, secure_delete, |conn| {
+        conn.execute("DELETE FROM access_logs WHERE timestamp < ?1", [cutoff])
+    })
+}
+
+/// Enregistre une purge dans le journal de rétention
+pub fn insert_retention_purge(
+    conn: &Connection,
+    purged_at: &str,
+    rule_id: &str,
+    target: &str,
+    item_count: i64,
+    technical_ids: &[String],
+    secure_delete: bool,
+) -> SqlResult<i64> {
+    let ids_json = if technical_ids.is_empty() {
+        None
+    } else {
+        serde_json::to_string(technical_ids).ok()
+    };
+    
+    conn.execute(
+        "INSERT INTO retention_purges (purged_at, rule_id, target, item_count, technical_ids, secure_delete)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![purged_at, rule_id, target, item_count, ids_json, secure_delete as i32],
+    )?;
+    
+    Ok(conn.last_insert_rowid())
+}
+
+/// Liste les dernières purges (plus récentes d'abord)
+pub fn list_retention_purges(conn: &Connection, limit: i64) -> SqlResult<Vec<RetentionPurgeEntry>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, purged_at, rule_id, target, item_count, technical_ids, secure_delete
+         FROM retention_purges ORDER BY id DESC LIMIT ?1",
+    )?;
+    
+    let rows = stmt.query_map([limit], |row| {
+        let ids: Option<String> = row.get(5)?;
+        Ok(RetentionPurgeEntry {
+            id: row.get(0)?,
+            purged_at: row.get(1)?,
+            rule_id: row.get(2)?,
+            target: row.get(3)?,
+            item_count: row.get(4)?,
+            technical_ids: ids
+                .and_then(|json| serde_json::from_str(&json).ok())
+                .unwrap_or_default(),
+            secure_delete: row.get::<_, i32>(6)? != 0,
+        })
+    })?;
+    
+    rows.collect()
+}
+
+// ============================================================================
+// Droits des personnes (RGPD) : accès et effacement
+// ============================================================================
+
+/// Entrée complète du journal d'accès (export RGPD)
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct AccessLogEntry {
+    pub id: i64,
+    pub timestamp: String,
+    pub ip_address: String,
+    pub method: String,
+    pub endpoint: String,
+    pub status_code: i32,
+    pub result: String,
+    pub request_id: String,
+    pub error_message: Option<String>,
+    pub user_id: Option<String>,
+}
+
+/// Nombre d'enregistrements effacés ou anonymisés
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct SubjectErasure {
+    pub reports: usize,
+    pub access_logs: usize,
+    pub locks: usize,
+}
+
+/// Certificat d'effacement enregistré
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ErasureCertificateEntry {
+    pub certificate_id: String,
+    pub issued_at: String,
+    pub mode: String,
+    pub certificate: serde_json::Value,
+}
+
+/// Rapports d'une personne (patient_id et/ou accession_number), expirés compris
+pub fn find_subject_reports(
+    conn: &Connection,
+    patient_id: Option<&str>,
+    accession_number: Option<&str>,
+) -> SqlResult<Vec<PendingReport>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM pending_reports
+         WHERE (?1 IS NOT NULL AND patient_id = ?1) OR (?2 IS NOT NULL AND accession_number = ?2)
+         ORDER BY created_at",
+        PENDING_REPORT_COLUMNS
+    ))?;
+    
+    let rows = stmt.query_map(params![patient_id, accession_number], row_to_pending_report)?;
+    rows.collect()
+}
+
+/// Logs d'accès dont l'endpoint ou le message d'erreur contient une des valeurs
+/// (technical_id, pseudonymes des identifiants)
+pub fn find_access_logs_referencing(conn: &Connection, needles: &[String]) -> SqlResult<Vec<AccessLogEntry>> {
+    if needles.is_empty() {
+        return Ok(Vec::new());
+    }
+    
+    let clauses: Vec<String> = (1..=needles.len())
+        .map(|i| format!("instr(endpoint, ?{0}) > 0 OR instr(COALESCE(error_message, ''), ?{0}) > 0", i))
+        .collect();
+    let mut stmt = conn.prepare(&format!(
+        "SELECT id, timestamp, ip_address, method, endpoint, status_code, result, request_id, error_message, user_id
+         FROM access_logs WHERE {} ORDER BY timestamp",
+        clauses.join(" OR ")
+    ))?;
+    
+    let rows = stmt.query_map(rusqlite::params_from_iter(needles.iter()), |row| {
+        Ok(AccessLogEntry {
+            id: row.get(0)?,
+            timestamp: row.get(1)?,
+            ip_address: row.get(2)?,
+            method: row.get(3)?,
+            endpoint: row.get(4)?,
+            status_code: row.get(5)?,
+            result: row.get(6)?,
+            request_id: row.get(7)?,
+            error_message: row.get(8)?,
+            user_id: row.get(9)?,
+        })
+    })?;
+    
+    rows.collect()
+}
+
+/// Efface (ou anonymise) les enregistrements d'une personne en une transaction.
+/// Anonymisation : identifiants et métadonnées des rapports mis à NULL, messages
+/// d'erreur des logs d'accès supprimés ; le contenu médical est conservé.
+pub fn erase_subject_records(
+    conn: &Connection,
+    technical_ids: &[String],
+    access_log_ids: &[i64],
+    anonymise: bool,
+    secure_delete: bool,
+) -> SqlResult<SubjectErasure> {
+    with_secure_delete(conn, secure_delete, |conn| {
+        let tx = conn.unchecked_transaction()?;
+        let mut erased = SubjectErasure::default();
+        
+        for technical_id in technical_ids {
+            if anonymise {
+                erased.reports += tx.execute(
+                    "UPDATE pending_reports
+                     SET patient_id = NULL, exam_uid = NULL, accession_number = NULL,
+                         study_instance_uid = NULL, metadata = NULL
+                     WHERE technical_id = ?1",
+                    [technical_id],
+                )?;
+            } else {
+                erased.reports += tx.execute("DELETE FROM pending_reports WHERE technical_id = ?1", [technical_id])?;
+                erased.locks += tx.execute("DELETE FROM report_locks WHERE technical_id = ?1", [technical_id])?;
+            }
+        }
+        
+        for id in access_log_ids {
+            erased.access_logs += if anonymise {
+                tx.execute("UPDATE access_logs SET error_message = NULL WHERE id = ?1", [id])?
+            } else {
+                tx.execute("DELETE FROM access_logs WHERE id = ?1", [id])?
+            };
+        }
+        
+        // Effacement : le texte des rapports ne doit pas survivre dans l'index
+        // plein texte (avant l'émission du certificat)
+        if !anonymise && erased.reports > 0 {
+            scrub_full_text_index(&tx)?;
+        }
+        
+        tx.commit()?;
+        Ok(erased)
+    })
+}
+
+/// Enregistre un certificat d'effacement (JSON signé)
+pub fn insert_erasure_certificate(
+    conn: &Connection,
+    certificate_id: &str,
+    issued_at: &str,
+    mode: &str,
+    certificate: &str,
+) -> SqlResult<()> {
+    conn.execute(
+        "INSERT INTO erasure_certificates (certificate_id, issued_at, mode, certificate) VALUES (?1, ?2, ?3, ?4)",
+        params![certificate_id, issued_at, mode, certificate],
+    )?;
+    
+    Ok(())
+}
+
+/// Liste les certificats d'effacement (plus récents d'abord)
+pub fn list_erasure_certificates(conn: &Connection, limit: i64) -> SqlResult<Vec<ErasureCertificateEntry>> {
+    let mut stmt = conn.prepare(
+        "SELECT certificate_id, issued_at, mode, certificate
+         FROM erasure_certificates ORDER BY issued_at DESC LIMIT ?1",
+    )?;
+    
+    let rows = stmt.query_map([limit], |row| {
+        let certificate: String = row.get(3)?;
+        Ok(ErasureCertificateEntry {
+            certificate_id: row.get(0)?,
+            issued_at: row.get(1)?,
+            mode: row.get(2)?,
+            certificate: serde_json::from_str(&certificate).unwrap_or(serde_json::Value::Null),
+        })
+    })?;
+    
+    rows.collect()
+}
+
+// ============================================================================
+// Rotations de la clé SQLCipher
+// ============================================================================
+
+/// Rotation de clé enregistrée
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct KeyRotationEntry {
+    pub rotated_at: String,
+    pub from_version: u32,
+    pub to_version: u32,
+    pub trigger: String,
+    pub backups_reencrypted: i64,
+    pub backups_invalidated: i64,
+}
+
+/// Enregistre une rotation de clé
+pub fn insert_key_rotation(conn: &Connection, entry: &KeyRotationEntry) -> SqlResult<()> {
+    conn.execute(
+        "INSERT INTO key_rotations (rotated_at, from_version, to_version, trigger, backups_reencrypted, backups_invalidated)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![
+            entry.rotated_at,
+            entry.from_version,
+            entry.to_version,
+            entry.trigger,
+            entry.backups_reencrypted,
+            entry.backups_invalidated,
+        ],
+    )?;
+    
+    Ok(())
+}
+
+/// Liste les rotations de clé (plus récentes d'abord)
+pub fn list_key_rotations(conn: &Connection, limit: i64) -> SqlResult<Vec<KeyRotationEntry>> {
+    let mut stmt = conn.prepare(
+        "SELECT rotated_at, from_version, to_version, trigger, backups_reencrypted, backups_invalidated
+         FROM key_rotations ORDER BY rotated_at DESC LIMIT ?1",
+    )?;
+    
+    let rows = stmt.query_map([limit], |row| {
+        Ok(KeyRotationEntry {
+            rotated_at: row.get(0)?,
+            from_version: row.get(1)?,
+            to_version: row.get(2)?,
+            trigger: row.get(3)?,
+            backups_reencrypted: row.get(4)?,
+            backups_invalidated: row.get(5)?,
+        })
+    })?;
+    
+    rows.collect()
+}
+
+/// Date de mise en service de la clé courante : dernière rotation, à défaut
+/// création du schéma (première migration appliquée)
+pub fn key_in_use_since(conn: &Connection) -> SqlResult<Option<String>> {
+    conn.query_row(
+        "SELECT COALESCE(
+            (SELECT MAX(rotated_at) FROM key_rotations),
+            (SELECT MIN(applied_at) FROM schema_migrations)
+         )",
+        [],
+        |row| row.get(0),
+    )
+}
+
+// ============================================================================
+// Profils radiologues (poste de lecture partagé)
+// ============================================================================
+
+/// Clé app_settings du radiologue actif
+const ACTIVE_USER_SETTING: &str = "active_user_id";
+
+/// Préférences stockées par radiologue
+pub const USER_PREFERENCE_KEYS: &[&str] = &["shortcuts", "speechmike_mapping", "injection_profile"];
+
+/// Profil radiologue
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct UserProfile {
+    pub id: String,
+    pub username: String,
+    pub display_name: String,
+    pub rpps: Option<String>,
+    pub is_active: bool,
+    pub created_at: String,
+    pub last_active_at: Option<String>,
+}
+
+/// Préférence d'un radiologue (valeur JSON brute)
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct UserPreference {
+    pub pref_key: String,
+    pub value: String,
+    pub updated_at: String,
+}
+
+fn row_to_user_profile(row: &rusqlite::Row) -> SqlResult<UserProfile> {
+    Ok(UserProfile {
+        id: row.get(0)?,
+        username: row.get(1)?,
+        display_name: row.get(2)?,
+        rpps: row.get(3)?,
+        is_active: row.get::<_, i32>(4)? == 1,
+        created_at: row.get(5)?,
+        last_active_at: row.get(6)?,
+    })
+}
+
+/// Crée un profil radiologue
+pub fn create_user(
+    conn: &Connection,
+    id: &str,
+    username: &str,
+    display_name: &str,
+    rpps: Option<&str>,
+) -> SqlResult<()> {
+    let now = Utc::now().to_rfc3339();
+    
+    conn.execute(
+        "INSERT INTO users (id, username, display_name, rpps, is_active, created_at)
+         VALUES (?1, ?2, ?3, ?4, 1, ?5)",
+        params![id, username, display_name, rpps, now],
+    )?;
+    
+    Ok(())
+}
+
+/// Liste les profils (actifs uniquement si `active_only`)
+pub fn list_users(conn: &Connection, active_only: bool) -> SqlResult<Vec<UserProfile>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, username, display_name, rpps, is_active, created_at, last_active_at
+         FROM users
+         WHERE is_active = 1 OR ?1 = 0
+         ORDER BY display_name COLLATE NOCASE"
+    )?;
+    
+    let users = stmt.query_map([active_only as i32], row_to_user_profile)?
+        .collect::<SqlResult<Vec<_>>>()?;
+    
+    Ok(users)
+}
+
+/// Récupère un profil par id ou par username
+pub fn find_user(conn: &Connection, id_or_username: &str) -> SqlResult<Option<UserProfile>> {
+    let result = conn.query_row(
+        "SELECT id, username, display_name, rpps, is_active, created_at, last_active_at
+         FROM users
+         WHERE id = ?1 OR username = ?1
+         LIMIT 1",
+        [id_or_username],
+        row_to_user_profile,
+    );
+    
+    match result {
+        Ok(user) => Ok(Some(user)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Désactive un profil (soft-delete, conserve la traçabilité des logs)
+pub fn deactivate_user(conn: &Connection, user_id: &str) -> SqlResult<bool> {
+    let rows = conn.execute(
+        "UPDATE users SET is_active = 0 WHERE id = ?1 AND is_active = 1",
+        [user_id],
+    )?;
+    
+    // Un profil désactivé ne peut rester le radiologue actif
+    conn.execute(
+        "DELETE FROM app_settings WHERE key = ?1 AND value = ?2",
+        params![ACTIVE_USER_SETTING, user_id],
+    )?;
+    
+    Ok(rows > 0)
+}
+
+/// Identifiant du radiologue actif sur le poste
+pub fn get_active_user_id(conn: &Connection) -> SqlResult<Option<String>> {
+    let result = conn.query_row(
+        "SELECT value FROM app_settings WHERE key = ?1",
+        [ACTIVE_USER_SETTING],
+        |row| row.get::<_, Option<String>>(0),
+    );
+    
+    match result {
+        Ok(value) => Ok(value),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Change le radiologue actif (None = aucun profil sélectionné)
+pub fn set_active_user(conn: &Connection, user_id: Option<&str>) -> SqlResult<()> {
+    let now = Utc::now().to_rfc3339();
+    
+    match user_id {
+        Some(id) => {
+            conn.execute(
+                "INSERT INTO app_settings (key, value, updated_at) VALUES (?1, ?2, ?3)
+                 ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
+                params![ACTIVE_USER_SETTING, id, now],
+            )?;
+            conn.execute(
+                "UPDATE users SET last_active_at = ?1 WHERE id = ?2",
+                params![now, id],
+            )?;
+        }
+        None => {
+            conn.execute("DELETE FROM app_settings WHERE key = ?1", [ACTIVE_USER_SETTING])?;
+        }
+    }
+    
+    Ok(())
+}
+
+/// Liste les préférences d'un radiologue
+pub fn list_user_preferences(conn: &Connection, user_id: &str) -> SqlResult<Vec<UserPreference>> {
+    let mut stmt = conn.prepare(
+        "SELECT pref_key, value, updated_at FROM user_preferences WHERE user_id = ?1 ORDER BY pref_key"
+    )?;
+    
+    let prefs = stmt.query_map([user_id], |row| {
+        Ok(UserPreference {
+            pref_key: row.get(0)?,
+            value: row.get(1)?,
+            updated_at: row.get(2)?,
+        })
+    })?
+    .collect::<SqlResult<Vec<_>>>()?;
+    
+    Ok(prefs)
+}
+
+/// Enregistre (ou remplace) une préférence d'un radiologue
+pub fn set_user_preference(conn: &Connection, user_id: &str, pref_key: &str, value: &str) -> SqlResult<()> {
+    let now = Utc::now().to_rfc3339();
+    
+    conn.execute(
+        "INSERT INTO user_preferences (user_id, pref_key, value, updated_at) VALUES (?1, ?2, ?3, ?4)
+         ON CONFLICT(user_id, pref_key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
+        params![user_id, pref_key, value, now],
+    )?;
+    
+    Ok(())
+}
+
+// ============================================================================
+// Tests unitaires
 // ============================================================================
 
 #[cfg(test)]
@@ -669,6 +1983,234 @@ mod tests {
         assert!(result.is_none());
     }
     
+    #[test]
+    fn test_find_prefers_report_assigned_to_user() {
+        let conn = setup_test_db();
+        create_user(&conn, "user-a", "dr.a", "Dr A", None).unwrap();
+        create_user(&conn, "user-b", "dr.b", "Dr B", None).unwrap();
+        
+        for (id, tid, created_at) in [("r1", "TEST_A", "2025-12-15T10:00:00Z"), ("r2", "TEST_B", "2025-12-15T11:00:00Z")] {
+            insert_pending_report(
+                &conn, id, tid, Some("PAT777"), None, None, None,
+                r#"{"title": "Test"}"#, "test", None, None, None,
+                created_at, "2099-12-31T23:59:59Z",
+            ).unwrap();
+        }
+        assign_pending_report(&conn, "TEST_A", Some("user-a")).unwrap();
+        assign_pending_report(&conn, "TEST_B", Some("user-b")).unwrap();
+        
+        let by_patient = ReportIdentifiers { patient_id: Some("PAT777"), ..Default::default() };
+        let found = find_report_candidates(&conn, &by_patient, MatchMode::Ranked, Some("user-a"), 1).unwrap();
+        assert_eq!(found[0].report.technical_id, "TEST_A");
+        
+        // Sans préférence : le plus récent
+        let found = find_report_candidates(&conn, &by_patient, MatchMode::Ranked, None, 1).unwrap();
+        assert_eq!(found[0].report.technical_id, "TEST_B");
+        
+        set_active_user(&conn, Some("user-b")).unwrap();
+        assert_eq!(get_active_user_id(&conn).unwrap(), Some("user-b".to_string()));
+        deactivate_user(&conn, "user-b").unwrap();
+        assert_eq!(get_active_user_id(&conn).unwrap(), None);
+    }
+    
+    #[test]
+    fn test_find_candidates_ranks_exam_identifiers_over_patient() {
+        let conn = setup_test_db();
+        
+        // Ancien examen du patient, puis examen demandé par le RIS
+        insert_pending_report(
+            &conn, "r1", "OLD_EXAM", Some("PAT900"), None, Some("ACC-OLD"), None,
+            r#"{"title": "Test"}"#, "test", None, None, None,
+            "2025-12-15T11:00:00Z", "2099-12-31T23:59:59Z",
+        ).unwrap();
+        insert_pending_report(
+            &conn, "r2", "NEW_EXAM", Some("PAT900"), None, Some("ACC-NEW"), Some("1.2.840.900"),
+            r#"{"title": "Test"}"#, "test", None, None, None,
+            "2025-12-15T10:00:00Z", "2099-12-31T23:59:59Z",
+        ).unwrap();
+        
+        let ids = ReportIdentifiers {
+            patient_id: Some("PAT900"),
+            accession_number: Some("ACC-NEW"),
+            ..Default::default()
+        };
+        let ranked = find_report_candidates(&conn, &ids, MatchMode::Ranked, None, MAX_MATCH_CANDIDATES).unwrap();
+        assert_eq!(ranked.len(), 2);
+        assert_eq!(ranked[0].report.technical_id, "NEW_EXAM");
+        assert_eq!(ranked[0].score, 50);
+        assert_eq!(ranked[0].matched_on, vec!["accession_number", "patient_id"]);
+        assert_eq!(ranked[1].score, 10);
+        assert!(!is_ambiguous_match(&ranked));
+        
+        let strict = find_report_candidates(&conn, &ids, MatchMode::Strict, None, MAX_MATCH_CANDIDATES).unwrap();
+        assert_eq!(strict.len(), 1);
+        
+        // study_instance_uid : clé de recherche à part entière
+        let by_study = ReportIdentifiers { study_instance_uid: Some("1.2.840.900"), ..Default::default() };
+        let found = find_report_candidates(&conn, &by_study, MatchMode::Strict, None, 1).unwrap();
+        assert_eq!(found[0].report.technical_id, "NEW_EXAM");
+        
+        // Patient seul : deux examens à égalité → ambigu
+        let by_patient = ReportIdentifiers { patient_id: Some("PAT900"), ..Default::default() };
+        let found = find_report_candidates(&conn, &by_patient, MatchMode::Ranked, None, MAX_MATCH_CANDIDATES).unwrap();
+        assert!(is_ambiguous_match(&found));
+    }
+    
+    #[test]
+    fn test_search_reports_fts() {
+        let conn = setup_test_db();
+        
+        insert_pending_report(
+            &conn, "r1", "SEARCH_A", Some("PAT1"), None, None, None,
+            r#"{"title": "Mammographie", "results": "Masse spiculée", "conclusion": "Lésion classée BI-RADS 4"}"#,
+            "test", None, Some("MG"), None,
+            "2025-12-15T10:00:00Z", "2099-12-31T23:59:59Z",
+        ).unwrap();
+        insert_pending_report(
+            &conn, "r2", "SEARCH_B", Some("PAT2"), None, None, None,
+            r#"{"title": "Scanner thoracique", "conclusion": "Nodule pulmonaire de 6 mm"}"#,
+            "test", None, Some("CT"), None,
+            "2025-12-15T11:00:00Z", "2099-12-31T23:59:59Z",
+        ).unwrap();
+        
+        let hits = search_reports(&conn, "BI-RADS 4", None, 20).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].technical_id, "SEARCH_A");
+        assert!(hits[0].snippet.contains("<mark>"));
+        
+        // Accents ignorés, recherche par préfixe, filtre modalité
+        assert_eq!(search_reports(&conn, "lesion", None, 20).unwrap().len(), 1);
+        assert_eq!(search_reports(&conn, "nodul*", Some("CT"), 20).unwrap().len(), 1);
+        assert!(search_reports(&conn, "nodule", Some("MG"), 20).unwrap().is_empty());
+        
+        // Index maintenu à la suppression
+        delete_pending_report(&conn, "SEARCH_B").unwrap();
+        assert!(search_reports(&conn, "nodule", None, 20).unwrap().is_empty());
+        assert!(build_fts_query("  \" * ").is_none());
+    }
+    
+    #[test]
+    fn test_restored_report_replaces_indexed_text() {
+        let conn = setup_test_db();
+        
+        for (id, text) in [("r1", "Fracture du radius"), ("r2", "Entorse de la cheville")] {
+            insert_pending_report(
+                &conn, id, "SEARCH_SAME", Some("PAT1"), None, None, None,
+                &format!(r#"{{"title": "Radio", "conclusion": "{}"}}"#, text),
+                "test", None, Some("CR"), None,
+                "2025-12-15T10:00:00Z", "2099-12-31T23:59:59Z",
+            ).unwrap();
+        }
+        
+        assert!(search_reports(&conn, "radius", None, 20).unwrap().is_empty());
+        let hits = search_reports(&conn, "cheville", None, 20).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].technical_id, "SEARCH_SAME");
+        assert_eq!(search_reports(&conn, "radio", None, 20).unwrap().len(), 1);
+        let indexed: i64 = conn
+            .query_row("SELECT COUNT(*) FROM pending_reports_fts", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(indexed, 1);
+    }
+    
+    #[test]
+    fn test_report_template_crud() {
+        let conn = setup_test_db();
+        
+        let fields = ReportTemplateFields {
+            name: "scanner-thorax-normal",
+            modality: Some("CT"),
+            body_part: Some("chest"),
+            language: "fr",
+            content: r#"{"title": "Scanner thoracique", "results": "Absence de nodule.", "conclusion": "Examen normal."}"#,
+        };
+        insert_report_template(&conn, "tpl-1", &fields, Some("user-a")).unwrap();
+        
+        // Nom unique
+        assert!(insert_report_template(&conn, "tpl-2", &fields, None).is_err());
+        
+        assert_eq!(get_report_template(&conn, "scanner-thorax-normal").unwrap().unwrap().id, "tpl-1");
+        assert_eq!(list_report_templates(&conn, Some("CT"), Some("CHEST"), None).unwrap().len(), 1);
+        assert!(list_report_templates(&conn, Some("MR"), None, None).unwrap().is_empty());
+        
+        let renamed = ReportTemplateFields { name: "ct-thorax-normal", ..fields };
+        assert!(update_report_template(&conn, "tpl-1", &renamed).unwrap());
+        assert_eq!(get_report_template(&conn, "tpl-1").unwrap().unwrap().name, "ct-thorax-normal");
+        
+        // Suppression par nom, comme la lecture
+        assert!(delete_report_template(&conn, "ct-thorax-normal").unwrap());
+        assert!(get_report_template(&conn, "tpl-1").unwrap().is_none());
+        assert!(!delete_report_template(&conn, "tpl-1").unwrap());
+    }
+    
+    #[test]
+    fn test_worklist_orders_by_priority_then_age() {
+        let conn = setup_test_db();
+        
+        for (id, tid, created_at) in [
+            ("w1", "ROUTINE_OLD", "2025-12-15T08:00:00Z"),
+            ("w2", "URGENT", "2025-12-15T09:00:00Z"),
+            ("w3", "STAT", "2025-12-15T10:00:00Z"),
+            ("w4", "ROUTINE_NEW", "2025-12-15T11:00:00Z"),
+        ] {
+            insert_pending_report(
+                &conn, id, tid, None, None, None, None,
+                r#"{"title": "Test"}"#, "test", None, Some("CT"), None,
+                created_at, "2099-12-31T23:59:59Z",
+            ).unwrap();
+        }
+        set_report_schedule(&conn, "STAT", "stat", Some("2020-01-01T00:00:00+00:00")).unwrap();
+        set_report_priority(&conn, "URGENT", "urgent").unwrap();
+        
+        let filter = WorklistFilter { limit: 10, ..Default::default() };
+        let items = list_worklist(&conn, &filter).unwrap();
+        let tids: Vec<&str> = items.iter().map(|i| i.technical_id.as_str()).collect();
+        assert_eq!(tids, vec!["STAT", "URGENT", "ROUTINE_OLD", "ROUTINE_NEW"]);
+        assert!(items[0].overdue);
+        assert!(!items[1].overdue);
+        
+        // Rapport en cours exclu, filtre priorité
+        let filter = WorklistFilter {
+            exclude_tid: Some("STAT".to_string()),
+            priority: Some("routine".to_string()),
+            limit: 10,
+            ..Default::default()
+        };
+        let items = list_worklist(&conn, &filter).unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].technical_id, "ROUTINE_OLD");
+    }
+    
+    #[test]
+    fn test_report_lock_lease() {
+        let conn = setup_test_db();
+        
+        let lock = match acquire_report_lock(&conn, "TEST_LOCK", "user-a", Some("Dr A"), "ws-1", 300).unwrap() {
+            LockAcquisition::Acquired(lock) => lock,
+            LockAcquisition::Held(_) => panic!("verrou libre attendu"),
+        };
+        assert_eq!(lock.holder_id, "user-a");
+        
+        // Autre client : refusé avec l'identité du détenteur
+        match acquire_report_lock(&conn, "TEST_LOCK", "user-b", None, "ws-2", 300).unwrap() {
+            LockAcquisition::Held(held) => assert_eq!(held.workstation, "ws-1"),
+            LockAcquisition::Acquired(_) => panic!("conflit attendu"),
+        }
+        assert!(heartbeat_report_lock(&conn, "TEST_LOCK", "user-b", "ws-2", 300).unwrap().is_none());
+        assert!(heartbeat_report_lock(&conn, "TEST_LOCK", "user-a", "ws-1", 300).unwrap().is_some());
+        
+        // Bail expiré : le verrou peut être repris
+        conn.execute("UPDATE report_locks SET expires_at = '2020-01-01T00:00:00+00:00'", []).unwrap();
+        assert!(matches!(
+            acquire_report_lock(&conn, "TEST_LOCK", "user-b", None, "ws-2", 300).unwrap(),
+            LockAcquisition::Acquired(_)
+        ));
+        
+        assert!(!release_report_lock(&conn, "TEST_LOCK", Some(("user-a", "ws-1"))).unwrap());
+        assert!(release_report_lock(&conn, "TEST_LOCK", None).unwrap());
+        assert!(list_report_locks(&conn).unwrap().is_empty());
+    }
+    
     #[test]
     fn test_expired_report_not_returned() {
         let conn = setup_test_db();
@@ -689,4 +2231,121 @@ mod tests {
         let result = get_pending_report_by_tid(&conn, "TEST_003").unwrap();
         assert!(result.is_none()); // Ne devrait pas être retourné car expiré
     }
+    
+    #[test]
+    fn test_secure_purge_and_journal() {
+        let conn = setup_test_db();
+        
+        insert_pending_report(
+            &conn,
+            "test-id-4",
+            "TEST_PURGE",
+            Some("PAT777"),
+            None, None, None,
+            r#"{"title": "Scanner thoracique"}"#,
+            "teo_hub",
+            None, Some("CT"), None,
+            "2020-01-01T10:00:00+00:00",
+            "2099-12-31T23:59:59+00:00",
+        ).unwrap();
+        
+        let candidates = list_retention_candidates(&conn).unwrap();
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].source_type, "teo_hub");
+        assert_eq!(candidates[0].status, "pending");
+        
+        let secure_before: i64 = conn.query_row("PRAGMA secure_delete", [], |row| row.get(0)).unwrap();
+        let ids = vec!["TEST_PURGE".to_string()];
+        let in_fts_data = |term: &str| -> i64 {
+            conn.query_row(
+                "SELECT COUNT(*) FROM pending_reports_fts_data WHERE instr(block, CAST(?1 AS BLOB)) > 0",
+                [term],
+                |row| row.get(0),
+            )
+            .unwrap()
+        };
+        assert!(in_fts_data("thoracique") > 0);
+        assert_eq!(purge_pending_reports(&conn, &ids, true).unwrap(), 1);
+        assert!(list_retention_candidates(&conn).unwrap().is_empty());
+        assert_eq!(search_reports(&conn, "thoracique", None, 10).unwrap().len(), 0);
+        // Termes effacés de l'index, pas seulement masqués par un marqueur
+        assert_eq!(in_fts_data("thoracique"), 0);
+        
+        // Réglage secure_delete de la connexion restauré après la purge
+        let secure: i64 = conn.query_row("PRAGMA secure_delete", [], |row| row.get(0)).unwrap();
+        assert_eq!(secure, secure_before);
+        
+        insert_retention_purge(&conn, "2026-01-01T00:00:00+00:00", "teo_ct", "pending_reports", 1, &ids, true).unwrap();
+        let journal = list_retention_purges(&conn, 10).unwrap();
+        assert_eq!(journal.len(), 1);
+        assert_eq!(journal[0].rule_id, "teo_ct");
+        assert_eq!(journal[0].technical_ids, ids);
+        assert!(journal[0].secure_delete);
+    }
+    
+    #[test]
+    fn test_subject_records_anonymise_then_erase() {
+        let conn = setup_test_db();
+        
+        let subjects = [
+            ("test-id-5", "TEST_GDPR_1", "PAT555", r#"{"title": "IRM genou", "conclusion": "Fissure meniscale"}"#),
+            ("test-id-6", "TEST_GDPR_2", "PAT666", r#"{"title": "IRM genou"}"#),
+        ];
+        for (id, tid, patient, structured) in subjects {
+            insert_pending_report(
+                &conn,
+                id,
+                tid,
+                Some(patient),
+                None, Some("ACC555"), None,
+                structured,
+                "ris_local",
+                None, Some("MR"), Some(r#"{"ward": "B"}"#),
+                "2026-01-01T10:00:00+00:00",
+                "2099-12-31T23:59:59+00:00",
+            ).unwrap();
+        }
+        insert_access_log(
+            &conn, "2026-01-01T10:00:00+00:00", "127.0.0.1", "GET", "/report-locks/TEST_GDPR_1/heartbeat",
+            200, "success", None, None, "req-1", 3, None, None,
+        ).unwrap();
+        insert_access_log(
+            &conn, "2026-01-01T10:01:00+00:00", "127.0.0.1", "GET", "/pending-report",
+            404, "not_found", None, None, "req-2", 2, Some("Report not found for pid_0123456789abcdef"), None,
+        ).unwrap();
+        
+        let reports = find_subject_reports(&conn, Some("PAT555"), None).unwrap();
+        assert_eq!(reports.len(), 1);
+        assert_eq!(find_subject_reports(&conn, None, Some("ACC555")).unwrap().len(), 2);
+        
+        let needles = vec!["TEST_GDPR_1".to_string(), "pid_0123456789abcdef".to_string()];
+        let logs = find_access_logs_referencing(&conn, &needles).unwrap();
+        assert_eq!(logs.len(), 2);
+        let log_ids: Vec<i64> = logs.iter().map(|l| l.id).collect();
+        
+        let ids = vec!["TEST_GDPR_1".to_string()];
+        let erased = erase_subject_records(&conn, &ids, &log_ids, true, true).unwrap();
+        assert_eq!((erased.reports, erased.access_logs), (1, 2));
+        let report = get_pending_report_by_tid(&conn, "TEST_GDPR_1").unwrap().unwrap();
+        assert!(report.patient_id.is_none() && report.metadata.is_none());
+        assert_eq!(report.structured_data, subjects[0].3);
+        assert!(find_subject_reports(&conn, Some("PAT555"), None).unwrap().is_empty());
+        
+        let in_fts_data = |term: &str| -> i64 {
+            conn.query_row(
+                "SELECT COUNT(*) FROM pending_reports_fts_data WHERE instr(block, CAST(?1 AS BLOB)) > 0",
+                [term],
+                |row| row.get(0),
+            )
+            .unwrap()
+        };
+        assert!(in_fts_data("meniscale") > 0);
+        let erased = erase_subject_records(&conn, &ids, &log_ids, false, true).unwrap();
+        assert_eq!((erased.reports, erased.access_logs), (1, 2));
+        assert!(get_pending_report_by_tid(&conn, "TEST_GDPR_1").unwrap().is_none());
+        assert_eq!(in_fts_data("meniscale"), 0);
+        assert_eq!(search_reports(&conn, "genou", None, 10).unwrap().len(), 1);
+        assert!(find_access_logs_referencing(&conn, &needles).unwrap().is_empty());
+        assert!(get_pending_report_by_tid(&conn, "TEST_GDPR_2").unwrap().is_some());
+    }
 }
@@ -0,0 +1,360 @@
+// ============================================================================
+// AIRADCR Desktop - Restauration sûre d'un backup
+// ============================================================================
+// La base ne doit pas être remplacée sous des connexions ouvertes (Windows
+// refuse le renommage, et un lecteur WAL mélangerait les deux fichiers).
+// Déroulement d'une restauration :
+//   1. le backup est vérifié avant toute interruption (empreinte, intégrité,
+//      clé, version du schéma) : voir `BackupManager::restore_backup` ;
+//   2. mode maintenance : le serveur HTTP répond 503, les tâches de fond
+//      sautent leur cycle, aucune nouvelle instance `Database` ne s'ouvre et
+//      les instances existantes doivent se fermer ;
+//   3. les connexions de la base partagée sont fermées et la base courante
+//      est conservée telle quelle (instantané `.db.before_restore`) ;
+//   4. le backup est copié à sa place, vérifié (clé, intégrité, schéma) puis
+//      la base est rouverte (migrations éventuelles comprises) ;
+//   5. au moindre échec, l'instantané est remis en place et rouvert.
+// Chaque étape est signalée via un callback (événement frontend).
+// ============================================================================
+
+use rusqlite::Connection;
+use serde::Serialize;
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+use log::{info, warn, error};
+
+use super::{apply_sqlcipher_key, migrations, Database};
+
+/// Délai laissé aux requêtes et aux autres instances pour se terminer
+pub(super) const DRAIN_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Restauration (ou rotation de clé) en cours
+static MAINTENANCE: AtomicBool = AtomicBool::new(false);
+
+/// Instances `Database` ouvertes sur la base du poste
+static INSTANCES: AtomicUsize = AtomicUsize::new(0);
+
+/// Indique si une restauration ou une rotation de clé est en cours (accès à
+/// la base suspendus)
+pub fn maintenance_active() -> bool {
+    MAINTENANCE.load(Ordering::SeqCst)
+}
+
+/// Instance `Database` ouverte sur la base du poste (comptée tant qu'elle vit)
+pub struct InstanceGuard(());
+
+impl InstanceGuard {
+    /// Compte une nouvelle instance ; refusée pendant une restauration
+    pub(super) fn acquire() -> rusqlite::Result<Self> {
+        // Compter avant de tester : une restauration qui démarre entre les
+        // deux attend la fermeture de cette instance
+        INSTANCES.fetch_add(1, Ordering::SeqCst);
+        let guard = InstanceGuard(());
+
+        if maintenance_active() {
+            return Err(super::unavailable_error(
+                "Maintenance de la base en cours (restauration ou rotation de clé) : réessayez dans quelques instants",
+            ));
+        }
+        Ok(guard)
+    }
+}
+
+impl Drop for InstanceGuard {
+    fn drop(&mut self) {
+        INSTANCES.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Mode maintenance, levé en fin d'opération (succès ou échec)
+pub(super) struct Maintenance;
+
+impl Maintenance {
+    pub(super) fn begin(operation: &str) -> Result<Self, String> {
+        MAINTENANCE
+            .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+            .map_err(|_| "Une restauration ou une rotation de clé est déjà en cours".to_string())?;
+        warn!("[Database] Mode maintenance activé ({}) : accès à la base suspendus", operation);
+        Ok(Maintenance)
+    }
+}
+
+impl Drop for Maintenance {
+    fn drop(&mut self) {
+        MAINTENANCE.store(false, Ordering::SeqCst);
+        info!("[Database] Mode maintenance levé");
+    }
+}
+
+/// Étape de la restauration
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RestoreStep {
+    Verifying,
+    Pausing,
+    Snapshot,
+    Restoring,
+    Checking,
+    Reopening,
+    RollingBack,
+    Completed,
+    RolledBack,
+    Failed,
+}
+
+/// Progression signalée au frontend (événement `airadcr:restore_progress`)
+#[derive(Debug, Clone, Serialize)]
+pub struct RestoreProgress {
+    pub step: RestoreStep,
+    pub backup: String,
+    pub message: String,
+}
+
+/// Résultat d'une restauration réussie
+#[derive(Debug, Clone, Serialize)]
+pub struct RestoreReport {
+    pub backup: String,
+    /// Base remplacée, conservée jusqu'à la prochaine restauration
+    pub snapshot: String,
+    pub schema_version: u32,
+    pub duration_ms: u64,
+}
+
+/// Journalise une étape et la transmet au callback
+pub(super) fn notify(progress: &dyn Fn(RestoreProgress), step: RestoreStep, backup: &str, message: String) {
+    match step {
+        RestoreStep::Failed | RestoreStep::RollingBack => error!("[Restore] {:?}: {}", step, message),
+        _ => info!("[Restore] {:?}: {}", step, message),
+    }
+    progress(RestoreProgress {
+        step,
+        backup: backup.to_string(),
+        message,
+    });
+}
+
+/// Remplace la base de `db` par `candidate` (copie chiffrée avec la clé du
+/// poste, déjà vérifiée), avec retour à l'instantané en cas d'échec
+pub(super) fn replace_database(
+    db: &Database,
+    candidate: &Path,
+    backup: &str,
+    progress: &dyn Fn(RestoreProgress),
+) -> Result<RestoreReport, String> {
+    let emit = |step, message: String| notify(progress, step, backup, message);
+    let fail = |message: String| {
+        emit(RestoreStep::Failed, message.clone());
+        message
+    };
+
+    let started = Instant::now();
+    let encryption_key = db
+        .encryption_key()
+        .ok_or_else(|| fail("Restauration impossible sur une base en mémoire".to_string()))?;
+    let db_path = db.path().to_path_buf();
+    let snapshot = db_path.with_extension("db.before_restore");
+
+    let _maintenance = Maintenance::begin("restauration").map_err(fail)?;
+
+    emit(RestoreStep::Pausing, "Mise en pause du serveur HTTP et des tâches de fond".to_string());
+    wait_for_instances(db).map_err(fail)?;
+    db.suspend(DRAIN_TIMEOUT)
+        .map_err(|e| fail(format!("Fermeture de la base impossible: {}", e)))?;
+
+    emit(RestoreStep::Snapshot, format!("Conservation de la base courante: {}", snapshot.display()));
+    if let Err(e) = take_snapshot(&db_path, &snapshot) {
+        // Fichiers inchangés : la base courante est simplement rouverte
+        let reopened = db.resume().map(|_| "base courante inchangée".to_string())
+            .unwrap_or_else(|e| format!("réouverture impossible: {}", e));
+        return Err(fail(format!("Instantané impossible: {} ({})", e, reopened)));
+    }
+
+    let result = (|| -> Result<u32, String> {
+        emit(RestoreStep::Restoring, "Copie du backup à la place de la base".to_string());
+        install(candidate, &db_path)?;
+
+        emit(RestoreStep::Checking, "Vérification de la base restaurée (clé, intégrité, schéma)".to_string());
+        let schema_version = check_restored(&db_path, &encryption_key)?;
+
+        emit(RestoreStep::Reopening, "Réouverture de la base".to_string());
+        db.resume().map_err(|e| format!("Réouverture de la base restaurée: {}", e))?;
+        Ok(schema_version)
+    })();
+
+    match result {
+        Ok(schema_version) => {
+            let duration_ms = started.elapsed().as_millis() as u64;
+            emit(
+                RestoreStep::Completed,
+                format!("Base restaurée depuis {} (schéma v{}) en {}ms", backup, schema_version, duration_ms),
+            );
+            Ok(RestoreReport {
+                backup: backup.to_string(),
+                snapshot: snapshot.to_string_lossy().to_string(),
+                schema_version,
+                duration_ms,
+            })
+        }
+        Err(e) => {
+            emit(RestoreStep::RollingBack, format!("{} : retour à la base précédente", e));
+            match roll_back(db, &db_path, &snapshot) {
+                Ok(()) => {
+                    emit(RestoreStep::RolledBack, "Base précédente remise en service".to_string());
+                    Err(format!("Restauration échouée ({}) : base précédente remise en service", e))
+                }
+                Err(rollback_error) => Err(fail(format!(
+                    "Restauration échouée ({}) et retour arrière impossible ({}) : base précédente conservée sous {}",
+                    e,
+                    rollback_error,
+                    snapshot.display()
+                ))),
+            }
+        }
+    }
+}
+
+/// Attend la fermeture des autres instances `Database` (commandes en cours)
+pub(super) fn wait_for_instances(db: &Database) -> Result<(), String> {
+    let own = usize::from(db._instance.is_some());
+    let deadline = Instant::now() + DRAIN_TIMEOUT;
+
+    loop {
+        let others = INSTANCES.load(Ordering::SeqCst).saturating_sub(own);
+        if others == 0 {
+            return Ok(());
+        }
+        if Instant::now() >= deadline {
+            return Err(format!("{} autre(s) accès à la base toujours ouvert(s) : opération annulée", others));
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+}
+
+/// Fichier annexe SQLite (`-wal`, `-shm`) d'une base
+fn sidecar(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = OsString::from(path.as_os_str());
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+fn remove_if_exists(path: &Path) -> Result<(), String> {
+    match fs::remove_file(path) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(format!("Suppression de {:?}: {}", path, e)),
+    }
+}
+
+/// Déplace la base (et son journal WAL éventuel) vers `snapshot`
+fn take_snapshot(db_path: &Path, snapshot: &Path) -> Result<(), String> {
+    for path in [snapshot.to_path_buf(), sidecar(snapshot, "-wal"), sidecar(snapshot, "-shm")] {
+        remove_if_exists(&path)?;
+    }
+
+    fs::rename(db_path, snapshot).map_err(|e| format!("Renommage de {:?}: {}", db_path, e))?;
+    if sidecar(db_path, "-wal").exists() {
+        fs::rename(sidecar(db_path, "-wal"), sidecar(snapshot, "-wal"))
+            .map_err(|e| format!("Renommage du journal WAL: {}", e))?;
+    }
+    remove_if_exists(&sidecar(db_path, "-shm"))
+}
+
+/// Copie le backup à la place de la base (fichier temporaire puis renommage)
+fn install(candidate: &Path, db_path: &Path) -> Result<(), String> {
+    let partial = db_path.with_extension("db.restore.partial");
+    fs::copy(candidate, &partial).map_err(|e| {
+        let _ = fs::remove_file(&partial);
+        format!("Copie du backup: {}", e)
+    })?;
+    fs::rename(&partial, db_path).map_err(|e| {
+        let _ = fs::remove_file(&partial);
+        format!("Mise en place du backup: {}", e)
+    })
+}
+
+/// Ouvre la base restaurée avec la clé du poste : intégrité et version du schéma
+fn check_restored(db_path: &Path, encryption_key: &str) -> Result<u32, String> {
+    let conn = Connection::open(db_path).map_err(|e| format!("Ouverture de la base restaurée: {}", e))?;
+    apply_sqlcipher_key(&conn, encryption_key)
+        .map_err(|e| format!("Clé du poste refusée par la base restaurée: {}", e))?;
+
+    let integrity: String = conn
+        .query_row("PRAGMA integrity_check", [], |row| row.get(0))
+        .map_err(|e| format!("Vérification d'intégrité: {}", e))?;
+    if integrity != "ok" {
+        return Err(format!("Base restaurée corrompue: {}", integrity));
+    }
+
+    migrations::ensure_supported(&conn).map_err(|e| e.to_string())
+}
+
+/// Remet l'instantané en place et rouvre la base
+fn roll_back(db: &Database, db_path: &Path, snapshot: &Path) -> Result<(), String> {
+    db.suspend(DRAIN_TIMEOUT).map_err(|e| e.to_string())?;
+
+    for path in [db_path.to_path_buf(), sidecar(db_path, "-wal"), sidecar(db_path, "-shm")] {
+        remove_if_exists(&path)?;
+    }
+    fs::rename(snapshot, db_path).map_err(|e| format!("Remise en place de l'instantané: {}", e))?;
+    if sidecar(snapshot, "-wal").exists() {
+        fs::rename(sidecar(snapshot, "-wal"), sidecar(db_path, "-wal"))
+            .map_err(|e| format!("Remise en place du journal WAL: {}", e))?;
+    }
+    remove_if_exists(&sidecar(snapshot, "-shm"))?;
+
+    db.resume().map_err(|e| format!("Réouverture de la base précédente: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::backup::BackupManager;
+    use std::cell::RefCell;
+
+    #[test]
+    fn test_restore_replaces_database_and_rolls_back_on_failure() {
+        let dir = std::env::temp_dir().join(format!("airadcr-restore-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let db_path = dir.join("pending_reports.db");
+        let conn = Connection::open(&db_path).unwrap();
+        apply_sqlcipher_key(&conn, "k-restore").unwrap();
+        super::super::schema::initialize(&conn).unwrap();
+        let db = Database::with_read_pool(conn, db_path.clone(), db_path.clone(), Some("k-restore".to_string()), None)
+            .unwrap();
+
+        db.add_api_key("k-before", "airadcr_", "hash-before", "Avant backup").unwrap();
+        let keys = db.count_active_api_keys().unwrap();
+        let manager = BackupManager::new(db_path.clone(), 7);
+        let backup = manager.create_backup(&db).unwrap();
+        db.add_api_key("k-after", "airadcr_", "hash-after", "Après backup").unwrap();
+
+        let steps = RefCell::new(Vec::new());
+        let report = manager
+            .restore_backup(&db, &backup.filename, None, &|p| steps.borrow_mut().push(p.step))
+            .unwrap();
+        assert_eq!(steps.borrow().last(), Some(&RestoreStep::Completed));
+        assert_eq!(report.schema_version, migrations::latest_version());
+        assert!(Path::new(&report.snapshot).exists());
+
+        // Base rouverte : lectures et écritures sur le contenu du backup
+        assert_eq!(db.count_active_api_keys().unwrap(), keys);
+        db.add_api_key("k-restored", "airadcr_", "hash-restored", "Après restauration").unwrap();
+
+        // Fichier illisible avec la clé du poste : retour à la base précédente
+        let garbage = dir.join("garbage.db");
+        fs::write(&garbage, vec![0x5au8; 8192]).unwrap();
+        steps.borrow_mut().clear();
+        let error = replace_database(&db, &garbage, "garbage.db", &|p| steps.borrow_mut().push(p.step)).unwrap_err();
+        assert!(error.contains("base précédente remise en service"), "{}", error);
+        assert_eq!(steps.borrow().last(), Some(&RestoreStep::RolledBack));
+        assert_eq!(db.count_active_api_keys().unwrap(), keys + 1);
+        assert!(!maintenance_active());
+
+        drop(db);
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -6,6 +6,8 @@ use rusqlite::{Connection, Result as SqlResult};
 use sha2::{Sha256, Digest};
 use rand::Rng;
 
+use super::migrations::Migration;
+
 /// Génère une clé API aléatoire sécurisée
 fn generate_secure_api_key() -> String {
     let suffix: String = rand::thread_rng()
@@ -23,8 +25,65 @@ fn hash_api_key(key: &str) -> String {
     hex::encode(hasher.finalize())
 }
 
+/// Ajoute une colonne à une table existante si elle est absente
+/// (les bases créées avant l'ajout de la colonne ne sont pas recréées)
+fn ensure_column(conn: &Connection, table: &str, column: &str, definition: &str) -> SqlResult<()> {
+    let exists: bool = conn.query_row(
+        &format!("SELECT COUNT(*) FROM pragma_table_info('{}') WHERE name = ?1", table),
+        [column],
+        |row| row.get::<_, i64>(0),
+    )? > 0;
+    
+    if !exists {
+        conn.execute(&format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition), [])?;
+        println!("🔧 [Database] Colonne {}.{} ajoutée", table, column);
+    }
+    
+    Ok(())
+}
+
+/// Expressions SQL extrayant les champs indexés (title, indication, results,
+/// conclusion) d'une colonne JSON ; NULL si le JSON est invalide
+fn fts_field_exprs(column: &str) -> String {
+    ["title", "indication", "results", "conclusion"]
+        .iter()
+        .map(|field| format!("CASE WHEN json_valid({0}) THEN json_extract({0}, '$.{1}') END", column, field))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Migrations numérotées du schéma, dans l'ordre d'application.
+///
+/// ⚠️ Ne jamais modifier ni renuméroter une migration publiée : ajouter une
+/// nouvelle entrée en fin de liste. Chaque étape reste idempotente
+/// (`IF NOT EXISTS`, `ensure_column`) pour adopter sans casse les bases
+/// créées avant le suivi par `PRAGMA user_version` (version 0).
+pub const MIGRATIONS: &[Migration] = &[
+    Migration { version: 1, name: "initial_schema", apply: migrate_v1_initial_schema },
+    Migration { version: 2, name: "radiologist_profiles", apply: migrate_v2_radiologist_profiles },
+    Migration { version: 3, name: "worklist_priority", apply: migrate_v3_worklist_priority },
+    Migration { version: 4, name: "report_locks", apply: migrate_v4_report_locks },
+    Migration { version: 5, name: "report_templates", apply: migrate_v5_report_templates },
+    Migration { version: 6, name: "full_text_search", apply: migrate_v6_full_text_search },
+    Migration { version: 7, name: "retention_purges", apply: migrate_v7_retention_purges },
+    Migration { version: 8, name: "erasure_certificates", apply: migrate_v8_erasure_certificates },
+    Migration { version: 9, name: "key_rotations", apply: migrate_v9_key_rotations },
+    Migration { version: 10, name: "key_rotation_recovery", apply: migrate_v10_key_rotation_recovery },
+    Migration { version: 11, name: "full_text_reindex", apply: migrate_v11_full_text_reindex },
+    Migration { version: 12, name: "full_text_scrub", apply: migrate_v12_full_text_scrub },
+];
+
 /// Initialise le schéma de la base de données
+///
+/// Applique les migrations en attente (chacune dans sa transaction) puis
+/// crée la clé API initiale si nécessaire.
 pub fn initialize(conn: &Connection) -> SqlResult<()> {
+    super::migrations::apply_pending(conn)?;
+    seed_api_key(conn)
+}
+
+/// v1 — Rapports en attente, clés API et journal d'accès (AUDIT)
+fn migrate_v1_initial_schema(conn: &Connection) -> SqlResult<()> {
     // Table des rapports en attente - AVEC identifiants patients (LOCAL UNIQUEMENT)
     conn.execute(
         "CREATE TABLE IF NOT EXISTS pending_reports (
@@ -96,6 +155,11 @@ pub fn initialize(conn: &Connection) -> SqlResult<()> {
         [],
     )?;
     
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_pending_study_uid ON pending_reports(study_instance_uid)",
+        [],
+    )?;
+    
     conn.execute(
         "CREATE INDEX IF NOT EXISTS idx_pending_status ON pending_reports(status)",
         [],
@@ -160,9 +224,280 @@ pub fn initialize(conn: &Connection) -> SqlResult<()> {
         [],
     )?;
     
-    // =========================================================================
-    // Clé API de production - EXTERNALISÉE (Phase 1)
-    // =========================================================================
+    Ok(())
+}
+
+/// v2 — Profils radiologues (poste de lecture partagé)
+fn migrate_v2_radiologist_profiles(conn: &Connection) -> SqlResult<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS users (
+            id TEXT PRIMARY KEY,
+            username TEXT UNIQUE NOT NULL,
+            display_name TEXT NOT NULL,
+            rpps TEXT,
+            is_active INTEGER DEFAULT 1,
+            created_at TEXT NOT NULL,
+            last_active_at TEXT
+        )",
+        [],
+    )?;
+    
+    // Préférences par radiologue (valeurs JSON)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS user_preferences (
+            user_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+            pref_key TEXT NOT NULL CHECK (pref_key IN ('shortcuts', 'speechmike_mapping', 'injection_profile')),
+            value TEXT NOT NULL,
+            updated_at TEXT NOT NULL,
+            PRIMARY KEY (user_id, pref_key)
+        )",
+        [],
+    )?;
+    
+    // Réglages du poste (ex: radiologue actif)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS app_settings (
+            key TEXT PRIMARY KEY,
+            value TEXT,
+            updated_at TEXT NOT NULL
+        )",
+        [],
+    )?;
+    
+    // Attribution des rapports et traçabilité du radiologue actif
+    ensure_column(conn, "pending_reports", "assigned_to", "TEXT")?;
+    ensure_column(conn, "access_logs", "user_id", "TEXT")?;
+    
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_pending_assigned_to ON pending_reports(assigned_to)",
+        [],
+    )?;
+    
+    Ok(())
+}
+
+/// v3 — Liste de travail : priorité et échéance de lecture
+fn migrate_v3_worklist_priority(conn: &Connection) -> SqlResult<()> {
+    ensure_column(
+        conn,
+        "pending_reports",
+        "priority",
+        "TEXT NOT NULL DEFAULT 'routine' CHECK (priority IN ('routine', 'urgent', 'stat'))",
+    )?;
+    ensure_column(conn, "pending_reports", "due_at", "TEXT")?;
+    
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_pending_worklist ON pending_reports(status, priority, created_at)",
+        [],
+    )?;
+    
+    Ok(())
+}
+
+/// v4 — Verrous de rapports (bail avec heartbeat)
+fn migrate_v4_report_locks(conn: &Connection) -> SqlResult<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS report_locks (
+            technical_id TEXT PRIMARY KEY,
+            holder_id TEXT NOT NULL,
+            holder_name TEXT,
+            workstation TEXT NOT NULL,
+            acquired_at TEXT NOT NULL,
+            heartbeat_at TEXT NOT NULL,
+            expires_at TEXT NOT NULL
+        )",
+        [],
+    )?;
+    
+    Ok(())
+}
+
+/// v5 — Modèles de rapports (comptes rendus types)
+fn migrate_v5_report_templates(conn: &Connection) -> SqlResult<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS report_templates (
+            id TEXT PRIMARY KEY,
+            name TEXT NOT NULL UNIQUE,
+            modality TEXT,
+            body_part TEXT,
+            language TEXT NOT NULL DEFAULT 'fr',
+            content TEXT NOT NULL,
+            created_by TEXT,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        )",
+        [],
+    )?;
+    
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_templates_lookup ON report_templates(modality, body_part, language)",
+        [],
+    )?;
+    
+    Ok(())
+}
+
+/// v6 — Recherche plein texte (FTS5) sur le contenu structuré
+fn migrate_v6_full_text_search(conn: &Connection) -> SqlResult<()> {
+    // Table virtuelle dans la base principale : ses tables internes sont
+    // chiffrées par SQLCipher comme le reste du fichier. Indexée par triggers
+    // à partir des champs JSON de structured_data.
+    let fts_exists: bool = conn.query_row(
+        "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = 'pending_reports_fts'",
+        [],
+        |row| row.get::<_, i64>(0),
+    )? > 0;
+    
+    conn.execute(
+        "CREATE VIRTUAL TABLE IF NOT EXISTS main.pending_reports_fts USING fts5(
+            technical_id UNINDEXED,
+            title,
+            indication,
+            results,
+            conclusion,
+            tokenize = 'unicode61 remove_diacritics 2'
+        )",
+        [],
+    )?;
+    
+    conn.execute_batch(&format!(
+        "CREATE TRIGGER IF NOT EXISTS pending_reports_fts_insert AFTER INSERT ON pending_reports BEGIN
+            INSERT INTO pending_reports_fts (technical_id, title, indication, results, conclusion)
+            VALUES (new.technical_id, {new_fields});
+        END;
+        CREATE TRIGGER IF NOT EXISTS pending_reports_fts_update AFTER UPDATE OF technical_id, structured_data ON pending_reports BEGIN
+            DELETE FROM pending_reports_fts WHERE technical_id = old.technical_id;
+            INSERT INTO pending_reports_fts (technical_id, title, indication, results, conclusion)
+            VALUES (new.technical_id, {new_fields});
+        END;
+        CREATE TRIGGER IF NOT EXISTS pending_reports_fts_delete AFTER DELETE ON pending_reports BEGIN
+            DELETE FROM pending_reports_fts WHERE technical_id = old.technical_id;
+        END;",
+        new_fields = fts_field_exprs("new.structured_data"),
+    ))?;
+    
+    if !fts_exists {
+        // Index créé sur une base existante : indexer les rapports déjà stockés
+        let indexed = conn.execute(
+            &format!(
+                "INSERT INTO pending_reports_fts (technical_id, title, indication, results, conclusion)
+                 SELECT technical_id, {} FROM pending_reports",
+                fts_field_exprs("structured_data")
+            ),
+            [],
+        )?;
+        if indexed > 0 {
+            println!("🔧 [Database] Index plein texte construit ({} rapports)", indexed);
+        }
+    }
+    
+    Ok(())
+}
+
+/// v7 — Journal des purges de rétention (quoi, quand, par quelle règle)
+fn migrate_v7_retention_purges(conn: &Connection) -> SqlResult<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS retention_purges (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            purged_at TEXT NOT NULL,
+            rule_id TEXT NOT NULL,
+            target TEXT NOT NULL CHECK (target IN ('pending_reports', 'access_logs')),
+            item_count INTEGER NOT NULL,
+            technical_ids TEXT,
+            secure_delete INTEGER NOT NULL DEFAULT 1
+        )",
+        [],
+    )?;
+    
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_retention_purges_date ON retention_purges(purged_at)",
+        [],
+    )?;
+    
+    Ok(())
+}
+
+/// v8 — Certificats d'effacement RGPD (identifiants pseudonymisés uniquement)
+fn migrate_v8_erasure_certificates(conn: &Connection) -> SqlResult<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS erasure_certificates (
+            certificate_id TEXT PRIMARY KEY,
+            issued_at TEXT NOT NULL,
+            mode TEXT NOT NULL CHECK (mode IN ('erase', 'anonymise')),
+            certificate TEXT NOT NULL
+        )",
+        [],
+    )?;
+    
+    Ok(())
+}
+
+/// v9 — Journal des rotations de la clé SQLCipher
+fn migrate_v9_key_rotations(conn: &Connection) -> SqlResult<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS key_rotations (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            rotated_at TEXT NOT NULL,
+            from_version INTEGER NOT NULL,
+            to_version INTEGER NOT NULL,
+            trigger TEXT NOT NULL CHECK (trigger IN ('manual', 'scheduled')),
+            backups_reencrypted INTEGER NOT NULL DEFAULT 0,
+            backups_invalidated INTEGER NOT NULL DEFAULT 0
+        )",
+        [],
+    )?;
+    
+    Ok(())
+}
+
+/// v10 — Clé SQLCipher restaurée depuis un séquestre (déclencheur `recovery`).
+/// SQLite ne modifie pas une contrainte CHECK : la table est reconstruite.
+fn migrate_v10_key_rotation_recovery(conn: &Connection) -> SqlResult<()> {
+    conn.execute_batch(
+        "CREATE TABLE key_rotations_v10 (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            rotated_at TEXT NOT NULL,
+            from_version INTEGER NOT NULL,
+            to_version INTEGER NOT NULL,
+            trigger TEXT NOT NULL CHECK (trigger IN ('manual', 'scheduled', 'recovery')),
+            backups_reencrypted INTEGER NOT NULL DEFAULT 0,
+            backups_invalidated INTEGER NOT NULL DEFAULT 0
+        );
+        INSERT INTO key_rotations_v10
+            SELECT id, rotated_at, from_version, to_version, trigger, backups_reencrypted, backups_invalidated
+            FROM key_rotations;
+        DROP TABLE key_rotations;
+        ALTER TABLE key_rotations_v10 RENAME TO key_rotations;",
+    )?;
+    
+    Ok(())
+}
+
+/// v11 — Index plein texte reconstruit : les rapports ré-enregistrés par
+/// INSERT OR REPLACE y avaient laissé l'ancien texte (trigger de suppression
+/// non déclenché)
+fn migrate_v11_full_text_reindex(conn: &Connection) -> SqlResult<()> {
+    conn.execute("DELETE FROM pending_reports_fts", [])?;
+    conn.execute(
+        &format!(
+            "INSERT INTO pending_reports_fts (technical_id, title, indication, results, conclusion)
+             SELECT technical_id, {} FROM pending_reports",
+            fts_field_exprs("structured_data")
+        ),
+        [],
+    )?;
+    
+    Ok(())
+}
+
+/// v12 — Termes des rapports déjà purgés effacés de l'index plein texte
+/// (FTS5 ne laissait qu'un marqueur de suppression dans ses segments)
+fn migrate_v12_full_text_scrub(conn: &Connection) -> SqlResult<()> {
+    super::queries::scrub_full_text_index(conn)
+}
+
+/// Clé API de production - EXTERNALISÉE (Phase 1)
+fn seed_api_key(conn: &Connection) -> SqlResult<()> {
     let count: i64 = conn.query_row(
         "SELECT COUNT(*) FROM api_keys",
         [],
@@ -0,0 +1,525 @@
+// ============================================================================
+// AIRADCR Desktop - Droits des personnes (RGPD) : accès et effacement
+// ============================================================================
+// Opérations administrateur par patient_id et/ou accession_number :
+//   - export : tout ce que le poste détient sur la personne (rapports, verrous,
+//     entrées du journal d'accès les référençant) en bundle JSON signé
+//   - effacement (erase) ou anonymisation (anonymise) de ces enregistrements,
+//     avec un certificat d'effacement signé conservé dans erasure_certificates
+//     et transmis aux sinks d'audit
+//
+// Les deux opérations indiquent quels backups contiennent encore les données.
+// Signature : HMAC-SHA256 (clé "gdpr-signing-key" du keychain OS) sur le JSON
+// du champ "content", vérifiable par `verify`.
+// ============================================================================
+
+use chrono::{DateTime, Duration, Utc};
+use hmac::{Hmac, Mac};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use crate::audit::{self, AuditEvent, AuditEventKind, AuditSeverity};
+use crate::config::get_config;
+use crate::database::backup::BackupManager;
+use crate::database::queries::{self, AccessLogEntry, PendingReport, ReportLock, SubjectErasure};
+use crate::database::{keychain, Database};
+use crate::pii::pseudonym::{pseudonymize, IdentifierKind};
+
+/// Format du bundle d'export
+pub const EXPORT_FORMAT: &str = "airadcr-gdpr-export/1";
+
+/// Format du certificat d'effacement
+pub const CERTIFICATE_FORMAT: &str = "airadcr-erasure-certificate/1";
+
+const SIGNATURE_ALGORITHM: &str = "HMAC-SHA256";
+const SIGNATURE_KEY: &str = "gdpr-signing-key";
+
+/// Catégories de données non conservées par le poste (mentionnées dans l'export)
+const NOT_STORED_LOCALLY: &[(&str, &str)] = &[
+    (
+        "report_revisions",
+        "Aucun historique de révisions : seule la dernière version du rapport est stockée",
+    ),
+    (
+        "teo_submissions",
+        "Les rapports approuvés envoyés à TÉO Hub ne sont pas conservés localement (demande à adresser au TÉO Hub)",
+    ),
+];
+
+/// Personne concernée (au moins un identifiant)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DataSubject {
+    #[serde(default)]
+    pub patient_id: Option<String>,
+    #[serde(default)]
+    pub accession_number: Option<String>,
+}
+
+impl DataSubject {
+    fn patient_id(&self) -> Option<&str> {
+        self.patient_id.as_deref().map(str::trim).filter(|v| !v.is_empty())
+    }
+
+    fn accession_number(&self) -> Option<&str> {
+        self.accession_number.as_deref().map(str::trim).filter(|v| !v.is_empty())
+    }
+
+    /// Vérifie qu'au moins un identifiant est fourni
+    pub fn validate(&self) -> Result<(), String> {
+        if self.patient_id().is_none() && self.accession_number().is_none() {
+            return Err("patient_id or accession_number is required".to_string());
+        }
+        Ok(())
+    }
+
+    /// Identifiants pseudonymisés (certificat, audit)
+    fn pseudonyms(&self) -> SubjectPseudonyms {
+        SubjectPseudonyms {
+            patient_id: self.patient_id().map(|v| pseudonymize(IdentifierKind::PatientId, v)),
+            accession_number: self.accession_number().map(|v| pseudonymize(IdentifierKind::AccessionNumber, v)),
+        }
+    }
+}
+
+/// Identifiants de la personne sous forme de pseudonymes
+#[derive(Debug, Clone, Serialize)]
+pub struct SubjectPseudonyms {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub patient_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accession_number: Option<String>,
+}
+
+impl SubjectPseudonyms {
+    fn label(&self) -> String {
+        [self.patient_id.as_deref(), self.accession_number.as_deref()]
+            .iter()
+            .flatten()
+            .copied()
+            .collect::<Vec<_>>()
+            .join(" / ")
+    }
+}
+
+/// Effacement complet ou anonymisation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErasureMode {
+    Erase,
+    Anonymise,
+}
+
+impl ErasureMode {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "erase" => Some(ErasureMode::Erase),
+            "anonymise" | "anonymize" => Some(ErasureMode::Anonymise),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ErasureMode::Erase => "erase",
+            ErasureMode::Anonymise => "anonymise",
+        }
+    }
+}
+
+/// Catégorie de données absente du poste
+#[derive(Debug, Clone, Serialize)]
+pub struct NotStoredLocally {
+    pub category: &'static str,
+    pub reason: &'static str,
+}
+
+/// Présence des données de la personne dans un backup
+#[derive(Debug, Clone, Serialize)]
+pub struct BackupExposure {
+    pub filename: String,
+    pub created_at: Option<String>,
+    /// Date de suppression automatique (backup_retention_days)
+    pub expires_at: Option<String>,
+    /// None si le backup n'a pas pu être lu
+    pub contains_subject: Option<bool>,
+    pub report_count: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Contenu de l'export (droit d'accès)
+#[derive(Debug, Clone, Serialize)]
+pub struct SubjectExport {
+    pub format: &'static str,
+    pub generated_at: String,
+    pub subject: DataSubject,
+    pub reports: Vec<Value>,
+    pub report_locks: Vec<ReportLock>,
+    pub access_logs: Vec<AccessLogEntry>,
+    pub not_stored_locally: Vec<NotStoredLocally>,
+    pub backups: Vec<BackupExposure>,
+}
+
+/// Certificat d'effacement (aucun identifiant en clair)
+#[derive(Debug, Clone, Serialize)]
+pub struct ErasureCertificate {
+    pub format: &'static str,
+    pub certificate_id: String,
+    pub issued_at: String,
+    pub mode: &'static str,
+    pub subject: SubjectPseudonyms,
+    pub technical_ids: Vec<String>,
+    pub erased: SubjectErasure,
+    pub secure_delete: bool,
+    /// Backups contenant encore les données (ou illisibles)
+    pub backups_with_data: Vec<BackupExposure>,
+    pub backup_retention_days: u32,
+}
+
+/// Signature d'un bundle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleSignature {
+    pub algorithm: String,
+    pub key: String,
+    pub content_sha256: String,
+    pub value: String,
+}
+
+/// Bundle JSON signé
+#[derive(Debug, Clone, Serialize)]
+pub struct SignedBundle<T> {
+    pub content: T,
+    pub signature: BundleSignature,
+}
+
+// ============================================================================
+// Signature
+// ============================================================================
+
+fn signing_key() -> Result<Vec<u8>, String> {
+    let key = keychain::get_or_create_signing_key()?;
+    Ok(hex::decode(&key).unwrap_or_else(|_| key.into_bytes()))
+}
+
+/// Octets signés : JSON compact du contenu
+fn canonical_bytes(content: &Value) -> Result<Vec<u8>, String> {
+    serde_json::to_vec(content).map_err(|e| format!("Erreur sérialisation: {}", e))
+}
+
+fn hmac_hex(key: &[u8], bytes: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepte toute taille de clé");
+    mac.update(bytes);
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// Signe un contenu avec une clé donnée
+pub fn sign_with_key<T: Serialize>(key: &[u8], content: T) -> Result<SignedBundle<T>, String> {
+    let value = serde_json::to_value(&content).map_err(|e| format!("Erreur sérialisation: {}", e))?;
+    let bytes = canonical_bytes(&value)?;
+
+    Ok(SignedBundle {
+        content,
+        signature: BundleSignature {
+            algorithm: SIGNATURE_ALGORITHM.to_string(),
+            key: SIGNATURE_KEY.to_string(),
+            content_sha256: hex::encode(Sha256::digest(&bytes)),
+            value: hmac_hex(key, &bytes),
+        },
+    })
+}
+
+/// Vérifie la signature d'un bundle avec une clé donnée
+pub fn verify_with_key(key: &[u8], bundle: &Value) -> Result<bool, String> {
+    let content = bundle.get("content").ok_or("bundle sans champ content")?;
+    let signature: BundleSignature = bundle
+        .get("signature")
+        .cloned()
+        .ok_or("bundle sans champ signature")
+        .and_then(|s| serde_json::from_value(s).map_err(|_| "signature invalide"))?;
+
+    if signature.algorithm != SIGNATURE_ALGORITHM {
+        return Err(format!("algorithme non supporté: {}", signature.algorithm));
+    }
+
+    let bytes = canonical_bytes(content)?;
+    Ok(hex::encode(Sha256::digest(&bytes)) == signature.content_sha256
+        && hmac_hex(key, &bytes) == signature.value)
+}
+
+/// Vérifie un bundle exporté par ce poste
+pub fn verify(bundle: &Value) -> Result<bool, String> {
+    verify_with_key(&signing_key()?, bundle)
+}
+
+// ============================================================================
+// Collecte des données
+// ============================================================================
+
+/// Données de la personne présentes en base
+struct SubjectRecords {
+    reports: Vec<PendingReport>,
+    access_logs: Vec<AccessLogEntry>,
+}
+
+impl SubjectRecords {
+    fn technical_ids(&self) -> Vec<String> {
+        self.reports.iter().map(|r| r.technical_id.clone()).collect()
+    }
+}
+
+fn collect(db: &Database, subject: &DataSubject) -> Result<SubjectRecords, String> {
+    let reports = db
+        .find_subject_reports(subject.patient_id(), subject.accession_number())
+        .map_err(|e| format!("Database error: {}", e))?;
+
+    // Les logs ne contiennent que des technical_id et des pseudonymes
+    let mut needles: Vec<String> = Vec::new();
+    let mut push = |value: String| {
+        if value != "-" && !needles.contains(&value) {
+            needles.push(value);
+        }
+    };
+    if let Some(patient_id) = subject.patient_id() {
+        push(pseudonymize(IdentifierKind::PatientId, patient_id));
+    }
+    if let Some(accession_number) = subject.accession_number() {
+        push(pseudonymize(IdentifierKind::AccessionNumber, accession_number));
+    }
+    for report in &reports {
+        push(report.technical_id.clone());
+        let identifiers = [
+            (IdentifierKind::PatientId, &report.patient_id),
+            (IdentifierKind::AccessionNumber, &report.accession_number),
+            (IdentifierKind::StudyUid, &report.exam_uid),
+            (IdentifierKind::StudyUid, &report.study_instance_uid),
+        ];
+        for (kind, value) in identifiers {
+            if let Some(value) = value {
+                push(pseudonymize(kind, value));
+            }
+        }
+    }
+
+    let access_logs = db
+        .find_access_logs_referencing(&needles)
+        .map_err(|e| format!("Database error: {}", e))?;
+
+    Ok(SubjectRecords { reports, access_logs })
+}
+
+/// Rapport au format JSON (structured_data décodé)
+fn report_to_json(report: &PendingReport) -> Value {
+    json!({
+        "technical_id": report.technical_id,
+        "patient_id": report.patient_id,
+        "exam_uid": report.exam_uid,
+        "accession_number": report.accession_number,
+        "study_instance_uid": report.study_instance_uid,
+        "structured_data": serde_json::from_str::<Value>(&report.structured_data)
+            .unwrap_or_else(|_| Value::String(report.structured_data.clone())),
+        "source_type": report.source_type,
+        "ai_modules": report.ai_modules,
+        "modality": report.modality,
+        "metadata": report.metadata.as_deref().and_then(|m| serde_json::from_str::<Value>(m).ok()),
+        "status": report.status,
+        "created_at": report.created_at,
+        "expires_at": report.expires_at,
+        "retrieved_at": report.retrieved_at,
+        "assigned_to": report.assigned_to,
+        "priority": report.priority,
+        "due_at": report.due_at,
+    })
+}
+
+/// Recherche les données de la personne dans chaque backup (ouvert avec la clé SQLCipher)
+pub fn scan_backups(db: &Database, subject: &DataSubject) -> Vec<BackupExposure> {
+    let retention_days = get_config().backup_retention_days;
+    let manager = BackupManager::new(db.path().to_path_buf(), retention_days);
+
+    manager
+        .list_backups()
+        .into_iter()
+        .map(|backup| {
+            let expires_at = backup
+                .created_at
+                .as_deref()
+                .and_then(|created| DateTime::parse_from_rfc3339(created).ok())
+                .map(|created| (created + Duration::days(retention_days as i64)).to_rfc3339());
+
+            let scan = db.open_keyed_readonly(std::path::Path::new(&backup.path))
+                .and_then(|conn| queries::find_subject_reports(&conn, subject.patient_id(), subject.accession_number()));
+
+            match scan {
+                Ok(reports) => BackupExposure {
+                    filename: backup.filename,
+                    created_at: backup.created_at,
+                    expires_at,
+                    contains_subject: Some(!reports.is_empty()),
+                    report_count: reports.len(),
+                    error: None,
+                },
+                Err(e) => {
+                    warn!("⚠️ [GDPR] Backup {} illisible: {}", backup.filename, e);
+                    BackupExposure {
+                        filename: backup.filename,
+                        created_at: backup.created_at,
+                        expires_at,
+                        contains_subject: None,
+                        report_count: 0,
+                        error: Some(e.to_string()),
+                    }
+                }
+            }
+        })
+        .collect()
+}
+
+// ============================================================================
+// Opérations
+// ============================================================================
+
+/// Export signé de toutes les données détenues sur la personne
+pub fn export(db: &Database, subject: &DataSubject, origin: &str) -> Result<SignedBundle<SubjectExport>, String> {
+    subject.validate()?;
+    let records = collect(db, subject)?;
+
+    let mut report_locks = Vec::new();
+    for technical_id in records.technical_ids() {
+        if let Some(lock) = db
+            .get_report_lock(&technical_id)
+            .map_err(|e| format!("Database error: {}", e))?
+        {
+            report_locks.push(lock);
+        }
+    }
+
+    let content = SubjectExport {
+        format: EXPORT_FORMAT,
+        generated_at: Utc::now().to_rfc3339(),
+        subject: subject.clone(),
+        reports: records.reports.iter().map(report_to_json).collect(),
+        report_locks,
+        access_logs: records.access_logs,
+        not_stored_locally: NOT_STORED_LOCALLY
+            .iter()
+            .map(|(category, reason)| NotStoredLocally { category, reason })
+            .collect(),
+        backups: scan_backups(db, subject),
+    };
+
+    let message = format!(
+        "subject access export for {}: {} report(s), {} access log(s)",
+        subject.pseudonyms().label(),
+        content.reports.len(),
+        content.access_logs.len()
+    );
+    let bundle = sign_with_key(&signing_key()?, content)?;
+
+    info!("📦 [GDPR] {}", message);
+    emit_event(&bundle.content.generated_at, "gdpr_export", origin, message);
+    Ok(bundle)
+}
+
+/// Efface ou anonymise les données de la personne et délivre un certificat signé
+pub fn erase(
+    db: &Database,
+    subject: &DataSubject,
+    mode: ErasureMode,
+    origin: &str,
+) -> Result<SignedBundle<ErasureCertificate>, String> {
+    subject.validate()?;
+    let key = signing_key()?;
+    let records = collect(db, subject)?;
+    let technical_ids = records.technical_ids();
+    let access_log_ids: Vec<i64> = records.access_logs.iter().map(|log| log.id).collect();
+
+    let config = get_config();
+    let secure_delete = config.retention.secure_delete;
+    let erased = db
+        .erase_subject_records(&technical_ids, &access_log_ids, mode == ErasureMode::Anonymise, secure_delete)
+        .map_err(|e| format!("Database error: {}", e))?;
+
+    let certificate = ErasureCertificate {
+        format: CERTIFICATE_FORMAT,
+        certificate_id: Uuid::new_v4().to_string(),
+        issued_at: Utc::now().to_rfc3339(),
+        mode: mode.as_str(),
+        subject: subject.pseudonyms(),
+        technical_ids,
+        erased,
+        secure_delete,
+        backups_with_data: scan_backups(db, subject)
+            .into_iter()
+            .filter(|backup| backup.contains_subject != Some(false))
+            .collect(),
+        backup_retention_days: config.backup_retention_days,
+    };
+
+    let bundle = sign_with_key(&key, certificate)?;
+    let certificate = &bundle.content;
+    let stored = serde_json::to_string(&bundle).map_err(|e| format!("Erreur sérialisation: {}", e))?;
+    db.insert_erasure_certificate(&certificate.certificate_id, &certificate.issued_at, certificate.mode, &stored)
+        .map_err(|e| format!("Database error: {}", e))?;
+
+    let message = format!(
+        "certificate {} ({}) for {}: {} report(s), {} access log(s), {} lock(s); {} backup(s) still hold data",
+        certificate.certificate_id,
+        certificate.mode,
+        certificate.subject.label(),
+        certificate.erased.reports,
+        certificate.erased.access_logs,
+        certificate.erased.locks,
+        certificate.backups_with_data.len()
+    );
+    warn!("🗑️ [GDPR] Effacement: {}", message);
+    emit_event(&certificate.issued_at, "gdpr_erasure_certificate", origin, message);
+    Ok(bundle)
+}
+
+/// Transmet une opération RGPD aux sinks d'audit
+fn emit_event(timestamp: &str, event_type: &str, origin: &str, message: String) {
+    audit::emit(AuditEvent {
+        timestamp: timestamp.to_string(),
+        kind: AuditEventKind::Gdpr,
+        severity: AuditSeverity::Notice,
+        event_type: event_type.to_string(),
+        request_id: crate::telemetry::generate_request_id(),
+        ip_address: origin.to_string(),
+        method: "-".to_string(),
+        endpoint: "gdpr".to_string(),
+        status_code: None,
+        result: None,
+        api_key_prefix: None,
+        user_agent: None,
+        duration_ms: None,
+        message: Some(message),
+        user_id: None,
+    });
+}
+
+// ============================================================================
+// Tests unitaires
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signed_bundle_detects_tampering() {
+        let key = [7u8; 32];
+        let bundle = sign_with_key(&key, json!({ "format": EXPORT_FORMAT, "reports": [{ "technical_id": "T1" }] })).unwrap();
+        let mut value = serde_json::to_value(&bundle).unwrap();
+
+        assert!(verify_with_key(&key, &value).unwrap());
+        assert!(!verify_with_key(&[8u8; 32], &value).unwrap());
+
+        value["content"]["reports"][0]["technical_id"] = json!("T2");
+        assert!(!verify_with_key(&key, &value).unwrap());
+
+        assert!(DataSubject::default().validate().is_err());
+        assert_eq!(ErasureMode::parse("Anonymize"), Some(ErasureMode::Anonymise));
+    }
+}
@@ -5,15 +5,24 @@
 use actix_web::{web, HttpRequest, HttpResponse};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
 use chrono::{Utc, Duration};
 use uuid::Uuid;
 use tauri::Manager;
 
 use super::HttpServerState;
-use super::middleware::{validate_api_key, validate_admin_key, RequestInfo};
+use super::middleware::{authenticate_api_key, validate_api_key, validate_admin_key, validate_patient_safe_for, RequestInfo};
+use crate::pii::policy::{self as patient_safe, Destination};
+use crate::pii::pseudonym::{pseudonymize, IdentifierKind};
 use crate::APP_HANDLE;
 use crate::teo_client;
+use crate::worklist;
+use crate::report_locks::{self, LockError, LockHolder};
+use crate::report_model::{self, templates, StructuredReport};
 use crate::config::get_config;
+use crate::database::Database;
+use crate::database::queries::{self, is_ambiguous_match, MatchMode, ReportIdentifiers, ReportTemplate, MAX_MATCH_CANDIDATES};
 
 // ============================================================================
 // Fonctions utilitaires de sécurité
@@ -28,13 +37,9 @@ fn is_auth_disabled() -> bool {
     disabled
 }
 
-/// 🛡️ Masque un identifiant sensible pour les logs (affiche seulement les 4 premiers caractères)
+/// 🛡️ Pseudonyme d'un identifiant patient pour les logs (HMAC à clé, stable)
 fn mask_sensitive_id(id: &str) -> String {
-    if id.len() <= 4 {
-        "****".to_string()
-    } else {
-        format!("{}****", &id[..4])
-    }
+    pseudonymize(IdentifierKind::PatientId, id)
 }
 
 // ============================================================================
@@ -49,8 +54,13 @@ pub struct StorePendingReportRequest {
     pub exam_uid: Option<String>,
     pub accession_number: Option<String>,
     pub study_instance_uid: Option<String>,
-    // Données structurées
+    // Données structurées (optionnelles si un modèle est fourni)
+    #[serde(default)]
     pub structured: Value,
+    /// Modèle de rapport (id ou nom) fusionné avec `structured`
+    pub template: Option<String>,
+    /// Valeurs des placeholders {{nom}} du modèle
+    pub template_variables: Option<HashMap<String, String>>,
     #[serde(default = "default_source_type")]
     pub source_type: String,
     pub ai_modules: Option<Vec<String>>,
@@ -58,6 +68,12 @@ pub struct StorePendingReportRequest {
     pub metadata: Option<Value>,
     #[serde(default = "default_expires_hours")]
     pub expires_in_hours: i64,
+    /// Radiologue destinataire (id ou username)
+    pub assigned_to: Option<String>,
+    /// Priorité de lecture : routine (défaut), urgent, stat
+    pub priority: Option<String>,
+    /// Échéance de lecture (RFC 3339)
+    pub due_at: Option<String>,
 }
 
 fn default_source_type() -> String {
@@ -89,6 +105,41 @@ pub struct GetReportResponse {
     pub success: bool,
     pub data: Option<ReportData>,
     pub error: Option<String>,
+    /// Verrou d'édition pris pour le client
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lock: Option<crate::database::queries::ReportLock>,
+}
+
+/// Réponse 409 : rapport verrouillé par un autre client
+#[derive(Serialize)]
+pub struct LockConflictResponse {
+    pub success: bool,
+    pub error: String,
+    pub technical_id: String,
+    pub lock: crate::database::queries::ReportLock,
+}
+
+#[derive(Serialize)]
+pub struct ReportLocksResponse {
+    pub success: bool,
+    pub count: usize,
+    pub locks: Vec<crate::database::queries::ReportLock>,
+}
+
+#[derive(Serialize)]
+pub struct ReportLockResponse {
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lock: Option<crate::database::queries::ReportLock>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub released: Option<bool>,
+}
+
+#[derive(Deserialize)]
+pub struct ReleaseLockQuery {
+    /// Libération forcée (requiert X-Admin-Key)
+    #[serde(default)]
+    pub force: bool,
 }
 
 #[derive(Serialize)]
@@ -114,6 +165,12 @@ pub struct ReportData {
     pub metadata: Option<Value>,
     pub status: String,
     pub created_at: String,
+    // Liste de travail
+    pub priority: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub due_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assigned_to: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -134,11 +191,63 @@ pub struct TidQuery {
     pub tid: Option<String>,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, Clone)]
 pub struct FindReportQuery {
     pub patient_id: Option<String>,
     pub accession_number: Option<String>,
     pub exam_uid: Option<String>,
+    pub study_instance_uid: Option<String>,
+    /// strict (tous les identifiants) ou ranked (défaut, classement par score)
+    pub match_mode: Option<String>,
+}
+
+impl FindReportQuery {
+    fn identifiers(&self) -> ReportIdentifiers<'_> {
+        ReportIdentifiers {
+            accession_number: self.accession_number.as_deref(),
+            study_instance_uid: self.study_instance_uid.as_deref(),
+            exam_uid: self.exam_uid.as_deref(),
+            patient_id: self.patient_id.as_deref(),
+        }
+    }
+}
+
+/// Rapport candidat d'une recherche par identifiants RIS
+#[derive(Serialize)]
+pub struct MatchCandidateSummary {
+    pub technical_id: String,
+    pub score: u32,
+    pub matched_on: Vec<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accession_number: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exam_uid: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub study_instance_uid: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub modality: Option<String>,
+    pub status: String,
+    pub created_at: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assigned_to: Option<String>,
+}
+
+impl From<&crate::database::queries::MatchCandidate> for MatchCandidateSummary {
+    fn from(candidate: &crate::database::queries::MatchCandidate) -> Self {
+        let report = &candidate.report;
+        Self {
+            technical_id: report.technical_id.clone(),
+            score: candidate.score,
+            matched_on: candidate.matched_on.clone(),
+            accession_number: report.accession_number.clone(),
+            exam_uid: report.exam_uid.clone(),
+            study_instance_uid: report.study_instance_uid.clone(),
+            modality: report.modality.clone(),
+            status: report.status.clone(),
+            created_at: report.created_at.clone(),
+            assigned_to: report.assigned_to.clone(),
+        }
+    }
 }
 
 #[derive(Serialize)]
@@ -150,6 +259,16 @@ pub struct FindReportResponse {
     pub retrieval_url: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub match_mode: Option<&'static str>,
+    /// Score du meilleur candidat
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub score: Option<u32>,
+    /// Plusieurs candidats partagent le meilleur score
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ambiguous: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub candidates: Option<Vec<MatchCandidateSummary>>,
 }
 
 #[derive(Deserialize)]
@@ -158,7 +277,13 @@ pub struct OpenReportQuery {
     pub accession_number: Option<String>,
     pub patient_id: Option<String>,
     pub exam_uid: Option<String>,
+    /// Alias DICOM de exam_uid
     pub study_uid: Option<String>,
+    pub study_instance_uid: Option<String>,
+    /// strict ou ranked (défaut)
+    pub match_mode: Option<String>,
+    /// Radiologue cible (id ou username) : devient le profil actif
+    pub user: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -174,6 +299,8 @@ pub struct OpenReportResponse {
     pub error: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub source: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assigned_to: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -181,6 +308,8 @@ pub struct TeoHubFetchQuery {
     pub patient_id: Option<String>,
     pub study_uid: Option<String>,
     pub exam_uid: Option<String>,
+    /// Modèle de rapport (id ou nom) fusionné avec le brouillon IA
+    pub template: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -196,6 +325,117 @@ pub struct TeoHubFetchResponse {
     pub error: Option<String>,
 }
 
+#[derive(Deserialize)]
+pub struct TemplateListQuery {
+    pub modality: Option<String>,
+    pub body_part: Option<String>,
+    pub language: Option<String>,
+}
+
+/// Modèle de rapport (contenu JSON décodé)
+#[derive(Serialize)]
+pub struct TemplateData {
+    pub id: String,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub modality: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body_part: Option<String>,
+    pub language: String,
+    pub content: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_by: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl From<ReportTemplate> for TemplateData {
+    fn from(template: ReportTemplate) -> Self {
+        Self {
+            content: serde_json::from_str(&template.content).unwrap_or(Value::Null),
+            id: template.id,
+            name: template.name,
+            modality: template.modality,
+            body_part: template.body_part,
+            language: template.language,
+            created_by: template.created_by,
+            created_at: template.created_at,
+            updated_at: template.updated_at,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct TemplatesResponse {
+    pub success: bool,
+    pub count: usize,
+    pub templates: Vec<TemplateData>,
+}
+
+#[derive(Serialize)]
+pub struct TemplateResponse {
+    pub success: bool,
+    pub template: TemplateData,
+}
+
+#[derive(Deserialize)]
+pub struct MergeTemplateRequest {
+    /// Rapport entrant (structured RIS ou brouillon IA) ; absent = modèle seul
+    #[serde(default)]
+    pub structured: Value,
+    #[serde(default)]
+    pub variables: HashMap<String, String>,
+    pub modality: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct MergeTemplateResponse {
+    pub success: bool,
+    #[serde(flatten)]
+    pub merged: templates::MergedReport,
+}
+
+#[derive(Deserialize)]
+pub struct SearchQuery {
+    /// Termes recherchés (ET implicite, "*" final = préfixe)
+    pub q: Option<String>,
+    pub modality: Option<String>,
+    pub limit: Option<i64>,
+}
+
+#[derive(Serialize)]
+pub struct SearchResponse {
+    pub success: bool,
+    pub query: String,
+    pub count: usize,
+    pub results: Vec<crate::database::queries::SearchHit>,
+}
+
+/// Longueur maximale d'une requête de recherche
+const MAX_SEARCH_QUERY_LEN: usize = 200;
+
+#[derive(Deserialize)]
+pub struct WorklistQuery {
+    /// Radiologue (id ou username) ; défaut : radiologue actif
+    pub user: Option<String>,
+    /// Uniquement les rapports attribués (exclut les non attribués)
+    pub assigned_only: Option<bool>,
+    pub modality: Option<String>,
+    pub priority: Option<String>,
+    pub limit: Option<i64>,
+}
+
+#[derive(Serialize)]
+pub struct WorklistResponse {
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current_report: Option<String>,
+    pub count: usize,
+    pub items: Vec<crate::database::queries::WorklistItem>,
+}
+
 #[derive(Deserialize)]
 pub struct CreateApiKeyRequest {
     pub name: String,
@@ -293,6 +533,162 @@ fn validate_api_key_name(name: &str) -> Result<(), String> {
     Ok(())
 }
 
+/// Vérifie la clé API d'une route d'écriture ; Some(réponse 401) si elle est invalide
+async fn require_api_key(req: &HttpRequest, request_info: &RequestInfo, state: &HttpServerState) -> Option<HttpResponse> {
+    let api_key = req
+        .headers()
+        .get("x-api-key")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("");
+    
+    if is_auth_disabled() || api_key_is_valid(state, api_key).await {
+        return None;
+    }
+    
+    log::warn!("❌ [HTTP] {} {} sans API key valide", req.method(), req.path());
+    request_info.log_access(&state.db, 401, "unauthorized", Some("Invalid API key"));
+    Some(HttpResponse::Unauthorized().json(ErrorResponse {
+        error: "Invalid API key".to_string(),
+        field: None,
+    }))
+}
+
+/// Erreur d'un appel exécuté sur le pool de threads bloquants (tâche annulée)
+trait FromBlocking {
+    fn from_blocking(e: actix_web::error::BlockingError) -> Self;
+}
+
+impl FromBlocking for rusqlite::Error {
+    fn from_blocking(e: actix_web::error::BlockingError) -> Self {
+        rusqlite::Error::SqliteFailure(
+            rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_INTERRUPT),
+            Some(format!("Tâche base de données interrompue: {}", e)),
+        )
+    }
+}
+
+impl FromBlocking for String {
+    fn from_blocking(e: actix_web::error::BlockingError) -> Self {
+        format!("Tâche base de données interrompue: {}", e)
+    }
+}
+
+impl FromBlocking for LockError {
+    fn from_blocking(e: actix_web::error::BlockingError) -> Self {
+        LockError::Database(String::from_blocking(e))
+    }
+}
+
+/// Exécute un accès SQLite via `web::block` : la requête (pool de lecture ou
+/// connexion d'écriture) ne bloque jamais le worker asynchrone actix
+async fn db_call<T, E, F>(state: &HttpServerState, f: F) -> Result<T, E>
+where
+    F: FnOnce(&Database) -> Result<T, E> + Send + 'static,
+    T: Send + 'static,
+    E: FromBlocking + Send + 'static,
+{
+    let db = Arc::clone(&state.db);
+    web::block(move || f(&db)).await.unwrap_or_else(|e| Err(E::from_blocking(e)))
+}
+
+/// Modèle de rapport (id ou nom) lu via `db_call`
+async fn find_template(state: &HttpServerState, id_or_name: &str) -> rusqlite::Result<Option<ReportTemplate>> {
+    let id_or_name = id_or_name.to_string();
+    db_call(state, move |db| db.get_report_template(&id_or_name)).await
+}
+
+/// Clé API présentée dans le header X-API-Key ("" si absente)
+fn api_key_header(req: &HttpRequest) -> String {
+    req.headers()
+        .get("x-api-key")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("")
+        .to_string()
+}
+
+/// Détenteur de verrou d'une requête : clé API validée en base (id et nom de api_keys)
+fn request_holder(db: &Database, request_info: &RequestInfo, api_key: &str) -> LockHolder {
+    LockHolder::from_request(request_info, authenticate_api_key(db, api_key).as_ref(), db)
+}
+
+/// Verrouille un rapport au nom du client de la requête (via `db_call`)
+async fn acquire_lock(state: &HttpServerState, req: &HttpRequest, request_info: &RequestInfo, technical_id: &str) -> Result<Option<crate::database::queries::ReportLock>, LockError> {
+    let (info, tid, api_key) = (request_info.clone(), technical_id.to_string(), api_key_header(req));
+    db_call(state, move |db| report_locks::acquire(db, &tid, &request_holder(db, &info, &api_key))).await
+}
+
+/// Vérifie une clé API en base via `db_call` (clé refusée si la tâche échoue)
+async fn api_key_is_valid(state: &HttpServerState, api_key: &str) -> bool {
+    let api_key = api_key.to_string();
+    db_call(state, move |db| Ok::<_, String>(validate_api_key(db, &api_key)))
+        .await
+        .unwrap_or(false)
+}
+
+fn database_error_response(request_info: &RequestInfo, state: &HttpServerState, e: rusqlite::Error) -> HttpResponse {
+    log::error!("❌ [HTTP] Erreur base de données: {}", e);
+    request_info.log_access(&state.db, 500, "error", Some(&format!("Database error: {}", e)));
+    HttpResponse::InternalServerError().json(ErrorResponse {
+        error: format!("Database error: {}", e),
+        field: None,
+    })
+}
+
+/// Mode d'appariement RIS (défaut : ranked)
+fn parse_match_mode(value: Option<&str>) -> Result<MatchMode, String> {
+    match value.filter(|v| !v.is_empty()) {
+        None => Ok(MatchMode::Ranked),
+        Some(v) => MatchMode::parse(v).ok_or_else(|| "match_mode must be one of: strict, ranked".to_string()),
+    }
+}
+
+/// Réponse HTTP d'un échec de verrouillage (409 si détenu par un autre client)
+fn lock_error_response(request_info: &RequestInfo, state: &HttpServerState, technical_id: &str, error: LockError) -> HttpResponse {
+    let message = error.message();
+    match error {
+        LockError::Held(lock) => {
+            request_info.log_access(&state.db, 409, "error", Some(&message));
+            HttpResponse::Conflict().json(LockConflictResponse {
+                success: false,
+                error: message,
+                technical_id: technical_id.to_string(),
+                lock: *lock,
+            })
+        }
+        LockError::Database(_) => {
+            log::error!("❌ [HTTP] Erreur verrouillage: {}", message);
+            request_info.log_access(&state.db, 500, "error", Some(&message));
+            HttpResponse::InternalServerError().json(ErrorResponse {
+                error: message,
+                field: None,
+            })
+        }
+    }
+}
+
+/// Résout un profil radiologue actif par id ou username via `db_call` (None si valeur vide)
+async fn resolve_user_profile(
+    state: &HttpServerState,
+    id_or_username: Option<&str>,
+) -> Result<Option<crate::database::queries::UserProfile>, String> {
+    let value = match id_or_username.map(str::trim).filter(|s| !s.is_empty()) {
+        Some(value) => value.to_string(),
+        None => return Ok(None),
+    };
+    db_call(state, move |db| match db.find_user(&value) {
+        Ok(Some(user)) if user.is_active => Ok(Some(user)),
+        Ok(Some(_)) => Err(format!("User profile '{}' is deactivated", value)),
+        Ok(None) => Err(format!("Unknown user profile '{}'", value)),
+        Err(e) => Err(format!("Database error: {}", e)),
+    })
+    .await
+}
+
+/// Radiologue actif du poste via `db_call` (None si aucun ou en cas d'erreur)
+async fn active_user_id(state: &HttpServerState) -> Option<String> {
+    db_call(state, |db| db.get_active_user_id()).await.ok().flatten()
+}
+
 // ============================================================================
 // Handlers
 // ============================================================================
@@ -321,7 +717,7 @@ pub async fn store_pending_report(
         .and_then(|v| v.to_str().ok())
         .unwrap_or("");
     
-    if !is_auth_disabled() && !validate_api_key(&state.db, api_key) {
+    if !is_auth_disabled() && !api_key_is_valid(&state, api_key).await {
         log::warn!("❌ [HTTP] Clé API invalide");
         request_info.log_access(&state.db, 401, "unauthorized", Some("Invalid API key"));
         return HttpResponse::Unauthorized().json(ErrorResponse {
@@ -340,17 +736,113 @@ pub async fn store_pending_report(
         });
     }
     
-    // 3. NOTE: Patient-Safe validation désactivée pour le serveur LOCAL
-    // En local, les identifiants patients sont autorisés car les données
-    // ne quittent jamais la machine de l'utilisateur.
-    // La validation Patient-Safe reste active pour le cloud (Supabase).
+    // 3. Radiologue destinataire (optionnel)
+    let assignee = match resolve_user_profile(&state, body.assigned_to.as_deref()).await {
+        Ok(user) => user,
+        Err(msg) => {
+            log::warn!("❌ [HTTP] Invalid assigned_to: {}", msg);
+            request_info.log_access(&state.db, 400, "bad_request", Some(&msg));
+            return HttpResponse::BadRequest().json(ErrorResponse {
+                error: msg,
+                field: Some("assigned_to".to_string()),
+            });
+        }
+    };
+    
+    // 4. Priorité et échéance de lecture (liste de travail)
+    let priority = match body.priority.as_deref() {
+        Some(value) => match worklist::parse_priority(value) {
+            Ok(priority) => priority,
+            Err(msg) => {
+                request_info.log_access(&state.db, 400, "bad_request", Some(&msg));
+                return HttpResponse::BadRequest().json(ErrorResponse {
+                    error: msg,
+                    field: Some("priority".to_string()),
+                });
+            }
+        },
+        None => worklist::DEFAULT_PRIORITY.to_string(),
+    };
+    let due_at = match body.due_at.as_deref().filter(|s| !s.trim().is_empty()) {
+        Some(value) => match worklist::normalize_due_at(value) {
+            Ok(due_at) => Some(due_at),
+            Err(msg) => {
+                request_info.log_access(&state.db, 400, "bad_request", Some(&msg));
+                return HttpResponse::BadRequest().json(ErrorResponse {
+                    error: msg,
+                    field: Some("due_at".to_string()),
+                });
+            }
+        },
+        None => None,
+    };
+    
+    // 5. Politique Patient-Safe de la destination "local" : aucun contrôle par
+    // défaut (les données ne quittent pas la machine), activable dans patient_safe.toml
+    let local_payload = serde_json::json!({ "structured": &body.structured });
+    if let Err((field, message)) = validate_patient_safe_for(&local_payload, Destination::Local) {
+        request_info.log_access(&state.db, 400, "bad_request", Some(&message));
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: message,
+            field: Some(field),
+        });
+    }
+    
+    // 6. Contenu structuré : fusion avec le modèle éventuel, puis schéma de la modalité
+    let template = match body.template.as_deref().filter(|t| !t.is_empty()) {
+        Some(key) => match find_template(&state, key).await {
+            Ok(Some(template)) => Some(template),
+            Ok(None) => {
+                let msg = format!("Unknown report template: {}", key);
+                request_info.log_access(&state.db, 400, "bad_request", Some(&msg));
+                return HttpResponse::BadRequest().json(ErrorResponse {
+                    error: msg,
+                    field: Some("template".to_string()),
+                });
+            }
+            Err(e) => {
+                request_info.log_access(&state.db, 500, "error", Some(&format!("Database error: {}", e)));
+                return HttpResponse::InternalServerError().json(ErrorResponse {
+                    error: format!("Database error: {}", e),
+                    field: None,
+                });
+            }
+        },
+        None => None,
+    };
+    let validated = match &template {
+        Some(template) => templates::merge_template(
+            template,
+            &body.structured,
+            &body.template_variables.clone().unwrap_or_default(),
+            body.modality.as_deref(),
+        )
+        .map(|merged| {
+            if !merged.unresolved.is_empty() {
+                log::info!("📝 [HTTP] Modèle {}: placeholders non résolus {:?}", template.name, merged.unresolved);
+            }
+            merged.structured
+        }),
+        None => report_model::validate(body.modality.as_deref(), &body.structured),
+    };
+    let structured = match validated {
+        Ok(report) => report,
+        Err(e) => {
+            log::warn!("❌ [HTTP] Rapport structuré invalide ({}): {}", e.field, e.message);
+            request_info.log_access(&state.db, 400, "bad_request", Some(&e.message));
+            return HttpResponse::BadRequest().json(ErrorResponse {
+                error: e.message,
+                field: Some(e.field),
+            });
+        }
+    };
     
-    // 4. Préparer les données
+    // 7. Préparer les données
     let id = Uuid::new_v4().to_string();
     let now = Utc::now();
     let expires_at = now + Duration::hours(body.expires_in_hours);
     
-    let structured_json = serde_json::to_string(&body.structured).unwrap_or_default();
+    let structured_json = serde_json::to_string(&structured).unwrap_or_default();
     let ai_modules_json = body.ai_modules
         .as_ref()
         .map(|m| serde_json::to_string(m).unwrap_or_default());
@@ -358,23 +850,47 @@ pub async fn store_pending_report(
         .as_ref()
         .map(|m| serde_json::to_string(m).unwrap_or_default());
     
-    // 5. Insérer en base avec identifiants patients
-    match state.db.insert_pending_report(
-        &id,
-        &body.technical_id,
-        body.patient_id.as_deref(),
-        body.exam_uid.as_deref(),
-        body.accession_number.as_deref(),
-        body.study_instance_uid.as_deref(),
-        &structured_json,
-        &body.source_type,
-        ai_modules_json.as_deref(),
-        body.modality.as_deref(),
-        metadata_json.as_deref(),
-        &now.to_rfc3339(),
-        &expires_at.to_rfc3339(),
-    ) {
-        Ok(_) => {
+    // 8. Insérer en base avec identifiants patients (hors du worker actix)
+    let modality = body.modality.clone().or_else(|| template.as_ref().and_then(|t| t.modality.clone()));
+    let assignee = assignee.map(|user| (user.id, user.username));
+    let schedule = (priority != worklist::DEFAULT_PRIORITY || due_at.is_some()).then_some((priority, due_at));
+    let created_at = now.to_rfc3339();
+    let expires_at_str = expires_at.to_rfc3339();
+    let body = body.into_inner();
+    // Rapport, attribution et priorité : une seule transaction (tout ou rien)
+    let stored: rusqlite::Result<_> = db_call(&state, move |db| {
+        db.with_write_transaction(|conn| {
+            queries::insert_pending_report(
+                conn,
+                &id,
+                &body.technical_id,
+                body.patient_id.as_deref(),
+                body.exam_uid.as_deref(),
+                body.accession_number.as_deref(),
+                body.study_instance_uid.as_deref(),
+                &structured_json,
+                &body.source_type,
+                ai_modules_json.as_deref(),
+                modality.as_deref(),
+                metadata_json.as_deref(),
+                &created_at,
+                &expires_at_str,
+            )?;
+            if let Some((user_id, username)) = &assignee {
+                queries::assign_pending_report(conn, &body.technical_id, Some(user_id))?;
+                log::debug!("👤 [HTTP] Rapport {} attribué à {}", body.technical_id, username);
+            }
+            if let Some((priority, due_at)) = &schedule {
+                queries::set_report_schedule(conn, &body.technical_id, priority, due_at.as_deref())?;
+            }
+            Ok(())
+        })?;
+        Ok(body)
+    })
+    .await;
+    
+    match stored {
+        Ok(body) => {
             // 🛡️ SÉCURITÉ: Masquer les identifiants sensibles dans les logs
             let masked_patient_id = body.patient_id.as_ref().map(|id| mask_sensitive_id(id));
             log::info!("✅ [HTTP] Rapport stocké: tid={}, patient_id={:?}",
@@ -417,7 +933,8 @@ pub async fn get_pending_report(
         }
     };
     
-    match state.db.get_pending_report(tid) {
+    let lookup_tid = tid.clone();
+    match db_call(&state, move |db| db.get_pending_report(&lookup_tid)).await {
         Ok(Some(report)) => {
             // Parser le JSON stocké
             let structured: Value = serde_json::from_str(&report.structured_data)
@@ -429,8 +946,20 @@ pub async fn get_pending_report(
                 .as_ref()
                 .and_then(|s| serde_json::from_str(s).ok());
             
+            // 🔒 Verrou d'édition au nom du client : lecture anonyme = sans verrou
+            let authenticated = is_auth_disabled() || api_key_is_valid(&state, &api_key_header(&req)).await;
+            let lock = if authenticated {
+                match acquire_lock(&state, &req, &request_info, tid).await {
+                    Ok(lock) => lock,
+                    Err(e) => return lock_error_response(&request_info, &state, tid, e),
+                }
+            } else {
+                None
+            };
+            
             // Marquer comme récupéré
-            let _ = state.db.mark_as_retrieved(tid);
+            let retrieved_tid = tid.clone();
+            let _ = db_call(&state, move |db| db.mark_as_retrieved(&retrieved_tid)).await;
             
             // 🛡️ SÉCURITÉ: Masquer les identifiants sensibles dans les logs
             let masked_patient_id = report.patient_id.as_ref().map(|id| mask_sensitive_id(id));
@@ -451,8 +980,12 @@ pub async fn get_pending_report(
                     metadata,
                     status: report.status,
                     created_at: report.created_at,
+                    priority: report.priority,
+                    due_at: report.due_at,
+                    assigned_to: report.assigned_to,
                 }),
                 error: None,
+                lock,
             })
         }
         Ok(None) => {
@@ -462,6 +995,7 @@ pub async fn get_pending_report(
                 success: false,
                 data: None,
                 error: Some("Report not found or expired".to_string()),
+                lock: None,
             })
         }
         Err(e) => {
@@ -490,7 +1024,7 @@ pub async fn delete_pending_report(
         .and_then(|v| v.to_str().ok())
         .unwrap_or("");
     
-    if !is_auth_disabled() && !validate_api_key(&state.db, api_key) {
+    if !is_auth_disabled() && !api_key_is_valid(&state, api_key).await {
         log::warn!("❌ [HTTP] DELETE sans API key valide");
         request_info.log_access(&state.db, 401, "unauthorized", Some("Invalid API key for DELETE"));
         return HttpResponse::Unauthorized().json(ErrorResponse {
@@ -510,7 +1044,8 @@ pub async fn delete_pending_report(
         }
     };
     
-    match state.db.delete_pending_report(tid) {
+    let delete_tid = tid.clone();
+    match db_call(&state, move |db| db.delete_pending_report(&delete_tid)).await {
         Ok(deleted) => {
             log::info!("🗑️ [HTTP] Rapport supprimé: tid={} (deleted={})", tid, deleted);
             request_info.log_access(&state.db, 200, "success", None);
@@ -547,6 +1082,7 @@ pub async fn create_api_key(
     
     if !validate_admin_key(admin_key) {
         log::warn!("❌ [HTTP] Clé admin invalide pour création API key");
+        request_info.log_security_event("invalid_admin_key", "Invalid or missing admin key");
         request_info.log_access(&state.db, 401, "unauthorized", Some("Invalid admin key"));
         return HttpResponse::Unauthorized().json(ErrorResponse {
             error: "Invalid or missing admin key".to_string(),
@@ -587,7 +1123,9 @@ pub async fn create_api_key(
     let id = Uuid::new_v4().to_string();
     
     // 7. Insérer en base
-    match state.db.add_api_key(&id, key_prefix, &key_hash, &body.name) {
+    let (prefix, name) = (key_prefix.to_string(), body.name.clone());
+    let key_id = id.clone();
+    match db_call(&state, move |db| db.add_api_key(&key_id, &prefix, &key_hash, &name)).await {
         Ok(_) => {
             log::info!("✅ [HTTP] Nouvelle clé API créée: name={}, prefix={}", body.name, key_prefix);
             request_info.log_access(&state.db, 201, "success", None);
@@ -626,6 +1164,7 @@ pub async fn list_api_keys(
     
     if !validate_admin_key(admin_key) {
         log::warn!("❌ [HTTP] Clé admin invalide pour liste API keys");
+        request_info.log_security_event("invalid_admin_key", "Invalid or missing admin key");
         request_info.log_access(&state.db, 401, "unauthorized", Some("Invalid admin key"));
         return HttpResponse::Unauthorized().json(ErrorResponse {
             error: "Invalid or missing admin key".to_string(),
@@ -633,7 +1172,7 @@ pub async fn list_api_keys(
         });
     }
     
-    match state.db.list_api_keys() {
+    match db_call(&state, |db| db.list_api_keys()).await {
         Ok(keys) => {
             let api_keys: Vec<ApiKeyInfo> = keys.into_iter().map(|(id, name, prefix, is_active, created_at)| {
                 ApiKeyInfo { id, name, prefix, is_active, created_at }
@@ -674,6 +1213,7 @@ pub async fn revoke_api_key(
     
     if !validate_admin_key(admin_key) {
         log::warn!("❌ [HTTP] Clé admin invalide pour révocation API key");
+        request_info.log_security_event("invalid_admin_key", "Invalid or missing admin key");
         request_info.log_access(&state.db, 401, "unauthorized", Some("Invalid admin key"));
         return HttpResponse::Unauthorized().json(ErrorResponse {
             error: "Invalid or missing admin key".to_string(),
@@ -691,7 +1231,8 @@ pub async fn revoke_api_key(
         });
     }
     
-    match state.db.revoke_api_key(&prefix) {
+    let revoked_prefix = prefix.clone();
+    match db_call(&state, move |db| db.revoke_api_key(&revoked_prefix)).await {
         Ok(revoked) => {
             if revoked {
                 log::info!("✅ [HTTP] Clé API révoquée: prefix={}", prefix);
@@ -716,7 +1257,8 @@ pub async fn revoke_api_key(
     }
 }
 
-/// GET /find-report - Recherche un rapport par identifiants RIS (patient_id, accession_number, exam_uid)
+/// GET /find-report - Recherche un rapport par identifiants RIS
+/// (patient_id, accession_number, exam_uid, study_instance_uid), candidats classés par score
 pub async fn find_report(
     req: HttpRequest,
     query: web::Query<FindReportQuery>,
@@ -724,28 +1266,51 @@ pub async fn find_report(
 ) -> HttpResponse {
     let request_info = RequestInfo::from_request(&req);
     
-    // Vérifier qu'au moins un identifiant est fourni
-    let has_patient_id = query.patient_id.as_ref().map_or(false, |s| !s.is_empty());
-    let has_accession = query.accession_number.as_ref().map_or(false, |s| !s.is_empty());
-    let has_exam_uid = query.exam_uid.as_ref().map_or(false, |s| !s.is_empty());
+    let mode = match parse_match_mode(query.match_mode.as_deref()) {
+        Ok(mode) => mode,
+        Err(msg) => {
+            request_info.log_access(&state.db, 400, "bad_request", Some(&msg));
+            return HttpResponse::BadRequest().json(ErrorResponse {
+                error: msg,
+                field: Some("match_mode".to_string()),
+            });
+        }
+    };
+    
+    let identifiers = query.identifiers();
     
-    if !has_patient_id && !has_accession && !has_exam_uid {
+    // Vérifier qu'au moins un identifiant est fourni
+    if identifiers.is_empty() {
         request_info.log_access(&state.db, 400, "bad_request", Some("At least one identifier required"));
         return HttpResponse::BadRequest().json(ErrorResponse {
-            error: "At least one identifier required: patient_id, accession_number, or exam_uid".to_string(),
+            error: "At least one identifier required: patient_id, accession_number, exam_uid, or study_instance_uid".to_string(),
             field: None,
         });
     }
     
-    log::info!("🔍 [HTTP] Recherche rapport: patient_id={:?}, accession={:?}, exam_uid={:?}",
-             query.patient_id, query.accession_number, query.exam_uid);
+    log::info!("🔍 [HTTP] Recherche rapport ({}): patient_id={:?}, accession={:?}, exam_uid={:?}, study_uid={:?}",
+             mode.as_str(),
+             query.patient_id.as_deref().map(mask_sensitive_id),
+             query.accession_number.as_deref().map(|v| pseudonymize(IdentifierKind::AccessionNumber, v)),
+             query.exam_uid.as_deref().map(|v| pseudonymize(IdentifierKind::StudyUid, v)),
+             query.study_instance_uid.as_deref().map(|v| pseudonymize(IdentifierKind::StudyUid, v)));
     
-    match state.db.find_pending_report_by_identifiers(
-        query.patient_id.as_deref(),
-        query.accession_number.as_deref(),
-        query.exam_uid.as_deref(),
-    ) {
-        Ok(Some(report)) => {
+    // Les rapports attribués au radiologue actif sont prioritaires à score égal
+    let lookup = query.clone();
+    let candidates = db_call(&state, move |db| {
+        let active_user_id = db.get_active_user_id().ok().flatten();
+        db.find_report_candidates(&lookup.identifiers(), mode, active_user_id.as_deref(), MAX_MATCH_CANDIDATES)
+    })
+    .await;
+    
+    match candidates {
+        Ok(candidates) if !candidates.is_empty() => {
+            let ambiguous = is_ambiguous_match(&candidates);
+            let summaries: Vec<MatchCandidateSummary> = candidates.iter().map(MatchCandidateSummary::from).collect();
+            let best = candidates.into_iter().next().expect("candidates non vide");
+            let score = best.score;
+            let report = best.report;
+            
             // Parser le JSON stocké
             let structured: Value = serde_json::from_str(&report.structured_data)
                 .unwrap_or(Value::Null);
@@ -756,7 +1321,12 @@ pub async fn find_report(
                 .as_ref()
                 .and_then(|s| serde_json::from_str(s).ok());
             
-            log::info!("✅ [HTTP] Rapport trouvé: tid={}", report.technical_id);
+            if ambiguous {
+                log::warn!("⚠️ [HTTP] Appariement ambigu: {} candidats au score {}", 
+                    summaries.iter().filter(|c| c.score == score).count(), score);
+            } else {
+                log::info!("✅ [HTTP] Rapport trouvé: tid={} (score {})", report.technical_id, score);
+            }
             let tid = report.technical_id.clone();
             request_info.log_access(&state.db, 200, "success", None);
             HttpResponse::Ok().json(FindReportResponse {
@@ -774,12 +1344,19 @@ pub async fn find_report(
                     metadata,
                     status: report.status,
                     created_at: report.created_at,
+                    priority: report.priority,
+                    due_at: report.due_at,
+                    assigned_to: report.assigned_to,
                 }),
                 retrieval_url: Some(format!("http://localhost:8741/pending-report?tid={}", tid)),
                 error: None,
+                match_mode: Some(mode.as_str()),
+                score: Some(score),
+                ambiguous: Some(ambiguous),
+                candidates: Some(summaries),
             })
         }
-        Ok(None) => {
+        Ok(_) => {
             log::warn!("⚠️ [HTTP] Aucun rapport trouvé pour ces identifiants");
             request_info.log_access(&state.db, 404, "not_found", Some("No report found"));
             HttpResponse::NotFound().json(FindReportResponse {
@@ -787,6 +1364,10 @@ pub async fn find_report(
                 data: None,
                 retrieval_url: None,
                 error: Some("No report found matching the provided identifiers".to_string()),
+                match_mode: Some(mode.as_str()),
+                score: None,
+                ambiguous: None,
+                candidates: None,
             })
         }
         Err(e) => {
@@ -812,6 +1393,23 @@ fn show_main_window() {
     }
 }
 
+/// Notifie le frontend du changement de radiologue actif (profil + préférences)
+pub fn emit_profile_changed(db: &crate::database::Database, user: Option<&crate::database::queries::UserProfile>) {
+    let preferences: serde_json::Map<String, Value> = user
+        .and_then(|u| db.list_user_preferences(&u.id).ok())
+        .unwrap_or_default()
+        .into_iter()
+        .map(|p| (p.pref_key, serde_json::from_str(&p.value).unwrap_or(Value::Null)))
+        .collect();
+
+    if let Some(app_handle) = APP_HANDLE.get() {
+        let _ = app_handle.emit_all("airadcr:profile_changed", serde_json::json!({
+            "user": user,
+            "preferences": preferences,
+        }));
+    }
+}
+
 /// POST /open-report - Ouvre un rapport dans l'iframe AIRADCR (navigation depuis RIS)
 /// 🔒 Requiert une clé API valide
 /// Recherche par tid OU par identifiants RIS (accession_number, patient_id, exam_uid)
@@ -829,7 +1427,7 @@ pub async fn open_report(
         .and_then(|v| v.to_str().ok())
         .unwrap_or("");
     
-    if !is_auth_disabled() && !validate_api_key(&state.db, api_key) {
+    if !is_auth_disabled() && !api_key_is_valid(&state, api_key).await {
         log::warn!("❌ [HTTP] POST /open-report sans API key valide");
         request_info.log_access(&state.db, 401, "unauthorized", Some("Invalid API key for open-report"));
         return HttpResponse::Unauthorized().json(ErrorResponse {
@@ -838,6 +1436,51 @@ pub async fn open_report(
         });
     }
     
+    // 👤 Radiologue cible : devient le profil actif s'il diffère
+    let target_user = match resolve_user_profile(&state, query.user.as_deref()).await {
+        Ok(user) => user,
+        Err(msg) => {
+            log::warn!("❌ [HTTP] Profil radiologue invalide pour open-report: {}", msg);
+            request_info.log_access(&state.db, 400, "bad_request", Some(&msg));
+            return HttpResponse::BadRequest().json(ErrorResponse {
+                error: msg,
+                field: Some("user".to_string()),
+            });
+        }
+    };
+    let target_user_id = match &target_user {
+        Some(user) => {
+            let profile = user.clone();
+            let switched = db_call(&state, move |db| {
+                if db.get_active_user_id()?.as_deref() == Some(profile.id.as_str()) {
+                    return Ok(false);
+                }
+                db.set_active_user(Some(&profile.id))?;
+                emit_profile_changed(db, Some(&profile));
+                Ok::<_, rusqlite::Error>(true)
+            })
+            .await;
+            match switched {
+                Ok(true) => log::info!("👤 [HTTP] Radiologue actif: {}", user.username),
+                Ok(false) => {}
+                Err(e) => log::error!("❌ [HTTP] Erreur changement de profil: {}", e),
+            }
+            Some(user.id.clone())
+        }
+        None => active_user_id(&state).await,
+    };
+    
+    let mode = match parse_match_mode(query.match_mode.as_deref()) {
+        Ok(mode) => mode,
+        Err(msg) => {
+            request_info.log_access(&state.db, 400, "bad_request", Some(&msg));
+            return HttpResponse::BadRequest().json(ErrorResponse {
+                error: msg,
+                field: Some("match_mode".to_string()),
+            });
+        }
+    };
+    
     // 📱 Ouvrir la fenêtre IMMÉDIATEMENT, avant toute logique de recherche
     show_main_window();
     
@@ -850,7 +1493,6 @@ pub async fn open_report(
         }
     } else {
         // Rechercher par identifiants RIS
-        let has_accession = query.accession_number.as_ref().map_or(false, |s| !s.is_empty());
         let has_patient = query.patient_id.as_ref().map_or(false, |s| !s.is_empty());
         let has_exam = query.exam_uid.as_ref().map_or(false, |s| !s.is_empty())
             || query.study_uid.as_ref().map_or(false, |s| !s.is_empty());
@@ -860,23 +1502,72 @@ pub async fn open_report(
             .filter(|s| !s.is_empty())
             .or_else(|| query.study_uid.as_deref().filter(|s| !s.is_empty()));
         
-        if has_accession || has_patient || has_exam {
-            match state.db.find_pending_report_by_identifiers(
-                query.patient_id.as_deref(),
-                query.accession_number.as_deref(),
-                resolved_exam_uid,
-            ) {
-                Ok(Some(report)) => Some(report.technical_id),
-                Ok(None) => {
+        let identifiers = ReportIdentifiers {
+            accession_number: query.accession_number.as_deref(),
+            study_instance_uid: query.study_instance_uid.as_deref(),
+            exam_uid: resolved_exam_uid,
+            patient_id: query.patient_id.as_deref(),
+        };
+        
+        if !identifiers.is_empty() {
+            let lookup = [
+                identifiers.accession_number,
+                identifiers.study_instance_uid,
+                identifiers.exam_uid,
+                identifiers.patient_id,
+            ]
+            .map(|value| value.map(str::to_string));
+            let preferred_user = target_user_id.clone();
+            let candidates = db_call(&state, move |db| {
+                let [accession_number, study_instance_uid, exam_uid, patient_id] = &lookup;
+                let identifiers = ReportIdentifiers {
+                    accession_number: accession_number.as_deref(),
+                    study_instance_uid: study_instance_uid.as_deref(),
+                    exam_uid: exam_uid.as_deref(),
+                    patient_id: patient_id.as_deref(),
+                };
+                db.find_report_candidates(&identifiers, mode, preferred_user.as_deref(), MAX_MATCH_CANDIDATES)
+            })
+            .await;
+            
+            match candidates {
+                // 🔒 Patient-safety : ne jamais ouvrir un rapport choisi au hasard parmi des ex-aequo
+                Ok(candidates) if is_ambiguous_match(&candidates) => {
+                    let summaries: Vec<MatchCandidateSummary> = candidates.iter().map(MatchCandidateSummary::from).collect();
+                    let score = candidates[0].score;
+                    let tied = summaries.iter().filter(|c| c.score == score).count();
+                    log::warn!("⚠️ [HTTP] open-report refusé: {} rapports au score {}", tied, score);
+                    request_info.log_access(&state.db, 409, "error", Some("Ambiguous report match"));
+                    return HttpResponse::Conflict().json(FindReportResponse {
+                        success: false,
+                        data: None,
+                        retrieval_url: None,
+                        error: Some(format!(
+                            "Ambiguous match: {} reports share the best score ({}); provide accession_number or study_instance_uid",
+                            tied, score
+                        )),
+                        match_mode: Some(mode.as_str()),
+                        score: Some(score),
+                        ambiguous: Some(true),
+                        candidates: Some(summaries),
+                    });
+                }
+                Ok(candidates) if !candidates.is_empty() => {
+                    let best = &candidates[0];
+                    log::info!("🔍 [HTTP] Meilleur candidat ({}): tid={} score={} via {:?}",
+                        mode.as_str(), best.report.technical_id, best.score, best.matched_on);
+                    Some(best.report.technical_id.clone())
+                }
+                Ok(_) => {
                     // 🆕 Fallback: tenter de récupérer depuis TÉO Hub
                     let config = get_config();
                     if config.teo_hub.enabled && has_patient && has_exam {
                         let patient_id_val = query.patient_id.as_deref().unwrap_or("");
                         let exam_uid_val = resolved_exam_uid.unwrap_or("");
                         
-                        log::info!("🔄 [HTTP] Fallback TÉO Hub: patient_id={}, exam_uid={}...",
+                        log::info!("🔄 [HTTP] Fallback TÉO Hub: patient_id={}, exam_uid={}",
                             mask_sensitive_id(patient_id_val),
-                            &exam_uid_val[..exam_uid_val.len().min(20)]);
+                            pseudonymize(IdentifierKind::StudyUid, exam_uid_val));
                         
                         match teo_client::fetch_ai_report(patient_id_val, exam_uid_val).await {
                             Ok(teo_report) => {
@@ -886,7 +1577,9 @@ pub async fn open_report(
                                     query.patient_id.as_deref(),
                                     query.exam_uid.as_deref(),
                                     query.accession_number.as_deref(),
-                                ) {
+                                    query.study_instance_uid.as_deref(),
+                                    None,
+                                ).await {
                                     Some(tid) => {
                                         log::info!("✅ [HTTP] Rapport TÉO Hub stocké localement: tid={}", tid);
                                         Some(tid)
@@ -941,6 +1634,7 @@ pub async fn open_report(
             if let Some(app_handle) = APP_HANDLE.get() {
                 if let Some(window) = app_handle.get_window("main") {
                     let _ = window.emit("airadcr:navigate_to_report", "");
+                    worklist::set_current_report(None);
                 }
             }
             
@@ -951,6 +1645,7 @@ pub async fn open_report(
                 navigated_to: Some("https://airadcr.com/app?tori=true".to_string()),
                 error: None,
                 source: Some("no_report".to_string()),
+                assigned_to: None,
             });
         }
     };
@@ -966,9 +1661,33 @@ pub async fn open_report(
             navigated_to: None,
             error: Some(format!("Invalid technical_id: {}", msg)),
             source: None,
+            assigned_to: None,
         });
     }
     
+    // 🔒 Verrou d'édition : refuser si un autre client édite ce rapport
+    if let Err(e) = acquire_lock(&state, &req, &request_info, &tid).await {
+        return lock_error_response(&request_info, &state, &tid, e);
+    }
+    
+    // 👤 Attribuer au radiologue cible un rapport encore non attribué
+    let assign_tid = tid.clone();
+    let assigned_to = db_call(&state, move |db| {
+        Ok::<_, String>(match db.get_pending_report(&assign_tid) {
+            Ok(Some(report)) => match (report.assigned_to, target_user_id) {
+                (Some(owner), _) => Some(owner),
+                (None, Some(user_id)) => match db.assign_pending_report(&assign_tid, Some(&user_id)) {
+                    Ok(true) => Some(user_id),
+                    _ => None,
+                },
+                (None, None) => None,
+            },
+            _ => None,
+        })
+    })
+    .await
+    .unwrap_or(None);
+    
     // Émettre l'événement Tauri pour naviguer vers le rapport
     if let Some(app_handle) = APP_HANDLE.get() {
         if let Some(window) = app_handle.get_window("main") {
@@ -976,6 +1695,7 @@ pub async fn open_report(
             match window.emit("airadcr:navigate_to_report", &tid) {
                 Ok(_) => {
                     log::info!("✅ [HTTP] Navigation émise: tid={}", tid);
+                    worklist::set_current_report(Some(&tid));
                     
                     
                     request_info.log_access(&state.db, 200, "success", None);
@@ -986,6 +1706,7 @@ pub async fn open_report(
                         navigated_to: Some(format!("https://airadcr.com/app?tori=true&tid={}", tid)),
                         error: None,
                         source: Some("local".to_string()),
+                        assigned_to: assigned_to.clone(),
                     })
                 }
                 Err(e) => {
@@ -998,6 +1719,7 @@ pub async fn open_report(
                         navigated_to: None,
                         error: Some(format!("Failed to trigger navigation event: {}", e)),
                         source: None,
+                        assigned_to: None,
                     })
                 }
             }
@@ -1011,6 +1733,7 @@ pub async fn open_report(
                 navigated_to: None,
                 error: Some("Main window not found".to_string()),
                 source: None,
+                assigned_to: None,
             })
         }
     } else {
@@ -1025,6 +1748,7 @@ pub async fn open_report(
                 navigated_to: None,
                 error: Some("Application not yet ready. Please try again in a moment.".to_string()),
                 source: None,
+                assigned_to: None,
             })
     }
 }
@@ -1034,12 +1758,14 @@ pub async fn open_report(
 // ============================================================================
 
 /// Convertit un rapport TÉO Hub en pending_report local et le stocke en SQLite
-fn store_teo_report_locally(
+async fn store_teo_report_locally(
     state: &web::Data<HttpServerState>,
     teo_report: &teo_client::models::TeoAiReportResponse,
     patient_id: Option<&str>,
     exam_uid: Option<&str>,
     accession_number: Option<&str>,
+    study_instance_uid: Option<&str>,
+    template: Option<&crate::database::queries::ReportTemplate>,
 ) -> Option<String> {
     // Générer un technical_id unique
     let tid = format!("teo_{}", &Uuid::new_v4().to_string()[..8]);
@@ -1047,31 +1773,60 @@ fn store_teo_report_locally(
     let now = Utc::now();
     let expires_at = now + Duration::hours(24);
     
-    // Construire le JSON structuré depuis la réponse TÉO
-    let structured = serde_json::json!({
-        "title": teo_report.result.structured_report.title,
-        "results": teo_report.result.structured_report.results,
-        "conclusion": teo_report.result.structured_report.conclusion,
-        "translated_text": teo_report.result.translation.translated_text,
-        "translation_language": teo_report.result.translation.language,
-    });
+    // Construire le rapport structuré depuis la réponse TÉO (traduction en champs additionnels)
+    let teo_structured = &teo_report.result.structured_report;
+    let mut extra = serde_json::Map::new();
+    extra.insert("translated_text".to_string(), Value::String(teo_report.result.translation.translated_text.clone()));
+    extra.insert("translation_language".to_string(), Value::String(teo_report.result.translation.language.clone()));
+    let structured = StructuredReport {
+        title: teo_structured.title.clone(),
+        results: teo_structured.results.clone(),
+        conclusion: teo_structured.conclusion.clone(),
+        extra,
+        ..Default::default()
+    };
+    
+    // Modèle éventuel : le brouillon IA complète le compte rendu type
+    let structured = match template {
+        Some(template) => {
+            let draft = serde_json::to_value(&structured).unwrap_or(Value::Null);
+            match templates::merge_template(template, &draft, &HashMap::new(), None) {
+                Ok(merged) => merged.structured,
+                Err(e) => {
+                    log::warn!("⚠️ [HTTP] Fusion modèle {} impossible ({}), brouillon IA seul", template.name, e.message);
+                    structured
+                }
+            }
+        }
+        None => structured,
+    };
     let structured_json = serde_json::to_string(&structured).unwrap_or_default();
     
-    match state.db.insert_pending_report(
-        &id,
-        &tid,
-        patient_id,
-        exam_uid,
-        accession_number,
-        None, // study_instance_uid
-        &structured_json,
-        "teo_hub_auto",
-        None, // ai_modules
-        None, // modality
-        None, // metadata
-        &now.to_rfc3339(),
-        &expires_at.to_rfc3339(),
-    ) {
+    // Insertion hors du worker actix
+    let stored_tid = tid.clone();
+    let identifiers = [patient_id, exam_uid, accession_number, study_instance_uid].map(|v| v.map(str::to_string));
+    let modality = template.and_then(|t| t.modality.clone());
+    let stored: rusqlite::Result<_> = db_call(state, move |db| {
+        let [patient_id, exam_uid, accession_number, study_instance_uid] = &identifiers;
+        db.insert_pending_report(
+            &id,
+            &stored_tid,
+            patient_id.as_deref(),
+            exam_uid.as_deref(),
+            accession_number.as_deref(),
+            study_instance_uid.as_deref(),
+            &structured_json,
+            "teo_hub_auto",
+            None, // ai_modules
+            modality.as_deref(),
+            None, // metadata
+            &now.to_rfc3339(),
+            &expires_at.to_rfc3339(),
+        )
+    })
+    .await;
+    
+    match stored {
         Ok(_) => Some(tid),
         Err(e) => {
             log::error!("❌ [HTTP] Erreur stockage rapport TÉO Hub: {}", e);
@@ -1100,7 +1855,7 @@ pub async fn fetch_from_teo_hub(
         .and_then(|v| v.to_str().ok())
         .unwrap_or("");
     
-    if !is_auth_disabled() && !validate_api_key(&state.db, api_key) {
+    if !is_auth_disabled() && !api_key_is_valid(&state, api_key).await {
         log::warn!("❌ [HTTP] GET /teo-hub/fetch sans API key valide");
         request_info.log_access(&state.db, 401, "unauthorized", Some("Invalid API key"));
         return HttpResponse::Unauthorized().json(ErrorResponse {
@@ -1139,8 +1894,27 @@ pub async fn fetch_from_teo_hub(
         });
     }
     
-    log::info!("🔄 [HTTP] TÉO Hub fetch: patient_id={}, study_uid={}...",
-        mask_sensitive_id(patient_id), &study_uid[..study_uid.len().min(20)]);
+    // Modèle de rapport optionnel (fusionné avec le brouillon IA)
+    let template = match query.template.as_deref().filter(|t| !t.is_empty()) {
+        Some(key) => match find_template(&state, key).await {
+            Ok(Some(template)) => Some(template),
+            _ => {
+                let msg = format!("Unknown report template: {}", key);
+                request_info.log_access(&state.db, 400, "bad_request", Some(&msg));
+                return HttpResponse::BadRequest().json(TeoHubFetchResponse {
+                    success: false,
+                    technical_id: None,
+                    retrieval_url: None,
+                    source: None,
+                    error: Some(msg),
+                });
+            }
+        },
+        None => None,
+    };
+    
+    log::info!("🔄 [HTTP] TÉO Hub fetch: patient_id={}, study_uid={}",
+        mask_sensitive_id(patient_id), pseudonymize(IdentifierKind::StudyUid, study_uid));
     
     match teo_client::fetch_ai_report(patient_id, study_uid).await {
         Ok(teo_report) => {
@@ -1150,7 +1924,9 @@ pub async fn fetch_from_teo_hub(
                 Some(patient_id),
                 Some(study_uid),
                 None,
-            ) {
+                Some(study_uid),
+                template.as_ref(),
+            ).await {
                 Some(tid) => {
                     log::info!("✅ [HTTP] TÉO Hub fetch réussi: tid={}", tid);
                     request_info.log_access(&state.db, 200, "success", None);
@@ -1188,3 +1964,986 @@ pub async fn fetch_from_teo_hub(
         }
     }
 }
+
+// ============================================================================
+// Liste de travail
+// ============================================================================
+
+/// GET /worklist - Rapports à lire triés par priorité, échéance et ancienneté (🔒 requiert API key)
+pub async fn get_worklist(
+    req: HttpRequest,
+    query: web::Query<WorklistQuery>,
+    state: web::Data<HttpServerState>,
+) -> HttpResponse {
+    let request_info = RequestInfo::from_request(&req);
+    
+    if let Some(response) = require_api_key(&req, &request_info, &state).await {
+        return response;
+    }
+    
+    let user_id = match resolve_user_profile(&state, query.user.as_deref()).await {
+        Ok(Some(user)) => Some(user.id),
+        Ok(None) => active_user_id(&state).await,
+        Err(msg) => {
+            request_info.log_access(&state.db, 400, "bad_request", Some(&msg));
+            return HttpResponse::BadRequest().json(ErrorResponse {
+                error: msg,
+                field: Some("user".to_string()),
+            });
+        }
+    };
+    
+    let priority = match query.priority.as_deref().filter(|s| !s.is_empty()) {
+        Some(value) => match worklist::parse_priority(value) {
+            Ok(priority) => Some(priority),
+            Err(msg) => {
+                request_info.log_access(&state.db, 400, "bad_request", Some(&msg));
+                return HttpResponse::BadRequest().json(ErrorResponse {
+                    error: msg,
+                    field: Some("priority".to_string()),
+                });
+            }
+        },
+        None => None,
+    };
+    
+    let filter = crate::database::queries::WorklistFilter {
+        user_id: user_id.clone(),
+        assigned_only: query.assigned_only.unwrap_or(false),
+        modality: query.modality.clone(),
+        priority,
+        exclude_tid: None,
+        limit: query.limit.unwrap_or(50),
+    };
+    
+    match db_call(&state, move |db| db.list_worklist(&filter)).await {
+        Ok(items) => {
+            request_info.log_access(&state.db, 200, "success", None);
+            HttpResponse::Ok().json(WorklistResponse {
+                success: true,
+                user_id,
+                current_report: worklist::current_report(),
+                count: items.len(),
+                items,
+            })
+        }
+        Err(e) => {
+            log::error!("❌ [HTTP] Erreur liste de travail: {}", e);
+            request_info.log_access(&state.db, 500, "error", Some(&format!("Database error: {}", e)));
+            HttpResponse::InternalServerError().json(ErrorResponse {
+                error: format!("Database error: {}", e),
+                field: None,
+            })
+        }
+    }
+}
+
+/// POST /worklist/next - Ouvre le rapport suivant du radiologue actif
+/// 🔒 Requiert une clé API valide
+pub async fn open_next_report(
+    req: HttpRequest,
+    state: web::Data<HttpServerState>,
+) -> HttpResponse {
+    let request_info = RequestInfo::from_request(&req);
+    
+    let api_key = req
+        .headers()
+        .get("x-api-key")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("");
+    
+    if !is_auth_disabled() && !api_key_is_valid(&state, api_key).await {
+        log::warn!("❌ [HTTP] POST /worklist/next sans API key valide");
+        request_info.log_access(&state.db, 401, "unauthorized", Some("Invalid API key for worklist/next"));
+        return HttpResponse::Unauthorized().json(ErrorResponse {
+            error: "API key required for worklist/next".to_string(),
+            field: None,
+        });
+    }
+    
+    show_main_window();
+    
+    let (info, holder_key) = (request_info.clone(), api_key.to_string());
+    let next = db_call(&state, move |db| worklist::open_next_report(db, &request_holder(db, &info, &holder_key))).await;
+    match next {
+        Ok(Some(item)) => {
+            request_info.log_access(&state.db, 200, "success", None);
+            HttpResponse::Ok().json(OpenReportResponse {
+                success: true,
+                message: Some(format!("Navigation triggered ({} priority)", item.priority)),
+                navigated_to: Some(format!("https://airadcr.com/app?tori=true&tid={}", item.technical_id)),
+                technical_id: Some(item.technical_id),
+                error: None,
+                source: Some("worklist".to_string()),
+                assigned_to: item.assigned_to,
+            })
+        }
+        Ok(None) => {
+            request_info.log_access(&state.db, 200, "success", Some("Worklist empty"));
+            HttpResponse::Ok().json(OpenReportResponse {
+                success: true,
+                message: Some("Worklist is empty".to_string()),
+                technical_id: None,
+                navigated_to: None,
+                error: None,
+                source: Some("worklist".to_string()),
+                assigned_to: None,
+            })
+        }
+        Err(e) => {
+            log::error!("❌ [HTTP] Erreur rapport suivant: {}", e);
+            request_info.log_access(&state.db, 500, "error", Some(&e));
+            HttpResponse::InternalServerError().json(OpenReportResponse {
+                success: false,
+                message: None,
+                technical_id: None,
+                navigated_to: None,
+                error: Some(e),
+                source: None,
+                assigned_to: None,
+            })
+        }
+    }
+}
+
+// ============================================================================
+// Verrous de rapports
+// ============================================================================
+
+/// GET /report-locks - Liste les verrous actifs
+pub async fn list_report_locks(
+    req: HttpRequest,
+    state: web::Data<HttpServerState>,
+) -> HttpResponse {
+    let request_info = RequestInfo::from_request(&req);
+    
+    if let Some(response) = require_api_key(&req, &request_info, &state).await {
+        return response;
+    }
+    
+    match db_call(&state, |db| db.list_report_locks()).await {
+        Ok(locks) => {
+            request_info.log_access(&state.db, 200, "success", None);
+            HttpResponse::Ok().json(ReportLocksResponse {
+                success: true,
+                count: locks.len(),
+                locks,
+            })
+        }
+        Err(e) => {
+            request_info.log_access(&state.db, 500, "error", Some(&format!("Database error: {}", e)));
+            HttpResponse::InternalServerError().json(ErrorResponse {
+                error: format!("Database error: {}", e),
+                field: None,
+            })
+        }
+    }
+}
+
+/// POST /report-locks/{tid}/heartbeat - Prolonge le bail du verrou détenu par le client
+pub async fn heartbeat_report_lock(
+    req: HttpRequest,
+    path: web::Path<String>,
+    state: web::Data<HttpServerState>,
+) -> HttpResponse {
+    let request_info = RequestInfo::from_request(&req);
+    
+    if let Some(response) = require_api_key(&req, &request_info, &state).await {
+        return response;
+    }
+    let tid = path.into_inner();
+    
+    if let Err(msg) = validate_technical_id(&tid) {
+        request_info.log_access(&state.db, 400, "bad_request", Some(&msg));
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: msg,
+            field: Some("technical_id".to_string()),
+        });
+    }
+    
+    let (info, lock_tid, api_key) = (request_info.clone(), tid.clone(), api_key_header(&req));
+    match db_call(&state, move |db| report_locks::heartbeat(db, &lock_tid, &request_holder(db, &info, &api_key))).await {
+        Ok(lock) => {
+            request_info.log_access(&state.db, 200, "success", None);
+            HttpResponse::Ok().json(ReportLockResponse {
+                success: true,
+                lock: Some(lock),
+                released: None,
+            })
+        }
+        Err(e) => lock_error_response(&request_info, &state, &tid, e),
+    }
+}
+
+/// DELETE /report-locks/{tid} - Libère le verrou du client
+/// (?force=true avec X-Admin-Key : libère le verrou quel que soit son détenteur)
+pub async fn release_report_lock(
+    req: HttpRequest,
+    path: web::Path<String>,
+    query: web::Query<ReleaseLockQuery>,
+    state: web::Data<HttpServerState>,
+) -> HttpResponse {
+    let request_info = RequestInfo::from_request(&req);
+    
+    if let Some(response) = require_api_key(&req, &request_info, &state).await {
+        return response;
+    }
+    let tid = path.into_inner();
+    
+    if query.force {
+        let admin_key = req
+            .headers()
+            .get("x-admin-key")
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or("");
+        
+        if !validate_admin_key(admin_key) {
+            log::warn!("❌ [HTTP] Clé admin invalide pour libération forcée de verrou");
+            request_info.log_security_event("invalid_admin_key", "Invalid or missing admin key");
+            request_info.log_access(&state.db, 401, "unauthorized", Some("Invalid admin key"));
+            return HttpResponse::Unauthorized().json(ErrorResponse {
+                error: "Invalid or missing admin key".to_string(),
+                field: None,
+            });
+        }
+        
+        let lock_tid = tid.clone();
+        return match db_call(&state, move |db| report_locks::force_release(db, &lock_tid)).await {
+            Ok(lock) => {
+                if let Some(lock) = &lock {
+                    request_info.log_security_event(
+                        "report_lock_forced",
+                        &format!("Lock on {} held by {}@{} force-released", tid, lock.holder_id, lock.workstation),
+                    );
+                }
+                request_info.log_access(&state.db, 200, "success", None);
+                HttpResponse::Ok().json(ReportLockResponse {
+                    success: true,
+                    released: Some(lock.is_some()),
+                    lock,
+                })
+            }
+            Err(e) => {
+                request_info.log_access(&state.db, 500, "error", Some(&e));
+                HttpResponse::InternalServerError().json(ErrorResponse {
+                    error: e,
+                    field: None,
+                })
+            }
+        };
+    }
+    
+    let (info, lock_tid, api_key) = (request_info.clone(), tid.clone(), api_key_header(&req));
+    let released = db_call(&state, move |db| {
+        let released = report_locks::release(db, &lock_tid, &request_holder(db, &info, &api_key))?;
+        // Verrou restant : détenu par un autre client
+        let remaining = if released { None } else { db.get_report_lock(&lock_tid).ok().flatten() };
+        Ok::<_, String>((released, remaining))
+    })
+    .await;
+    
+    match released {
+        Ok((true, _)) => {
+            request_info.log_access(&state.db, 200, "success", None);
+            HttpResponse::Ok().json(ReportLockResponse {
+                success: true,
+                lock: None,
+                released: Some(true),
+            })
+        }
+        Ok((false, Some(lock))) => lock_error_response(&request_info, &state, &tid, LockError::Held(Box::new(lock))),
+        Ok((false, None)) => {
+            request_info.log_access(&state.db, 200, "success", None);
+            HttpResponse::Ok().json(ReportLockResponse {
+                success: true,
+                lock: None,
+                released: Some(false),
+            })
+        }
+        Err(e) => {
+            request_info.log_access(&state.db, 500, "error", Some(&e));
+            HttpResponse::InternalServerError().json(ErrorResponse {
+                error: e,
+                field: None,
+            })
+        }
+    }
+}
+
+// ============================================================================
+// Recherche plein texte
+// ============================================================================
+
+/// GET /search - Recherche plein texte dans le contenu des rapports (FTS5)
+pub async fn search_reports(
+    req: HttpRequest,
+    query: web::Query<SearchQuery>,
+    state: web::Data<HttpServerState>,
+) -> HttpResponse {
+    let request_info = RequestInfo::from_request(&req);
+    
+    if let Some(response) = require_api_key(&req, &request_info, &state).await {
+        return response;
+    }
+    
+    let q = query.q.as_deref().map(str::trim).unwrap_or("");
+    if q.is_empty() || q.chars().count() > MAX_SEARCH_QUERY_LEN {
+        let msg = format!("q is required (max {} characters)", MAX_SEARCH_QUERY_LEN);
+        request_info.log_access(&state.db, 400, "bad_request", Some(&msg));
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: msg,
+            field: Some("q".to_string()),
+        });
+    }
+    
+    let modality = query.modality.clone().filter(|m| !m.is_empty());
+    // 🛡️ SÉCURITÉ: Texte recherché (noms, conclusions) jamais journalisé en clair
+    log::info!("🔎 [HTTP] Recherche plein texte: {} caractère(s), modality={:?}", q.chars().count(), modality);
+    
+    let (text, limit) = (q.to_string(), query.limit.unwrap_or(20));
+    match db_call(&state, move |db| db.search_reports(&text, modality.as_deref(), limit)).await {
+        Ok(results) => {
+            let tids: Vec<&str> = results.iter().map(|hit| hit.technical_id.as_str()).collect();
+            log::info!("✅ [HTTP] Recherche plein texte: {} résultat(s), tid={:?}", results.len(), tids);
+            request_info.log_access(&state.db, 200, "success", None);
+            HttpResponse::Ok().json(SearchResponse {
+                success: true,
+                query: q.to_string(),
+                count: results.len(),
+                results,
+            })
+        }
+        Err(e) => {
+            log::error!("❌ [HTTP] Erreur recherche plein texte: {}", e);
+            request_info.log_access(&state.db, 500, "error", Some(&format!("Database error: {}", e)));
+            HttpResponse::InternalServerError().json(ErrorResponse {
+                error: format!("Database error: {}", e),
+                field: None,
+            })
+        }
+    }
+}
+
+// ============================================================================
+// Schémas des rapports structurés
+// ============================================================================
+
+/// GET /schemas/{modality} - JSON Schema du rapport structuré d'une modalité
+/// (CT, MR, MG, US, CR ou default)
+pub async fn get_report_schema(
+    req: HttpRequest,
+    path: web::Path<String>,
+    state: web::Data<HttpServerState>,
+) -> HttpResponse {
+    let request_info = RequestInfo::from_request(&req);
+    let modality = path.into_inner();
+    
+    match report_model::schema_for(&modality) {
+        Some(schema) => {
+            request_info.log_access(&state.db, 200, "success", None);
+            HttpResponse::Ok()
+                .content_type("application/schema+json")
+                .json(schema)
+        }
+        None => {
+            request_info.log_access(&state.db, 404, "not_found", Some("Unknown modality schema"));
+            HttpResponse::NotFound().json(ErrorResponse {
+                error: format!(
+                    "No schema for modality '{}'; available: {}, {}",
+                    modality,
+                    report_model::MODALITIES.join(", "),
+                    report_model::DEFAULT_SCHEMA
+                ),
+                field: Some("modality".to_string()),
+            })
+        }
+    }
+}
+
+// ============================================================================
+// Validation Patient-Safe (simulation)
+// ============================================================================
+
+/// Corps de POST /validate
+#[derive(Debug, Deserialize)]
+pub struct ValidatePayloadRequest {
+    /// local, cloud (défaut) ou teo
+    pub destination: Option<String>,
+    pub payload: Value,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ValidatePayloadResponse {
+    pub success: bool,
+    #[serde(flatten)]
+    pub report: patient_safe::PolicyReport,
+}
+
+/// POST /validate - Évalue un payload selon la politique Patient-Safe d'une
+/// destination, sans rien envoyer ni stocker (règle déclenchée, chemin JSON)
+pub async fn validate_payload(
+    req: HttpRequest,
+    body: web::Json<ValidatePayloadRequest>,
+    state: web::Data<HttpServerState>,
+) -> HttpResponse {
+    let request_info = RequestInfo::from_request(&req);
+    
+    let destination = match body.destination.as_deref() {
+        None => Destination::Cloud,
+        Some(value) => match Destination::parse(value) {
+            Some(destination) => destination,
+            None => {
+                request_info.log_access(&state.db, 400, "bad_request", Some("Invalid destination"));
+                return HttpResponse::BadRequest().json(ErrorResponse {
+                    error: "destination must be one of: local, cloud, teo".to_string(),
+                    field: Some("destination".to_string()),
+                });
+            }
+        },
+    };
+    
+    let report = patient_safe::evaluate(destination, &body.payload);
+    request_info.log_access(&state.db, 200, "success", None);
+    HttpResponse::Ok().json(ValidatePayloadResponse {
+        success: true,
+        report,
+    })
+}
+
+/// Corps de POST /admin/reidentify
+#[derive(Debug, Deserialize)]
+pub struct ReidentifyRequest {
+    /// Pseudonyme relevé dans les logs (pid_…, acc_…, uid_…)
+    pub pseudonym: String,
+    /// Valeurs candidates à tester si l'identifiant n'est plus en base
+    #[serde(default)]
+    pub candidates: Vec<String>,
+}
+
+/// POST /admin/reidentify - Ré-identification d'un pseudonyme (investigation, clé admin)
+pub async fn reidentify_pseudonym(
+    req: HttpRequest,
+    body: web::Json<ReidentifyRequest>,
+    state: web::Data<HttpServerState>,
+) -> HttpResponse {
+    let request_info = RequestInfo::from_request(&req);
+    
+    let admin_key = req
+        .headers()
+        .get("x-admin-key")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("");
+    
+    if !validate_admin_key(admin_key) {
+        request_info.log_security_event("invalid_admin_key", "Invalid or missing admin key");
+        request_info.log_access(&state.db, 401, "unauthorized", Some("Invalid admin key"));
+        return HttpResponse::Unauthorized().json(ErrorResponse {
+            error: "Invalid or missing admin key".to_string(),
+            field: None,
+        });
+    }
+    
+    let (pseudonym, candidates) = (body.pseudonym.clone(), body.candidates.clone());
+    match db_call(&state, move |db| crate::pii::pseudonym::reidentify(db, &pseudonym, &candidates)).await {
+        Ok(result) => {
+            request_info.log_security_event(
+                "pseudonym_reidentified",
+                &format!(
+                    "Re-identification of {} ({})",
+                    result.pseudonym,
+                    if result.value.is_some() { "found" } else { "not found" }
+                ),
+            );
+            request_info.log_access(&state.db, 200, "success", None);
+            HttpResponse::Ok().json(result)
+        }
+        Err(msg) if msg.starts_with("Database error") => {
+            request_info.log_access(&state.db, 500, "error", Some(&msg));
+            HttpResponse::InternalServerError().json(ErrorResponse {
+                error: msg,
+                field: None,
+            })
+        }
+        Err(msg) => {
+            request_info.log_access(&state.db, 400, "bad_request", Some(&msg));
+            HttpResponse::BadRequest().json(ErrorResponse {
+                error: msg,
+                field: Some("pseudonym".to_string()),
+            })
+        }
+    }
+}
+
+// ============================================================================
+// Rétention (admin)
+// ============================================================================
+
+/// Paramètres des listes admin (journal des purges, certificats d'effacement)
+#[derive(Debug, Deserialize)]
+pub struct AdminListQuery {
+    pub limit: Option<i64>,
+}
+
+/// Réponse de GET /admin/retention/purges
+#[derive(Serialize)]
+pub struct RetentionPurgesResponse {
+    pub success: bool,
+    pub count: usize,
+    pub purges: Vec<crate::database::queries::RetentionPurgeEntry>,
+}
+
+/// Vérifie la clé admin ; Some(réponse 401) si invalide
+fn require_admin(req: &HttpRequest, request_info: &RequestInfo, state: &HttpServerState) -> Option<HttpResponse> {
+    let admin_key = req
+        .headers()
+        .get("x-admin-key")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("");
+    
+    if validate_admin_key(admin_key) {
+        return None;
+    }
+    
+    request_info.log_security_event("invalid_admin_key", "Invalid or missing admin key");
+    request_info.log_access(&state.db, 401, "unauthorized", Some("Invalid admin key"));
+    Some(HttpResponse::Unauthorized().json(ErrorResponse {
+        error: "Invalid or missing admin key".to_string(),
+        field: None,
+    }))
+}
+
+/// GET /admin/retention/preview - Simulation du prochain cycle de purge (clé admin)
+pub async fn preview_retention_purge(
+    req: HttpRequest,
+    state: web::Data<HttpServerState>,
+) -> HttpResponse {
+    let request_info = RequestInfo::from_request(&req);
+    if let Some(response) = require_admin(&req, &request_info, &state) {
+        return response;
+    }
+    
+    match db_call(&state, crate::retention::preview).await {
+        Ok(report) => {
+            request_info.log_access(&state.db, 200, "success", None);
+            HttpResponse::Ok().json(report)
+        }
+        Err(msg) => {
+            request_info.log_access(&state.db, 500, "error", Some(&msg));
+            HttpResponse::InternalServerError().json(ErrorResponse {
+                error: msg,
+                field: None,
+            })
+        }
+    }
+}
+
+/// GET /admin/retention/purges - Journal des purges effectuées (clé admin)
+pub async fn list_retention_purges(
+    req: HttpRequest,
+    query: web::Query<AdminListQuery>,
+    state: web::Data<HttpServerState>,
+) -> HttpResponse {
+    let request_info = RequestInfo::from_request(&req);
+    if let Some(response) = require_admin(&req, &request_info, &state) {
+        return response;
+    }
+    
+    let limit = query.limit.unwrap_or(100).clamp(1, 1000);
+    match db_call(&state, move |db| db.list_retention_purges(limit)).await {
+        Ok(purges) => {
+            request_info.log_access(&state.db, 200, "success", None);
+            HttpResponse::Ok().json(RetentionPurgesResponse {
+                success: true,
+                count: purges.len(),
+                purges,
+            })
+        }
+        Err(e) => database_error_response(&request_info, &state, e),
+    }
+}
+
+// ============================================================================
+// Droits des personnes RGPD (admin)
+// ============================================================================
+
+/// Corps de POST /admin/gdpr/erase
+#[derive(Debug, Deserialize)]
+pub struct GdprEraseRequest {
+    #[serde(flatten)]
+    pub subject: crate::gdpr::DataSubject,
+    /// erase (défaut) ou anonymise
+    pub mode: Option<String>,
+}
+
+/// Réponse de GET /admin/gdpr/certificates
+#[derive(Serialize)]
+pub struct ErasureCertificatesResponse {
+    pub success: bool,
+    pub count: usize,
+    pub certificates: Vec<crate::database::queries::ErasureCertificateEntry>,
+}
+
+/// POST /admin/gdpr/export - Export signé des données d'une personne (clé admin)
+pub async fn gdpr_export(
+    req: HttpRequest,
+    body: web::Json<crate::gdpr::DataSubject>,
+    state: web::Data<HttpServerState>,
+) -> HttpResponse {
+    let request_info = RequestInfo::from_request(&req);
+    if let Some(response) = require_admin(&req, &request_info, &state) {
+        return response;
+    }
+    
+    if let Err(msg) = body.validate() {
+        request_info.log_access(&state.db, 400, "bad_request", Some(&msg));
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: msg,
+            field: Some("patient_id".to_string()),
+        });
+    }
+    
+    let (subject, origin) = (body.into_inner(), request_info.ip_address.clone());
+    match db_call(&state, move |db| crate::gdpr::export(db, &subject, &origin)).await {
+        Ok(bundle) => {
+            request_info.log_security_event("gdpr_export", "Subject access export generated");
+            request_info.log_access(&state.db, 200, "success", None);
+            HttpResponse::Ok().json(bundle)
+        }
+        Err(msg) => {
+            request_info.log_access(&state.db, 500, "error", Some(&msg));
+            HttpResponse::InternalServerError().json(ErrorResponse {
+                error: msg,
+                field: None,
+            })
+        }
+    }
+}
+
+/// POST /admin/gdpr/erase - Effacement / anonymisation avec certificat (clé admin)
+pub async fn gdpr_erase(
+    req: HttpRequest,
+    body: web::Json<GdprEraseRequest>,
+    state: web::Data<HttpServerState>,
+) -> HttpResponse {
+    let request_info = RequestInfo::from_request(&req);
+    if let Some(response) = require_admin(&req, &request_info, &state) {
+        return response;
+    }
+    
+    if let Err(msg) = body.subject.validate() {
+        request_info.log_access(&state.db, 400, "bad_request", Some(&msg));
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: msg,
+            field: Some("patient_id".to_string()),
+        });
+    }
+    
+    let Some(mode) = crate::gdpr::ErasureMode::parse(body.mode.as_deref().unwrap_or("erase")) else {
+        request_info.log_access(&state.db, 400, "bad_request", Some("Invalid erasure mode"));
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: "mode must be one of: erase, anonymise".to_string(),
+            field: Some("mode".to_string()),
+        });
+    };
+    
+    let (subject, origin) = (body.into_inner().subject, request_info.ip_address.clone());
+    match db_call(&state, move |db| crate::gdpr::erase(db, &subject, mode, &origin)).await {
+        Ok(bundle) => {
+            request_info.log_security_event(
+                "gdpr_erasure",
+                &format!("Erasure certificate {} issued ({})", bundle.content.certificate_id, mode.as_str()),
+            );
+            request_info.log_access(&state.db, 200, "success", None);
+            HttpResponse::Ok().json(bundle)
+        }
+        Err(msg) => {
+            request_info.log_access(&state.db, 500, "error", Some(&msg));
+            HttpResponse::InternalServerError().json(ErrorResponse {
+                error: msg,
+                field: None,
+            })
+        }
+    }
+}
+
+/// GET /admin/gdpr/certificates - Certificats d'effacement délivrés (clé admin)
+pub async fn list_erasure_certificates(
+    req: HttpRequest,
+    query: web::Query<AdminListQuery>,
+    state: web::Data<HttpServerState>,
+) -> HttpResponse {
+    let request_info = RequestInfo::from_request(&req);
+    if let Some(response) = require_admin(&req, &request_info, &state) {
+        return response;
+    }
+    
+    let limit = query.limit.unwrap_or(100).clamp(1, 1000);
+    match db_call(&state, move |db| db.list_erasure_certificates(limit)).await {
+        Ok(certificates) => {
+            request_info.log_access(&state.db, 200, "success", None);
+            HttpResponse::Ok().json(ErasureCertificatesResponse {
+                success: true,
+                count: certificates.len(),
+                certificates,
+            })
+        }
+        Err(e) => database_error_response(&request_info, &state, e),
+    }
+}
+
+// ============================================================================
+// Modèles de rapports (comptes rendus types)
+// ============================================================================
+
+/// GET /templates - Liste les modèles (filtres modality, body_part, language)
+pub async fn list_report_templates(
+    req: HttpRequest,
+    query: web::Query<TemplateListQuery>,
+    state: web::Data<HttpServerState>,
+) -> HttpResponse {
+    let request_info = RequestInfo::from_request(&req);
+    
+    let modality = query.modality.as_deref().filter(|m| !m.is_empty()).map(str::to_uppercase);
+    let body_part = query.body_part.clone().filter(|b| !b.is_empty());
+    let language = query.language.clone().filter(|l| !l.is_empty());
+    
+    let templates = db_call(&state, move |db| {
+        db.list_report_templates(modality.as_deref(), body_part.as_deref(), language.as_deref())
+    })
+    .await;
+    match templates {
+        Ok(list) => {
+            request_info.log_access(&state.db, 200, "success", None);
+            HttpResponse::Ok().json(TemplatesResponse {
+                success: true,
+                count: list.len(),
+                templates: list.into_iter().map(TemplateData::from).collect(),
+            })
+        }
+        Err(e) => database_error_response(&request_info, &state, e),
+    }
+}
+
+/// GET /templates/{id} - Détail d'un modèle (id ou nom)
+pub async fn get_report_template(
+    req: HttpRequest,
+    path: web::Path<String>,
+    state: web::Data<HttpServerState>,
+) -> HttpResponse {
+    let request_info = RequestInfo::from_request(&req);
+    
+    match find_template(&state, &path.into_inner()).await {
+        Ok(Some(template)) => {
+            request_info.log_access(&state.db, 200, "success", None);
+            HttpResponse::Ok().json(TemplateResponse {
+                success: true,
+                template: template.into(),
+            })
+        }
+        Ok(None) => {
+            request_info.log_access(&state.db, 404, "not_found", Some("Template not found"));
+            HttpResponse::NotFound().json(ErrorResponse {
+                error: "Template not found".to_string(),
+                field: None,
+            })
+        }
+        Err(e) => database_error_response(&request_info, &state, e),
+    }
+}
+
+/// POST /templates - Crée un modèle
+/// 🔒 Requiert une clé API valide
+pub async fn create_report_template(
+    req: HttpRequest,
+    body: web::Json<templates::TemplateInput>,
+    state: web::Data<HttpServerState>,
+) -> HttpResponse {
+    let request_info = RequestInfo::from_request(&req);
+    if let Some(response) = require_api_key(&req, &request_info, &state).await {
+        return response;
+    }
+    
+    let validated = match templates::validate_template(&body) {
+        Ok(validated) => validated,
+        Err(e) => {
+            request_info.log_access(&state.db, 400, "bad_request", Some(&e.message));
+            return HttpResponse::BadRequest().json(ErrorResponse {
+                error: e.message,
+                field: Some(e.field),
+            });
+        }
+    };
+    
+    if let Ok(Some(_)) = find_template(&state, &validated.name).await {
+        request_info.log_access(&state.db, 400, "bad_request", Some("Template name already exists"));
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: format!("A template named '{}' already exists", validated.name),
+            field: Some("name".to_string()),
+        });
+    }
+    
+    let id = Uuid::new_v4().to_string();
+    let created = db_call(&state, move |db| {
+        let created_by = db.get_active_user_id().ok().flatten();
+        let fields = crate::database::queries::ReportTemplateFields {
+            name: &validated.name,
+            modality: validated.modality.as_deref(),
+            body_part: validated.body_part.as_deref(),
+            language: &validated.language,
+            content: &validated.content,
+        };
+        db.insert_report_template(&id, &fields, created_by.as_deref())?;
+        db.get_report_template(&id)
+    })
+    .await;
+    
+    match created {
+        Ok(Some(template)) => {
+            log::info!("📝 [HTTP] Modèle de rapport créé: {} ({})", template.name, template.id);
+            request_info.log_access(&state.db, 201, "success", None);
+            HttpResponse::Created().json(TemplateResponse {
+                success: true,
+                template: template.into(),
+            })
+        }
+        Ok(None) => database_error_response(&request_info, &state, rusqlite::Error::QueryReturnedNoRows),
+        Err(e) => database_error_response(&request_info, &state, e),
+    }
+}
+
+/// PUT /templates/{id} - Remplace un modèle
+/// 🔒 Requiert une clé API valide
+pub async fn update_report_template(
+    req: HttpRequest,
+    path: web::Path<String>,
+    body: web::Json<templates::TemplateInput>,
+    state: web::Data<HttpServerState>,
+) -> HttpResponse {
+    let request_info = RequestInfo::from_request(&req);
+    if let Some(response) = require_api_key(&req, &request_info, &state).await {
+        return response;
+    }
+    
+    let existing = match find_template(&state, &path.into_inner()).await {
+        Ok(Some(template)) => template,
+        Ok(None) => {
+            request_info.log_access(&state.db, 404, "not_found", Some("Template not found"));
+            return HttpResponse::NotFound().json(ErrorResponse {
+                error: "Template not found".to_string(),
+                field: None,
+            });
+        }
+        Err(e) => return database_error_response(&request_info, &state, e),
+    };
+    
+    let validated = match templates::validate_template(&body) {
+        Ok(validated) => validated,
+        Err(e) => {
+            request_info.log_access(&state.db, 400, "bad_request", Some(&e.message));
+            return HttpResponse::BadRequest().json(ErrorResponse {
+                error: e.message,
+                field: Some(e.field),
+            });
+        }
+    };
+    
+    if let Ok(Some(other)) = find_template(&state, &validated.name).await {
+        if other.id != existing.id {
+            request_info.log_access(&state.db, 400, "bad_request", Some("Template name already exists"));
+            return HttpResponse::BadRequest().json(ErrorResponse {
+                error: format!("A template named '{}' already exists", validated.name),
+                field: Some("name".to_string()),
+            });
+        }
+    }
+    
+    let updated = db_call(&state, move |db| {
+        let fields = crate::database::queries::ReportTemplateFields {
+            name: &validated.name,
+            modality: validated.modality.as_deref(),
+            body_part: validated.body_part.as_deref(),
+            language: &validated.language,
+            content: &validated.content,
+        };
+        db.update_report_template(&existing.id, &fields)?;
+        db.get_report_template(&existing.id)
+    })
+    .await;
+    
+    match updated {
+        Ok(Some(template)) => {
+            log::info!("📝 [HTTP] Modèle de rapport modifié: {} ({})", template.name, template.id);
+            request_info.log_access(&state.db, 200, "success", None);
+            HttpResponse::Ok().json(TemplateResponse {
+                success: true,
+                template: template.into(),
+            })
+        }
+        Ok(None) => database_error_response(&request_info, &state, rusqlite::Error::QueryReturnedNoRows),
+        Err(e) => database_error_response(&request_info, &state, e),
+    }
+}
+
+/// DELETE /templates/{id} - Supprime un modèle
+/// 🔒 Requiert une clé API valide
+pub async fn delete_report_template(
+    req: HttpRequest,
+    path: web::Path<String>,
+    state: web::Data<HttpServerState>,
+) -> HttpResponse {
+    let request_info = RequestInfo::from_request(&req);
+    if let Some(response) = require_api_key(&req, &request_info, &state).await {
+        return response;
+    }
+    
+    let id_or_name = path.into_inner();
+    match db_call(&state, move |db| db.delete_report_template(&id_or_name)).await {
+        Ok(deleted) => {
+            request_info.log_access(&state.db, 200, "success", None);
+            HttpResponse::Ok().json(DeleteResponse {
+                success: true,
+                deleted,
+            })
+        }
+        Err(e) => database_error_response(&request_info, &state, e),
+    }
+}
+
+/// POST /templates/{id}/merge - Aperçu de la fusion modèle + rapport entrant (sans stockage)
+pub async fn merge_report_template(
+    req: HttpRequest,
+    path: web::Path<String>,
+    body: web::Json<MergeTemplateRequest>,
+    state: web::Data<HttpServerState>,
+) -> HttpResponse {
+    let request_info = RequestInfo::from_request(&req);
+    
+    let template = match find_template(&state, &path.into_inner()).await {
+        Ok(Some(template)) => template,
+        Ok(None) => {
+            request_info.log_access(&state.db, 404, "not_found", Some("Template not found"));
+            return HttpResponse::NotFound().json(ErrorResponse {
+                error: "Template not found".to_string(),
+                field: None,
+            });
+        }
+        Err(e) => return database_error_response(&request_info, &state, e),
+    };
+    
+    match templates::merge_template(&template, &body.structured, &body.variables, body.modality.as_deref()) {
+        Ok(merged) => {
+            request_info.log_access(&state.db, 200, "success", None);
+            HttpResponse::Ok().json(MergeTemplateResponse {
+                success: true,
+                merged,
+            })
+        }
+        Err(e) => {
+            request_info.log_access(&state.db, 400, "bad_request", Some(&e.message));
+            HttpResponse::BadRequest().json(ErrorResponse {
+                error: e.message,
+                field: Some(e.field),
+            })
+        }
+    }
+}
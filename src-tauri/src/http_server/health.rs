@@ -0,0 +1,411 @@
+// ============================================================================
+// AIRADCR Desktop - Sondes de vivacité et de disponibilité
+// ============================================================================
+// GET /health/live  : le processus répond (aucune dépendance vérifiée)
+// GET /health/ready : vérifie chaque composant et rend un verdict global
+//   - ready     (200) : tous les composants sont OK
+//   - degraded  (200) : un composant est dégradé, ou un composant non
+//                       critique est hors service
+//   - not_ready (503) : au moins un composant critique est hors service (down)
+// Sans clé admin, seul l'état de chaque composant est publié ; les détails
+// (version SQLCipher, pool, disque, backup, erreurs TÉO) exigent X-Admin-Key.
+// Les composants hors du serveur HTTP (SpeechMike, raccourcis globaux) sont
+// fournis par des sondes enregistrées au démarrage via `register_probe`.
+// ============================================================================
+
+use actix_web::{http::StatusCode, web, HttpRequest, HttpResponse};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::{Mutex, RwLock};
+use std::time::{Duration, Instant};
+use chrono::Utc;
+use once_cell::sync::Lazy;
+
+use super::HttpServerState;
+use super::metrics::Metrics;
+use crate::config::get_config;
+use crate::database::backup::BackupManager;
+use crate::teo_client;
+
+/// Délai maximal d'une vérification TÉO Hub (le client fait lui-même des retries)
+const TEO_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// État d'un composant
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ComponentStatus {
+    Ok,
+    Degraded,
+    Down,
+    Disabled,
+}
+
+/// Résultat de la vérification d'un composant
+#[derive(Debug, Clone, Serialize)]
+pub struct ComponentHealth {
+    pub status: ComponentStatus,
+    /// Un composant critique en échec rend le service "not_ready"
+    pub critical: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    pub checked_at: String,
+    pub duration_ms: u64,
+}
+
+impl ComponentHealth {
+    pub fn new(status: ComponentStatus, message: Option<String>) -> Self {
+        Self {
+            status,
+            critical: false,
+            message,
+            checked_at: Utc::now().to_rfc3339(),
+            duration_ms: 0,
+        }
+    }
+
+    pub fn ok(message: impl Into<String>) -> Self {
+        Self::new(ComponentStatus::Ok, Some(message.into()))
+    }
+
+    pub fn degraded(message: impl Into<String>) -> Self {
+        Self::new(ComponentStatus::Degraded, Some(message.into()))
+    }
+
+    pub fn down(message: impl Into<String>) -> Self {
+        Self::new(ComponentStatus::Down, Some(message.into()))
+    }
+
+    pub fn disabled(message: impl Into<String>) -> Self {
+        Self::new(ComponentStatus::Disabled, Some(message.into()))
+    }
+
+    /// Composant dégradé ou hors service
+    fn is_failing(&self) -> bool {
+        matches!(self.status, ComponentStatus::Degraded | ComponentStatus::Down)
+    }
+
+    /// Seul un composant critique hors service rend le service indisponible
+    fn is_blocking(&self) -> bool {
+        self.critical && self.status == ComponentStatus::Down
+    }
+}
+
+#[derive(Serialize)]
+pub struct LivenessResponse {
+    pub status: String,
+    pub timestamp: String,
+    pub uptime_seconds: u64,
+}
+
+/// État publié sans clé admin (aucun détail sur l'installation)
+#[derive(Debug, Clone, Serialize)]
+pub struct ComponentSummary {
+    pub status: ComponentStatus,
+}
+
+#[derive(Serialize)]
+pub struct ReadinessResponse<C: Serialize> {
+    pub status: String,
+    pub timestamp: String,
+    pub checks: BTreeMap<String, C>,
+}
+
+// ============================================================================
+// Sondes externes (enregistrées par main.rs)
+// ============================================================================
+
+type ProbeFn = Box<dyn Fn() -> ComponentHealth + Send + Sync>;
+
+struct RegisteredProbe {
+    name: &'static str,
+    critical: bool,
+    probe: ProbeFn,
+}
+
+static PROBES: Lazy<RwLock<Vec<RegisteredProbe>>> = Lazy::new(|| RwLock::new(Vec::new()));
+
+/// Enregistre une sonde de composant externe au serveur HTTP
+/// (appelée hors runtime async : la sonde doit être rapide et non bloquante)
+pub fn register_probe<F>(name: &'static str, critical: bool, probe: F)
+where
+    F: Fn() -> ComponentHealth + Send + Sync + 'static,
+{
+    if let Ok(mut probes) = PROBES.write() {
+        probes.retain(|p| p.name != name);
+        probes.push(RegisteredProbe {
+            name,
+            critical,
+            probe: Box::new(probe),
+        });
+    }
+}
+
+// ============================================================================
+// Vérifications intégrées
+// ============================================================================
+
+/// Accès SQLCipher : lecture effective d'une table système avec la clé appliquée
+fn check_database(state: &HttpServerState) -> ComponentHealth {
+    match state.db.check_access() {
+        Ok(Some(cipher_version)) => {
+            let (connections, idle) = state.db.read_pool_state();
+            ComponentHealth::ok(format!(
+                "SQLCipher {} (pool de lecture: {} ouverte(s), {} libre(s))",
+                cipher_version, connections, idle
+            ))
+        }
+        Ok(None) => ComponentHealth::degraded("Base accessible mais non chiffrée (SQLCipher absent)"),
+        Err(e) => ComponentHealth::down(format!("Base inaccessible: {}", e)),
+    }
+}
+
+/// Espace disque disponible dans le répertoire de données
+fn check_disk_space(state: &HttpServerState) -> ComponentHealth {
+    let config = &get_config().health;
+    let data_dir = match state.db.path().parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+        _ => return ComponentHealth::disabled("Base en mémoire"),
+    };
+
+    match fs2::available_space(&data_dir) {
+        Ok(bytes) => {
+            let free_mb = bytes / (1024 * 1024);
+            if free_mb < config.min_free_disk_mb {
+                ComponentHealth::down(format!(
+                    "{} Mo libres (minimum {} Mo)", free_mb, config.min_free_disk_mb
+                ))
+            } else if free_mb < config.min_free_disk_mb.saturating_mul(2) {
+                ComponentHealth::degraded(format!("{} Mo libres", free_mb))
+            } else {
+                ComponentHealth::ok(format!("{} Mo libres", free_mb))
+            }
+        }
+        Err(e) => ComponentHealth::down(format!("Espace disque illisible: {}", e)),
+    }
+}
+
+/// Fraîcheur du backup local le plus récent
+fn check_backup(state: &HttpServerState) -> ComponentHealth {
+    let config = get_config();
+    if !config.backup_enabled {
+        return ComponentHealth::disabled("Backups automatiques désactivés");
+    }
+
+    let backups = BackupManager::new(state.db.path().to_path_buf(), config.backup_retention_days).list_backups();
+    let latest = backups
+        .first()
+        .and_then(|b| b.created_at.as_deref())
+        .and_then(|ts| chrono::DateTime::parse_from_rfc3339(ts).ok());
+
+    match latest {
+        None => ComponentHealth::degraded("Aucun backup local"),
+        Some(created_at) => {
+            let age_hours = (Utc::now() - created_at.with_timezone(&Utc)).num_hours();
+            if age_hours > config.health.backup_max_age_hours as i64 {
+                ComponentHealth::degraded(format!(
+                    "Dernier backup il y a {}h (maximum {}h)", age_hours, config.health.backup_max_age_hours
+                ))
+            } else {
+                ComponentHealth::ok(format!("Dernier backup il y a {}h", age_hours))
+            }
+        }
+    }
+}
+
+/// Dernier résultat TÉO Hub (mis en cache pour ne pas solliciter TÉO à chaque sonde)
+static TEO_CACHE: Lazy<Mutex<Option<(Instant, ComponentHealth)>>> = Lazy::new(|| Mutex::new(None));
+
+/// Joignabilité TÉO Hub via check_health (résultat en cache)
+async fn check_teo_hub() -> ComponentHealth {
+    let config = get_config();
+    if !config.teo_hub.enabled {
+        return ComponentHealth::disabled("Client TÉO Hub désactivé");
+    }
+
+    let ttl = Duration::from_secs(config.health.teo_cache_secs);
+    if let Ok(cache) = TEO_CACHE.lock() {
+        if let Some((checked, health)) = cache.as_ref() {
+            if checked.elapsed() < ttl {
+                return health.clone();
+            }
+        }
+    }
+
+    let start = Instant::now();
+    let mut health = match tokio::time::timeout(TEO_CHECK_TIMEOUT, teo_client::check_health()).await {
+        Ok(Ok(response)) if response.ok => ComponentHealth::ok(format!("{} joignable", response.service)),
+        Ok(Ok(response)) => ComponentHealth::degraded(format!("{} signale un état dégradé", response.service)),
+        Ok(Err(e)) => ComponentHealth::down(e.to_string()),
+        Err(_) => ComponentHealth::down(format!("Pas de réponse en {}s", TEO_CHECK_TIMEOUT.as_secs())),
+    };
+    health.duration_ms = start.elapsed().as_millis() as u64;
+
+    if let Ok(mut cache) = TEO_CACHE.lock() {
+        *cache = Some((Instant::now(), health.clone()));
+    }
+    health
+}
+
+/// Exécute une vérification synchrone en mesurant sa durée
+fn timed<F: FnOnce() -> ComponentHealth>(critical: bool, check: F) -> ComponentHealth {
+    let start = Instant::now();
+    let mut health = check();
+    health.critical = critical;
+    health.duration_ms = start.elapsed().as_millis() as u64;
+    health
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// GET /health/live - Le processus répond (sans vérification des dépendances)
+pub async fn liveness_handler() -> HttpResponse {
+    HttpResponse::Ok().json(LivenessResponse {
+        status: "alive".to_string(),
+        timestamp: Utc::now().to_rfc3339(),
+        uptime_seconds: Metrics::get().uptime_seconds(),
+    })
+}
+
+/// Verdict global des vérifications
+fn readiness_verdict(checks: &BTreeMap<String, ComponentHealth>) -> &'static str {
+    if checks.values().any(ComponentHealth::is_blocking) {
+        "not_ready"
+    } else if checks.values().any(ComponentHealth::is_failing) {
+        "degraded"
+    } else {
+        "ready"
+    }
+}
+
+/// Code HTTP d'un verdict : 503 uniquement si le service n'est pas prêt
+fn verdict_http_status(verdict: &str) -> StatusCode {
+    if verdict == "not_ready" {
+        StatusCode::SERVICE_UNAVAILABLE
+    } else {
+        StatusCode::OK
+    }
+}
+
+/// GET /health/ready - Vérifie les dépendances et rend un verdict global
+/// (détails des composants avec clé admin uniquement)
+pub async fn readiness_handler(req: HttpRequest, state: web::Data<HttpServerState>) -> HttpResponse {
+    let health_config = get_config().health.clone();
+
+    // Vérifications bloquantes (SQLite, système de fichiers, sondes) hors du worker actix
+    let state_for_checks = state.clone();
+    let local_checks = web::block(move || {
+        let mut checks = BTreeMap::new();
+        checks.insert("database".to_string(), timed(true, || check_database(&state_for_checks)));
+        checks.insert("disk_space".to_string(), timed(true, || check_disk_space(&state_for_checks)));
+        checks.insert("backup".to_string(), timed(false, || check_backup(&state_for_checks)));
+
+        if let Ok(probes) = PROBES.read() {
+            for probe in probes.iter() {
+                checks.insert(probe.name.to_string(), timed(probe.critical, || (probe.probe)()));
+            }
+        }
+        checks
+    })
+    .await;
+
+    let mut checks = match local_checks {
+        Ok(checks) => checks,
+        Err(e) => {
+            let mut checks = BTreeMap::new();
+            let mut health = ComponentHealth::down(format!("Vérifications interrompues: {}", e));
+            health.critical = true;
+            checks.insert("server".to_string(), health);
+            checks
+        }
+    };
+
+    let mut teo = check_teo_hub().await;
+    teo.critical = health_config.require_teo_hub;
+    checks.insert("teo_hub".to_string(), teo);
+
+    let status = readiness_verdict(&checks);
+    if status == "not_ready" {
+        log::warn!("⚠️ [Health] Service non prêt: {:?}",
+            checks.iter()
+                .filter(|(_, c)| c.is_blocking())
+                .map(|(name, _)| name.as_str())
+                .collect::<Vec<_>>());
+    }
+
+    let admin_key = req
+        .headers()
+        .get("x-admin-key")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("");
+
+    let mut response = HttpResponse::build(verdict_http_status(status));
+    if !admin_key.is_empty() && super::middleware::validate_admin_key(admin_key) {
+        response.json(ReadinessResponse {
+            status: status.to_string(),
+            timestamp: Utc::now().to_rfc3339(),
+            checks,
+        })
+    } else {
+        response.json(ReadinessResponse {
+            status: status.to_string(),
+            timestamp: Utc::now().to_rfc3339(),
+            checks: public_checks(checks),
+        })
+    }
+}
+
+/// Vue publique des vérifications : état de chaque composant, sans message
+fn public_checks(checks: BTreeMap<String, ComponentHealth>) -> BTreeMap<String, ComponentSummary> {
+    checks
+        .into_iter()
+        .map(|(name, health)| (name, ComponentSummary { status: health.status }))
+        .collect()
+}
+
+// ============================================================================
+// Tests unitaires
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checks(components: &[(&str, ComponentStatus, bool)]) -> BTreeMap<String, ComponentHealth> {
+        components
+            .iter()
+            .map(|(name, status, critical)| {
+                let mut health = ComponentHealth::new(*status, Some(format!("détail {}", name)));
+                health.critical = *critical;
+                (name.to_string(), health)
+            })
+            .collect()
+    }
+
+    fn http_status(components: &[(&str, ComponentStatus, bool)]) -> (&'static str, u16) {
+        let verdict = readiness_verdict(&checks(components));
+        (verdict, verdict_http_status(verdict).as_u16())
+    }
+
+    #[test]
+    fn test_readiness_status_to_http_code() {
+        use ComponentStatus::*;
+
+        assert_eq!(http_status(&[("database", Ok, true), ("backup", Disabled, false)]), ("ready", 200));
+        // Critique dégradé : le service reste disponible
+        assert_eq!(http_status(&[("database", Degraded, true), ("disk_space", Ok, true)]), ("degraded", 200));
+        // Non critique hors service : dégradé
+        assert_eq!(http_status(&[("database", Ok, true), ("teo_hub", Down, false)]), ("degraded", 200));
+        // Critique hors service : indisponible
+        assert_eq!(http_status(&[("database", Ok, true), ("disk_space", Down, true)]), ("not_ready", 503));
+        assert_eq!(http_status(&[("disk_space", Degraded, true), ("database", Down, true)]), ("not_ready", 503));
+    }
+
+    #[test]
+    fn test_public_checks_hide_details() {
+        let public = public_checks(checks(&[("database", ComponentStatus::Ok, true)]));
+        let json = serde_json::to_value(&public).unwrap();
+        assert_eq!(json, serde_json::json!({ "database": { "status": "ok" } }));
+    }
+}
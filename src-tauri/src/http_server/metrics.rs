@@ -3,11 +3,201 @@
 // ============================================================================
 
 use actix_web::{HttpResponse, web};
-use std::sync::atomic::{AtomicU64, Ordering};
-use std::sync::OnceLock;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use super::HttpServerState;
+use crate::config::get_config;
+
+// ============================================================================
+// Familles de métriques labellisées
+// ============================================================================
+
+/// Échappe une valeur de label Prometheus (\\, \" et retour ligne)
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Formate un ensemble de labels : {a="x",b="y"}
+fn format_labels(names: &[&str], values: &[String], extra: Option<(&str, &str)>) -> String {
+    let mut parts: Vec<String> = names
+        .iter()
+        .zip(values.iter())
+        .map(|(name, value)| format!("{}=\"{}\"", name, escape_label_value(value)))
+        .collect();
+    if let Some((name, value)) = extra {
+        parts.push(format!("{}=\"{}\"", name, value));
+    }
+    if parts.is_empty() {
+        String::new()
+    } else {
+        format!("{{{}}}", parts.join(","))
+    }
+}
+
+/// Histogramme à buckets fixes (bornes supérieures en secondes)
+#[derive(Debug, Clone)]
+pub struct Histogram {
+    bounds: Vec<f64>,
+    counts: Vec<u64>,
+    sum: f64,
+    count: u64,
+}
+
+impl Histogram {
+    pub fn new(bounds: &[f64]) -> Self {
+        Self {
+            bounds: bounds.to_vec(),
+            counts: vec![0; bounds.len()],
+            sum: 0.0,
+            count: 0,
+        }
+    }
+    
+    /// Enregistre une observation
+    pub fn observe(&mut self, value: f64) {
+        // Buckets non cumulés en interne, cumulés au rendu
+        if let Some(index) = self.bounds.iter().position(|bound| value <= *bound) {
+            self.counts[index] += 1;
+        }
+        self.sum += value;
+        self.count += 1;
+    }
+    
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+    
+    fn render(&self, name: &str, label_names: &[&str], label_values: &[String], output: &mut String) {
+        let mut cumulative = 0u64;
+        for (bound, count) in self.bounds.iter().zip(self.counts.iter()) {
+            cumulative += count;
+            let le = format!("{}", bound);
+            output.push_str(&format!("{}_bucket{} {}\n",
+                name, format_labels(label_names, label_values, Some(("le", &le))), cumulative));
+        }
+        output.push_str(&format!("{}_bucket{} {}\n",
+            name, format_labels(label_names, label_values, Some(("le", "+Inf"))), self.count));
+        output.push_str(&format!("{}_sum{} {}\n",
+            name, format_labels(label_names, label_values, None), self.sum));
+        output.push_str(&format!("{}_count{} {}\n",
+            name, format_labels(label_names, label_values, None), self.count));
+    }
+}
+
+/// Famille d'histogrammes indexée par valeurs de labels
+pub struct HistogramFamily {
+    label_names: &'static [&'static str],
+    bounds: Vec<f64>,
+    series: Mutex<BTreeMap<Vec<String>, Histogram>>,
+}
+
+impl HistogramFamily {
+    fn new(label_names: &'static [&'static str], bounds: Vec<f64>) -> Self {
+        Self {
+            label_names,
+            bounds,
+            series: Mutex::new(BTreeMap::new()),
+        }
+    }
+    
+    /// Enregistre une durée pour la série correspondant aux labels
+    pub fn observe(&self, label_values: &[&str], duration: Duration) {
+        let key: Vec<String> = label_values.iter().map(|v| v.to_string()).collect();
+        if let Ok(mut series) = self.series.lock() {
+            series
+                .entry(key)
+                .or_insert_with(|| Histogram::new(&self.bounds))
+                .observe(duration.as_secs_f64());
+        }
+    }
+    
+    /// Rend l'histogramme et, optionnellement, un compteur dérivé (_total)
+    fn render(&self, name: &str, help: &str, counter: Option<(&str, &str)>, output: &mut String) {
+        let series = match self.series.lock() {
+            Ok(series) => series.clone(),
+            Err(_) => return,
+        };
+        
+        if let Some((counter_name, counter_help)) = counter {
+            output.push_str(&format!("# HELP {} {}\n", counter_name, counter_help));
+            output.push_str(&format!("# TYPE {} counter\n", counter_name));
+            for (values, histogram) in series.iter() {
+                output.push_str(&format!("{}{} {}\n",
+                    counter_name, format_labels(self.label_names, values, None), histogram.count()));
+            }
+        }
+        
+        output.push_str(&format!("# HELP {} {}\n", name, help));
+        output.push_str(&format!("# TYPE {} histogram\n", name));
+        for (values, histogram) in series.iter() {
+            histogram.render(name, self.label_names, values, output);
+        }
+    }
+}
+
+/// Famille de compteurs indexée par valeurs de labels
+pub struct CounterFamily {
+    label_names: &'static [&'static str],
+    series: Mutex<BTreeMap<Vec<String>, u64>>,
+}
+
+impl CounterFamily {
+    fn new(label_names: &'static [&'static str]) -> Self {
+        Self {
+            label_names,
+            series: Mutex::new(BTreeMap::new()),
+        }
+    }
+    
+    pub fn inc(&self, label_values: &[&str]) {
+        let key: Vec<String> = label_values.iter().map(|v| v.to_string()).collect();
+        if let Ok(mut series) = self.series.lock() {
+            *series.entry(key).or_insert(0) += 1;
+        }
+    }
+    
+    fn render(&self, name: &str, help: &str, output: &mut String) {
+        let series = match self.series.lock() {
+            Ok(series) => series.clone(),
+            Err(_) => return,
+        };
+        output.push_str(&format!("# HELP {} {}\n", name, help));
+        output.push_str(&format!("# TYPE {} counter\n", name));
+        for (values, count) in series.iter() {
+            output.push_str(&format!("{}{} {}\n",
+                name, format_labels(self.label_names, values, None), count));
+        }
+    }
+}
+
+/// Normalise une liste de buckets (positifs, triés, sans doublons)
+fn normalize_buckets(buckets: &[f64]) -> Vec<f64> {
+    let mut bounds: Vec<f64> = buckets.iter().copied().filter(|b| b.is_finite() && *b > 0.0).collect();
+    bounds.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    bounds.dedup();
+    bounds
+}
+
+/// Résultat d'une requête HTTP déduit du code de statut (aligné sur access_logs.result)
+pub fn result_for_status(status: u16) -> &'static str {
+    match status {
+        200..=399 => "success",
+        401 | 403 => "unauthorized",
+        404 => "not_found",
+        400..=499 => "bad_request",
+        _ => "error",
+    }
+}
+
+// ============================================================================
+// Registre global
+// ============================================================================
 
 /// Compteurs globaux pour métriques
 pub struct Metrics {
@@ -22,6 +212,21 @@ pub struct Metrics {
     
     /// Durée totale des requêtes (pour calcul moyenne)
     pub total_duration_ms: AtomicU64,
+    
+    /// Latence HTTP par route, méthode et résultat
+    pub http_requests: HistogramFamily,
+    
+    /// Appels TÉO Hub par opération et issue
+    pub teo_calls: HistogramFamily,
+    
+    /// Injections de texte par mode et issue
+    pub injections: HistogramFamily,
+    
+    /// Appuis sur les boutons SpeechMike
+    pub speechmike_buttons: CounterFamily,
+    
+    /// SpeechMike actuellement connecté
+    pub speechmike_connected: AtomicBool,
 }
 
 static METRICS: OnceLock<Metrics> = OnceLock::new();
@@ -34,6 +239,7 @@ impl Metrics {
                 .duration_since(UNIX_EPOCH)
                 .unwrap_or_default()
                 .as_secs();
+            let config = &get_config().metrics;
             
             Metrics {
                 requests_total: AtomicU64::new(0),
@@ -42,6 +248,20 @@ impl Metrics {
                 requests_unauthorized: AtomicU64::new(0),
                 start_time: now,
                 total_duration_ms: AtomicU64::new(0),
+                http_requests: HistogramFamily::new(
+                    &["route", "method", "result"],
+                    normalize_buckets(&config.http_latency_buckets_secs),
+                ),
+                teo_calls: HistogramFamily::new(
+                    &["operation", "outcome"],
+                    normalize_buckets(&config.teo_latency_buckets_secs),
+                ),
+                injections: HistogramFamily::new(
+                    &["mode", "outcome"],
+                    normalize_buckets(&config.injection_latency_buckets_secs),
+                ),
+                speechmike_buttons: CounterFamily::new(&["button"]),
+                speechmike_connected: AtomicBool::new(false),
             }
         })
     }
@@ -64,6 +284,34 @@ impl Metrics {
         };
     }
     
+    /// Enregistre une requête HTTP (appelé par le middleware du serveur)
+    pub fn record_http_request(route: &str, method: &str, status: u16, duration: Duration) {
+        let result = result_for_status(status);
+        Self::record_request(result, duration.as_millis() as u64);
+        Self::get().http_requests.observe(&[route, method, result], duration);
+    }
+    
+    /// Enregistre un appel TÉO Hub
+    pub fn record_teo_call(operation: &str, outcome: &str, duration: Duration) {
+        Self::get().teo_calls.observe(&[operation, outcome], duration);
+    }
+    
+    /// Enregistre une injection de texte
+    pub fn record_injection(mode: &str, success: bool, duration: Duration) {
+        let outcome = if success { "success" } else { "error" };
+        Self::get().injections.observe(&[mode, outcome], duration);
+    }
+    
+    /// Enregistre un appui sur un bouton SpeechMike
+    pub fn record_speechmike_button(button: &str) {
+        Self::get().speechmike_buttons.inc(&[button]);
+    }
+    
+    /// Met à jour l'état de connexion SpeechMike
+    pub fn set_speechmike_connected(connected: bool) {
+        Self::get().speechmike_connected.store(connected, Ordering::Relaxed);
+    }
+    
     /// Calcule l'uptime en secondes
     pub fn uptime_seconds(&self) -> u64 {
         let now = SystemTime::now()
@@ -96,19 +344,28 @@ pub async fn metrics_handler(
         .unwrap_or("");
     
     if !super::middleware::validate_admin_key(admin_key) {
+        super::middleware::RequestInfo::from_request(&req)
+            .log_security_event("invalid_admin_key", "Admin key required for metrics endpoint");
         return HttpResponse::Unauthorized().json(serde_json::json!({
             "error": "Admin key required for metrics endpoint"
         }));
     }
     let m = Metrics::get();
     
-    // Récupérer les stats depuis la base de données
-    let (pending_count, api_keys_count, db_size) = {
-        let pending = state.db.count_pending_reports().unwrap_or(0);
-        let keys = state.db.count_active_api_keys().unwrap_or(0);
-        let size = state.db.get_database_size().unwrap_or(0);
-        (pending, keys, size)
-    };
+    // Récupérer les stats depuis la base de données et les sauvegardes (hors du worker actix)
+    let db = std::sync::Arc::clone(&state.db);
+    let ((pending_count, api_keys_count, db_size), backups) = web::block(move || {
+        let pending = db.count_pending_reports().unwrap_or(0);
+        let keys = db.count_active_api_keys().unwrap_or(0);
+        let size = db.get_database_size().unwrap_or(0);
+        let backups = crate::database::backup::BackupManager::new(
+            db.path().to_path_buf(),
+            get_config().backup_retention_days,
+        ).list_backups();
+        ((pending, keys, size), backups)
+    })
+    .await
+    .unwrap_or_default();
     
     // Générer le format Prometheus
     let mut output = String::new();
@@ -157,6 +414,102 @@ pub async fn metrics_handler(
     output.push_str("# TYPE airadcr_db_size_bytes gauge\n");
     output.push_str(&format!("airadcr_db_size_bytes {}\n", db_size));
     
+    // Métriques HTTP par route
+    m.http_requests.render(
+        "airadcr_http_request_duration_seconds",
+        "HTTP request latency by route, method and result",
+        Some(("airadcr_http_requests_total", "HTTP requests by route, method and result")),
+        &mut output,
+    );
+    
+    // Métriques TÉO Hub
+    m.teo_calls.render(
+        "airadcr_teo_request_duration_seconds",
+        "TEO Hub call latency by operation and outcome",
+        Some(("airadcr_teo_requests_total", "TEO Hub calls by operation and outcome")),
+        &mut output,
+    );
+    
+    // Métriques SpeechMike
+    output.push_str("# HELP airadcr_speechmike_connected Whether a SpeechMike device is connected\n");
+    output.push_str("# TYPE airadcr_speechmike_connected gauge\n");
+    output.push_str(&format!("airadcr_speechmike_connected {}\n",
+        if m.speechmike_connected.load(Ordering::Relaxed) { 1 } else { 0 }));
+    
+    m.speechmike_buttons.render(
+        "airadcr_speechmike_button_presses_total",
+        "SpeechMike button presses by button",
+        &mut output,
+    );
+    
+    // Métriques injection
+    m.injections.render(
+        "airadcr_injection_duration_seconds",
+        "Text injection latency by mode and outcome",
+        Some(("airadcr_injections_total", "Text injections by mode and outcome")),
+        &mut output,
+    );
+    
+    // Métriques backup
+    output.push_str("# HELP airadcr_backup_count Number of local backup files\n");
+    output.push_str("# TYPE airadcr_backup_count gauge\n");
+    output.push_str(&format!("airadcr_backup_count {}\n", backups.len()));
+    
+    let latest_backup_age = backups
+        .first()
+        .and_then(|b| b.created_at.as_deref())
+        .and_then(|ts| chrono::DateTime::parse_from_rfc3339(ts).ok())
+        .map(|ts| (chrono::Utc::now() - ts.with_timezone(&chrono::Utc)).num_seconds().max(0));
+    
+    if let Some(age) = latest_backup_age {
+        output.push_str("# HELP airadcr_backup_age_seconds Age of the most recent local backup\n");
+        output.push_str("# TYPE airadcr_backup_age_seconds gauge\n");
+        output.push_str(&format!("airadcr_backup_age_seconds {}\n", age));
+    }
+    
+    // Destinations distantes des backups
+    let targets = crate::backup_targets::statuses();
+    if !targets.is_empty() {
+        output.push_str("# HELP airadcr_backup_target_up Whether the last upload to the backup target succeeded\n");
+        output.push_str("# TYPE airadcr_backup_target_up gauge\n");
+        for target in &targets {
+            output.push_str(&format!("airadcr_backup_target_up{{target=\"{}\"}} {}\n",
+                target.name, if target.last_error.is_none() { 1 } else { 0 }));
+        }
+        
+        output.push_str("# HELP airadcr_backup_target_last_success_timestamp_seconds Unix time of the last verified upload\n");
+        output.push_str("# TYPE airadcr_backup_target_last_success_timestamp_seconds gauge\n");
+        for target in &targets {
+            if let Some(ts) = target.last_success_at.as_deref()
+                .and_then(|ts| chrono::DateTime::parse_from_rfc3339(ts).ok())
+            {
+                output.push_str(&format!("airadcr_backup_target_last_success_timestamp_seconds{{target=\"{}\"}} {}\n",
+                    target.name, ts.timestamp()));
+            }
+        }
+        
+        output.push_str("# HELP airadcr_backup_target_uploads_total Backup uploads by target and result\n");
+        output.push_str("# TYPE airadcr_backup_target_uploads_total counter\n");
+        for target in &targets {
+            output.push_str(&format!("airadcr_backup_target_uploads_total{{target=\"{}\",result=\"success\"}} {}\n",
+                target.name, target.uploads_ok));
+            output.push_str(&format!("airadcr_backup_target_uploads_total{{target=\"{}\",result=\"failure\"}} {}\n",
+                target.name, target.uploads_failed));
+        }
+        
+        output.push_str("# HELP airadcr_backup_target_pruned_total Files deleted by target retention\n");
+        output.push_str("# TYPE airadcr_backup_target_pruned_total counter\n");
+        for target in &targets {
+            output.push_str(&format!("airadcr_backup_target_pruned_total{{target=\"{}\"}} {}\n",
+                target.name, target.pruned_total));
+        }
+    }
+    
+    // Audit externe
+    output.push_str("# HELP airadcr_audit_events_dropped_total Audit events dropped because the sink queue was full\n");
+    output.push_str("# TYPE airadcr_audit_events_dropped_total counter\n");
+    output.push_str(&format!("airadcr_audit_events_dropped_total {}\n", crate::audit::dropped_events()));
+    
     // Version
     output.push_str("# HELP airadcr_info Application info\n");
     output.push_str("# TYPE airadcr_info gauge\n");
@@ -175,6 +528,7 @@ pub struct ExtendedHealthResponse {
     pub uptime_seconds: u64,
     pub database: DatabaseHealth,
     pub requests: RequestsHealth,
+    pub backups: BackupHealth,
 }
 
 #[derive(serde::Serialize)]
@@ -185,6 +539,22 @@ pub struct DatabaseHealth {
     pub size_bytes: u64,
 }
 
+#[derive(serde::Serialize)]
+pub struct BackupHealth {
+    pub enabled: bool,
+    pub local_count: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latest_file: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latest_at: Option<String>,
+    /// Version du schéma contenue dans le dernier backup (manifeste)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latest_schema_version: Option<u32>,
+    pub export_enabled: bool,
+    /// État des envois vers chaque destination distante
+    pub targets: Vec<crate::backup_targets::TargetStatus>,
+}
+
 #[derive(serde::Serialize)]
 pub struct RequestsHealth {
     pub total: u64,
@@ -206,24 +576,43 @@ pub async fn extended_health_handler(
         .unwrap_or("");
     
     if !super::middleware::validate_admin_key(admin_key) {
+        super::middleware::RequestInfo::from_request(&req)
+            .log_security_event("invalid_admin_key", "Admin key required for extended health endpoint");
         return HttpResponse::Unauthorized().json(serde_json::json!({
             "error": "Admin key required for extended health endpoint"
         }));
     }
     let m = Metrics::get();
     
-    // Vérifier la connexion à la base de données
-    let (db_status, pending, keys, size) = match state.db.count_pending_reports() {
-        Ok(pending) => {
-            let keys = state.db.count_active_api_keys().unwrap_or(0);
-            let size = state.db.get_database_size().unwrap_or(0);
-            ("ok".to_string(), pending, keys, size)
-        }
-        Err(e) => (format!("error: {}", e), 0, 0, 0),
+    // Vérifier la connexion à la base de données et lister les sauvegardes (hors du worker actix)
+    let config = get_config();
+    let db = std::sync::Arc::clone(&state.db);
+    let checks = web::block(move || {
+        let stats = match db.count_pending_reports() {
+            Ok(pending) => {
+                let keys = db.count_active_api_keys().unwrap_or(0);
+                let size = db.get_database_size().unwrap_or(0);
+                ("ok".to_string(), pending, keys, size)
+            }
+            Err(e) => (format!("error: {}", e), 0, 0, 0),
+        };
+        let backups = crate::database::backup::BackupManager::new(
+            db.path().to_path_buf(),
+            get_config().backup_retention_days,
+        ).list_backups();
+        (stats, backups)
+    })
+    .await;
+    let ((db_status, pending, keys, size), backups) = match checks {
+        Ok(checks) => checks,
+        Err(e) => ((format!("error: {}", e), 0, 0, 0), Vec::new()),
     };
+    let latest = backups.first();
+    let targets = crate::backup_targets::statuses();
+    let targets_ok = targets.iter().all(|t| t.last_error.is_none());
     
     let response = ExtendedHealthResponse {
-        status: if db_status == "ok" { "healthy".to_string() } else { "degraded".to_string() },
+        status: if db_status == "ok" && targets_ok { "healthy".to_string() } else { "degraded".to_string() },
         version: env!("CARGO_PKG_VERSION").to_string(),
         uptime_seconds: m.uptime_seconds(),
         database: DatabaseHealth {
@@ -238,7 +627,57 @@ pub async fn extended_health_handler(
             errors: m.requests_error.load(Ordering::Relaxed),
             avg_duration_ms: m.avg_duration_ms(),
         },
+        backups: BackupHealth {
+            enabled: config.backup_enabled,
+            local_count: backups.len(),
+            latest_file: latest.map(|b| b.filename.clone()),
+            latest_at: latest.and_then(|b| b.created_at.clone()),
+            latest_schema_version: latest.and_then(|b| b.manifest.as_ref()).map(|m| m.schema_version),
+            export_enabled: config.backup_export.enabled,
+            targets,
+        },
     };
     
     HttpResponse::Ok().json(response)
 }
+
+// ============================================================================
+// Tests unitaires
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_histogram_buckets_are_cumulative() {
+        let family = HistogramFamily::new(&["route"], vec![0.01, 0.1, 1.0]);
+        family.observe(&["/health"], Duration::from_millis(5));
+        family.observe(&["/health"], Duration::from_millis(50));
+        family.observe(&["/health"], Duration::from_secs(3));
+        
+        let mut output = String::new();
+        family.render("test_duration_seconds", "test", Some(("test_total", "test")), &mut output);
+        
+        assert!(output.contains("test_total{route=\"/health\"} 3\n"));
+        assert!(output.contains("test_duration_seconds_bucket{route=\"/health\",le=\"0.01\"} 1\n"));
+        assert!(output.contains("test_duration_seconds_bucket{route=\"/health\",le=\"0.1\"} 2\n"));
+        assert!(output.contains("test_duration_seconds_bucket{route=\"/health\",le=\"1\"} 2\n"));
+        assert!(output.contains("test_duration_seconds_bucket{route=\"/health\",le=\"+Inf\"} 3\n"));
+        assert!(output.contains("test_duration_seconds_count{route=\"/health\"} 3\n"));
+    }
+    
+    #[test]
+    fn test_result_for_status() {
+        assert_eq!(result_for_status(200), "success");
+        assert_eq!(result_for_status(401), "unauthorized");
+        assert_eq!(result_for_status(404), "not_found");
+        assert_eq!(result_for_status(409), "bad_request");
+        assert_eq!(result_for_status(502), "error");
+    }
+    
+    #[test]
+    fn test_normalize_buckets() {
+        assert_eq!(normalize_buckets(&[1.0, 0.5, -1.0, 0.5, f64::NAN]), vec![0.5, 1.0]);
+    }
+}
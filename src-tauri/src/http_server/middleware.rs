@@ -6,89 +6,28 @@ use serde_json::Value;
 use sha2::{Sha256, Digest};
 use std::sync::Arc;
 use crate::database::Database;
-
-// ============================================================================
-// Patterns interdits pour la validation Patient-Safe
-// ============================================================================
-
-/// Liste des champs/patterns interdits (données nominatives)
-const FORBIDDEN_PATTERNS: &[&str] = &[
-    // Identifiants patient
-    "patient_id", "patientid", "patient_name", "patientname",
-    "patient_identifier", "patientidentifier",
-    
-    // Données démographiques
-    "birth_date", "birthdate", "date_of_birth", "dob", "date_naissance",
-    "first_name", "last_name", "firstname", "lastname",
-    "nom", "prenom", "nom_patient", "prenom_patient",
-    "full_name", "fullname", "nom_complet",
-    
-    // Identifiants administratifs
-    "ssn", "social_security", "social_security_number",
-    "insurance_number", "numero_securite_sociale", "nss",
-    "ipp", "nis", "nh", "numero_hopital",
-    
-    // Coordonnées
-    "address", "adresse", "street", "rue", "city", "ville",
-    "postal_code", "code_postal", "zip", "zipcode",
-    "phone", "telephone", "mobile", "email", "mail",
-    
-    // Identifiants DICOM/études
-    "study_uid", "studyuid", "study_instance_uid",
-    "series_uid", "seriesuid", "sop_instance_uid",
-    "accession_number", "accessionnumber", "accession",
-    
-    // Autres identifiants sensibles
-    "mrn", "medical_record_number", "numero_dossier",
-    "encounter_id", "visit_id", "sejour_id",
-];
+use crate::database::queries::ApiKeyIdentity;
 
 // ============================================================================
 // Validation Patient-Safe
 // ============================================================================
+// Règles configurables : voir pii::policy (patient_safe.toml)
+
+use crate::pii::policy::{self, Destination};
 
 /// Valide qu'un payload JSON ne contient pas de données nominatives
+/// (politique de la destination cloud : noms de clés et valeurs)
 /// Retourne Ok(()) si valide, Err((field, message)) si violation
 pub fn validate_patient_safe(json: &Value) -> Result<(), (String, String)> {
-    validate_json_recursive(json, "")
+    validate_patient_safe_for(json, Destination::Cloud)
 }
 
-fn validate_json_recursive(json: &Value, path: &str) -> Result<(), (String, String)> {
-    match json {
-        Value::Object(map) => {
-            for (key, value) in map {
-                let key_lower = key.to_lowercase();
-                let current_path = if path.is_empty() {
-                    key.clone()
-                } else {
-                    format!("{}.{}", path, key)
-                };
-                
-                // Vérifier si la clé correspond à un pattern interdit
-                for pattern in FORBIDDEN_PATTERNS {
-                    if key_lower.contains(pattern) {
-                        return Err((
-                            current_path,
-                            format!("nominative data detected (key contains '{}')", pattern),
-                        ));
-                    }
-                }
-                
-                // Récursion sur les valeurs
-                validate_json_recursive(value, &current_path)?;
-            }
-        }
-        Value::Array(arr) => {
-            for (i, item) in arr.iter().enumerate() {
-                let current_path = format!("{}[{}]", path, i);
-                validate_json_recursive(item, &current_path)?;
-            }
-        }
-        // Les valeurs primitives sont acceptées
-        _ => {}
+/// Valide un payload selon la politique d'une destination
+pub fn validate_patient_safe_for(json: &Value, destination: Destination) -> Result<(), (String, String)> {
+    match policy::evaluate(destination, json).first_violation() {
+        Some(violation) => Err(violation),
+        None => Ok(()),
     }
-    
-    Ok(())
 }
 
 // ============================================================================
@@ -96,9 +35,14 @@ fn validate_json_recursive(json: &Value, path: &str) -> Result<(), (String, Stri
 // ============================================================================
 
 /// Valide une clé API contre la base de données
-pub fn validate_api_key(db: &Arc<Database>, api_key: &str) -> bool {
+pub fn validate_api_key(db: &Database, api_key: &str) -> bool {
+    authenticate_api_key(db, api_key).is_some()
+}
+
+/// Identité (id, nom) de la clé API présentée, None si absente, inconnue ou révoquée
+pub fn authenticate_api_key(db: &Database, api_key: &str) -> Option<ApiKeyIdentity> {
     if api_key.is_empty() {
-        return false;
+        return None;
     }
     
     // Calcul du hash SHA-256
@@ -114,11 +58,11 @@ pub fn validate_api_key(db: &Arc<Database>, api_key: &str) -> bool {
     };
     
     // Vérification en base
-    match db.validate_api_key(key_prefix, &key_hash) {
-        Ok(valid) => valid,
+    match db.find_api_key(key_prefix, &key_hash) {
+        Ok(identity) => identity,
         Err(e) => {
             log::error!("❌ [Middleware] Erreur validation API key: {}", e);
-            false
+            None
         }
     }
 }
@@ -215,9 +159,10 @@ fn constant_time_compare(a: &[u8; 32], b: &[u8; 32]) -> bool {
 // ============================================================================
 
 use chrono::Utc;
-use uuid::Uuid;
+use actix_web::HttpMessage;
 
 /// Structure pour capturer les informations d'une requête avant traitement
+#[derive(Clone)]
 pub struct RequestInfo {
     pub request_id: String,
     pub start_time: std::time::Instant,
@@ -258,7 +203,12 @@ impl RequestInfo {
             .map(|s| s.chars().take(200).collect::<String>());
         
         Self {
-            request_id: Uuid::new_v4().to_string()[..8].to_string(),
+            // Identifiant posé par le middleware de corrélation (X-Request-Id / traceparent)
+            request_id: req
+                .extensions()
+                .get::<crate::telemetry::RequestContext>()
+                .map(|context| context.request_id.clone())
+                .unwrap_or_else(crate::telemetry::generate_request_id),
             start_time: std::time::Instant::now(),
             ip_address,
             method: req.method().to_string(),
@@ -279,28 +229,88 @@ impl RequestInfo {
         let duration_ms = self.start_time.elapsed().as_millis() as i64;
         let timestamp = Utc::now().to_rfc3339();
         
-        match db.insert_access_log(
-            &timestamp,
-            &self.ip_address,
-            &self.method,
-            &self.endpoint,
-            status_code as i32,
-            result,
-            self.api_key_prefix.as_deref(),
-            self.user_agent.as_deref(),
-            &self.request_id,
-            duration_ms,
-            error_message,
-        ) {
-            Ok(id) => {
-                log::info!("📝 [Access Log] #{} {} {} {} → {} ({}ms)", 
-                    id, self.method, self.endpoint, self.ip_address, result, duration_ms);
+        let db = Arc::clone(db);
+        let info = self.clone();
+        let result = result.to_string();
+        let error_message = error_message.map(|m| m.to_string());
+        let write = move || {
+            // Radiologue actif sur le poste au moment de la requête
+            let user_id = db.get_active_user_id().ok().flatten();
+            
+            match db.insert_access_log(
+                &timestamp,
+                &info.ip_address,
+                &info.method,
+                &info.endpoint,
+                status_code as i32,
+                &result,
+                info.api_key_prefix.as_deref(),
+                info.user_agent.as_deref(),
+                &info.request_id,
+                duration_ms,
+                error_message.as_deref(),
+                user_id.as_deref(),
+            ) {
+                Ok(id) => {
+                    log::info!("📝 [Access Log] #{} {} {} {} → {} ({}ms)", 
+                        id, info.method, info.endpoint, info.ip_address, result, duration_ms);
+                }
+                Err(e) => {
+                    log::error!("❌ [Access Log] Erreur insertion: {}", e);
+                }
             }
-            Err(e) => {
-                log::error!("❌ [Access Log] Erreur insertion: {}", e);
+            
+            // 📡 Transmission aux sinks d'audit externes (non bloquant)
+            crate::audit::emit(crate::audit::AuditEvent {
+                timestamp,
+                kind: crate::audit::AuditEventKind::Access,
+                severity: crate::audit::AuditSeverity::from_result(&result),
+                event_type: "http_access".to_string(),
+                request_id: info.request_id,
+                ip_address: info.ip_address,
+                method: info.method,
+                endpoint: info.endpoint,
+                status_code: Some(status_code),
+                result: Some(result),
+                api_key_prefix: info.api_key_prefix,
+                user_agent: info.user_agent,
+                duration_ms: Some(duration_ms),
+                message: error_message,
+                user_id,
+            });
+        };
+        
+        // Écriture SQLite hors du worker asynchrone (pool de threads bloquants)
+        match tokio::runtime::Handle::try_current() {
+            Ok(handle) => {
+                handle.spawn_blocking(write);
             }
+            Err(_) => write(),
         }
     }
+    
+    /// Signale un événement de sécurité (ex: clé admin invalide) aux sinks d'audit
+    pub fn log_security_event(&self, event_type: &str, message: &str) {
+        log::warn!("🚨 [Security] {} sur {} {} depuis {}", event_type, self.method, self.endpoint, self.ip_address);
+        
+        crate::audit::emit(crate::audit::AuditEvent {
+            timestamp: Utc::now().to_rfc3339(),
+            kind: crate::audit::AuditEventKind::Security,
+            severity: crate::audit::AuditSeverity::Warning,
+            event_type: event_type.to_string(),
+            request_id: self.request_id.clone(),
+            ip_address: self.ip_address.clone(),
+            method: self.method.clone(),
+            endpoint: self.endpoint.clone(),
+            status_code: None,
+            result: None,
+            api_key_prefix: self.api_key_prefix.clone(),
+            user_agent: self.user_agent.clone(),
+            duration_ms: None,
+            message: Some(message.to_string()),
+            user_id: None,
+        });
+    }
 }
 
 // ============================================================================
@@ -365,4 +375,35 @@ mod tests {
         let result = validate_patient_safe(&payload);
         assert!(result.is_err());
     }
+    
+    #[test]
+    fn test_forbidden_value() {
+        let payload = json!({
+            "title": "IRM",
+            "sections": [
+                { "title": "Contact", "content": "Joindre jean.dupont@example.fr" }
+            ]
+        });
+        
+        let result = validate_patient_safe(&payload);
+        assert!(result.is_err());
+        let (field, _) = result.unwrap_err();
+        assert_eq!(field, "sections[0].content");
+    }
+    
+    #[test]
+    fn test_exact_rule_does_not_block_similar_keys() {
+        let payload = json!({
+            "title": "Scanner abdominal",
+            "nombre_lesions": 3,
+            "nomenclature": "CCAM"
+        });
+        
+        assert!(validate_patient_safe(&payload).is_ok());
+        
+        // Stockage local : pas de contrôle par défaut
+        let payload = json!({ "nom": "Dupont" });
+        assert!(validate_patient_safe(&payload).is_err());
+        assert!(validate_patient_safe_for(&payload, Destination::Local).is_ok());
+    }
 }
@@ -9,12 +9,17 @@ pub mod routes;
 pub mod handlers;
 pub mod middleware;
 pub mod metrics;
+pub mod health;
 
 use actix_web::{App, HttpServer, web, middleware::Logger};
+use actix_web::dev::Service;
+use actix_web::HttpMessage;
+use tracing::Instrument;
 use actix_cors::Cors;
 use actix_governor::{Governor, GovernorConfigBuilder};
 use std::sync::Arc;
 use crate::database::Database;
+use crate::telemetry;
 
 /// État partagé du serveur HTTP
 pub struct HttpServerState {
@@ -50,21 +55,94 @@ pub async fn start_server(port: u16, bind_address: &str, db: Arc<Database>) -> s
                 .allowed_origin_fn(|origin, _req_head| {
                     origin.as_bytes().starts_with(b"http://localhost:")
                 })
-                .allowed_methods(vec!["GET", "POST", "DELETE", "OPTIONS"])
+                .allowed_methods(vec!["GET", "POST", "PUT", "DELETE", "OPTIONS"])
                 .allowed_headers(vec![
                     actix_web::http::header::CONTENT_TYPE,
                     actix_web::http::header::AUTHORIZATION,
                     actix_web::http::header::HeaderName::from_static("x-api-key"),
                     actix_web::http::header::HeaderName::from_static("x-admin-key"),
+                    actix_web::http::header::HeaderName::from_static(telemetry::REQUEST_ID_HEADER),
+                    actix_web::http::header::HeaderName::from_static(telemetry::TRACEPARENT_HEADER),
+                ])
+                .expose_headers(vec![
+                    actix_web::http::header::HeaderName::from_static(telemetry::REQUEST_ID_HEADER),
                 ])
                 .max_age(3600);
             
             App::new()
                 .app_data(state_clone.clone())
                 .app_data(web::JsonConfig::default().limit(1_048_576)) // 🔒 1 MB max payload
+                // 🛠️ Maintenance de la base (restauration, rotation de clé) : 503 (seule la vivacité répond)
+                .wrap_fn(|req, srv| {
+                    let call = if crate::database::restore::maintenance_active() && req.path() != "/health/live" {
+                        Err(req)
+                    } else {
+                        Ok(srv.call(req))
+                    };
+                    async move {
+                        match call {
+                            Ok(fut) => fut.await.map(|res| res.map_into_left_body()),
+                            Err(req) => Ok(req
+                                .into_response(
+                                    actix_web::HttpResponse::ServiceUnavailable()
+                                        .insert_header((actix_web::http::header::RETRY_AFTER, "30"))
+                                        .json(handlers::ErrorResponse {
+                                            error: "Maintenance de la base en cours, réessayez dans quelques instants".to_string(),
+                                            field: None,
+                                        }),
+                                )
+                                .map_into_right_body()),
+                        }
+                    }
+                })
                 .wrap(Governor::new(&governor_conf))
                 .wrap(cors)
                 .wrap(Logger::new("%a \"%r\" %s %b %Dms"))
+                // 🔗 Corrélation : X-Request-Id / traceparent entrants, span racine, écho dans la réponse
+                .wrap_fn(|req, srv| {
+                    let header = |name: &str| {
+                        req.headers().get(name).and_then(|v| v.to_str().ok()).map(str::to_string)
+                    };
+                    let context = telemetry::RequestContext::from_incoming(
+                        header(telemetry::REQUEST_ID_HEADER).as_deref(),
+                        header(telemetry::TRACEPARENT_HEADER).as_deref(),
+                    );
+                    let route = req.match_pattern().unwrap_or_else(|| "unmatched".to_string());
+                    let span = telemetry::http_request_span(req.method().as_str(), &route, &context);
+                    req.extensions_mut().insert(context.clone());
+                    
+                    let request_id = context.request_id.clone();
+                    let fut = span.in_scope(|| srv.call(req));
+                    telemetry::scope(context, async move {
+                        let mut res = fut.await;
+                        if let Ok(response) = res.as_mut() {
+                            tracing::Span::current().record("http.status_code", response.status().as_u16());
+                            if let Ok(value) = actix_web::http::header::HeaderValue::from_str(&request_id) {
+                                response.headers_mut().insert(
+                                    actix_web::http::header::HeaderName::from_static(telemetry::REQUEST_ID_HEADER),
+                                    value,
+                                );
+                            }
+                        }
+                        res
+                    }.instrument(span))
+                })
+                // 📊 Métriques par route (le plus externe : inclut CORS et rate limiting)
+                .wrap_fn(|req, srv| {
+                    let start = std::time::Instant::now();
+                    let method = req.method().to_string();
+                    let route = req.match_pattern().unwrap_or_else(|| "unmatched".to_string());
+                    let fut = srv.call(req);
+                    async move {
+                        let res = fut.await;
+                        let status = match &res {
+                            Ok(response) => response.status().as_u16(),
+                            Err(e) => e.as_response_error().status_code().as_u16(),
+                        };
+                        metrics::Metrics::record_http_request(&route, &method, status, start.elapsed());
+                        res
+                    }
+                })
                 .configure(routes::configure)
         })
         .client_request_timeout(std::time::Duration::from_secs(30))
@@ -5,6 +5,7 @@
 use actix_web::web;
 use super::handlers;
 use super::metrics;
+use super::health;
 
 /// Configure toutes les routes du serveur HTTP
 pub fn configure(cfg: &mut web::ServiceConfig) {
@@ -12,6 +13,10 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
         // Health check (sans authentification)
         .route("/health", web::get().to(handlers::health_check))
         
+        // Sondes de vivacité / disponibilité (monitoring, sans authentification)
+        .route("/health/live", web::get().to(health::liveness_handler))
+        .route("/health/ready", web::get().to(health::readiness_handler))
+        
         // 🆕 Health check étendu (Phase 2)
         .route("/health/extended", web::get().to(metrics::extended_health_handler))
         
@@ -32,9 +37,47 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
         // Alias pour intégration RIS (même handler que /open-report)
         .route("/refresh_gui", web::post().to(handlers::open_report))
         
+        // Liste de travail (file de lecture priorisée)
+        .route("/worklist", web::get().to(handlers::get_worklist))
+        .route("/worklist/next", web::post().to(handlers::open_next_report))
+        
+        // Schémas des rapports structurés (JSON Schema par modalité)
+        .route("/schemas/{modality}", web::get().to(handlers::get_report_schema))
+        
+        // Validation Patient-Safe (simulation, règle déclenchée)
+        .route("/validate", web::post().to(handlers::validate_payload))
+        
+        // Modèles de rapports (comptes rendus types)
+        .route("/templates", web::get().to(handlers::list_report_templates))
+        .route("/templates", web::post().to(handlers::create_report_template))
+        .route("/templates/{id}", web::get().to(handlers::get_report_template))
+        .route("/templates/{id}", web::put().to(handlers::update_report_template))
+        .route("/templates/{id}", web::delete().to(handlers::delete_report_template))
+        .route("/templates/{id}/merge", web::post().to(handlers::merge_report_template))
+        
+        // Recherche plein texte
+        .route("/search", web::get().to(handlers::search_reports))
+        
+        // Verrous d'édition des rapports
+        .route("/report-locks", web::get().to(handlers::list_report_locks))
+        .route("/report-locks/{tid}/heartbeat", web::post().to(handlers::heartbeat_report_lock))
+        .route("/report-locks/{tid}", web::delete().to(handlers::release_report_lock))
+        
         // 🆕 TÉO Hub fetch endpoint (fetch from TÉO Hub without navigation)
         .route("/teo-hub/fetch", web::get().to(handlers::fetch_from_teo_hub))
         
+        // Ré-identification d'un pseudonyme (admin only)
+        .route("/admin/reidentify", web::post().to(handlers::reidentify_pseudonym))
+        
+        // Rétention : simulation et journal des purges (admin only)
+        .route("/admin/retention/preview", web::get().to(handlers::preview_retention_purge))
+        .route("/admin/retention/purges", web::get().to(handlers::list_retention_purges))
+        
+        // Droits des personnes RGPD : export signé, effacement certifié (admin only)
+        .route("/admin/gdpr/export", web::post().to(handlers::gdpr_export))
+        .route("/admin/gdpr/erase", web::post().to(handlers::gdpr_erase))
+        .route("/admin/gdpr/certificates", web::get().to(handlers::list_erasure_certificates))
+        
         // API Keys management (admin only)
         .route("/api-keys", web::post().to(handlers::create_api_key))
         .route("/api-keys", web::get().to(handlers::list_api_keys))
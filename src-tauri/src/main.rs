@@ -36,12 +36,24 @@ extern crate chrono;
 // 🌐 Global AppHandle pour communication HTTP → Tauri
 pub static APP_HANDLE: OnceLock<AppHandle> = OnceLock::new();
 
+// 🗄️ Base partagée par le serveur HTTP et les tâches de fond (restauration)
+pub static SHARED_DB: OnceLock<Arc<database::Database>> = OnceLock::new();
+
 // 🌐 Modules serveur HTTP et base de données
 mod http_server;
 mod database;
 mod teo_client;
 mod config;
 mod speechmike;
+mod audit;
+mod telemetry;
+mod worklist;
+mod report_locks;
+mod report_model;
+mod pii;
+mod retention;
+mod gdpr;
+mod backup_targets;
 
 #[cfg(target_os = "windows")]
 use winapi::um::winuser::{GetSystemMetrics, SM_XVIRTUALSCREEN, SM_YVIRTUALSCREEN, SM_CXVIRTUALSCREEN, SM_CYVIRTUALSCREEN};
@@ -246,6 +258,13 @@ async fn check_app_focus(window: tauri::Window, state: State<'_, AppState>) -> R
 
 #[tauri::command]
 async fn perform_injection_at_position(text: String, html: Option<String>, x: i32, y: i32, state: State<'_, AppState>) -> Result<(), String> {
+    let start = std::time::Instant::now();
+    let result = perform_injection_at_position_inner(text, html, x, y, state).await;
+    http_server::metrics::Metrics::record_injection("position", result.is_ok(), start.elapsed());
+    result
+}
+
+async fn perform_injection_at_position_inner(text: String, html: Option<String>, x: i32, y: i32, state: State<'_, AppState>) -> Result<(), String> {
     ensure_accessibility()?;
     // Thread-safe clipboard operations
     let _clipboard_guard = match state.clipboard_lock.lock() {
@@ -294,6 +313,13 @@ async fn perform_injection_at_position(text: String, html: Option<String>, x: i3
 
 #[tauri::command]
 async fn perform_injection(text: String, html: Option<String>, state: State<'_, AppState>) -> Result<(), String> {
+    let start = std::time::Instant::now();
+    let result = perform_injection_inner(text, html, state).await;
+    http_server::metrics::Metrics::record_injection("focused", result.is_ok(), start.elapsed());
+    result
+}
+
+async fn perform_injection_inner(text: String, html: Option<String>, state: State<'_, AppState>) -> Result<(), String> {
     ensure_accessibility()?;
     // Thread-safe clipboard operations
     let _clipboard_guard = match state.clipboard_lock.lock() {
@@ -491,6 +517,13 @@ use winapi::um::winuser::WINDOWPLACEMENT;
 // 🆕 INJECTION WINDOWS ROBUSTE avec Win32 API pour multi-écrans
 #[tauri::command]
 async fn perform_injection_at_position_direct(x: i32, y: i32, text: String, html: Option<String>, state: State<'_, AppState>) -> Result<(), String> {
+    let start = std::time::Instant::now();
+    let result = perform_injection_at_position_direct_inner(x, y, text, html, state).await;
+    http_server::metrics::Metrics::record_injection("direct", result.is_ok(), start.elapsed());
+    result
+}
+
+async fn perform_injection_at_position_direct_inner(x: i32, y: i32, text: String, html: Option<String>, state: State<'_, AppState>) -> Result<(), String> {
     ensure_accessibility()?;
     let _clipboard_guard = match state.clipboard_lock.lock() {
         Ok(guard) => guard,
@@ -1301,6 +1334,206 @@ async fn get_database_stats() -> Result<database::queries::DatabaseStats, String
         .map_err(|e| format!("Erreur lecture stats: {}", e))
 }
 
+/// État des migrations du schéma (diagnostic "migrations status" du Debug Panel)
+#[tauri::command]
+async fn get_migrations_status() -> Result<database::migrations::MigrationStatus, String> {
+    let app_data_dir = dirs::data_local_dir()
+        .unwrap_or_else(|| std::path::PathBuf::from("."))
+        .join("airadcr-desktop");
+    
+    let db = database::Database::new(app_data_dir)
+        .map_err(|e| format!("Erreur ouverture DB: {}", e))?;
+    
+    db.migration_status()
+        .map_err(|e| format!("Erreur lecture migrations: {}", e))
+}
+
+/// Archive chiffrée pour la DSI (si activée) puis copie vers les destinations distantes
+fn publish_backup(
+    db: &database::Database,
+    manager: &database::backup::BackupManager,
+    backup: &database::backup::BackupInfo,
+) {
+    let config = config::get_config();
+    let files = if config.backup_export.enabled {
+        match manager.export_archive(db, backup, &config.backup_export) {
+            Ok(path) => {
+                info!("[Backup] Archive exportée: {:?}", path);
+                vec![path]
+            }
+            Err(e) => {
+                error!("[Backup] Erreur export d'archive (aucun envoi distant): {}", e);
+                return;
+            }
+        }
+    } else {
+        let path = std::path::PathBuf::from(&backup.path);
+        vec![path.clone(), database::backup::manifest_path(&path)]
+    };
+    backup_targets::replicate(&config.backup_targets, &files);
+}
+
+/// Séquestre la clé SQLCipher active pour les clés publiques `[key_escrow]`,
+/// puis le réplique vers les destinations des backups
+fn escrow_db_key(db: &database::Database) {
+    let config = config::get_config();
+    if !config.key_escrow.enabled {
+        return;
+    }
+    match database::key_escrow::escrow_with_recipients(db, &config.key_escrow) {
+        Ok(path) => {
+            backup_targets::replicate(&config.backup_targets, &[path]);
+        }
+        Err(e) => error!("[KeyEscrow] Séquestre de la clé impossible: {}", e),
+    }
+}
+
+/// État de la clé SQLCipher (version, âge, prochaine rotation, historique)
+#[tauri::command]
+async fn get_key_rotation_status() -> Result<database::key_rotation::KeyStatus, String> {
+    let db = SHARED_DB.get().ok_or("Base non initialisée")?;
+    database::key_rotation::status(db, &config::get_config().key_rotation)
+}
+
+/// Backend des secrets (keychain OS ou keystore fichier) et détenteurs de la
+/// clé SQLCipher et du token TÉO
+#[tauri::command]
+async fn get_keystore_status() -> Result<database::keychain::KeystoreStatus, String> {
+    Ok(database::keychain::keystore_status())
+}
+
+/// Rotation immédiate de la clé SQLCipher (ex. départ d'un membre du personnel),
+/// suivie d'un backup chiffré avec la nouvelle clé et publié (clé admin requise)
+#[tauri::command]
+async fn rotate_db_key_cmd(admin_key: String) -> Result<database::key_rotation::RotationReport, String> {
+    if !http_server::middleware::validate_admin_key(&admin_key) {
+        return Err("Invalid admin key".to_string());
+    }
+    
+    let db = Arc::clone(SHARED_DB.get().ok_or("Base non initialisée")?);
+    
+    warn!("[KeyRotation] Rotation manuelle demandée");
+    tauri::async_runtime::spawn_blocking(move || {
+        let report = database::key_rotation::rotate(&db, database::key_rotation::RotationTrigger::Manual)?;
+        escrow_db_key(&db);
+        
+        let config = config::get_config();
+        if config.backup_enabled {
+            let manager = database::backup::BackupManager::new(db.path().to_path_buf(), config.backup_retention_days);
+            match manager.create_backup(&db) {
+                Ok(backup) => publish_backup(&db, &manager, &backup),
+                Err(e) => error!("[Backup] Erreur backup après rotation: {}", e),
+            }
+        }
+        Ok(report)
+    })
+    .await
+    .map_err(|e| format!("Erreur tâche de rotation: {}", e))?
+}
+
+/// Séquestres de la clé SQLCipher présents avec les backups
+#[tauri::command]
+async fn get_key_escrow_status() -> Result<database::key_escrow::EscrowStatus, String> {
+    let db = SHARED_DB.get().ok_or("Base non initialisée")?;
+    Ok(database::key_escrow::status(db, &config::get_config().key_escrow))
+}
+
+/// Séquestre la clé active avec une passphrase de reprise (administrateur),
+/// répliqué vers les destinations des backups (clé admin requise)
+#[tauri::command]
+async fn escrow_db_key_cmd(admin_key: String, passphrase: String) -> Result<String, String> {
+    if !http_server::middleware::validate_admin_key(&admin_key) {
+        return Err("Invalid admin key".to_string());
+    }
+    
+    let db = Arc::clone(SHARED_DB.get().ok_or("Base non initialisée")?);
+    
+    tauri::async_runtime::spawn_blocking(move || {
+        let path = database::key_escrow::escrow_with_passphrase(&db, &passphrase)?;
+        backup_targets::replicate(&config::get_config().backup_targets, &[path.clone()]);
+        Ok(path.to_string_lossy().to_string())
+    })
+    .await
+    .map_err(|e| format!("Erreur tâche de séquestre: {}", e))?
+}
+
+/// Reprise après sinistre : installe dans le keychain la clé d'un séquestre
+/// (clé privée age ou passphrase), après vérification sur le backup à restaurer
+/// (clé admin requise)
+#[tauri::command]
+async fn recover_db_key_cmd(
+    admin_key: String,
+    escrow_path: String,
+    identity_file: Option<String>,
+    passphrase: Option<String>,
+    backup_path: String,
+) -> Result<database::key_escrow::RecoveryReport, String> {
+    if !http_server::middleware::validate_admin_key(&admin_key) {
+        return Err("Invalid admin key".to_string());
+    }
+    
+    let db = Arc::clone(SHARED_DB.get().ok_or("Base non initialisée")?);
+    
+    warn!("[KeyEscrow] Reprise de clé demandée depuis {}", escrow_path);
+    tauri::async_runtime::spawn_blocking(move || {
+        let identity_file = identity_file.filter(|f| !f.is_empty()).map(std::path::PathBuf::from);
+        let report = database::key_escrow::recover(
+            &db,
+            std::path::Path::new(&escrow_path),
+            identity_file.as_deref(),
+            passphrase.as_deref(),
+            std::path::Path::new(&backup_path),
+        )?;
+        if report.rotation.is_some() {
+            escrow_db_key(&db);
+        }
+        Ok(report)
+    })
+    .await
+    .map_err(|e| format!("Erreur tâche de reprise: {}", e))?
+}
+
+/// Backups locaux disponibles (plus récents d'abord)
+#[tauri::command]
+async fn list_backups_cmd() -> Result<Vec<database::backup::BackupInfo>, String> {
+    let db = SHARED_DB.get().ok_or("Base non initialisée")?;
+    let config = config::get_config();
+    let manager = database::backup::BackupManager::new(db.path().to_path_buf(), config.backup_retention_days);
+    Ok(manager.list_backups())
+}
+
+/// Restaure un backup (ou une archive exportée avec la clé privée age de la
+/// DSI) : serveur HTTP et tâches de fond en pause, retour arrière automatique.
+/// La progression est émise sur l'événement `airadcr:restore_progress`
+/// (clé admin requise).
+#[tauri::command]
+async fn restore_backup_cmd(
+    admin_key: String,
+    filename: String,
+    identity_file: Option<String>,
+) -> Result<database::restore::RestoreReport, String> {
+    if !http_server::middleware::validate_admin_key(&admin_key) {
+        return Err("Invalid admin key".to_string());
+    }
+    
+    let db = Arc::clone(SHARED_DB.get().ok_or("Base non initialisée")?);
+    let config = config::get_config();
+    
+    warn!("[Restore] Restauration demandée: {}", filename);
+    tauri::async_runtime::spawn_blocking(move || {
+        let manager = database::backup::BackupManager::new(db.path().to_path_buf(), config.backup_retention_days);
+        let identity_file = identity_file.map(std::path::PathBuf::from);
+        
+        manager.restore_backup(&db, &filename, identity_file.as_deref(), &|progress| {
+            if let Some(app) = APP_HANDLE.get() {
+                let _ = app.emit_all("airadcr:restore_progress", progress);
+            }
+        })
+    })
+    .await
+    .map_err(|e| format!("Erreur tâche de restauration: {}", e))?
+}
+
 /// Nettoie les rapports expirés (pour Debug Panel)
 #[tauri::command]
 async fn cleanup_expired_reports_cmd() -> Result<usize, String> {
@@ -1500,131 +1733,874 @@ async fn cleanup_access_logs(days: Option<i64>) -> Result<i64, String> {
     Ok(count as i64)
 }
 
-// ============================================================================
-// COMMANDES TAURI - TÉO HUB CLIENT
-// ============================================================================
-
-/// Vérifie la disponibilité du serveur TÉO Hub
+/// Simule le prochain cycle de purge de rétention (rien n'est supprimé)
 #[tauri::command]
-async fn teo_check_health() -> Result<teo_client::models::TeoHealthResponse, String> {
-    teo_client::check_health().await.map_err(|e| e.to_string())
+async fn preview_retention_purge() -> Result<retention::PurgeReport, String> {
+    let app_data_dir = dirs::data_local_dir()
+        .unwrap_or_else(|| std::path::PathBuf::from("."))
+        .join("airadcr-desktop");
+    
+    let db = database::Database::new(app_data_dir)
+        .map_err(|e| format!("Erreur ouverture DB: {}", e))?;
+    
+    retention::preview(&db)
 }
 
-/// Récupère un rapport IA depuis TÉO Hub par patient_id + study_uid
+/// Exécute immédiatement un cycle de purge de rétention ([retention] dry_run respecté,
+/// clé admin requise)
 #[tauri::command]
-async fn teo_fetch_report(patient_id: String, study_uid: String) -> Result<teo_client::models::TeoAiReportResponse, String> {
-    teo_client::fetch_ai_report(&patient_id, &study_uid).await.map_err(|e| e.to_string())
+async fn run_retention_purge(admin_key: String) -> Result<retention::PurgeReport, String> {
+    if !http_server::middleware::validate_admin_key(&admin_key) {
+        return Err("Invalid admin key".to_string());
+    }
+    
+    let app_data_dir = dirs::data_local_dir()
+        .unwrap_or_else(|| std::path::PathBuf::from("."))
+        .join("airadcr-desktop");
+    
+    let db = database::Database::new(app_data_dir)
+        .map_err(|e| format!("Erreur ouverture DB: {}", e))?;
+    
+    retention::run_cycle(&db)
 }
 
-/// Envoie un rapport validé à TÉO Hub
+/// Journal des purges de rétention (plus récentes d'abord)
 #[tauri::command]
-async fn teo_submit_approved(
-    patient_id: String,
-    study_uid: String,
-    approved_report: String,
-) -> Result<teo_client::models::TeoApprovalResponse, String> {
-    let report = teo_client::models::TeoApprovedReport {
-        patient_id,
-        study_uid,
-        approved_report,
-    };
+async fn get_retention_purges(limit: Option<i64>) -> Result<Vec<database::queries::RetentionPurgeEntry>, String> {
+    let app_data_dir = dirs::data_local_dir()
+        .unwrap_or_else(|| std::path::PathBuf::from("."))
+        .join("airadcr-desktop");
     
-    teo_client::submit_approved_report(report).await.map_err(|e| e.to_string())
+    let db = database::Database::new(app_data_dir)
+        .map_err(|e| format!("Erreur ouverture DB: {}", e))?;
+    
+    db.list_retention_purges(limit.unwrap_or(100).clamp(1, 1000))
+        .map_err(|e| format!("Erreur lecture journal de purge: {}", e))
 }
 
-/// Récupère la configuration TÉO Hub actuelle (sans secrets)
-#[tauri::command]
-fn teo_get_config() -> teo_client::models::TeoHubConfigInfo {
-    let cfg = config::get_config();
-    teo_client::models::TeoHubConfigInfo {
-        enabled: cfg.teo_hub.enabled,
-        host: cfg.teo_hub.host.clone(),
-        port: cfg.teo_hub.port,
-        tls_enabled: cfg.teo_hub.tls_enabled,
-        timeout_secs: cfg.teo_hub.timeout_secs,
-        retry_count: cfg.teo_hub.retry_count,
-        has_api_token: !cfg.teo_hub.api_token.is_empty()
-            || crate::database::keychain::get_teo_token()
-                .map(|opt| opt.is_some()).unwrap_or(false),
-        has_tls_certs: !cfg.teo_hub.cert_file.is_empty() && !cfg.teo_hub.key_file.is_empty(),
+// ============================================================================
+// COMMANDES TAURI - PROFILS RADIOLOGUES
+// ============================================================================
+
+/// Taille maximale d'une préférence JSON (raccourcis, mapping SpeechMike, profil d'injection)
+const MAX_USER_PREFERENCE_BYTES: usize = 64 * 1024;
+
+/// Profil radiologue et ses préférences (payload de airadcr:profile_changed)
+#[derive(Debug, Serialize)]
+struct UserProfileWithPreferences {
+    user: Option<database::queries::UserProfile>,
+    preferences: std::collections::BTreeMap<String, serde_json::Value>,
+}
+
+/// Username accepté : 2 à 64 caractères [a-z0-9._-]
+fn validate_username(username: &str) -> Result<(), String> {
+    if username.len() < 2 || username.len() > 64 {
+        return Err("Le username doit contenir entre 2 et 64 caractères".to_string());
     }
+    if !username.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '.' | '_' | '-')) {
+        return Err("Le username ne peut contenir que [a-z0-9._-]".to_string());
+    }
+    Ok(())
 }
 
-/// Récupère le statut de connexion TÉO Hub
+/// Met à jour l'entrée du tray avec le radiologue actif
+fn update_profile_tray_item(app: &AppHandle, user: Option<&database::queries::UserProfile>) {
+    let title = match user {
+        Some(user) => format!("Radiologue : {} (changer…)", user.display_name),
+        None => "Changer de radiologue…".to_string(),
+    };
+    if let Err(e) = app.tray_handle().get_item("switch_profile").set_title(title) {
+        warn!("[Profiles] Mise à jour du tray impossible: {}", e);
+    }
+}
+
+/// Liste les profils radiologues
 #[tauri::command]
-fn teo_get_connection_status() -> String {
-    format!("{:?}", teo_client::get_connection_status())
+async fn list_user_profiles(include_inactive: Option<bool>) -> Result<Vec<database::queries::UserProfile>, String> {
+    let app_data_dir = dirs::data_local_dir()
+        .unwrap_or_else(|| std::path::PathBuf::from("."))
+        .join("airadcr-desktop");
+    
+    let db = database::Database::new(app_data_dir)
+        .map_err(|e| format!("Erreur ouverture DB: {}", e))?;
+    
+    db.list_users(!include_inactive.unwrap_or(false))
+        .map_err(|e| format!("Erreur lecture profils: {}", e))
 }
 
-/// 🆕 Informations runtime pour le Debug Panel
-#[derive(Serialize)]
-struct RuntimeInfo {
-    disable_api_auth: bool,
-    http_port: u16,
-    config_path: String,
-    teo_hub_enabled: bool,
-    build_version: String,
+/// Crée un profil radiologue
+#[tauri::command]
+async fn create_user_profile(username: String, display_name: String, rpps: Option<String>) -> Result<database::queries::UserProfile, String> {
+    let username = username.trim().to_lowercase();
+    validate_username(&username)?;
+    
+    let display_name = display_name.trim().to_string();
+    if display_name.is_empty() || display_name.len() > 100 {
+        return Err("Le nom affiché doit contenir entre 1 et 100 caractères".to_string());
+    }
+    
+    // RPPS : 11 chiffres
+    let rpps = rpps.map(|r| r.trim().to_string()).filter(|r| !r.is_empty());
+    if let Some(rpps) = &rpps {
+        if rpps.len() != 11 || !rpps.chars().all(|c| c.is_ascii_digit()) {
+            return Err("Le numéro RPPS doit contenir 11 chiffres".to_string());
+        }
+    }
+    
+    let app_data_dir = dirs::data_local_dir()
+        .unwrap_or_else(|| std::path::PathBuf::from("."))
+        .join("airadcr-desktop");
+    
+    let db = database::Database::new(app_data_dir)
+        .map_err(|e| format!("Erreur ouverture DB: {}", e))?;
+    
+    if db.find_user(&username).map_err(|e| e.to_string())?.is_some() {
+        return Err(format!("Le profil '{}' existe déjà", username));
+    }
+    
+    let id = uuid::Uuid::new_v4().to_string();
+    db.create_user(&id, &username, &display_name, rpps.as_deref())
+        .map_err(|e| format!("Erreur création profil: {}", e))?;
+    
+    info!("[Profiles] Profil créé: {}", username);
+    
+    db.find_user(&id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Profil introuvable après création".to_string())
 }
 
+/// Désactive un profil radiologue (les rapports attribués restent attribués)
 #[tauri::command]
-fn get_runtime_info() -> RuntimeInfo {
-    let cfg = config::get_config();
-    let config_path = config::AppConfig::config_path()
-        .map(|p| p.to_string_lossy().to_string())
-        .unwrap_or_else(|| "inconnu".to_string());
+async fn deactivate_user_profile(app: AppHandle, user_id: String) -> Result<bool, String> {
+    let app_data_dir = dirs::data_local_dir()
+        .unwrap_or_else(|| std::path::PathBuf::from("."))
+        .join("airadcr-desktop");
     
-    RuntimeInfo {
-        disable_api_auth: cfg.disable_api_auth,
-        http_port: cfg.http_port,
-        config_path,
-        teo_hub_enabled: cfg.teo_hub.enabled,
-        build_version: env!("CARGO_PKG_VERSION").to_string(),
+    let db = database::Database::new(app_data_dir)
+        .map_err(|e| format!("Erreur ouverture DB: {}", e))?;
+    
+    let was_active = db.get_active_user_id().map_err(|e| e.to_string())?.as_deref() == Some(user_id.as_str());
+    let deactivated = db.deactivate_user(&user_id)
+        .map_err(|e| format!("Erreur désactivation profil: {}", e))?;
+    
+    if deactivated {
+        info!("[Profiles] Profil désactivé: {}", user_id);
+        if was_active {
+            update_profile_tray_item(&app, None);
+            http_server::handlers::emit_profile_changed(&db, None);
+        }
     }
+    
+    Ok(deactivated)
 }
 
-// 🔗 EXTRACTION DU TID DEPUIS UNE DEEP LINK
-// Formats supportés:
-//   - airadcr://open?tid=ABC123
-//   - airadcr://open/ABC123
-//   - airadcr://ABC123
-fn extract_tid_from_deep_link(url: &str) -> Option<String> {
-    // Retirer le préfixe airadcr://
-    let path = url.strip_prefix("airadcr://")?;
+/// Radiologue actif et ses préférences
+#[tauri::command]
+async fn get_active_user_profile() -> Result<UserProfileWithPreferences, String> {
+    let app_data_dir = dirs::data_local_dir()
+        .unwrap_or_else(|| std::path::PathBuf::from("."))
+        .join("airadcr-desktop");
     
-    // 🛡️ SÉCURITÉ: Fonction de validation du tid
-    let validate_tid = |tid: &str| -> Option<String> {
-        // Limite de longueur (protection DoS)
-        if tid.is_empty() || tid.len() > 64 {
-            if tid.len() > 64 {
-                log::warn!("[Deep Link] tid trop long rejeté: {} chars", tid.len());
+    let db = database::Database::new(app_data_dir)
+        .map_err(|e| format!("Erreur ouverture DB: {}", e))?;
+    
+    let user = db.get_active_user().map_err(|e| e.to_string())?;
+    let preferences = match &user {
+        Some(user) => load_user_preferences(&db, &user.id)?,
+        None => Default::default(),
+    };
+    
+    Ok(UserProfileWithPreferences { user, preferences })
+}
+
+/// Change de radiologue actif (None = aucun profil)
+#[tauri::command]
+async fn switch_user_profile(app: AppHandle, user_id: Option<String>) -> Result<UserProfileWithPreferences, String> {
+    let app_data_dir = dirs::data_local_dir()
+        .unwrap_or_else(|| std::path::PathBuf::from("."))
+        .join("airadcr-desktop");
+    
+    let db = database::Database::new(app_data_dir)
+        .map_err(|e| format!("Erreur ouverture DB: {}", e))?;
+    
+    let user = match user_id.as_deref().filter(|id| !id.is_empty()) {
+        Some(id) => {
+            let user = db.find_user(id)
+                .map_err(|e| e.to_string())?
+                .ok_or_else(|| format!("Profil inconnu: {}", id))?;
+            if !user.is_active {
+                return Err(format!("Profil désactivé: {}", user.username));
             }
-            return None;
-        }
-        // Validation caractères autorisés (alphanumériques, tirets, underscores)
-        if tid.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_') {
-            Some(tid.to_string())
-        } else {
-            log::warn!("[Deep Link] tid avec caractères invalides rejeté");
-            None
+            Some(user)
         }
+        None => None,
     };
     
-    // Format: airadcr://open?tid=ABC123
-    if path.contains("tid=") {
-        let tid = path.split("tid=").nth(1)?;
-        let tid = tid.split('&').next()?; // Au cas où il y a d'autres params
-        return validate_tid(tid);
-    }
+    db.set_active_user(user.as_ref().map(|u| u.id.as_str()))
+        .map_err(|e| format!("Erreur changement de profil: {}", e))?;
     
-    // Format: airadcr://open/ABC123
-    if path.starts_with("open/") {
-        let tid = path.strip_prefix("open/")?;
-        return validate_tid(tid);
+    match &user {
+        Some(user) => info!("[Profiles] Radiologue actif: {}", user.username),
+        None => info!("[Profiles] Aucun radiologue actif"),
     }
     
-    // Format: airadcr://ABC123 (direct)
-    let tid = path.trim_start_matches("open").trim_start_matches('/').trim_start_matches('?');
-    validate_tid(tid)
+    update_profile_tray_item(&app, user.as_ref());
+    http_server::handlers::emit_profile_changed(&db, user.as_ref());
+    
+    let preferences = match &user {
+        Some(user) => load_user_preferences(&db, &user.id)?,
+        None => Default::default(),
+    };
+    
+    Ok(UserProfileWithPreferences { user, preferences })
+}
+
+fn load_user_preferences(
+    db: &database::Database,
+    user_id: &str,
+) -> Result<std::collections::BTreeMap<String, serde_json::Value>, String> {
+    let preferences = db.list_user_preferences(user_id)
+        .map_err(|e| format!("Erreur lecture préférences: {}", e))?;
+    Ok(preferences
+        .into_iter()
+        .map(|p| (p.pref_key, serde_json::from_str(&p.value).unwrap_or(serde_json::Value::Null)))
+        .collect())
+}
+
+/// Préférences d'un radiologue (raccourcis, mapping SpeechMike, profil d'injection)
+#[tauri::command]
+async fn get_user_preferences(user_id: String) -> Result<std::collections::BTreeMap<String, serde_json::Value>, String> {
+    let app_data_dir = dirs::data_local_dir()
+        .unwrap_or_else(|| std::path::PathBuf::from("."))
+        .join("airadcr-desktop");
+    
+    let db = database::Database::new(app_data_dir)
+        .map_err(|e| format!("Erreur ouverture DB: {}", e))?;
+    
+    load_user_preferences(&db, &user_id)
+}
+
+/// Enregistre une préférence d'un radiologue
+#[tauri::command]
+async fn set_user_preference(user_id: String, key: String, value: serde_json::Value) -> Result<(), String> {
+    if !database::queries::USER_PREFERENCE_KEYS.contains(&key.as_str()) {
+        return Err(format!(
+            "Préférence inconnue '{}' (attendu: {})",
+            key,
+            database::queries::USER_PREFERENCE_KEYS.join(", ")
+        ));
+    }
+    
+    let json = serde_json::to_string(&value).map_err(|e| e.to_string())?;
+    if json.len() > MAX_USER_PREFERENCE_BYTES {
+        return Err(format!("Préférence trop volumineuse ({} octets, maximum {})", json.len(), MAX_USER_PREFERENCE_BYTES));
+    }
+    
+    let app_data_dir = dirs::data_local_dir()
+        .unwrap_or_else(|| std::path::PathBuf::from("."))
+        .join("airadcr-desktop");
+    
+    let db = database::Database::new(app_data_dir)
+        .map_err(|e| format!("Erreur ouverture DB: {}", e))?;
+    
+    let user = db.find_user(&user_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Profil inconnu: {}", user_id))?;
+    
+    db.set_user_preference(&user.id, &key, &json)
+        .map_err(|e| format!("Erreur enregistrement préférence: {}", e))?;
+    
+    info!("[Profiles] Préférence '{}' mise à jour pour {}", key, user.username);
+    
+    // Appliquer immédiatement si le profil est actif
+    if db.get_active_user_id().ok().flatten().as_deref() == Some(user.id.as_str()) {
+        http_server::handlers::emit_profile_changed(&db, Some(&user));
+    }
+    
+    Ok(())
+}
+
+/// Attribue un rapport à un radiologue (None = retire l'attribution)
+#[tauri::command]
+async fn assign_pending_report_cmd(technical_id: String, user_id: Option<String>) -> Result<bool, String> {
+    let app_data_dir = dirs::data_local_dir()
+        .unwrap_or_else(|| std::path::PathBuf::from("."))
+        .join("airadcr-desktop");
+    
+    let db = database::Database::new(app_data_dir)
+        .map_err(|e| format!("Erreur ouverture DB: {}", e))?;
+    
+    let user_id = match user_id.as_deref().filter(|id| !id.is_empty()) {
+        Some(id) => {
+            let user = db.find_user(id)
+                .map_err(|e| e.to_string())?
+                .ok_or_else(|| format!("Profil inconnu: {}", id))?;
+            Some(user.id)
+        }
+        None => None,
+    };
+    
+    let updated = db.assign_pending_report(&technical_id, user_id.as_deref())
+        .map_err(|e| format!("Erreur attribution: {}", e))?;
+    
+    if updated {
+        info!("[Profiles] Rapport {} attribué à {:?}", technical_id, user_id);
+    }
+    
+    Ok(updated)
+}
+
+// ============================================================================
+// COMMANDES TAURI - LISTE DE TRAVAIL
+// ============================================================================
+
+/// Liste de travail (défaut : radiologue actif, rapports attribués + non attribués)
+#[tauri::command]
+async fn get_worklist(
+    user_id: Option<String>,
+    assigned_only: Option<bool>,
+    modality: Option<String>,
+    priority: Option<String>,
+    limit: Option<i64>,
+) -> Result<Vec<database::queries::WorklistItem>, String> {
+    let priority = priority
+        .as_deref()
+        .filter(|p| !p.is_empty())
+        .map(worklist::parse_priority)
+        .transpose()?;
+    
+    let app_data_dir = dirs::data_local_dir()
+        .unwrap_or_else(|| std::path::PathBuf::from("."))
+        .join("airadcr-desktop");
+    
+    let db = database::Database::new(app_data_dir)
+        .map_err(|e| format!("Erreur ouverture DB: {}", e))?;
+    
+    let user_id = match user_id.filter(|id| !id.is_empty()) {
+        Some(id) => Some(id),
+        None => db.get_active_user_id().map_err(|e| e.to_string())?,
+    };
+    
+    let filter = database::queries::WorklistFilter {
+        user_id,
+        assigned_only: assigned_only.unwrap_or(false),
+        modality,
+        priority,
+        exclude_tid: None,
+        limit: limit.unwrap_or(50),
+    };
+    
+    db.list_worklist(&filter)
+        .map_err(|e| format!("Erreur lecture liste de travail: {}", e))
+}
+
+/// Ouvre le rapport suivant de la liste de travail (None si vide)
+#[tauri::command]
+async fn open_next_report() -> Result<Option<database::queries::WorklistItem>, String> {
+    let app_data_dir = dirs::data_local_dir()
+        .unwrap_or_else(|| std::path::PathBuf::from("."))
+        .join("airadcr-desktop");
+    
+    let db = database::Database::new(app_data_dir)
+        .map_err(|e| format!("Erreur ouverture DB: {}", e))?;
+    
+    worklist::open_next_report(&db, &report_locks::LockHolder::local(&db))
+}
+
+/// Définit la priorité (routine/urgent/stat) et l'échéance d'un rapport
+#[tauri::command]
+async fn set_report_priority(technical_id: String, priority: String, due_at: Option<String>) -> Result<bool, String> {
+    let priority = worklist::parse_priority(&priority)?;
+    let due_at = due_at
+        .as_deref()
+        .filter(|d| !d.is_empty())
+        .map(worklist::normalize_due_at)
+        .transpose()?;
+    
+    let app_data_dir = dirs::data_local_dir()
+        .unwrap_or_else(|| std::path::PathBuf::from("."))
+        .join("airadcr-desktop");
+    
+    let db = database::Database::new(app_data_dir)
+        .map_err(|e| format!("Erreur ouverture DB: {}", e))?;
+    
+    let updated = db.set_report_schedule(&technical_id, &priority, due_at.as_deref())
+        .map_err(|e| format!("Erreur mise à jour priorité: {}", e))?;
+    
+    if updated {
+        info!("[Worklist] Rapport {} → priorité {} (échéance {:?})", technical_id, priority, due_at);
+    }
+    
+    Ok(updated)
+}
+
+/// Signale le rapport affiché par le frontend (navigation interne à l'iframe)
+#[tauri::command]
+fn set_current_report(technical_id: Option<String>) {
+    worklist::set_current_report(technical_id.as_deref());
+}
+
+/// Recherche plein texte dans le contenu des rapports (extraits surlignés <mark>)
+#[tauri::command]
+async fn search_reports(query: String, modality: Option<String>, limit: Option<i64>) -> Result<Vec<database::queries::SearchHit>, String> {
+    let app_data_dir = dirs::data_local_dir()
+        .unwrap_or_else(|| std::path::PathBuf::from("."))
+        .join("airadcr-desktop");
+    
+    let db = database::Database::new(app_data_dir)
+        .map_err(|e| format!("Erreur ouverture DB: {}", e))?;
+    
+    db.search_reports(query.trim(), modality.as_deref().filter(|m| !m.is_empty()), limit.unwrap_or(20))
+        .map_err(|e| format!("Database error: {}", e))
+}
+
+// ============================================================================
+// COMMANDES TAURI - MODÈLES DE RAPPORTS
+// ============================================================================
+
+/// Liste les modèles de rapports (filtres optionnels)
+#[tauri::command]
+async fn list_report_templates(
+    modality: Option<String>,
+    body_part: Option<String>,
+    language: Option<String>,
+) -> Result<Vec<database::queries::ReportTemplate>, String> {
+    let app_data_dir = dirs::data_local_dir()
+        .unwrap_or_else(|| std::path::PathBuf::from("."))
+        .join("airadcr-desktop");
+    
+    let db = database::Database::new(app_data_dir)
+        .map_err(|e| format!("Erreur ouverture DB: {}", e))?;
+    
+    let modality = modality.filter(|m| !m.is_empty()).map(|m| m.to_uppercase());
+    db.list_report_templates(
+        modality.as_deref(),
+        body_part.as_deref().filter(|b| !b.is_empty()),
+        language.as_deref().filter(|l| !l.is_empty()),
+    )
+    .map_err(|e| format!("Database error: {}", e))
+}
+
+/// Crée (id absent) ou remplace un modèle de rapport
+#[tauri::command]
+async fn save_report_template(
+    id: Option<String>,
+    template: report_model::templates::TemplateInput,
+) -> Result<database::queries::ReportTemplate, String> {
+    let validated = report_model::templates::validate_template(&template)
+        .map_err(|e| format!("{}: {}", e.field, e.message))?;
+    
+    let app_data_dir = dirs::data_local_dir()
+        .unwrap_or_else(|| std::path::PathBuf::from("."))
+        .join("airadcr-desktop");
+    
+    let db = database::Database::new(app_data_dir)
+        .map_err(|e| format!("Erreur ouverture DB: {}", e))?;
+    
+    let fields = database::queries::ReportTemplateFields {
+        name: &validated.name,
+        modality: validated.modality.as_deref(),
+        body_part: validated.body_part.as_deref(),
+        language: &validated.language,
+        content: &validated.content,
+    };
+    
+    if let Ok(Some(other)) = db.get_report_template(&validated.name) {
+        if id.as_deref() != Some(other.id.as_str()) {
+            return Err(format!("A template named '{}' already exists", validated.name));
+        }
+    }
+    
+    let id = match id.filter(|i| !i.is_empty()) {
+        Some(id) => {
+            if !db.update_report_template(&id, &fields).map_err(|e| format!("Database error: {}", e))? {
+                return Err(format!("Template {} not found", id));
+            }
+            id
+        }
+        None => {
+            let id = uuid::Uuid::new_v4().to_string();
+            let created_by = db.get_active_user_id().ok().flatten();
+            db.insert_report_template(&id, &fields, created_by.as_deref())
+                .map_err(|e| format!("Database error: {}", e))?;
+            id
+        }
+    };
+    
+    info!("[Templates] Modèle enregistré: {} ({})", validated.name, id);
+    db.get_report_template(&id)
+        .map_err(|e| format!("Database error: {}", e))?
+        .ok_or_else(|| format!("Template {} not found", id))
+}
+
+/// Supprime un modèle de rapport (id ou nom, comme DELETE /templates/{id})
+#[tauri::command]
+async fn delete_report_template(id: String) -> Result<bool, String> {
+    let app_data_dir = dirs::data_local_dir()
+        .unwrap_or_else(|| std::path::PathBuf::from("."))
+        .join("airadcr-desktop");
+    
+    let db = database::Database::new(app_data_dir)
+        .map_err(|e| format!("Erreur ouverture DB: {}", e))?;
+    
+    db.delete_report_template(&id).map_err(|e| format!("Database error: {}", e))
+}
+
+/// Fusionne un modèle (id ou nom) avec un rapport entrant, sans stockage
+#[tauri::command]
+async fn merge_report_template(
+    template: String,
+    structured: Option<serde_json::Value>,
+    variables: Option<std::collections::HashMap<String, String>>,
+    modality: Option<String>,
+) -> Result<report_model::templates::MergedReport, String> {
+    let app_data_dir = dirs::data_local_dir()
+        .unwrap_or_else(|| std::path::PathBuf::from("."))
+        .join("airadcr-desktop");
+    
+    let db = database::Database::new(app_data_dir)
+        .map_err(|e| format!("Erreur ouverture DB: {}", e))?;
+    
+    let template = db
+        .get_report_template(&template)
+        .map_err(|e| format!("Database error: {}", e))?
+        .ok_or_else(|| format!("Template {} not found", template))?;
+    
+    report_model::templates::merge_template(
+        &template,
+        &structured.unwrap_or(serde_json::Value::Null),
+        &variables.unwrap_or_default(),
+        modality.as_deref(),
+    )
+    .map_err(|e| format!("{}: {}", e.field, e.message))
+}
+
+// ============================================================================
+// COMMANDES TAURI - VERROUS DE RAPPORTS
+// ============================================================================
+
+/// Liste les verrous d'édition actifs
+#[tauri::command]
+async fn list_report_locks() -> Result<Vec<database::queries::ReportLock>, String> {
+    let app_data_dir = dirs::data_local_dir()
+        .unwrap_or_else(|| std::path::PathBuf::from("."))
+        .join("airadcr-desktop");
+    
+    let db = database::Database::new(app_data_dir)
+        .map_err(|e| format!("Erreur ouverture DB: {}", e))?;
+    
+    db.list_report_locks().map_err(|e| format!("Database error: {}", e))
+}
+
+/// Prolonge le bail du verrou détenu par ce poste
+#[tauri::command]
+async fn heartbeat_report_lock(technical_id: String) -> Result<database::queries::ReportLock, String> {
+    let app_data_dir = dirs::data_local_dir()
+        .unwrap_or_else(|| std::path::PathBuf::from("."))
+        .join("airadcr-desktop");
+    
+    let db = database::Database::new(app_data_dir)
+        .map_err(|e| format!("Erreur ouverture DB: {}", e))?;
+    
+    report_locks::heartbeat(&db, &technical_id, &report_locks::LockHolder::local(&db))
+        .map_err(|e| e.message())
+}
+
+/// Libère le verrou détenu par ce poste
+#[tauri::command]
+async fn release_report_lock(technical_id: String) -> Result<bool, String> {
+    let app_data_dir = dirs::data_local_dir()
+        .unwrap_or_else(|| std::path::PathBuf::from("."))
+        .join("airadcr-desktop");
+    
+    let db = database::Database::new(app_data_dir)
+        .map_err(|e| format!("Erreur ouverture DB: {}", e))?;
+    
+    report_locks::release(&db, &technical_id, &report_locks::LockHolder::local(&db))
+}
+
+/// Libère un verrou quel que soit son détenteur (poste abandonné)
+#[tauri::command]
+async fn force_release_report_lock(technical_id: String) -> Result<Option<database::queries::ReportLock>, String> {
+    let app_data_dir = dirs::data_local_dir()
+        .unwrap_or_else(|| std::path::PathBuf::from("."))
+        .join("airadcr-desktop");
+    
+    let db = database::Database::new(app_data_dir)
+        .map_err(|e| format!("Erreur ouverture DB: {}", e))?;
+    
+    report_locks::force_release(&db, &technical_id)
+}
+
+// ============================================================================
+// COMMANDES TAURI - TÉO HUB CLIENT
+// ============================================================================
+
+/// Vérifie la disponibilité du serveur TÉO Hub
+#[tauri::command]
+async fn teo_check_health() -> Result<teo_client::models::TeoHealthResponse, String> {
+    teo_client::check_health().await.map_err(|e| e.to_string())
+}
+
+/// Récupère un rapport IA depuis TÉO Hub par patient_id + study_uid
+#[tauri::command]
+async fn teo_fetch_report(patient_id: String, study_uid: String) -> Result<teo_client::models::TeoAiReportResponse, String> {
+    teo_client::fetch_ai_report(&patient_id, &study_uid).await.map_err(|e| e.to_string())
+}
+
+/// Envoie un rapport validé à TÉO Hub
+#[tauri::command]
+async fn teo_submit_approved(
+    patient_id: String,
+    study_uid: String,
+    approved_report: String,
+) -> Result<teo_client::models::TeoApprovalResponse, String> {
+    let report = teo_client::models::TeoApprovedReport {
+        patient_id,
+        study_uid,
+        approved_report,
+    };
+    
+    teo_client::submit_approved_report(report).await.map_err(|e| e.to_string())
+}
+
+/// Analyse un payload avant envoi : détections PII et payload masqué selon [pii]
+#[tauri::command]
+fn scan_pii(payload: serde_json::Value) -> pii::PiiScan {
+    pii::scanner().scan(&payload, "")
+}
+
+/// Ré-identifie un pseudonyme relevé dans les logs (clé admin requise)
+#[tauri::command]
+async fn reidentify_pseudonym(
+    admin_key: String,
+    pseudonym: String,
+    candidates: Option<Vec<String>>,
+) -> Result<pii::pseudonym::Reidentification, String> {
+    if !http_server::middleware::validate_admin_key(&admin_key) {
+        return Err("Invalid admin key".to_string());
+    }
+    
+    let app_data_dir = dirs::data_local_dir()
+        .unwrap_or_else(|| std::path::PathBuf::from("."))
+        .join("airadcr-desktop");
+    
+    let db = database::Database::new(app_data_dir)
+        .map_err(|e| format!("Erreur ouverture DB: {}", e))?;
+    
+    pii::pseudonym::reidentify(&db, &pseudonym, &candidates.unwrap_or_default())
+}
+
+/// Export RGPD signé des données d'un patient (clé admin requise)
+#[tauri::command]
+async fn gdpr_export(
+    admin_key: String,
+    patient_id: Option<String>,
+    accession_number: Option<String>,
+) -> Result<gdpr::SignedBundle<gdpr::SubjectExport>, String> {
+    if !http_server::middleware::validate_admin_key(&admin_key) {
+        return Err("Invalid admin key".to_string());
+    }
+    
+    let app_data_dir = dirs::data_local_dir()
+        .unwrap_or_else(|| std::path::PathBuf::from("."))
+        .join("airadcr-desktop");
+    
+    let db = database::Database::new(app_data_dir)
+        .map_err(|e| format!("Erreur ouverture DB: {}", e))?;
+    
+    gdpr::export(&db, &gdpr::DataSubject { patient_id, accession_number }, "local")
+}
+
+/// Effacement ou anonymisation RGPD avec certificat signé (clé admin requise)
+#[tauri::command]
+async fn gdpr_erase(
+    admin_key: String,
+    patient_id: Option<String>,
+    accession_number: Option<String>,
+    mode: Option<String>,
+) -> Result<gdpr::SignedBundle<gdpr::ErasureCertificate>, String> {
+    if !http_server::middleware::validate_admin_key(&admin_key) {
+        return Err("Invalid admin key".to_string());
+    }
+    
+    let mode = gdpr::ErasureMode::parse(mode.as_deref().unwrap_or("erase"))
+        .ok_or_else(|| "mode must be one of: erase, anonymise".to_string())?;
+    
+    let app_data_dir = dirs::data_local_dir()
+        .unwrap_or_else(|| std::path::PathBuf::from("."))
+        .join("airadcr-desktop");
+    
+    let db = database::Database::new(app_data_dir)
+        .map_err(|e| format!("Erreur ouverture DB: {}", e))?;
+    
+    gdpr::erase(&db, &gdpr::DataSubject { patient_id, accession_number }, mode, "local")
+}
+
+/// Certificats d'effacement délivrés (clé admin requise)
+#[tauri::command]
+async fn get_erasure_certificates(
+    admin_key: String,
+    limit: Option<i64>,
+) -> Result<Vec<database::queries::ErasureCertificateEntry>, String> {
+    if !http_server::middleware::validate_admin_key(&admin_key) {
+        return Err("Invalid admin key".to_string());
+    }
+    
+    let app_data_dir = dirs::data_local_dir()
+        .unwrap_or_else(|| std::path::PathBuf::from("."))
+        .join("airadcr-desktop");
+    
+    let db = database::Database::new(app_data_dir)
+        .map_err(|e| format!("Erreur ouverture DB: {}", e))?;
+    
+    db.list_erasure_certificates(limit.unwrap_or(100).clamp(1, 1000))
+        .map_err(|e| format!("Erreur lecture certificats: {}", e))
+}
+
+/// Vérifie la signature d'un export ou d'un certificat RGPD
+#[tauri::command]
+fn verify_gdpr_bundle(bundle: serde_json::Value) -> Result<bool, String> {
+    gdpr::verify(&bundle)
+}
+
+/// Évalue un payload selon la politique Patient-Safe d'une destination (local, cloud, teo)
+#[tauri::command]
+fn validate_patient_safe(destination: Option<String>, payload: serde_json::Value) -> Result<pii::policy::PolicyReport, String> {
+    let destination = match destination.as_deref() {
+        Some(value) => pii::policy::Destination::parse(value)
+            .ok_or_else(|| "destination must be one of: local, cloud, teo".to_string())?,
+        None => pii::policy::Destination::Cloud,
+    };
+    Ok(pii::policy::evaluate(destination, &payload))
+}
+
+/// Récupère la configuration TÉO Hub actuelle (sans secrets)
+#[tauri::command]
+fn teo_get_config() -> teo_client::models::TeoHubConfigInfo {
+    let cfg = config::get_config();
+    teo_client::models::TeoHubConfigInfo {
+        enabled: cfg.teo_hub.enabled,
+        host: cfg.teo_hub.host.clone(),
+        port: cfg.teo_hub.port,
+        tls_enabled: cfg.teo_hub.tls_enabled,
+        timeout_secs: cfg.teo_hub.timeout_secs,
+        retry_count: cfg.teo_hub.retry_count,
+        has_api_token: !cfg.teo_hub.api_token.is_empty()
+            || crate::database::keychain::get_teo_token()
+                .map(|opt| opt.is_some()).unwrap_or(false),
+        has_tls_certs: !cfg.teo_hub.cert_file.is_empty() && !cfg.teo_hub.key_file.is_empty(),
+    }
+}
+
+/// Récupère le statut de connexion TÉO Hub
+#[tauri::command]
+fn teo_get_connection_status() -> String {
+    format!("{:?}", teo_client::get_connection_status())
+}
+
+/// 🆕 Informations runtime pour le Debug Panel
+#[derive(Serialize)]
+struct RuntimeInfo {
+    disable_api_auth: bool,
+    http_port: u16,
+    config_path: String,
+    teo_hub_enabled: bool,
+    build_version: String,
+}
+
+#[tauri::command]
+fn get_runtime_info() -> RuntimeInfo {
+    let cfg = config::get_config();
+    let config_path = config::AppConfig::config_path()
+        .map(|p| p.to_string_lossy().to_string())
+        .unwrap_or_else(|| "inconnu".to_string());
+    
+    RuntimeInfo {
+        disable_api_auth: cfg.disable_api_auth,
+        http_port: cfg.http_port,
+        config_path,
+        teo_hub_enabled: cfg.teo_hub.enabled,
+        build_version: env!("CARGO_PKG_VERSION").to_string(),
+    }
+}
+
+// 🔗 EXTRACTION DU TID DEPUIS UNE DEEP LINK
+// Formats supportés:
+//   - airadcr://open?tid=ABC123
+//   - airadcr://open/ABC123
+//   - airadcr://ABC123
+fn extract_tid_from_deep_link(url: &str) -> Option<String> {
+    // Retirer le préfixe airadcr://
+    let path = url.strip_prefix("airadcr://")?;
+    
+    // 🛡️ SÉCURITÉ: Fonction de validation du tid
+    let validate_tid = |tid: &str| -> Option<String> {
+        // Limite de longueur (protection DoS)
+        if tid.is_empty() || tid.len() > 64 {
+            if tid.len() > 64 {
+                log::warn!("[Deep Link] tid trop long rejeté: {} chars", tid.len());
+            }
+            return None;
+        }
+        // Validation caractères autorisés (alphanumériques, tirets, underscores)
+        if tid.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_') {
+            Some(tid.to_string())
+        } else {
+            log::warn!("[Deep Link] tid avec caractères invalides rejeté");
+            None
+        }
+    };
+    
+    // Format: airadcr://open?tid=ABC123
+    if path.contains("tid=") {
+        let tid = path.split("tid=").nth(1)?;
+        let tid = tid.split('&').next()?; // Au cas où il y a d'autres params
+        return validate_tid(tid);
+    }
+    
+    // Format: airadcr://open/ABC123
+    if path.starts_with("open/") {
+        let tid = path.strip_prefix("open/")?;
+        return validate_tid(tid);
+    }
+    
+    // Format: airadcr://ABC123 (direct)
+    let tid = path.trim_start_matches("open").trim_start_matches('/').trim_start_matches('?');
+    validate_tid(tid)
+}
+
+// 🔑 REPRISE DE LA CLÉ SQLCIPHER AU LANCEMENT (keychain effacé)
+// --recover-key=<séquestre> avec --identity=<clé privée age> ou --passphrase
+// (lue dans AIRADCR_RECOVERY_PASSPHRASE, jamais sur la ligne de commande).
+// La clé est vérifiée sur la base du poste, ou sur --backup=<fichier>.
+fn recover_key_from_args(
+    db_path: &std::path::Path,
+) -> Option<(std::path::PathBuf, Result<database::key_escrow::RecoveryReport, String>)> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let value = |name: &str| {
+        args.iter()
+            .find_map(|arg| arg.strip_prefix(name))
+            .map(std::path::PathBuf::from)
+    };
+    
+    let escrow = value("--recover-key=")?;
+    let identity_file = value("--identity=");
+    let passphrase = args
+        .iter()
+        .any(|arg| arg == "--passphrase")
+        .then(|| std::env::var("AIRADCR_RECOVERY_PASSPHRASE").ok())
+        .flatten();
+    let verify_path = value("--backup=").unwrap_or_else(|| db_path.to_path_buf());
+    
+    warn!("[KeyEscrow] Reprise de clé demandée au lancement depuis {:?}", escrow);
+    let result = database::key_escrow::recover_offline(
+        &escrow,
+        identity_file.as_deref(),
+        passphrase.as_deref(),
+        &verify_path,
+    );
+    Some((escrow, result))
 }
 
 // 🔗 TRAITEMENT DES DEEP LINKS AU PREMIER LANCEMENT
@@ -1646,6 +2622,7 @@ fn process_initial_deep_link(app: &tauri::App) {
                         thread::sleep(Duration::from_millis(1500));
                         // Émettre uniquement le tid, pas l'URL complète
                         let _ = window_clone.emit("airadcr:navigate_to_report", &tid_clone);
+                        worklist::set_current_report(Some(tid_clone.as_str()));
                         info!("[Deep Link] Navigation émise vers tid={}", tid_clone);
                     });
                 }
@@ -1663,6 +2640,7 @@ fn process_initial_deep_link(app: &tauri::App) {
                     thread::sleep(Duration::from_millis(1500));
                     // Émettre uniquement le tid, pas l'URL complète
                     let _ = window_clone.emit("airadcr:navigate_to_report", &tid_string);
+                    worklist::set_current_report(Some(tid_string.as_str()));
                     info!("[CLI] Navigation émise vers tid={}", tid_string);
                 });
             }
@@ -1698,10 +2676,19 @@ fn main() {
         .unwrap_or_else(|| std::path::PathBuf::from("."))
         .join("airadcr-desktop");
     
-    let db = match database::Database::new(app_data_dir.clone()) {
+    // 🔑 Reprise de clé avant l'ouverture : sans clé, la base ne s'ouvre pas
+    let key_recovery = recover_key_from_args(&app_data_dir.join("pending_reports.db"));
+    if let Some((escrow, result @ Err(_))) = &key_recovery {
+        database::key_escrow::audit_recovery(result, escrow);
+        std::process::exit(1);
+    }
+    
+    let db = match database::Database::new(app_data_dir) {
         Ok(db) => {
             info!("[Database] Initialisée avec succès");
-            Arc::new(db)
+            let db = Arc::new(db);
+            let _ = SHARED_DB.set(Arc::clone(&db));
+            db
         }
         Err(e) => {
             error!("[Database] Erreur d'initialisation: {}", e);
@@ -1710,6 +2697,13 @@ fn main() {
         }
     };
     
+    // 📡 Sinks d'audit externes (syslog / JSON Lines) avant le premier accès HTTP
+    audit::init(&config::get_config().audit);
+    database::keychain::audit_keystore_startup();
+    if let Some((escrow, result)) = &key_recovery {
+        database::key_escrow::audit_recovery(result, escrow);
+    }
+    
     // Clone pour le serveur HTTP
     let db_for_server = Arc::clone(&db);
     
@@ -1718,6 +2712,8 @@ fn main() {
         let rt = tokio::runtime::Runtime::new().expect("Failed to create Tokio runtime");
         rt.block_on(async {
             let config = crate::config::get_config();
+            // 🔗 Export OTLP des spans (l'exporteur gRPC requiert le runtime Tokio)
+            telemetry::init(&config.tracing);
             if let Err(e) = http_server::start_server(config.http_port, &config.http_bind_address, db_for_server).await {
                 error!("[HTTP Server] Erreur: {}", e);
             }
@@ -1726,43 +2722,89 @@ fn main() {
     
     // Clone pour le cleanup périodique et backup
     let db_for_cleanup = Arc::clone(&db);
-    let db_path = app_data_dir.join("airadcr.db");
     
-    // 🧹 Démarrer le cleanup automatique des rapports expirés + backup quotidien (toutes les 10 minutes)
+    // 🧹 Démarrer la purge de rétention automatique + backup quotidien (toutes les 10 minutes)
     thread::spawn(move || {
         use std::sync::atomic::{AtomicU64, Ordering};
         use crate::database::backup::BackupManager;
         
         // Compteur pour backup quotidien (1 jour = 144 cycles de 10 min)
         static BACKUP_COUNTER: AtomicU64 = AtomicU64::new(0);
-        let backup_manager = BackupManager::new(db_path, 7); // 7 jours de rétention
+        let config = crate::config::get_config();
+        let backup_manager = config.backup_enabled.then(|| {
+            BackupManager::new(db_for_cleanup.path().to_path_buf(), config.backup_retention_days)
+        });
+        
+        let publish = |manager: &BackupManager, backup: &crate::database::backup::BackupInfo| {
+            publish_backup(&db_for_cleanup, manager, backup);
+        };
+        
+        // Rotation de clé interrompue (arrêt pendant le re-chiffrement des backups)
+        database::key_rotation::finish_pending(&db_for_cleanup);
+        
+        // Séquestre de la clé active (reprise après sinistre)
+        escrow_db_key(&db_for_cleanup);
         
         // Backup initial au démarrage
-        match backup_manager.create_backup() {
-            Ok(path) => info!("[Backup] Backup initial créé: {:?}", path),
-            Err(e) => warn!("[Backup] Erreur backup initial: {}", e),
+        match &backup_manager {
+            Some(manager) => match manager.create_backup(&db_for_cleanup) {
+                Ok(backup) => {
+                    info!("[Backup] Backup initial créé: {}", backup.path);
+                    publish(manager, &backup);
+                }
+                Err(e) => warn!("[Backup] Erreur backup initial: {}", e),
+            },
+            None => info!("[Backup] Backups automatiques désactivés (backup_enabled = false)"),
         }
         
         loop {
             thread::sleep(Duration::from_secs(600)); // 10 minutes
             
-            // Cleanup des rapports expirés
-            match db_for_cleanup.cleanup_expired_reports() {
-                Ok(count) if count > 0 => {
-                    info!("[Cleanup] {} rapport(s) expiré(s) supprimé(s)", count);
+            // Restauration ou rotation de clé en cours : cycle sauté
+            if database::restore::maintenance_active() {
+                info!("[Cleanup] Maintenance de la base en cours, cycle ignoré");
+                continue;
+            }
+            
+            // Rotation planifiée de la clé SQLCipher, suivie d'un backup avec la nouvelle clé
+            if database::key_rotation::is_due(&db_for_cleanup, &config.key_rotation, chrono::Local::now()) {
+                match database::key_rotation::rotate(&db_for_cleanup, database::key_rotation::RotationTrigger::Scheduled) {
+                    Ok(report) => {
+                        info!("[KeyRotation] Rotation planifiée terminée (clé v{})", report.to_version);
+                        escrow_db_key(&db_for_cleanup);
+                        if let Some(manager) = &backup_manager {
+                            match manager.create_backup(&db_for_cleanup) {
+                                Ok(backup) => publish(manager, &backup),
+                                Err(e) => error!("[Backup] Erreur backup après rotation: {}", e),
+                            }
+                        }
+                    }
+                    Err(e) => error!("[KeyRotation] Rotation planifiée échouée: {}", e),
+                }
+            }
+            
+            // Purge selon les règles de rétention (rapports + logs d'accès)
+            match retention::run_cycle(&db_for_cleanup) {
+                Ok(report) if !report.dry_run && report.total_reports + report.total_access_logs > 0 => {
+                    info!(
+                        "[Cleanup] {} rapport(s) et {} log(s) d'accès supprimé(s)",
+                        report.total_reports, report.total_access_logs
+                    );
                 }
-                Ok(_) => {} // Rien à nettoyer
+                Ok(_) => {} // Rien à nettoyer (ou simulation)
                 Err(e) => error!("[Cleanup] Erreur: {}", e),
             }
             
             // Backup quotidien (toutes les 144 cycles = 24h)
             let counter = BACKUP_COUNTER.fetch_add(1, Ordering::SeqCst);
             if counter % 144 == 0 && counter > 0 {
-                match backup_manager.create_backup() {
-                    Ok(path) => {
-                        info!("[Backup] Backup quotidien créé: {:?}", path);
+                let Some(manager) = &backup_manager else { continue };
+                match manager.create_backup(&db_for_cleanup) {
+                    Ok(backup) => {
+                        info!("[Backup] Backup quotidien créé: {}", backup.path);
+                        publish(manager, &backup);
                         // Nettoyer les anciens backups
-                        if let Ok(deleted) = backup_manager.cleanup_old_backups() {
+                        if let Ok(deleted) = manager.cleanup_old_backups() {
                             if deleted > 0 {
                                 info!("[Backup] {} ancien(s) backup(s) supprimé(s)", deleted);
                             }
@@ -1778,6 +2820,7 @@ fn main() {
     let show = CustomMenuItem::new("show".to_string(), "Afficher");
     let hide = CustomMenuItem::new("hide".to_string(), "Masquer");
     let always_on_top = CustomMenuItem::new("always_on_top".to_string(), "Toujours au premier plan");
+    let switch_profile = CustomMenuItem::new("switch_profile".to_string(), "Changer de radiologue…");
 
     let tray_menu = SystemTrayMenu::new()
         .add_item(show)
@@ -1785,6 +2828,8 @@ fn main() {
         .add_native_item(tauri::SystemTrayMenuItem::Separator)
         .add_item(always_on_top)
         .add_native_item(tauri::SystemTrayMenuItem::Separator)
+        .add_item(switch_profile)
+        .add_native_item(tauri::SystemTrayMenuItem::Separator)
         .add_item(quit);
 
     let system_tray = SystemTray::new().with_menu(tray_menu);
@@ -1812,6 +2857,7 @@ fn main() {
                         if let Some(window) = app.get_window("main") {
                             // Émettre uniquement le tid
                             let _ = window.emit("airadcr:navigate_to_report", &tid);
+                            worklist::set_current_report(Some(tid.as_str()));
                         }
                     }
                     break;
@@ -1823,6 +2869,7 @@ fn main() {
                     if let Some(window) = app.get_window("main") {
                         // Émettre uniquement le tid
                         let _ = window.emit("airadcr:navigate_to_report", tid);
+                        worklist::set_current_report(Some(tid));
                     }
                     break;
                 }
@@ -1845,6 +2892,7 @@ fn main() {
             }
             SystemTrayEvent::MenuItemClick { id, .. } => match id.as_str() {
                 "quit" => {
+                    telemetry::shutdown();
                     app.exit(0);
                 }
                 "show" => {
@@ -1858,6 +2906,14 @@ fn main() {
                         let _ = window.hide();
                     }
                 }
+                "switch_profile" => {
+                    // Le sélecteur de profil est affiché par le frontend
+                    if let Some(window) = app.get_window("main") {
+                        let _ = window.show();
+                        let _ = window.set_focus();
+                        let _ = window.emit("airadcr:show_profile_switcher", ());
+                    }
+                }
                 "always_on_top" => {
                     if let Some(window) = app.get_window("main") {
                         let state = app.state::<AppState>();
@@ -1921,6 +2977,15 @@ fn main() {
             get_all_pending_reports,
             get_api_keys_list,
             get_database_stats,
+            get_migrations_status,
+            list_backups_cmd,
+            restore_backup_cmd,
+            get_key_rotation_status,
+            rotate_db_key_cmd,
+            get_keystore_status,
+            get_key_escrow_status,
+            escrow_db_key_cmd,
+            recover_db_key_cmd,
             cleanup_expired_reports_cmd,
             delete_pending_report_cmd,
             create_api_key_cmd,
@@ -1929,10 +2994,44 @@ fn main() {
             get_access_logs,
             get_access_logs_stats,
             cleanup_access_logs,
+            // 🧹 Commandes Rétention
+            preview_retention_purge,
+            run_retention_purge,
+            get_retention_purges,
+            // 👤 Commandes Profils radiologues
+            list_user_profiles,
+            create_user_profile,
+            deactivate_user_profile,
+            get_active_user_profile,
+            switch_user_profile,
+            get_user_preferences,
+            set_user_preference,
+            assign_pending_report_cmd,
+            // 📋 Commandes Liste de travail
+            get_worklist,
+            open_next_report,
+            set_report_priority,
+            set_current_report,
+            search_reports,
+            list_report_templates,
+            save_report_template,
+            delete_report_template,
+            merge_report_template,
+            list_report_locks,
+            heartbeat_report_lock,
+            release_report_lock,
+            force_release_report_lock,
             // 🆕 Commandes TÉO Hub Client
             teo_check_health,
             teo_fetch_report,
             teo_submit_approved,
+            scan_pii,
+            validate_patient_safe,
+            reidentify_pseudonym,
+            gdpr_export,
+            gdpr_erase,
+            get_erasure_certificates,
+            verify_gdpr_bundle,
             teo_get_config,
             teo_get_connection_status,
             get_runtime_info,
@@ -1951,15 +3050,50 @@ fn main() {
             let _ = APP_HANDLE.set(app.handle());
             info!("[Global] AppHandle stocké pour communication HTTP → Tauri");
             
+            // 👤 Afficher le radiologue actif dans le tray
+            let app_data_dir = dirs::data_local_dir()
+                .unwrap_or_else(|| std::path::PathBuf::from("."))
+                .join("airadcr-desktop");
+            if let Ok(db) = database::Database::new(app_data_dir) {
+                update_profile_tray_item(&app.handle(), db.get_active_user().ok().flatten().as_ref());
+            }
+            
             // 🔗 Traiter les deep links au premier lancement
             process_initial_deep_link(app);
             
             // 🎤 Démarrer le thread de détection SpeechMike natif (HID USB)
             let sm_state = std::sync::Arc::new(speechmike::SpeechMikeState::default());
             app.manage(sm_state.clone());
+            let sm_status = sm_state.status.clone();
             speechmike::start_speechmike_thread(tx, sm_state, app.handle());
             info!("[SpeechMike] Thread de détection HID natif lancé");
             
+            // 🩺 Sondes /health/ready pour les composants hors serveur HTTP
+            let health_config = config::get_config().health.clone();
+            http_server::health::register_probe("speechmike", health_config.require_speechmike, move || {
+                match sm_status.lock() {
+                    Ok(status) if status.connected => http_server::health::ComponentHealth::ok(
+                        format!("{} connecté", status.device_name)),
+                    Ok(status) => http_server::health::ComponentHealth::degraded(status.device_name.clone()),
+                    Err(_) => http_server::health::ComponentHealth::down("État SpeechMike indisponible"),
+                }
+            });
+            
+            let shortcuts_handle = app.handle();
+            http_server::health::register_probe("global_shortcuts", false, move || {
+                let manager = shortcuts_handle.global_shortcut_manager();
+                let missing: Vec<&str> = GLOBAL_SHORTCUTS
+                    .iter()
+                    .map(|(accelerator, _)| *accelerator)
+                    .filter(|accelerator| !manager.is_registered(accelerator).unwrap_or(false))
+                    .collect();
+                if missing.is_empty() {
+                    http_server::health::ComponentHealth::ok(format!("{} raccourcis enregistrés", GLOBAL_SHORTCUTS.len()))
+                } else {
+                    http_server::health::ComponentHealth::degraded(format!("Non enregistrés: {}", missing.join(", ")))
+                }
+            });
+            
             // 🎯 Assertion Always-on-top après stabilisation WebView2
             if let Some(window) = app.get_window("main") {
                 let window_clone = window.clone();
@@ -1981,6 +3115,34 @@ fn main() {
     });
 }
 
+/// Action déclenchée par un raccourci global
+#[derive(Clone, Copy)]
+enum ShortcutAction {
+    /// Événement Tauri émis directement vers la fenêtre principale
+    Emit(&'static str),
+    /// Action transmise à la task tokio (dictée, injection, urgence)
+    Dispatch(&'static str),
+}
+
+/// Raccourcis globaux enregistrés par register_global_shortcuts (vérifiés par /health/ready)
+const GLOBAL_SHORTCUTS: &[(&str, ShortcutAction)] = &[
+    // 🎨 DEBUG PANEL / 📋 LOG WINDOW / 🧪 TEST INJECTION
+    ("CmdOrCtrl+Alt+D", ShortcutAction::Emit("airadcr:toggle_debug")),
+    ("CmdOrCtrl+Alt+L", ShortcutAction::Emit("airadcr:toggle_logs")),
+    ("CmdOrCtrl+Alt+I", ShortcutAction::Emit("airadcr:test_injection")),
+    // 🎤 DICTATION: Start/Stop et Pause/Resume
+    ("CmdOrCtrl+Shift+D", ShortcutAction::Dispatch("toggle_recording")),
+    ("CmdOrCtrl+Shift+P", ShortcutAction::Dispatch("toggle_pause")),
+    // 💉 INJECTION: texte brut et rapport structuré
+    ("CmdOrCtrl+Shift+T", ShortcutAction::Dispatch("inject_raw")),
+    ("CmdOrCtrl+Shift+S", ShortcutAction::Dispatch("inject_structured")),
+    // 🎯 ERGONOMIC: Alt+Space (évite conflit Spotlight macOS)
+    ("Alt+Space", ShortcutAction::Dispatch("toggle_recording")),
+    ("Alt+Shift+Space", ShortcutAction::Dispatch("toggle_pause")),
+    // ANTI-GHOST: F9 (désactiver click-through)
+    ("F9", ShortcutAction::Emit("airadcr:force_clickable")),
+];
+
 // ✅ Raccourcis globaux — Pattern officiel Tauri: channel tokio pour dispatch thread-safe
 // Corrige le problème fondamental : les callbacks GlobalShortcutManager s'exécutent dans un thread
 // secondaire où window.eval() (COM WebView2) et SetForegroundWindow() (UIPI) échouent silencieusement.
@@ -2012,6 +3174,23 @@ fn register_global_shortcuts(app_handle: tauri::AppHandle) -> tokio::sync::mpsc:
                         debug!("[Shortcuts/task] Émission airadcr:inject_structured");
                         window.emit("airadcr:inject_structured", ()).ok();
                     }
+                    "flag_urgent" => {
+                        debug!("[Shortcuts/task] Rapport en cours → urgent");
+                        // Accès SQLite hors du runtime async
+                        tauri::async_runtime::spawn_blocking(|| {
+                            let app_data_dir = dirs::data_local_dir()
+                                .unwrap_or_else(|| std::path::PathBuf::from("."))
+                                .join("airadcr-desktop");
+                            match database::Database::new(app_data_dir) {
+                                Ok(db) => {
+                                    if let Err(e) = worklist::flag_current_report_urgent(&db) {
+                                        warn!("[Worklist] Impossible de marquer le rapport urgent: {}", e);
+                                    }
+                                }
+                                Err(e) => error!("[Worklist] Erreur ouverture DB: {}", e),
+                            }
+                        });
+                    }
                     _ => {}
                 }
             }
@@ -2020,105 +3199,33 @@ fn register_global_shortcuts(app_handle: tauri::AppHandle) -> tokio::sync::mpsc:
 
     let mut shortcut_manager = app_handle.global_shortcut_manager();
 
-    // 🎨 DEBUG PANEL: Ctrl+Alt+D
-    let handle_debug = app_handle.clone();
-    shortcut_manager
-        .register("CmdOrCtrl+Alt+D", move || {
-            debug!("[Shortcuts] CmdOrCtrl+Alt+D pressé (debug panel)");
-            if let Some(window) = handle_debug.get_window("main") {
-                window.emit("airadcr:toggle_debug", ()).ok();
-            }
-        })
-        .unwrap_or_else(|e| warn!("Erreur enregistrement CmdOrCtrl+Alt+D: {}", e));
-
-    // 📋 LOG WINDOW: Ctrl+Alt+L
-    let handle_logs = app_handle.clone();
-    shortcut_manager
-        .register("CmdOrCtrl+Alt+L", move || {
-            debug!("[Shortcuts] CmdOrCtrl+Alt+L pressé (log window)");
-            if let Some(window) = handle_logs.get_window("main") {
-                window.emit("airadcr:toggle_logs", ()).ok();
-            }
-        })
-        .unwrap_or_else(|e| warn!("Erreur enregistrement CmdOrCtrl+Alt+L: {}", e));
-
-    // 🧪 TEST INJECTION: Ctrl+Alt+I
-    let handle_test = app_handle.clone();
-    shortcut_manager
-        .register("CmdOrCtrl+Alt+I", move || {
-            debug!("[Shortcuts] CmdOrCtrl+Alt+I pressé (test injection)");
-            if let Some(window) = handle_test.get_window("main") {
-                window.emit("airadcr:test_injection", ()).ok();
+    // Enregistrement depuis la table GLOBAL_SHORTCUTS (même source que /health/ready)
+    for &(accelerator, action) in GLOBAL_SHORTCUTS {
+        let result = match action {
+            ShortcutAction::Emit(event) => {
+                let handle = app_handle.clone();
+                shortcut_manager.register(accelerator, move || {
+                    debug!("[Shortcuts] {} pressé → {}", accelerator, event);
+                    if let Some(window) = handle.get_window("main") {
+                        window.emit(event, ()).ok();
+                    }
+                })
             }
-        })
-        .unwrap_or_else(|e| warn!("Erreur enregistrement CmdOrCtrl+Alt+I: {}", e));
-
-    // 🎤 DICTATION: Ctrl+Shift+D (Start/Stop dictée)
-    let tx_d = tx.clone();
-    shortcut_manager
-        .register("CmdOrCtrl+Shift+D", move || {
-            debug!("[Shortcuts] CmdOrCtrl+Shift+D pressé → tx.send(toggle_recording)");
-            let _ = tx_d.send("toggle_recording");
-        })
-        .unwrap_or_else(|e| warn!("Erreur enregistrement CmdOrCtrl+Shift+D: {}", e));
-
-    // 🎤 DICTATION: Ctrl+Shift+P (Pause/Resume dictée)
-    let tx_p = tx.clone();
-    shortcut_manager
-        .register("CmdOrCtrl+Shift+P", move || {
-            debug!("[Shortcuts] CmdOrCtrl+Shift+P pressé → tx.send(toggle_pause)");
-            let _ = tx_p.send("toggle_pause");
-        })
-        .unwrap_or_else(|e| warn!("Erreur enregistrement CmdOrCtrl+Shift+P: {}", e));
-
-    // 💉 INJECTION: Ctrl+Shift+T (Inject texte brut)
-    let tx_t = tx.clone();
-    shortcut_manager
-        .register("CmdOrCtrl+Shift+T", move || {
-            debug!("[Shortcuts] CmdOrCtrl+Shift+T pressé → tx.send(inject_raw)");
-            let _ = tx_t.send("inject_raw");
-        })
-        .unwrap_or_else(|e| warn!("Erreur enregistrement CmdOrCtrl+Shift+T: {}", e));
-
-    // 💉 INJECTION: Ctrl+Shift+S (Inject rapport structuré)
-    let tx_s = tx.clone();
-    shortcut_manager
-        .register("CmdOrCtrl+Shift+S", move || {
-            debug!("[Shortcuts] CmdOrCtrl+Shift+S pressé → tx.send(inject_structured)");
-            let _ = tx_s.send("inject_structured");
-        })
-        .unwrap_or_else(|e| warn!("Erreur enregistrement CmdOrCtrl+Shift+S: {}", e));
-
-    // 🎯 ERGONOMIC: Alt+Space → toggle_recording (évite conflit Spotlight macOS)
-    let tx_space = tx.clone();
-    shortcut_manager
-        .register("Alt+Space", move || {
-            debug!("[Shortcuts] Alt+Space pressé (ergonomic) → toggle_recording");
-            let _ = tx_space.send("toggle_recording");
-        })
-        .unwrap_or_else(|e| warn!("Erreur enregistrement Alt+Space: {}", e));
-
-    // 🎯 ERGONOMIC: Alt+Shift+Space → toggle_pause
-    let tx_shift_space = tx.clone();
-    shortcut_manager
-        .register("Alt+Shift+Space", move || {
-            debug!("[Shortcuts] Alt+Shift+Space pressé (ergonomic) → toggle_pause");
-            let _ = tx_shift_space.send("toggle_pause");
-        })
-        .unwrap_or_else(|e| warn!("Erreur enregistrement Alt+Shift+Space: {}", e));
-
-    // ANTI-GHOST: F9 (désactiver click-through)
-    let handle_f9 = app_handle.clone();
-    shortcut_manager
-        .register("F9", move || {
-            debug!("[Shortcuts] F9 pressé (anti-fantôme)");
-            if let Some(window) = handle_f9.get_window("main") {
-                window.emit("airadcr:force_clickable", ()).ok();
+            ShortcutAction::Dispatch(task_action) => {
+                let tx_action = tx.clone();
+                shortcut_manager.register(accelerator, move || {
+                    debug!("[Shortcuts] {} pressé → tx.send({})", accelerator, task_action);
+                    let _ = tx_action.send(task_action);
+                })
             }
-        })
-        .unwrap_or_else(|e| warn!("Erreur enregistrement F9: {}", e));
+        };
+        result.unwrap_or_else(|e| warn!("Erreur enregistrement {}: {}", accelerator, e));
+    }
 
-    info!("[Shortcuts] Raccourcis globaux enregistrés (channel tokio): CmdOrCtrl+Alt+D/L/I, F9, CmdOrCtrl+Shift+D/P/T/S, Alt+Space, Alt+Shift+Space");
+    info!(
+        "[Shortcuts] Raccourcis globaux enregistrés (channel tokio): {}",
+        GLOBAL_SHORTCUTS.iter().map(|(accelerator, _)| *accelerator).collect::<Vec<_>>().join(", ")
+    );
     
     tx // 🆕 Retourner le sender pour le thread SpeechMike
 }
\ No newline at end of file
@@ -0,0 +1,603 @@
+// ============================================================================
+// AIRADCR Desktop - Détection des données nominatives dans les valeurs
+// ============================================================================
+// Complète la validation Patient-Safe (noms de clés) par une analyse du texte
+// des valeurs avant tout envoi hors du poste (TÉO Hub…) :
+//   - NIR / INS (clé de contrôle exigée, ou libellé NIR/INS/sécurité sociale
+//     devant un numéro sans clé), date de naissance (en contexte),
+//     téléphone (FR/CH), email, IPP (en contexte + motifs de l'établissement),
+//     noms (civilité + dictionnaires configurables)
+//   - action par champ : reject (envoi refusé), redact ([NIR]) ou tokenise
+//     ([NIR_1], même jeton pour une même valeur dans un payload) ; une valeur
+//     numérique JSON masquée reste un nombre (0)
+//   - rapport de détection avec chemin JSON, sans la valeur détectée
+//   - règles sur les noms de clés et destinations : voir policy
+//   - pseudonymes HMAC des identifiants dans les logs : voir pseudonym
+// ============================================================================
+
+pub mod policy;
+pub mod pseudonym;
+
+use log::{info, warn};
+use regex::{Regex, RegexBuilder};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use crate::config::{get_config, PiiConfig};
+
+/// Type de donnée nominative détectée
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PiiKind {
+    Nir,
+    BirthDate,
+    Phone,
+    Email,
+    Ipp,
+    Name,
+}
+
+impl PiiKind {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "nir" | "ins" => Some(PiiKind::Nir),
+            "birth_date" | "dob" => Some(PiiKind::BirthDate),
+            "phone" => Some(PiiKind::Phone),
+            "email" => Some(PiiKind::Email),
+            "ipp" => Some(PiiKind::Ipp),
+            "name" => Some(PiiKind::Name),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PiiKind::Nir => "nir",
+            PiiKind::BirthDate => "birth_date",
+            PiiKind::Phone => "phone",
+            PiiKind::Email => "email",
+            PiiKind::Ipp => "ipp",
+            PiiKind::Name => "name",
+        }
+    }
+
+    /// Libellé inséré à la place de la valeur masquée
+    fn label(self) -> &'static str {
+        match self {
+            PiiKind::Nir => "NIR",
+            PiiKind::BirthDate => "DATE_NAISSANCE",
+            PiiKind::Phone => "TELEPHONE",
+            PiiKind::Email => "EMAIL",
+            PiiKind::Ipp => "IPP",
+            PiiKind::Name => "NOM",
+        }
+    }
+}
+
+/// Action appliquée à un champ contenant une donnée nominative
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PiiAction {
+    Reject,
+    Redact,
+    Tokenise,
+}
+
+impl PiiAction {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "reject" => Some(PiiAction::Reject),
+            "redact" => Some(PiiAction::Redact),
+            "tokenise" | "tokenize" => Some(PiiAction::Tokenise),
+            _ => None,
+        }
+    }
+}
+
+/// Donnée nominative détectée (la valeur elle-même n'est jamais rapportée)
+#[derive(Debug, Clone, Serialize)]
+pub struct PiiFinding {
+    /// Chemin JSON du champ (ex: "structured.sections[0].content")
+    pub path: String,
+    pub kind: PiiKind,
+    pub action: PiiAction,
+    /// Position dans le texte (en caractères)
+    pub start: usize,
+    pub end: usize,
+    /// Aperçu masqué (ex: "Du****")
+    pub preview: String,
+}
+
+/// Résultat d'une analyse : payload transformé et détections
+#[derive(Debug, Clone, Serialize)]
+pub struct PiiScan {
+    pub value: Value,
+    pub findings: Vec<PiiFinding>,
+}
+
+impl PiiScan {
+    /// Vrai si au moins une détection impose le refus de l'envoi
+    pub fn rejected(&self) -> bool {
+        self.findings.iter().any(|f| f.action == PiiAction::Reject)
+    }
+
+    /// Résumé des détections bloquantes (ex: "nir at approved_report")
+    pub fn summary(&self) -> String {
+        self.findings
+            .iter()
+            .filter(|f| f.action == PiiAction::Reject)
+            .map(|f| format!("{} at {}", f.kind.as_str(), f.path))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+// ============================================================================
+// Détecteurs
+// ============================================================================
+
+/// Contrôle complémentaire de la valeur capturée
+type Check = fn(&str) -> bool;
+
+struct Detector {
+    kind: PiiKind,
+    regex: Regex,
+    check: Check,
+}
+
+/// NIR complet : 13 caractères et clé de contrôle à 2 chiffres
+const NIR_PATTERN: &str =
+    r"\b[12][ .]?\d{2}[ .]?\d{2}[ .]?(?:\d{2}|2[ABab])[ .]?\d{3}[ .]?\d{3}[ .]?\d{2}\b";
+
+/// NIR sans clé, uniquement précédé d'un libellé explicite (un nombre de 13
+/// chiffres seul peut être un numéro d'accession ou un composant d'UID DICOM)
+const NIR_CONTEXT_PATTERN: &str = r"(?i)\b(?:NIR|INS|n°\s*(?:de\s+)?s[ée]cu(?:rit[ée]\s+sociale)?|num[ée]ro\s+de\s+s[ée]curit[ée]\s+sociale|s[ée]curit[ée]\s+sociale)\s*(?::|n°|#)?\s*([12][ .]?\d{2}[ .]?\d{2}[ .]?(?:\d{2}|2[ABab])[ .]?\d{3}[ .]?\d{3}(?:[ .]?\d{2})?)\b";
+
+const BIRTH_DATE_PATTERN: &str = r"(?i)\b(?:n[ée]e?\s+le|date\s+de\s+naissance|ddn|dob|date\s+of\s+birth|born(?:\s+on)?)\s*:?\s*(\d{1,2}[/.\-]\d{1,2}[/.\-](?:\d{4}|\d{2})|\d{4}-\d{2}-\d{2}|\d{1,2}\s+(?:janvier|f[ée]vrier|mars|avril|mai|juin|juillet|ao[uû]t|septembre|octobre|novembre|d[ée]cembre)\s+\d{4})";
+
+const PHONE_PATTERN: &str = r"(?:(?:\+|\b00)33\s?(?:\(0\)\s?)?|\b0)[1-9](?:[\s.\-]?\d{2}){4}\b|(?:(?:\+|\b00)41\s?(?:\(0\)\s?)?|\b0)[1-9]\d[\s.\-]?\d{3}(?:[\s.\-]?\d{2}){2}\b";
+
+const EMAIL_PATTERN: &str = r"\b[A-Za-z0-9._%+\-]+@[A-Za-z0-9.\-]+\.[A-Za-z]{2,}\b";
+
+const IPP_PATTERN: &str = r"(?i)\b(?:IPP|NIP|n°\s*(?:de\s+)?patient|num[ée]ro\s+(?:de\s+)?patient|patient\s+(?:id|n°))\s*(?::|n°|#)?\s*([A-Z0-9][A-Z0-9\-]{3,19})\b";
+
+const HONORIFIC_PATTERN: &str = r"\b(?:M\.|Mme|Mlle|Monsieur|Madame|Mademoiselle|Mr|Mrs|Ms)\s+(\p{Lu}[\p{L}'\-]+(?:\s+\p{Lu}[\p{L}'\-]+)?)";
+
+fn any_value(_: &str) -> bool {
+    true
+}
+
+fn contains_digit(value: &str) -> bool {
+    value.chars().any(|c| c.is_ascii_digit())
+}
+
+/// NIR avec clé de contrôle valide (hors contexte, la clé est obligatoire)
+fn valid_nir(value: &str) -> bool {
+    nir_key_matches(value) == Some(true)
+}
+
+/// NIR précédé d'un libellé : clé valide, ou absente
+fn valid_nir_in_context(value: &str) -> bool {
+    nir_key_matches(value) != Some(false)
+}
+
+/// Vérifie la clé de contrôle d'un NIR (97 - NIR mod 97 ; Corse 2A/2B = 19/18) ;
+/// None si le numéro n'a pas de clé
+fn nir_key_matches(value: &str) -> Option<bool> {
+    let compact: String = value.chars().filter(|c| c.is_ascii_alphanumeric()).collect::<String>().to_uppercase();
+    let (number, key) = match compact.len() {
+        13 => (&compact[..], None),
+        15 => (&compact[..13], Some(&compact[13..])),
+        _ => return Some(false),
+    };
+    let number = number.replace("2A", "19").replace("2B", "18");
+    let number: u64 = match number.parse() {
+        Ok(n) => n,
+        Err(_) => return Some(false),
+    };
+    match key.map(str::parse::<u64>) {
+        Some(Ok(key)) => Some(key == 97 - number % 97),
+        Some(Err(_)) => Some(false),
+        None => None,
+    }
+}
+
+/// Analyseur compilé à partir de la configuration
+pub struct PiiScanner {
+    detectors: Vec<Detector>,
+    default_action: PiiAction,
+    field_actions: HashMap<String, PiiAction>,
+}
+
+impl PiiScanner {
+    pub fn new(config: &PiiConfig) -> Self {
+        let enabled: Vec<PiiKind> = config
+            .detectors
+            .iter()
+            .filter_map(|d| {
+                let kind = PiiKind::parse(d);
+                if kind.is_none() {
+                    warn!("⚠️ [PII] Détecteur inconnu ignoré: {}", d);
+                }
+                kind
+            })
+            .collect();
+
+        let builtin: [(PiiKind, &str, Check); 7] = [
+            (PiiKind::Nir, NIR_PATTERN, valid_nir),
+            (PiiKind::Nir, NIR_CONTEXT_PATTERN, valid_nir_in_context),
+            (PiiKind::BirthDate, BIRTH_DATE_PATTERN, any_value),
+            (PiiKind::Phone, PHONE_PATTERN, any_value),
+            (PiiKind::Email, EMAIL_PATTERN, any_value),
+            (PiiKind::Ipp, IPP_PATTERN, contains_digit),
+            (PiiKind::Name, HONORIFIC_PATTERN, any_value),
+        ];
+
+        let mut detectors: Vec<Detector> = builtin
+            .into_iter()
+            .filter(|(kind, _, _)| enabled.contains(kind))
+            .map(|(kind, pattern, check)| Detector {
+                kind,
+                regex: Regex::new(pattern).expect("motif PII embarqué invalide"),
+                check,
+            })
+            .collect();
+
+        if enabled.contains(&PiiKind::Ipp) {
+            for pattern in &config.ipp_patterns {
+                match Regex::new(pattern) {
+                    Ok(regex) => detectors.push(Detector { kind: PiiKind::Ipp, regex, check: any_value }),
+                    Err(e) => warn!("⚠️ [PII] Motif IPP invalide ignoré ({}): {}", pattern, e),
+                }
+            }
+        }
+
+        if enabled.contains(&PiiKind::Name) {
+            if let Some(regex) = load_name_dictionaries(&config.name_dictionaries) {
+                detectors.push(Detector { kind: PiiKind::Name, regex, check: any_value });
+            }
+        }
+
+        let default_action = PiiAction::parse(&config.default_action).unwrap_or_else(|| {
+            warn!("⚠️ [PII] Action par défaut inconnue '{}', reject utilisé", config.default_action);
+            PiiAction::Reject
+        });
+
+        let field_actions = config
+            .field_actions
+            .iter()
+            .filter_map(|(field, action)| match PiiAction::parse(action) {
+                Some(action) => Some((field.clone(), action)),
+                None => {
+                    warn!("⚠️ [PII] Action inconnue ignorée pour {}: {}", field, action);
+                    None
+                }
+            })
+            .collect();
+
+        Self {
+            detectors,
+            default_action,
+            field_actions,
+        }
+    }
+
+    /// Action applicable à un champ : chemin sans indices, puis nom du champ, puis défaut
+    fn action_for(&self, path: &str) -> PiiAction {
+        let normalized = strip_indices(path);
+        let leaf = normalized.rsplit('.').next().unwrap_or(&normalized).trim_end_matches("[]");
+        self.field_actions
+            .get(&normalized)
+            .or_else(|| self.field_actions.get(leaf))
+            .copied()
+            .unwrap_or(self.default_action)
+    }
+
+    /// Détections dans un texte : (type, début, fin) en octets, sans chevauchement
+    fn detect(&self, text: &str) -> Vec<(PiiKind, usize, usize)> {
+        let mut spans: Vec<(PiiKind, usize, usize)> = Vec::new();
+        for detector in &self.detectors {
+            for caps in detector.regex.captures_iter(text) {
+                let m = match caps.get(1).or_else(|| caps.get(0)) {
+                    Some(m) => m,
+                    None => continue,
+                };
+                if (detector.check)(m.as_str()) {
+                    spans.push((detector.kind, m.start(), m.end()));
+                }
+            }
+        }
+
+        // Plus longue détection en premier à position égale, puis suppression des chevauchements
+        spans.sort_by(|a, b| a.1.cmp(&b.1).then(b.2.cmp(&a.2)));
+        let mut kept: Vec<(PiiKind, usize, usize)> = Vec::new();
+        for span in spans {
+            match kept.last() {
+                Some(last) if span.1 < last.2 => {}
+                _ => kept.push(span),
+            }
+        }
+        kept
+    }
+
+    /// Analyse un payload ; `root` préfixe les chemins rapportés
+    pub fn scan(&self, value: &Value, root: &str) -> PiiScan {
+        self.scan_filtered(value, root, &|_| false)
+    }
+
+    /// Analyse un payload en ignorant les champs pour lesquels `skip(chemin)` est vrai
+    pub fn scan_filtered(&self, value: &Value, root: &str, skip: &dyn Fn(&str) -> bool) -> PiiScan {
+        let mut findings = Vec::new();
+        let mut tokens = HashMap::new();
+        let value = self.scan_value(value, root, skip, &mut findings, &mut tokens);
+        PiiScan { value, findings }
+    }
+
+    fn scan_value(
+        &self,
+        value: &Value,
+        path: &str,
+        skip: &dyn Fn(&str) -> bool,
+        findings: &mut Vec<PiiFinding>,
+        tokens: &mut HashMap<(PiiKind, String), String>,
+    ) -> Value {
+        if !path.is_empty() && skip(path) {
+            return value.clone();
+        }
+        match value {
+            Value::Object(map) => Value::Object(
+                map.iter()
+                    .map(|(key, item)| {
+                        let child = if path.is_empty() { key.clone() } else { format!("{}.{}", path, key) };
+                        (key.clone(), self.scan_value(item, &child, skip, findings, tokens))
+                    })
+                    .collect(),
+            ),
+            Value::Array(items) => Value::Array(
+                items
+                    .iter()
+                    .enumerate()
+                    .map(|(i, item)| self.scan_value(item, &format!("{}[{}]", path, i), skip, findings, tokens))
+                    .collect(),
+            ),
+            Value::String(text) => match self.scan_text(text, path, findings, tokens) {
+                Some(replaced) => Value::String(replaced),
+                None => value.clone(),
+            },
+            // Un nombre masqué reste un nombre (le type attendu par le destinataire)
+            Value::Number(number) => match self.scan_text(&number.to_string(), path, findings, tokens) {
+                Some(_) => Value::from(0),
+                None => value.clone(),
+            },
+            _ => value.clone(),
+        }
+    }
+
+    /// Analyse un texte ; renvoie le texte transformé si une détection a été masquée
+    fn scan_text(
+        &self,
+        text: &str,
+        path: &str,
+        findings: &mut Vec<PiiFinding>,
+        tokens: &mut HashMap<(PiiKind, String), String>,
+    ) -> Option<String> {
+        let spans = self.detect(text);
+        if spans.is_empty() {
+            return None;
+        }
+
+        let action = self.action_for(path);
+        let mut output = String::with_capacity(text.len());
+        let mut cursor = 0;
+
+        for (kind, start, end) in spans {
+            let found = &text[start..end];
+            findings.push(PiiFinding {
+                path: path.to_string(),
+                kind,
+                action,
+                start: text[..start].chars().count(),
+                end: text[..end].chars().count(),
+                preview: mask(found),
+            });
+
+            output.push_str(&text[cursor..start]);
+            match action {
+                PiiAction::Reject => output.push_str(found),
+                PiiAction::Redact => output.push_str(&format!("[{}]", kind.label())),
+                PiiAction::Tokenise => {
+                    let next = tokens.keys().filter(|(k, _)| *k == kind).count() + 1;
+                    let token = tokens
+                        .entry((kind, normalize(found)))
+                        .or_insert_with(|| format!("[{}_{}]", kind.label(), next));
+                    output.push_str(token);
+                }
+            }
+            cursor = end;
+        }
+        output.push_str(&text[cursor..]);
+
+        (action != PiiAction::Reject).then_some(output)
+    }
+}
+
+/// "sections[0].content" → "sections[].content"
+pub(crate) fn strip_indices(path: &str) -> String {
+    let mut result = String::with_capacity(path.len());
+    let mut in_index = false;
+    for c in path.chars() {
+        match c {
+            '[' => {
+                in_index = true;
+                result.push('[');
+            }
+            ']' => {
+                in_index = false;
+                result.push(']');
+            }
+            _ if in_index => {}
+            _ => result.push(c),
+        }
+    }
+    result
+}
+
+/// Forme canonique d'une valeur pour l'attribution des jetons
+fn normalize(value: &str) -> String {
+    value
+        .chars()
+        .filter(|c| c.is_alphanumeric() || *c == '@')
+        .collect::<String>()
+        .to_lowercase()
+}
+
+/// Aperçu masqué : deux premiers caractères
+fn mask(value: &str) -> String {
+    format!("{}****", value.chars().take(2).collect::<String>())
+}
+
+/// Compile les dictionnaires de noms (un par ligne, # = commentaire) en une regex
+fn load_name_dictionaries(paths: &[String]) -> Option<Regex> {
+    let mut names: Vec<String> = Vec::new();
+    for path in paths {
+        match std::fs::read_to_string(path) {
+            Ok(content) => {
+                let before = names.len();
+                names.extend(
+                    content
+                        .lines()
+                        .map(str::trim)
+                        .filter(|line| line.chars().count() >= 3 && !line.starts_with('#'))
+                        .map(regex::escape),
+                );
+                info!("📖 [PII] {} nom(s) chargé(s) depuis {}", names.len() - before, path);
+            }
+            Err(e) => warn!("⚠️ [PII] Dictionnaire de noms illisible {}: {}", path, e),
+        }
+    }
+
+    if names.is_empty() {
+        return None;
+    }
+
+    RegexBuilder::new(&format!(r"\b(?:{})\b", names.join("|")))
+        .case_insensitive(true)
+        .size_limit(64 * 1024 * 1024)
+        .build()
+        .map_err(|e| warn!("⚠️ [PII] Dictionnaires de noms non compilables: {}", e))
+        .ok()
+}
+
+// ============================================================================
+// API publique
+// ============================================================================
+
+static SCANNER: OnceLock<PiiScanner> = OnceLock::new();
+
+/// Analyseur de la configuration courante
+pub fn scanner() -> &'static PiiScanner {
+    SCANNER.get_or_init(|| PiiScanner::new(&get_config().pii))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn config(default_action: &str, field_actions: &[(&str, &str)]) -> PiiConfig {
+        PiiConfig {
+            default_action: default_action.to_string(),
+            field_actions: field_actions
+                .iter()
+                .map(|(f, a)| (f.to_string(), a.to_string()))
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_detects_values_with_paths() {
+        let scanner = PiiScanner::new(&config("reject", &[]));
+        let scan = scanner.scan(
+            &json!({
+                "title": "Scanner thoracique du 12/03/2024",
+                "results": "Patient Monsieur Dupont, né le 05/07/1958, NIR 1 58 07 75 123 456 70.",
+                "sections": [{"title": "Contact", "content": "Tél 06 12 34 56 78, jean.dupont@example.fr, IPP: 00123456"}]
+            }),
+            "structured",
+        );
+
+        let kinds: Vec<(&str, PiiKind)> = scan.findings.iter().map(|f| (f.path.as_str(), f.kind)).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("structured.results", PiiKind::Name),
+                ("structured.results", PiiKind::BirthDate),
+                ("structured.results", PiiKind::Nir),
+                ("structured.sections[0].content", PiiKind::Phone),
+                ("structured.sections[0].content", PiiKind::Email),
+                ("structured.sections[0].content", PiiKind::Ipp),
+            ]
+        );
+        assert!(scan.rejected());
+        // Le titre (date d'examen) n'est pas une donnée nominative
+        assert!(scan.findings.iter().all(|f| f.path != "structured.title"));
+        assert!(!scan.findings[0].preview.contains("Dupont"));
+
+        // Clé de contrôle NIR incorrecte : pas de détection
+        assert!(scanner.scan(&json!("1 58 07 75 123 456 71"), "v").findings.is_empty());
+    }
+
+    #[test]
+    fn test_nir_without_key_needs_context() {
+        let scanner = PiiScanner::new(&config("reject", &[]));
+        let nir_kinds = |value: Value| -> usize {
+            scanner.scan(&value, "v").findings.iter().filter(|f| f.kind == PiiKind::Nir).count()
+        };
+
+        // Numéros d'accession et UID DICOM de forme NIR sans clé : ignorés
+        assert_eq!(nir_kinds(json!("Accession 1580775123456")), 0);
+        assert_eq!(nir_kinds(json!({"accession_number": "2231275123456"})), 0);
+        assert_eq!(nir_kinds(json!("1.2.840.113619.2.55.3.1580775123456.20240312")), 0);
+        assert_eq!(nir_kinds(json!("2.25.158077512345671.1")), 0);
+        assert_eq!(nir_kinds(json!({"series": 1580775123456_u64})), 0);
+
+        // Libellé explicite : clé facultative, mais vérifiée si présente
+        assert_eq!(nir_kinds(json!("NIR : 1 58 07 75 123 456")), 1);
+        assert_eq!(nir_kinds(json!("n° de sécurité sociale 1580775123456")), 1);
+        assert_eq!(nir_kinds(json!("INS 1 58 07 75 123 456 71")), 0);
+        assert_eq!(nir_kinds(json!("1 58 07 75 123 456 70")), 1);
+    }
+
+    #[test]
+    fn test_redacted_numbers_stay_numbers() {
+        let scanner = PiiScanner::new(&config("redact", &[]));
+        let scan = scanner.scan(&json!({"nir": 158077512345670_u64, "slices": 1580775123456_u64, "kv": 120}), "");
+
+        assert_eq!(scan.findings.len(), 1);
+        assert_eq!(scan.value, json!({"nir": 0, "slices": 1580775123456_u64, "kv": 120}));
+    }
+
+    #[test]
+    fn test_redact_and_tokenise_per_field() {
+        let scanner = PiiScanner::new(&config("reject", &[("results", "redact"), ("sections[].content", "tokenise")]));
+        let scan = scanner.scan(
+            &json!({
+                "results": "Joindre Madame Martin au 01 23 45 67 89.",
+                "sections": [
+                    {"title": "A", "content": "Madame Martin, puis Monsieur Durand"},
+                    {"title": "B", "content": "Rappeler Madame Martin"}
+                ]
+            }),
+            "",
+        );
+
+        assert!(!scan.rejected());
+        assert_eq!(scan.value["results"], "Joindre Madame [NOM] au [TELEPHONE].");
+        assert_eq!(scan.value["sections"][0]["content"], "Madame [NOM_1], puis Monsieur [NOM_2]");
+        assert_eq!(scan.value["sections"][1]["content"], "Rappeler Madame [NOM_1]");
+        assert_eq!(scan.findings[0].action, PiiAction::Redact);
+    }
+}
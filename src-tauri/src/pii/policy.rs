@@ -0,0 +1,683 @@
+// ============================================================================
+// AIRADCR Desktop - Politique Patient-Safe
+// ============================================================================
+// Règles sur les noms de clés JSON et activation par destination, chargées
+// depuis patient_safe.toml (répertoire de configuration) :
+//   - règles exact / token / prefix / regex (insensibles à la casse),
+//     éventuellement restreintes à certaines destinations
+//   - chemins autorisés (allowlist) globaux ou par destination
+//   - destinations : local (stockage sur le poste), cloud, teo (TÉO Hub),
+//     chacune avec contrôle des clés et/ou analyse des valeurs (voir pii)
+// Sans fichier (ou fichier invalide) : règles embarquées.
+// ============================================================================
+
+use log::{error, info, warn};
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+use super::{scanner, strip_indices, PiiAction, PiiFinding, PiiScanner};
+use crate::config::{get_config, AppConfig};
+
+/// Nom du fichier de politique dans le répertoire de configuration
+pub const POLICY_FILE_NAME: &str = "patient_safe.toml";
+
+/// Termes interdits dans les clés, comme segment entier du nom de clé
+/// (`nom` bloque `nom`, `nom_de_famille`, `nomPatient`, pas `nombre_lesions`)
+const BUILTIN_TOKENS: &[&str] = &[
+    // Identifiants patient
+    "patient_id", "patientid", "patient_name", "patientname",
+    "patient_identifier", "patientidentifier",
+
+    // Données démographiques
+    "birth_date", "birthdate", "date_of_birth", "dob", "date_naissance",
+    "first_name", "last_name", "firstname", "lastname",
+    "nom", "prenom", "nom_patient", "prenom_patient",
+    "full_name", "fullname", "nom_complet",
+
+    // Identifiants administratifs
+    "ssn", "social_security", "social_security_number",
+    "insurance_number", "numero_securite_sociale", "nss",
+    "ipp", "nis", "nh", "numero_hopital", "nir", "ins",
+
+    // Coordonnées
+    "address", "adresse", "street", "rue", "city", "ville",
+    "postal_code", "code_postal", "zip", "zipcode",
+    "phone", "telephone", "mobile", "email", "mail",
+
+    // Identifiants DICOM/études
+    "study_uid", "studyuid", "study_instance_uid",
+    "series_uid", "seriesuid", "sop_instance_uid",
+    "accession_number", "accessionnumber", "accession",
+
+    // Autres identifiants sensibles
+    "mrn", "medical_record_number", "numero_dossier",
+    "encounter_id", "visit_id", "sejour_id",
+];
+
+/// Formes composées (id, expression)
+const BUILTIN_REGEX: &[(&str, &str)] = &[
+    ("patient_identity", r"^patient_?(id|name|identifier|nom|prenom)"),
+    ("person_name", r"^(first|last|full|maiden|birth)_?name$|^(nom|prenom)_(patient|naissance|usage)"),
+    ("birth_date", r"(^|_)(birth_?date|date_?of_?birth|date_naissance)$"),
+    ("contact", r"(^|_)(phone|telephone|email|adresse|address)(_|$)"),
+    ("dicom_uid", r"^(study|series|sop)_?(instance_)?uid$"),
+];
+
+/// Destination d'un payload
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Destination {
+    /// Stockage local (POST /pending-report)
+    Local,
+    /// Services cloud AIRADCR
+    Cloud,
+    /// TÉO Hub (rapport approuvé)
+    Teo,
+}
+
+impl Destination {
+    pub const ALL: [Destination; 3] = [Destination::Local, Destination::Cloud, Destination::Teo];
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "local" => Some(Destination::Local),
+            "cloud" => Some(Destination::Cloud),
+            "teo" | "teo_hub" => Some(Destination::Teo),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Destination::Local => "local",
+            Destination::Cloud => "cloud",
+            Destination::Teo => "teo",
+        }
+    }
+}
+
+/// Type de correspondance d'une règle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchType {
+    Exact,
+    /// Terme présent comme segment(s) entier(s) de la clé (séparateurs _ - . espace, camelCase)
+    Token,
+    Prefix,
+    Regex,
+}
+
+impl MatchType {
+    fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "exact" => Some(MatchType::Exact),
+            "token" => Some(MatchType::Token),
+            "prefix" => Some(MatchType::Prefix),
+            "regex" => Some(MatchType::Regex),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            MatchType::Exact => "exact",
+            MatchType::Token => "token",
+            MatchType::Prefix => "prefix",
+            MatchType::Regex => "regex",
+        }
+    }
+}
+
+/// Règle sur les noms de clés
+#[derive(Debug, Clone)]
+struct KeyRule {
+    id: String,
+    match_type: MatchType,
+    pattern: String,
+    regex: Option<Regex>,
+    /// Destinations concernées (vide = toutes)
+    destinations: Vec<Destination>,
+    reason: Option<String>,
+    origin: &'static str,
+}
+
+impl KeyRule {
+    /// `key` : clé en minuscules ; `segments` : clé découpée par `key_segments`
+    fn matches(&self, key: &str, segments: &str) -> bool {
+        match self.match_type {
+            MatchType::Exact => key == self.pattern,
+            MatchType::Token => format!("_{}_", segments).contains(&format!("_{}_", self.pattern)),
+            MatchType::Prefix => key.starts_with(&self.pattern),
+            MatchType::Regex => self.regex.as_ref().is_some_and(|re| re.is_match(key)),
+        }
+    }
+
+    fn applies_to(&self, destination: Destination) -> bool {
+        self.destinations.is_empty() || self.destinations.contains(&destination)
+    }
+}
+
+/// Contrôles actifs pour une destination
+#[derive(Debug, Clone)]
+struct DestinationPolicy {
+    check_keys: bool,
+    scan_values: bool,
+    allow: Vec<String>,
+}
+
+impl DestinationPolicy {
+    fn builtin(destination: Destination) -> Self {
+        match destination {
+            // Les données ne quittent pas le poste
+            Destination::Local => Self { check_keys: false, scan_values: false, allow: Vec::new() },
+            Destination::Cloud => Self { check_keys: true, scan_values: true, allow: Vec::new() },
+            // patient_id / study_uid sont les clés d'appariement attendues par TÉO Hub
+            Destination::Teo => Self { check_keys: false, scan_values: true, allow: Vec::new() },
+        }
+    }
+}
+
+// ============================================================================
+// Fichier de politique
+// ============================================================================
+
+fn default_true() -> bool {
+    true
+}
+
+#[derive(Debug, Deserialize)]
+struct PolicyFile {
+    /// Conserver les règles embarquées en plus de celles du fichier
+    #[serde(default = "default_true")]
+    include_defaults: bool,
+    #[serde(default)]
+    rules: Vec<RuleEntry>,
+    #[serde(default)]
+    allow: Vec<String>,
+    #[serde(default)]
+    destinations: HashMap<String, DestinationEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RuleEntry {
+    id: Option<String>,
+    #[serde(rename = "match")]
+    match_type: String,
+    pattern: String,
+    #[serde(default)]
+    destinations: Vec<String>,
+    reason: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DestinationEntry {
+    check_keys: Option<bool>,
+    scan_values: Option<bool>,
+    #[serde(default)]
+    allow: Vec<String>,
+}
+
+fn parse_destinations(values: &[String]) -> Result<Vec<Destination>, String> {
+    values
+        .iter()
+        .map(|d| Destination::parse(d).ok_or_else(|| format!("destination inconnue: {}", d)))
+        .collect()
+}
+
+/// Segments d'une clé en minuscules, joints par "_"
+/// ("nomDeFamille" → "nom_de_famille", "ville-naissance" → "ville_naissance")
+fn key_segments(key: &str) -> String {
+    let mut segments = String::with_capacity(key.len() + 4);
+    let mut previous_lower = false;
+    for c in key.chars() {
+        if matches!(c, '_' | '-' | '.' | ' ') {
+            if !segments.is_empty() && !segments.ends_with('_') {
+                segments.push('_');
+            }
+            previous_lower = false;
+            continue;
+        }
+        if c.is_uppercase() && previous_lower && !segments.ends_with('_') {
+            segments.push('_');
+        }
+        previous_lower = c.is_lowercase() || c.is_ascii_digit();
+        segments.extend(c.to_lowercase());
+    }
+    segments.trim_end_matches('_').to_string()
+}
+
+/// Chemin normalisé d'un allowlist ("sections[0].x" → "sections[].x")
+fn normalize_allow(path: &str) -> String {
+    strip_indices(path.trim())
+}
+
+// ============================================================================
+// Politique
+// ============================================================================
+
+/// Politique Patient-Safe compilée
+pub struct PatientSafePolicy {
+    rules: Vec<KeyRule>,
+    allow: Vec<String>,
+    destinations: HashMap<Destination, DestinationPolicy>,
+    /// Origine de la politique (chemin du fichier ou "builtin")
+    source: String,
+}
+
+/// Violation de la politique
+#[derive(Debug, Clone, Serialize)]
+pub struct PolicyViolation {
+    /// Chemin JSON du champ
+    pub path: String,
+    /// "key" (nom de clé) ou "value" (contenu)
+    pub source: &'static str,
+    /// Identifiant de la règle (ou "pii:<type>" pour une valeur)
+    pub rule: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub match_type: Option<MatchType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+    /// "builtin" ou "file"
+    pub origin: &'static str,
+    pub message: String,
+}
+
+/// Résultat de l'évaluation d'un payload pour une destination
+#[derive(Debug, Clone, Serialize)]
+pub struct PolicyReport {
+    pub destination: Destination,
+    pub allowed: bool,
+    pub policy: String,
+    pub checked_keys: bool,
+    pub scanned_values: bool,
+    pub violations: Vec<PolicyViolation>,
+    /// Chemins ignorés car autorisés
+    pub allowlisted: Vec<String>,
+    /// Détections dans les valeurs (masquées ou bloquantes)
+    pub findings: Vec<PiiFinding>,
+    /// Payload tel qu'il serait envoyé (valeurs masquées)
+    pub sanitized: Value,
+}
+
+impl PolicyReport {
+    /// Première violation (chemin, message) au format de validate_patient_safe
+    pub fn first_violation(&self) -> Option<(String, String)> {
+        self.violations.first().map(|v| (v.path.clone(), v.message.clone()))
+    }
+
+    /// Résumé des violations (ex: "nom at structured.nom (key)")
+    pub fn summary(&self) -> String {
+        self.violations
+            .iter()
+            .map(|v| format!("{} at {} ({})", v.rule, v.path, v.source))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+impl PatientSafePolicy {
+    /// Règles embarquées
+    pub fn builtin() -> Self {
+        let mut rules: Vec<KeyRule> = BUILTIN_TOKENS
+            .iter()
+            .map(|pattern| KeyRule {
+                id: pattern.to_string(),
+                match_type: MatchType::Token,
+                pattern: pattern.to_string(),
+                regex: None,
+                destinations: Vec::new(),
+                reason: None,
+                origin: "builtin",
+            })
+            .collect();
+        rules.extend(BUILTIN_REGEX.iter().map(|(id, pattern)| KeyRule {
+            id: id.to_string(),
+            match_type: MatchType::Regex,
+            pattern: pattern.to_string(),
+            regex: Some(Regex::new(pattern).expect("règle Patient-Safe embarquée invalide")),
+            destinations: Vec::new(),
+            reason: None,
+            origin: "builtin",
+        }));
+
+        Self {
+            rules,
+            allow: Vec::new(),
+            destinations: Destination::ALL
+                .iter()
+                .map(|d| (*d, DestinationPolicy::builtin(*d)))
+                .collect(),
+            source: "builtin".to_string(),
+        }
+    }
+
+    /// Politique décrite par un fichier TOML
+    pub fn from_toml(content: &str, source: &str) -> Result<Self, String> {
+        let file: PolicyFile = toml::from_str(content).map_err(|e| e.to_string())?;
+
+        let mut policy = if file.include_defaults {
+            Self::builtin()
+        } else {
+            Self { rules: Vec::new(), ..Self::builtin() }
+        };
+        policy.source = source.to_string();
+
+        for (i, entry) in file.rules.into_iter().enumerate() {
+            let match_type = MatchType::parse(&entry.match_type)
+                .ok_or_else(|| format!("rules[{}]: match doit valoir exact, token, prefix ou regex", i))?;
+            let pattern = match match_type {
+                MatchType::Regex => entry.pattern.trim().to_string(),
+                MatchType::Token => key_segments(entry.pattern.trim()),
+                _ => entry.pattern.trim().to_lowercase(),
+            };
+            if pattern.is_empty() {
+                return Err(format!("rules[{}]: pattern vide", i));
+            }
+            let regex = match match_type {
+                MatchType::Regex => Some(
+                    RegexBuilder::new(&pattern)
+                        .case_insensitive(true)
+                        .build()
+                        .map_err(|e| format!("rules[{}]: regex invalide: {}", i, e))?,
+                ),
+                _ => None,
+            };
+            policy.rules.push(KeyRule {
+                id: entry.id.unwrap_or_else(|| pattern.clone()),
+                match_type,
+                pattern,
+                regex,
+                destinations: parse_destinations(&entry.destinations).map_err(|e| format!("rules[{}]: {}", i, e))?,
+                reason: entry.reason,
+                origin: "file",
+            });
+        }
+
+        policy.allow = file.allow.iter().map(|p| normalize_allow(p)).collect();
+
+        for (name, entry) in file.destinations {
+            let destination =
+                Destination::parse(&name).ok_or_else(|| format!("destination inconnue: {}", name))?;
+            let current = policy
+                .destinations
+                .entry(destination)
+                .or_insert_with(|| DestinationPolicy::builtin(destination));
+            if let Some(check_keys) = entry.check_keys {
+                current.check_keys = check_keys;
+            }
+            if let Some(scan_values) = entry.scan_values {
+                current.scan_values = scan_values;
+            }
+            current.allow = entry.allow.iter().map(|p| normalize_allow(p)).collect();
+        }
+
+        Ok(policy)
+    }
+
+    fn destination(&self, destination: Destination) -> DestinationPolicy {
+        self.destinations
+            .get(&destination)
+            .cloned()
+            .unwrap_or_else(|| DestinationPolicy::builtin(destination))
+    }
+
+    /// Vrai si le chemin (ou un de ses parents) est autorisé pour la destination
+    fn is_allowed(&self, destination: &DestinationPolicy, path: &str) -> bool {
+        let normalized = strip_indices(path);
+        self.allow.iter().chain(destination.allow.iter()).any(|allowed| {
+            normalized == *allowed
+                || normalized
+                    .strip_prefix(allowed.as_str())
+                    .is_some_and(|rest| rest.starts_with('.') || rest.starts_with('['))
+        })
+    }
+
+    fn check_keys(
+        &self,
+        value: &Value,
+        path: &str,
+        destination: Destination,
+        policy: &DestinationPolicy,
+        report: &mut PolicyReport,
+    ) {
+        match value {
+            Value::Object(map) => {
+                for (key, item) in map {
+                    let child = if path.is_empty() { key.clone() } else { format!("{}.{}", path, key) };
+                    if self.is_allowed(policy, &child) {
+                        report.allowlisted.push(child);
+                        continue;
+                    }
+
+                    let key_lower = key.to_lowercase();
+                    let segments = key_segments(key);
+                    let rule = self
+                        .rules
+                        .iter()
+                        .find(|rule| rule.applies_to(destination) && rule.matches(&key_lower, &segments));
+                    match rule {
+                        Some(rule) => report.violations.push(PolicyViolation {
+                            message: match &rule.reason {
+                                Some(reason) => format!("nominative data detected (key '{}': {})", key, reason),
+                                None => format!(
+                                    "nominative data detected (key '{}' matches {} rule '{}')",
+                                    key,
+                                    rule.match_type.as_str(),
+                                    rule.pattern
+                                ),
+                            },
+                            path: child,
+                            source: "key",
+                            rule: rule.id.clone(),
+                            match_type: Some(rule.match_type),
+                            pattern: Some(rule.pattern.clone()),
+                            origin: rule.origin,
+                        }),
+                        None => self.check_keys(item, &child, destination, policy, report),
+                    }
+                }
+            }
+            Value::Array(items) => {
+                for (i, item) in items.iter().enumerate() {
+                    self.check_keys(item, &format!("{}[{}]", path, i), destination, policy, report);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Évalue un payload pour une destination (analyse des valeurs si `scanner` est fourni)
+    pub fn evaluate(&self, destination: Destination, value: &Value, scanner: Option<&PiiScanner>) -> PolicyReport {
+        let policy = self.destination(destination);
+        let mut report = PolicyReport {
+            destination,
+            allowed: true,
+            policy: self.source.clone(),
+            checked_keys: policy.check_keys,
+            scanned_values: policy.scan_values && scanner.is_some(),
+            violations: Vec::new(),
+            allowlisted: Vec::new(),
+            findings: Vec::new(),
+            sanitized: value.clone(),
+        };
+
+        if policy.check_keys {
+            self.check_keys(value, "", destination, &policy, &mut report);
+        }
+
+        if let Some(scanner) = scanner.filter(|_| policy.scan_values) {
+            let scan = scanner.scan_filtered(value, "", &|path| self.is_allowed(&policy, path));
+            report.violations.extend(scan.findings.iter().filter(|f| f.action == PiiAction::Reject).map(|f| {
+                PolicyViolation {
+                    path: f.path.clone(),
+                    source: "value",
+                    rule: format!("pii:{}", f.kind.as_str()),
+                    match_type: None,
+                    pattern: None,
+                    origin: "builtin",
+                    message: format!("nominative data detected (value looks like {})", f.kind.as_str()),
+                }
+            }));
+            report.sanitized = scan.value;
+            report.findings = scan.findings;
+        }
+
+        report.allowed = report.violations.is_empty();
+        report
+    }
+}
+
+// ============================================================================
+// API publique
+// ============================================================================
+
+static POLICY: OnceLock<PatientSafePolicy> = OnceLock::new();
+
+/// Chemin du fichier de politique
+pub fn policy_path() -> Option<PathBuf> {
+    match &get_config().pii.policy_file {
+        Some(path) => Some(PathBuf::from(path)),
+        None => AppConfig::config_path().and_then(|p| p.parent().map(|d| d.join(POLICY_FILE_NAME))),
+    }
+}
+
+fn load_policy() -> PatientSafePolicy {
+    let path = match policy_path().filter(|p| p.exists()) {
+        Some(path) => path,
+        None => return PatientSafePolicy::builtin(),
+    };
+
+    let loaded = std::fs::read_to_string(&path)
+        .map_err(|e| e.to_string())
+        .and_then(|content| PatientSafePolicy::from_toml(&content, &path.display().to_string()));
+    match loaded {
+        Ok(policy) => {
+            info!("🛡️ [PatientSafe] Politique chargée depuis {:?} ({} règle(s))", path, policy.rules.len());
+            policy
+        }
+        Err(e) => {
+            error!("❌ [PatientSafe] {:?} ignoré ({}), règles embarquées utilisées", path, e);
+            PatientSafePolicy::builtin()
+        }
+    }
+}
+
+/// Politique en vigueur
+pub fn policy() -> &'static PatientSafePolicy {
+    POLICY.get_or_init(load_policy)
+}
+
+/// Évalue un payload avec la politique en vigueur et l'analyseur [pii]
+pub fn evaluate(destination: Destination, value: &Value) -> PolicyReport {
+    let scanner = get_config().pii.enabled.then(scanner);
+    policy().evaluate(destination, value, scanner)
+}
+
+/// Applique la politique avant un envoi hors du poste.
+/// Ok(rapport, payload masqué dans `sanitized`) ou Err(rapport) si l'envoi doit être refusé.
+pub fn check_outbound(destination: Destination, value: &Value) -> Result<PolicyReport, Box<PolicyReport>> {
+    let report = evaluate(destination, value);
+    if !report.allowed {
+        warn!(
+            "🛡️ [PII] Envoi vers {} refusé: donnée(s) nominative(s) détectée(s) ({})",
+            destination.as_str(),
+            report.summary()
+        );
+        return Err(Box::new(report));
+    }
+    if !report.findings.is_empty() {
+        info!(
+            "🛡️ [PII] {} donnée(s) nominative(s) masquée(s) avant envoi vers {}",
+            report.findings.len(),
+            destination.as_str()
+        );
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_policy_file_rules_and_destinations() {
+        let policy = PatientSafePolicy::from_toml(
+            r#"
+            allow = ["structured.metadata.patient_position"]
+
+            [[rules]]
+            id = "dossier_chu"
+            match = "prefix"
+            pattern = "dossier_"
+            destinations = ["cloud", "teo"]
+            reason = "numéro de dossier CHU"
+
+            [[rules]]
+            match = "regex"
+            pattern = "^ref_[a-z]+_patient$"
+
+            [destinations.teo]
+            check_keys = true
+            "#,
+            "test",
+        )
+        .unwrap();
+        let payload = json!({
+            "structured": {
+                "nombre_lesions": 2,
+                "metadata": {"patient_position": "HFS", "dossier_id": "D1"},
+                "refs": [{"REF_CHU_PATIENT": "x"}]
+            }
+        });
+
+        let report = policy.evaluate(Destination::Teo, &payload, None);
+        assert!(!report.allowed);
+        let rules: Vec<(&str, &str)> = report.violations.iter().map(|v| (v.path.as_str(), v.rule.as_str())).collect();
+        assert_eq!(
+            rules,
+            vec![
+                ("structured.metadata.dossier_id", "dossier_chu"),
+                ("structured.refs[0].REF_CHU_PATIENT", "^ref_[a-z]+_patient$"),
+            ]
+        );
+        assert_eq!(report.allowlisted, vec!["structured.metadata.patient_position"]);
+        assert!(report.violations[0].message.contains("numéro de dossier CHU"));
+
+        // Règle restreinte à cloud/teo ; local sans contrôle des clés par défaut
+        let report = policy.evaluate(Destination::Local, &payload, None);
+        assert!(report.allowed && !report.checked_keys);
+
+        assert!(PatientSafePolicy::from_toml("[[rules]]\nmatch = \"contains\"\npattern = \"x\"", "test").is_err());
+    }
+
+    #[test]
+    fn test_builtin_terms_match_compound_keys() {
+        let policy = PatientSafePolicy::builtin();
+        let blocked_by = |key: &str| -> Option<String> {
+            let mut payload = serde_json::Map::new();
+            payload.insert(key.to_string(), json!("x"));
+            policy
+                .evaluate(Destination::Cloud, &Value::Object(payload), None)
+                .violations
+                .first()
+                .map(|v| v.rule.clone())
+        };
+
+        assert_eq!(blocked_by("nom_de_famille").as_deref(), Some("nom"));
+        assert_eq!(blocked_by("ville_naissance").as_deref(), Some("ville"));
+        assert_eq!(blocked_by("prenom_usuel").as_deref(), Some("prenom"));
+        assert_eq!(blocked_by("nomDeFamille").as_deref(), Some("nom"));
+        assert_eq!(blocked_by("patient-email").as_deref(), Some("email"));
+        assert_eq!(blocked_by("Nom").as_deref(), Some("nom"));
+
+        // Termes inclus dans un autre mot : autorisés
+        for key in ["nombre_lesions", "nomenclature", "insuffisance", "mailing_list", "prenomination", "technique"] {
+            assert_eq!(blocked_by(key), None, "{}", key);
+        }
+    }
+}
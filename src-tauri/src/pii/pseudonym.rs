@@ -0,0 +1,191 @@
+// ============================================================================
+// AIRADCR Desktop - Pseudonymisation des identifiants patient
+// ============================================================================
+// Pseudonyme HMAC-SHA256 à clé (clé de 256 bits dans le keychain OS) :
+//   - stable : une même valeur donne le même pseudonyme dans tous les logs,
+//     messages d'erreur des logs d'accès et exports SIEM (corrélation possible)
+//   - non réversible sans la clé ; domaine séparé par type d'identifiant
+//     (pid_…, acc_…, uid_…)
+//   - ré-identification réservée à l'administrateur : recherche du pseudonyme
+//     parmi les identifiants de pending_reports et les valeurs candidates fournies
+// ============================================================================
+
+use hmac::{Hmac, Mac};
+use log::{info, warn};
+use rand::Rng;
+use serde::Serialize;
+use sha2::Sha256;
+use std::sync::OnceLock;
+
+use crate::database::{keychain, Database};
+
+/// Longueur du pseudonyme en caractères hexadécimaux (64 bits)
+const PSEUDONYM_HEX_LEN: usize = 16;
+
+/// Type d'identifiant pseudonymisé
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IdentifierKind {
+    PatientId,
+    AccessionNumber,
+    /// Study Instance UID / exam UID (même domaine : TÉO Hub reçoit l'un ou l'autre)
+    StudyUid,
+}
+
+impl IdentifierKind {
+    /// Préfixe du pseudonyme
+    pub fn prefix(self) -> &'static str {
+        match self {
+            IdentifierKind::PatientId => "pid",
+            IdentifierKind::AccessionNumber => "acc",
+            IdentifierKind::StudyUid => "uid",
+        }
+    }
+
+    /// Colonnes de pending_reports contenant ce type d'identifiant
+    fn columns(self) -> &'static [&'static str] {
+        match self {
+            IdentifierKind::PatientId => &["patient_id"],
+            IdentifierKind::AccessionNumber => &["accession_number"],
+            IdentifierKind::StudyUid => &["exam_uid", "study_instance_uid"],
+        }
+    }
+
+    fn from_pseudonym(pseudonym: &str) -> Option<Self> {
+        let (prefix, _) = pseudonym.split_once('_')?;
+        [IdentifierKind::PatientId, IdentifierKind::AccessionNumber, IdentifierKind::StudyUid]
+            .into_iter()
+            .find(|kind| kind.prefix() == prefix)
+    }
+}
+
+/// Clé HMAC ; `persistent` = false si le keychain est indisponible
+/// (clé de session : pseudonymes non corrélables entre deux démarrages)
+struct PseudonymKey {
+    bytes: Vec<u8>,
+    persistent: bool,
+}
+
+static KEY: OnceLock<PseudonymKey> = OnceLock::new();
+
+fn key() -> &'static PseudonymKey {
+    KEY.get_or_init(|| match keychain::get_or_create_pseudonym_key() {
+        Ok(key) => {
+            info!("🔑 [Pseudonym] Clé de pseudonymisation chargée depuis le keychain OS");
+            PseudonymKey {
+                bytes: hex::decode(&key).unwrap_or_else(|_| key.into_bytes()),
+                persistent: true,
+            }
+        }
+        Err(e) => {
+            warn!("⚠️ [Pseudonym] Keychain indisponible ({}), clé de session utilisée", e);
+            PseudonymKey {
+                bytes: (0..32).map(|_| rand::thread_rng().gen::<u8>()).collect(),
+                persistent: false,
+            }
+        }
+    })
+}
+
+/// Pseudonyme d'un identifiant avec une clé donnée
+pub fn pseudonym_with_key(key: &[u8], kind: IdentifierKind, value: &str) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepte toute taille de clé");
+    mac.update(kind.prefix().as_bytes());
+    mac.update(b":");
+    mac.update(value.trim().as_bytes());
+    let digest = hex::encode(mac.finalize().into_bytes());
+    format!("{}_{}", kind.prefix(), &digest[..PSEUDONYM_HEX_LEN])
+}
+
+/// Pseudonyme d'un identifiant pour les logs ("-" si vide)
+pub fn pseudonymize(kind: IdentifierKind, value: &str) -> String {
+    if value.trim().is_empty() {
+        return "-".to_string();
+    }
+    pseudonym_with_key(&key().bytes, kind, value)
+}
+
+/// Vrai si la clé est conservée dans le keychain (pseudonymes stables entre démarrages)
+pub fn is_persistent() -> bool {
+    key().persistent
+}
+
+// ============================================================================
+// Ré-identification (administrateur)
+// ============================================================================
+
+/// Résultat d'une ré-identification
+#[derive(Debug, Clone, Serialize)]
+pub struct Reidentification {
+    pub pseudonym: String,
+    pub kind: IdentifierKind,
+    /// Valeur d'origine (None si introuvable)
+    pub value: Option<String>,
+    /// Rapports portant cet identifiant
+    pub technical_ids: Vec<String>,
+    /// Vrai si la valeur provient des candidats fournis
+    pub from_candidates: bool,
+    /// Faux si la clé est une clé de session (pseudonymes antérieurs non retrouvables)
+    pub persistent_key: bool,
+}
+
+/// Retrouve l'identifiant d'origine d'un pseudonyme parmi les rapports
+/// (expirés compris) et les valeurs candidates
+pub fn reidentify(db: &Database, pseudonym: &str, candidates: &[String]) -> Result<Reidentification, String> {
+    let pseudonym = pseudonym.trim();
+    let kind = IdentifierKind::from_pseudonym(pseudonym)
+        .ok_or_else(|| "pseudonym must start with pid_, acc_ or uid_".to_string())?;
+
+    let mut result = Reidentification {
+        pseudonym: pseudonym.to_string(),
+        kind,
+        value: None,
+        technical_ids: Vec::new(),
+        from_candidates: false,
+        persistent_key: is_persistent(),
+    };
+
+    for column in kind.columns() {
+        let rows = db
+            .list_report_identifiers(column)
+            .map_err(|e| format!("Database error: {}", e))?;
+        for (technical_id, value) in rows {
+            if pseudonymize(kind, &value) == pseudonym {
+                result.value.get_or_insert(value);
+                if !result.technical_ids.contains(&technical_id) {
+                    result.technical_ids.push(technical_id);
+                }
+            }
+        }
+    }
+
+    if result.value.is_none() {
+        if let Some(candidate) = candidates.iter().find(|c| pseudonymize(kind, c) == pseudonym) {
+            result.value = Some(candidate.trim().to_string());
+            result.from_candidates = true;
+        }
+    }
+
+    warn!(
+        "🔓 [Pseudonym] Ré-identification de {}: {}",
+        result.pseudonym,
+        if result.value.is_some() { "identifiant retrouvé" } else { "introuvable" }
+    );
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keyed_pseudonyms() {
+        let key = [7u8; 32];
+        let pid = pseudonym_with_key(&key, IdentifierKind::PatientId, "12345");
+        assert!(pid.starts_with("pid_") && pid.len() == 20);
+        assert_eq!(pid, pseudonym_with_key(&key, IdentifierKind::PatientId, " 12345 "));
+        // Domaine séparé par type, dépend de la clé
+        assert_ne!(&pid[4..], &pseudonym_with_key(&key, IdentifierKind::AccessionNumber, "12345")[4..]);
+        assert_ne!(pid, pseudonym_with_key(&[8u8; 32], IdentifierKind::PatientId, "12345"));
+    }
+}
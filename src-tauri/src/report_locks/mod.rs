@@ -0,0 +1,229 @@
+// ============================================================================
+// AIRADCR Desktop - Verrous d'édition des rapports
+// ============================================================================
+// Un rapport ouvert (open_report, GET /pending-report, rapport suivant) est
+// verrouillé au nom de son détenteur pour une durée de bail ([locks]
+// lease_secs). Le détenteur prolonge le bail par heartbeat ; un autre client
+// reçoit 409 avec l'identité du détenteur. Un bail non renouvelé expire seul.
+//
+// Identité du détenteur (jamais lue dans des headers libres) :
+//   - poste      : adresse IP du pair ("local" en loopback)
+//   - détenteur  : radiologue actif (poste local), sinon id et nom de la clé
+//                  API validée en base (table api_keys)
+// Une requête distante sans clé API valide ne prend jamais de verrou.
+// ============================================================================
+
+use serde::Serialize;
+
+use crate::config::get_config;
+use crate::database::queries::{ApiKeyIdentity, LockAcquisition, ReportLock};
+use crate::database::Database;
+use crate::http_server::middleware::RequestInfo;
+
+/// Poste de travail local (application desktop, requêtes loopback)
+pub const LOCAL_WORKSTATION: &str = "local";
+
+/// Identité d'un détenteur de verrou
+#[derive(Debug, Clone, Serialize)]
+pub struct LockHolder {
+    pub holder_id: String,
+    pub holder_name: Option<String>,
+    pub workstation: String,
+}
+
+/// Échec de prise de verrou
+#[derive(Debug, Clone)]
+pub enum LockError {
+    /// Rapport verrouillé par un autre client
+    Held(Box<ReportLock>),
+    Database(String),
+}
+
+impl LockError {
+    pub fn message(&self) -> String {
+        match self {
+            LockError::Held(lock) => format!(
+                "Report is locked by {} on {} until {}",
+                lock.holder_name.as_deref().unwrap_or(&lock.holder_id),
+                lock.workstation,
+                lock.expires_at
+            ),
+            LockError::Database(e) => format!("Database error: {}", e),
+        }
+    }
+}
+
+impl LockHolder {
+    /// Détenteur local : radiologue actif sur ce poste
+    pub fn local(db: &Database) -> Self {
+        match db.get_active_user().ok().flatten() {
+            Some(user) => Self {
+                holder_id: user.id,
+                holder_name: Some(user.display_name),
+                workstation: LOCAL_WORKSTATION.to_string(),
+            },
+            None => Self {
+                holder_id: LOCAL_WORKSTATION.to_string(),
+                holder_name: None,
+                workstation: LOCAL_WORKSTATION.to_string(),
+            },
+        }
+    }
+
+    /// Détenteur d'une requête HTTP : radiologue actif en loopback, sinon la
+    /// clé API validée par l'appelant (authenticate_api_key) ; "anonymous"
+    /// uniquement si l'authentification est désactivée
+    pub fn from_request(request_info: &RequestInfo, api_key: Option<&ApiKeyIdentity>, db: &Database) -> Self {
+        let workstation = workstation_for_ip(&request_info.ip_address);
+
+        if workstation == LOCAL_WORKSTATION {
+            return Self::local(db);
+        }
+
+        match api_key {
+            Some(key) => Self {
+                holder_id: key.id.clone(),
+                holder_name: Some(key.name.clone()),
+                workstation,
+            },
+            None => Self {
+                holder_id: "anonymous".to_string(),
+                holder_name: None,
+                workstation,
+            },
+        }
+    }
+}
+
+/// Poste associé à une adresse IP (loopback = poste local)
+fn workstation_for_ip(ip: &str) -> String {
+    match ip.parse::<std::net::IpAddr>() {
+        Ok(addr) if addr.is_loopback() => LOCAL_WORKSTATION.to_string(),
+        _ => ip.to_string(),
+    }
+}
+
+fn lease_secs() -> i64 {
+    get_config().locks.lease_secs.max(10) as i64
+}
+
+/// Verrouille un rapport pour ce détenteur (None si le verrouillage est désactivé)
+pub fn acquire(db: &Database, technical_id: &str, holder: &LockHolder) -> Result<Option<ReportLock>, LockError> {
+    if !get_config().locks.enabled {
+        return Ok(None);
+    }
+
+    match db.acquire_report_lock(
+        technical_id,
+        &holder.holder_id,
+        holder.holder_name.as_deref(),
+        &holder.workstation,
+        lease_secs(),
+    ) {
+        Ok(LockAcquisition::Acquired(lock)) => Ok(Some(lock)),
+        Ok(LockAcquisition::Held(lock)) => {
+            log::warn!("🔒 [Locks] tid={} refusé à {}@{}: détenu par {}@{}",
+                technical_id, holder.holder_id, holder.workstation, lock.holder_id, lock.workstation);
+            Err(LockError::Held(Box::new(lock)))
+        }
+        Err(e) => Err(LockError::Database(e.to_string())),
+    }
+}
+
+/// Prolonge le bail ; renvoie le verrou du détenteur actuel s'il appartient à un autre client
+pub fn heartbeat(db: &Database, technical_id: &str, holder: &LockHolder) -> Result<ReportLock, LockError> {
+    match db.heartbeat_report_lock(technical_id, &holder.holder_id, &holder.workstation, lease_secs()) {
+        Ok(Some(lock)) => Ok(lock),
+        // Bail expiré sans concurrent : le détenteur le reprend
+        Ok(None) => acquire(db, technical_id, holder)?
+            .ok_or_else(|| LockError::Database("Report locking is disabled".to_string())),
+        Err(e) => Err(LockError::Database(e.to_string())),
+    }
+}
+
+/// Libère le verrou du détenteur (false si le verrou ne lui appartient pas)
+pub fn release(db: &Database, technical_id: &str, holder: &LockHolder) -> Result<bool, String> {
+    db.release_report_lock(technical_id, Some((&holder.holder_id, &holder.workstation)))
+        .map_err(|e| format!("Database error: {}", e))
+}
+
+/// Libération forcée (administrateur) ; renvoie le verrou libéré
+pub fn force_release(db: &Database, technical_id: &str) -> Result<Option<ReportLock>, String> {
+    let lock = db.get_report_lock(technical_id).map_err(|e| format!("Database error: {}", e))?;
+    db.release_report_lock(technical_id, None)
+        .map_err(|e| format!("Database error: {}", e))?;
+    if let Some(lock) = &lock {
+        log::warn!("🔓 [Locks] Verrou tid={} de {}@{} libéré de force", technical_id, lock.holder_id, lock.workstation);
+    }
+    Ok(lock)
+}
+
+/// Vrai si le rapport est verrouillé par un autre client que `holder`
+pub fn is_locked_by_other(locked_by: Option<&str>, locked_workstation: Option<&str>, holder: &LockHolder) -> bool {
+    match (locked_by, locked_workstation) {
+        (Some(holder_id), Some(workstation)) => holder_id != holder.holder_id || workstation != holder.workstation,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request_from(ip: &str) -> RequestInfo {
+        RequestInfo {
+            request_id: "req-test".to_string(),
+            start_time: std::time::Instant::now(),
+            ip_address: ip.to_string(),
+            method: "GET".to_string(),
+            endpoint: "/pending-report".to_string(),
+            api_key_prefix: Some("airadcr_".to_string()),
+            user_agent: None,
+        }
+    }
+
+    fn key(id: &str, name: &str) -> ApiKeyIdentity {
+        ApiKeyIdentity { id: id.to_string(), name: name.to_string() }
+    }
+
+    #[test]
+    fn test_remote_holder_is_validated_key_not_prefix() {
+        let db = Database::new_in_memory().unwrap();
+        let ris = key("key-ris", "RIS Xplore");
+
+        let holder = LockHolder::from_request(&request_from("10.0.0.12"), Some(&ris), &db);
+        assert_eq!(holder.holder_id, "key-ris");
+        assert_eq!(holder.holder_name.as_deref(), Some("RIS Xplore"));
+        assert_eq!(holder.workstation, "10.0.0.12");
+
+        // Sans clé validée : jamais le préfixe commun "airadcr_"
+        let anonymous = LockHolder::from_request(&request_from("10.0.0.12"), None, &db);
+        assert_eq!(anonymous.holder_id, "anonymous");
+
+        // Loopback : poste local, quelle que soit la clé
+        let local = LockHolder::from_request(&request_from("127.0.0.1"), Some(&ris), &db);
+        assert_eq!(local.workstation, LOCAL_WORKSTATION);
+    }
+
+    #[test]
+    fn test_distinct_keys_on_same_workstation_conflict() {
+        let db = Database::new_in_memory().unwrap();
+        let ris = LockHolder::from_request(&request_from("10.0.0.12"), Some(&key("key-ris", "RIS")), &db);
+        let pacs = LockHolder::from_request(&request_from("10.0.0.12"), Some(&key("key-pacs", "PACS")), &db);
+
+        assert!(acquire(&db, "TID-LOCK-1", &ris).unwrap().is_some());
+        match acquire(&db, "TID-LOCK-1", &pacs) {
+            Err(LockError::Held(lock)) => assert_eq!(lock.holder_id, "key-ris"),
+            other => panic!("verrou attendu détenu par key-ris: {:?}", other),
+        }
+        assert!(is_locked_by_other(Some("key-ris"), Some("10.0.0.12"), &pacs));
+        assert!(!is_locked_by_other(Some("key-ris"), Some("10.0.0.12"), &ris));
+
+        // Seul le détenteur prolonge ou libère
+        assert!(heartbeat(&db, "TID-LOCK-1", &ris).is_ok());
+        assert!(matches!(heartbeat(&db, "TID-LOCK-1", &pacs), Err(LockError::Held(_))));
+        assert!(!release(&db, "TID-LOCK-1", &pacs).unwrap());
+        assert!(release(&db, "TID-LOCK-1", &ris).unwrap());
+        assert!(acquire(&db, "TID-LOCK-1", &pacs).unwrap().is_some());
+    }
+}
@@ -0,0 +1,276 @@
+// ============================================================================
+// AIRADCR Desktop - Modèle de rapport structuré
+// ============================================================================
+// Contenu `structured` d'un rapport en attente :
+//   - modèle typé StructuredReport (sections fixes + sections libres)
+//   - un JSON Schema par modalité (CT, MR, MG, US, CR, default) embarqué dans
+//     le binaire, remplaçable par un fichier <MODALITÉ>.json du répertoire
+//     [report_schemas] directory
+//   - validation à la réception avec chemin du champ fautif
+//     (ex: "structured.sections[0].title")
+//   - modèles de rapports (comptes rendus types) et fusion : voir templates
+// ============================================================================
+
+pub mod templates;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+use crate::config::{get_config, AppConfig};
+
+/// Modalités disposant d'un schéma dédié
+pub const MODALITIES: [&str; 5] = ["CT", "MR", "MG", "US", "CR"];
+
+/// Schéma appliqué aux modalités sans schéma dédié
+pub const DEFAULT_SCHEMA: &str = "default";
+
+/// Schémas embarqués (nom, contenu JSON)
+const BUILTIN_SCHEMAS: [(&str, &str); 6] = [
+    ("default", include_str!("schemas/default.json")),
+    ("CT", include_str!("schemas/CT.json")),
+    ("MR", include_str!("schemas/MR.json")),
+    ("MG", include_str!("schemas/MG.json")),
+    ("US", include_str!("schemas/US.json")),
+    ("CR", include_str!("schemas/CR.json")),
+];
+
+/// Section libre d'un rapport (comparaison, recommandations…)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportSection {
+    pub title: String,
+    pub content: String,
+}
+
+/// Rapport structuré
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StructuredReport {
+    #[serde(default)]
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub indication: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub technique: Option<String>,
+    #[serde(default)]
+    pub results: String,
+    #[serde(default)]
+    pub conclusion: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sections: Vec<ReportSection>,
+    /// Champs propres à la modalité ou à la source (birads, contrast, traduction…)
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// Erreur de validation rattachée à un champ de la requête
+#[derive(Debug, Clone)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+/// Schéma chargé et compilé
+struct LoadedSchema {
+    schema: Value,
+    validator: jsonschema::Validator,
+}
+
+static SCHEMAS: OnceLock<HashMap<String, LoadedSchema>> = OnceLock::new();
+
+/// Répertoire des schémas personnalisés
+fn schema_dir() -> Option<PathBuf> {
+    match &get_config().report_schemas.directory {
+        Some(dir) => Some(PathBuf::from(dir)),
+        None => AppConfig::config_path().and_then(|p| p.parent().map(|d| d.join("schemas"))),
+    }
+}
+
+fn compile(name: &str, schema: Value) -> Result<LoadedSchema, String> {
+    jsonschema::options()
+        .with_draft(jsonschema::Draft::Draft7)
+        .build(&schema)
+        .map(|validator| LoadedSchema { schema, validator })
+        .map_err(|e| format!("schéma {} invalide: {}", name, e))
+}
+
+/// Charge les schémas : fichier personnalisé s'il est valide, sinon schéma embarqué
+fn load_schemas() -> HashMap<String, LoadedSchema> {
+    let dir = schema_dir();
+    let mut schemas = HashMap::new();
+
+    for (name, builtin) in BUILTIN_SCHEMAS {
+        let custom = dir
+            .as_ref()
+            .map(|d| d.join(format!("{}.json", name)))
+            .filter(|path| path.exists())
+            .and_then(|path| {
+                let loaded = std::fs::read_to_string(&path)
+                    .map_err(|e| e.to_string())
+                    .and_then(|content| serde_json::from_str::<Value>(&content).map_err(|e| e.to_string()))
+                    .and_then(|schema| compile(name, schema));
+                match loaded {
+                    Ok(schema) => {
+                        log::info!("📐 [Schemas] Schéma {} chargé depuis {:?}", name, path);
+                        Some(schema)
+                    }
+                    Err(e) => {
+                        log::error!("❌ [Schemas] {:?} ignoré ({}), schéma embarqué utilisé", path, e);
+                        None
+                    }
+                }
+            });
+
+        let schema = match custom {
+            Some(schema) => schema,
+            None => {
+                let value: Value = serde_json::from_str(builtin).expect("schéma embarqué invalide");
+                compile(name, value).expect("schéma embarqué invalide")
+            }
+        };
+        schemas.insert(name.to_string(), schema);
+    }
+
+    schemas
+}
+
+fn schemas() -> &'static HashMap<String, LoadedSchema> {
+    SCHEMAS.get_or_init(load_schemas)
+}
+
+/// Nom du schéma applicable à une modalité ("ct" → "CT", inconnue → "default")
+pub fn schema_name(modality: Option<&str>) -> &'static str {
+    modality
+        .map(|m| m.trim().to_uppercase())
+        .and_then(|m| MODALITIES.iter().find(|known| **known == m).copied())
+        .unwrap_or(DEFAULT_SCHEMA)
+}
+
+/// Schéma JSON publié pour une modalité (None si la modalité n'a pas de schéma)
+pub fn schema_for(name: &str) -> Option<&'static Value> {
+    let key = if name.eq_ignore_ascii_case(DEFAULT_SCHEMA) {
+        DEFAULT_SCHEMA.to_string()
+    } else {
+        name.trim().to_uppercase()
+    };
+    schemas().get(&key).map(|loaded| &loaded.schema)
+}
+
+/// Convertit un JSON Pointer ("/sections/0/title") en chemin de champ
+/// ("structured.sections[0].title")
+fn field_path(pointer: &str) -> String {
+    pointer
+        .split('/')
+        .skip(1)
+        .fold("structured".to_string(), |mut path, segment| {
+            let segment = segment.replace("~1", "/").replace("~0", "~");
+            if segment.chars().all(|c| c.is_ascii_digit()) && !segment.is_empty() {
+                path.push_str(&format!("[{}]", segment));
+            } else {
+                path.push('.');
+                path.push_str(&segment);
+            }
+            path
+        })
+}
+
+/// Valide le contenu structuré selon le schéma de la modalité et le convertit
+/// en modèle typé. Validation par schéma ignorée si [report_schemas] enabled = false ;
+/// une non-conformité n'est qu'un avertissement tant que enforce = false.
+pub fn validate(modality: Option<&str>, structured: &Value) -> Result<StructuredReport, FieldError> {
+    if !structured.is_object() {
+        return Err(FieldError {
+            field: "structured".to_string(),
+            message: "structured must be a JSON object".to_string(),
+        });
+    }
+
+    let config = &get_config().report_schemas;
+    if config.enabled {
+        if let Err(e) = check_schema(modality, structured) {
+            if config.enforce {
+                return Err(e);
+            }
+            log::warn!("⚠️ [Schemas] Rapport accepté malgré la non-conformité ({}): {}", e.field, e.message);
+        }
+    }
+
+    serde_json::from_value(structured.clone()).map_err(|e| FieldError {
+        field: "structured".to_string(),
+        message: format!("Invalid structured report: {}", e),
+    })
+}
+
+/// Contrôle le contenu structuré selon le schéma de la modalité (première erreur)
+fn check_schema(modality: Option<&str>, structured: &Value) -> Result<(), FieldError> {
+    let name = schema_name(modality);
+    let Some(loaded) = schemas().get(name) else {
+        return Ok(());
+    };
+
+    let errors: Vec<_> = loaded.validator.iter_errors(structured).collect();
+    let Some(first) = errors.first() else {
+        return Ok(());
+    };
+
+    let mut field = field_path(first.instance_path.as_str());
+    if let jsonschema::error::ValidationErrorKind::Required { property } = &first.kind {
+        if let Some(property) = property.as_str() {
+            field = format!("{}.{}", field, property);
+        }
+    }
+    let others = if errors.len() > 1 {
+        format!(" (+{} other error(s))", errors.len() - 1)
+    } else {
+        String::new()
+    };
+    Err(FieldError {
+        field,
+        message: format!("Report does not match {} schema: {}{}", name, first, others),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_validate_reports_field_path() {
+        let report = validate(Some("ct"), &json!({
+            "title": "Scanner thoracique",
+            "technique": "Acquisition hélicoïdale",
+            "results": "Nodule de 6 mm",
+            "conclusion": "",
+            "contrast": false
+        }))
+        .unwrap();
+        assert_eq!(report.title, "Scanner thoracique");
+        assert_eq!(report.extra.get("contrast"), Some(&json!(false)));
+
+        // Technique obligatoire en CT (avertissement seul par défaut)
+        let incomplete = json!({"title": "CT", "results": "", "conclusion": ""});
+        assert!(validate(Some("CT"), &incomplete).is_ok());
+        let err = check_schema(Some("CT"), &incomplete).unwrap_err();
+        assert_eq!(err.field, "structured.technique");
+
+        let err = check_schema(Some("MG"), &json!({
+            "title": "Mammographie", "results": "", "conclusion": "",
+            "birads": "7"
+        }))
+        .unwrap_err();
+        assert_eq!(err.field, "structured.birads");
+
+        let err = check_schema(None, &json!({
+            "title": "Echo", "results": "", "conclusion": "",
+            "sections": [{"title": "", "content": "x"}]
+        }))
+        .unwrap_err();
+        assert_eq!(err.field, "structured.sections[0].title");
+
+        assert_eq!(schema_name(Some("XA")), DEFAULT_SCHEMA);
+        assert!(schema_for("mr").is_some());
+        assert!(schema_for("XA").is_none());
+    }
+}
@@ -0,0 +1,300 @@
+// ============================================================================
+// AIRADCR Desktop - Modèles de rapports (comptes rendus types)
+// ============================================================================
+// Un modèle est un StructuredReport pouvant contenir des placeholders
+// {{nom}}. Fusion avec un rapport entrant (structured RIS ou brouillon IA) :
+//   - un champ non vide du rapport entrant remplace celui du modèle, sauf si
+//     le modèle y fait référence ({{results}} dans "results") : la valeur est
+//     alors insérée à l'emplacement du placeholder
+//   - sections : celles du modèle, remplacées/complétées par titre
+//   - placeholders résolus par les variables explicites, puis par les champs
+//     du rapport entrant ; les placeholders non résolus sont conservés et listés
+// ============================================================================
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use super::{schema_name, validate, FieldError, ReportSection, StructuredReport, DEFAULT_SCHEMA};
+use crate::database::queries::ReportTemplate;
+
+/// Langue par défaut des modèles
+pub const DEFAULT_LANGUAGE: &str = "fr";
+
+/// Création / modification d'un modèle
+#[derive(Debug, Clone, Deserialize)]
+pub struct TemplateInput {
+    pub name: String,
+    pub modality: Option<String>,
+    pub body_part: Option<String>,
+    pub language: Option<String>,
+    /// Rapport structuré avec placeholders {{nom}}
+    pub content: Value,
+}
+
+/// Modèle validé, prêt à être enregistré
+#[derive(Debug, Clone)]
+pub struct ValidatedTemplate {
+    pub name: String,
+    pub modality: Option<String>,
+    pub body_part: Option<String>,
+    pub language: String,
+    pub content: String,
+}
+
+/// Résultat d'une fusion
+#[derive(Debug, Clone, Serialize)]
+pub struct MergedReport {
+    pub template_id: String,
+    pub structured: StructuredReport,
+    /// Placeholders restés sans valeur (laissés dans le texte)
+    pub unresolved: Vec<String>,
+}
+
+fn placeholder_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"\{\{\s*([A-Za-z0-9_.-]+)\s*\}\}").expect("regex placeholder"))
+}
+
+fn validate_language(language: &str) -> bool {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"^[a-z]{2}(-[A-Z]{2})?$").expect("regex langue"))
+        .is_match(language)
+}
+
+/// Valide un modèle : nom, langue, contenu conforme au schéma de sa modalité
+pub fn validate_template(input: &TemplateInput) -> Result<ValidatedTemplate, FieldError> {
+    let name = input.name.trim();
+    if name.is_empty() || name.chars().count() > 100 {
+        return Err(FieldError {
+            field: "name".to_string(),
+            message: "name is required (max 100 characters)".to_string(),
+        });
+    }
+
+    let language = input
+        .language
+        .as_deref()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .unwrap_or(DEFAULT_LANGUAGE)
+        .to_string();
+    if !validate_language(&language) {
+        return Err(FieldError {
+            field: "language".to_string(),
+            message: "language must be an ISO 639-1 code (e.g. fr, en, fr-CH)".to_string(),
+        });
+    }
+
+    let modality = input
+        .modality
+        .as_deref()
+        .map(|m| m.trim().to_uppercase())
+        .filter(|m| !m.is_empty());
+    let body_part = input
+        .body_part
+        .as_deref()
+        .map(|b| b.trim().to_lowercase())
+        .filter(|b| !b.is_empty());
+
+    let content = validate(modality.as_deref(), &input.content).map_err(|e| FieldError {
+        field: e.field.replacen("structured", "content", 1),
+        message: e.message,
+    })?;
+
+    Ok(ValidatedTemplate {
+        name: name.to_string(),
+        modality,
+        body_part,
+        language,
+        content: serde_json::to_string(&content).unwrap_or_default(),
+    })
+}
+
+/// Vrai si `text` contient le placeholder {{name}}
+fn references(text: &str, name: &str) -> bool {
+    placeholder_regex()
+        .captures_iter(text)
+        .any(|caps| &caps[1] == name)
+}
+
+/// Champ fusionné : valeur entrante non vide, sauf si le modèle la référence
+fn merge_field(template: &str, incoming: &str, name: &str) -> String {
+    if incoming.trim().is_empty() || references(template, name) {
+        template.to_string()
+    } else {
+        incoming.to_string()
+    }
+}
+
+fn merge_optional(template: &Option<String>, incoming: &Option<String>, name: &str) -> Option<String> {
+    match (template, incoming) {
+        (Some(t), Some(i)) => Some(merge_field(t, i, name)),
+        (Some(t), None) => Some(t.clone()),
+        (None, Some(i)) => Some(i.clone()),
+        (None, None) => None,
+    }
+}
+
+/// Remplace les placeholders de `text` ; mémorise ceux qui restent sans valeur
+fn substitute(text: &str, values: &HashMap<String, String>, unresolved: &mut Vec<String>) -> String {
+    placeholder_regex()
+        .replace_all(text, |caps: &regex::Captures| match values.get(&caps[1]) {
+            Some(value) => value.clone(),
+            None => {
+                if !unresolved.iter().any(|u| u == &caps[1]) {
+                    unresolved.push(caps[1].to_string());
+                }
+                caps[0].to_string()
+            }
+        })
+        .into_owned()
+}
+
+/// Fusionne un modèle avec un rapport entrant et des variables explicites
+pub fn merge(
+    template: &StructuredReport,
+    incoming: &StructuredReport,
+    variables: &HashMap<String, String>,
+) -> (StructuredReport, Vec<String>) {
+    // Valeurs des placeholders : champs entrants, puis variables explicites (prioritaires)
+    let mut values: HashMap<String, String> = HashMap::new();
+    let named = [
+        ("title", Some(&incoming.title)),
+        ("indication", incoming.indication.as_ref()),
+        ("technique", incoming.technique.as_ref()),
+        ("results", Some(&incoming.results)),
+        ("conclusion", Some(&incoming.conclusion)),
+    ];
+    for (name, value) in named {
+        if let Some(value) = value.filter(|v| !v.trim().is_empty()) {
+            values.insert(name.to_string(), value.clone());
+        }
+    }
+    for (key, value) in &incoming.extra {
+        match value {
+            Value::String(s) => values.insert(key.clone(), s.clone()),
+            Value::Number(n) => values.insert(key.clone(), n.to_string()),
+            Value::Bool(b) => values.insert(key.clone(), b.to_string()),
+            _ => None,
+        };
+    }
+    values.extend(variables.iter().map(|(k, v)| (k.clone(), v.clone())));
+
+    let mut sections = template.sections.clone();
+    for section in &incoming.sections {
+        match sections.iter_mut().find(|s| s.title.eq_ignore_ascii_case(&section.title)) {
+            Some(existing) => existing.content = merge_field(&existing.content, &section.content, &section.title),
+            None => sections.push(section.clone()),
+        }
+    }
+
+    let mut extra = template.extra.clone();
+    extra.extend(incoming.extra.clone());
+
+    let mut unresolved = Vec::new();
+    let merged = StructuredReport {
+        title: substitute(&merge_field(&template.title, &incoming.title, "title"), &values, &mut unresolved),
+        indication: merge_optional(&template.indication, &incoming.indication, "indication")
+            .map(|t| substitute(&t, &values, &mut unresolved)),
+        technique: merge_optional(&template.technique, &incoming.technique, "technique")
+            .map(|t| substitute(&t, &values, &mut unresolved)),
+        results: substitute(&merge_field(&template.results, &incoming.results, "results"), &values, &mut unresolved),
+        conclusion: substitute(
+            &merge_field(&template.conclusion, &incoming.conclusion, "conclusion"),
+            &values,
+            &mut unresolved,
+        ),
+        sections: sections
+            .into_iter()
+            .map(|s| ReportSection {
+                title: substitute(&s.title, &values, &mut unresolved),
+                content: substitute(&s.content, &values, &mut unresolved),
+            })
+            .collect(),
+        extra,
+    };
+
+    (merged, unresolved)
+}
+
+/// Fusionne un modèle enregistré avec un contenu `structured` entrant
+/// (null ou {} = modèle seul) ; le résultat est validé selon la modalité
+pub fn merge_template(
+    template: &ReportTemplate,
+    incoming: &Value,
+    variables: &HashMap<String, String>,
+    modality: Option<&str>,
+) -> Result<MergedReport, FieldError> {
+    let base: StructuredReport = serde_json::from_str(&template.content).map_err(|e| FieldError {
+        field: "template".to_string(),
+        message: format!("Template {} has invalid content: {}", template.name, e),
+    })?;
+
+    let incoming: StructuredReport = match incoming {
+        Value::Null => StructuredReport::default(),
+        Value::Object(_) => serde_json::from_value(incoming.clone()).map_err(|e| FieldError {
+            field: "structured".to_string(),
+            message: format!("Invalid structured report: {}", e),
+        })?,
+        _ => {
+            return Err(FieldError {
+                field: "structured".to_string(),
+                message: "structured must be a JSON object".to_string(),
+            })
+        }
+    };
+
+    let (merged, unresolved) = merge(&base, &incoming, variables);
+
+    // Schéma : modalité du rapport, sinon celle du modèle
+    let modality = modality
+        .filter(|m| schema_name(Some(m)) != DEFAULT_SCHEMA)
+        .or(template.modality.as_deref());
+    let structured = validate(modality, &serde_json::to_value(&merged).unwrap_or(Value::Null))?;
+
+    Ok(MergedReport {
+        template_id: template.id.clone(),
+        structured,
+        unresolved,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_template_merge() {
+        let template: StructuredReport = serde_json::from_value(json!({
+            "title": "Scanner thoracique",
+            "indication": "{{indication}}",
+            "technique": "Acquisition sans injection.",
+            "results": "Absence de nodule. {{remarque}}",
+            "conclusion": "Examen normal.",
+            "sections": [{"title": "Comparaison", "content": "Pas d'examen antérieur."}]
+        }))
+        .unwrap();
+        let incoming: StructuredReport = serde_json::from_value(json!({
+            "indication": "Toux chronique",
+            "conclusion": "Nodule de 6 mm, contrôle à 6 mois.",
+            "sections": [{"title": "Recommandations", "content": "Fleischner"}]
+        }))
+        .unwrap();
+
+        let (merged, unresolved) = merge(&template, &incoming, &HashMap::new());
+        assert_eq!(merged.indication.as_deref(), Some("Toux chronique"));
+        assert_eq!(merged.conclusion, "Nodule de 6 mm, contrôle à 6 mois.");
+        assert_eq!(merged.results, "Absence de nodule. {{remarque}}");
+        assert_eq!(merged.sections.len(), 2);
+        assert_eq!(unresolved, vec!["remarque"]);
+
+        let variables = HashMap::from([("remarque".to_string(), "Médiastin normal.".to_string())]);
+        let (merged, unresolved) = merge(&template, &StructuredReport::default(), &variables);
+        assert_eq!(merged.results, "Absence de nodule. Médiastin normal.");
+        assert_eq!(unresolved, vec!["indication"]);
+    }
+}
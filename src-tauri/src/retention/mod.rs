@@ -0,0 +1,437 @@
+// ============================================================================
+// AIRADCR Desktop - Rétention des données et purge sécurisée
+// ============================================================================
+// Chaque cycle de nettoyage évalue les rapports en attente :
+//   1. règle intégrée "expires_at" : rapport expiré (expires_in_hours) ou
+//      marqué 'expired'
+//   2. règles [[retention.rules]] dans l'ordre : la première règle dont les
+//      critères (source_type, modality, status) correspondent décide ; le
+//      rapport est purgé si son âge dépasse max_age_hours
+// puis les logs d'accès plus anciens que log_retention_days.
+//
+// Les suppressions sont faites avec PRAGMA secure_delete (pages écrasées),
+// journalisées dans retention_purges et transmises aux sinks d'audit.
+// En mode simulation (dry_run), le rapport de purge est produit sans rien
+// supprimer.
+// ============================================================================
+
+use chrono::{DateTime, Duration, Utc};
+use log::{info, warn};
+use serde::Serialize;
+
+use crate::audit::{self, AuditEvent, AuditEventKind, AuditSeverity};
+use crate::config::{get_config, RetentionConfig, RetentionRuleConfig};
+use crate::database::queries::RetentionCandidate;
+use crate::database::Database;
+
+/// Règle intégrée : date d'expiration du rapport dépassée
+pub const EXPIRY_RULE: &str = "expires_at";
+
+/// Règle intégrée : rétention des logs d'accès (log_retention_days)
+pub const ACCESS_LOG_RULE: &str = "access_logs";
+
+/// Statuts acceptés par pending_reports
+const REPORT_STATUSES: &[&str] = &["pending", "retrieved", "expired"];
+
+/// Règle de rétention validée
+#[derive(Debug, Clone)]
+pub struct RetentionRule {
+    pub id: String,
+    source_type: Option<String>,
+    modality: Option<String>,
+    status: Option<String>,
+    max_age_hours: u32,
+}
+
+impl RetentionRule {
+    /// Valide une règle de la configuration
+    pub fn from_config(rule: &RetentionRuleConfig) -> Result<Self, String> {
+        let id = rule.id.trim();
+        if id.is_empty() || id == EXPIRY_RULE || id == ACCESS_LOG_RULE {
+            return Err(format!("identifiant de règle invalide: '{}'", rule.id));
+        }
+
+        let criterion = |value: &Option<String>| {
+            value.as_deref().map(str::trim).filter(|v| !v.is_empty()).map(str::to_lowercase)
+        };
+
+        let status = criterion(&rule.status);
+        if let Some(status) = &status {
+            if !REPORT_STATUSES.contains(&status.as_str()) {
+                return Err(format!("règle '{}': statut inconnu '{}'", id, status));
+            }
+        }
+
+        Ok(Self {
+            id: id.to_string(),
+            source_type: criterion(&rule.source_type),
+            modality: criterion(&rule.modality),
+            status,
+            max_age_hours: rule.max_age_hours,
+        })
+    }
+
+    /// Vrai si les critères de la règle correspondent au rapport
+    fn matches(&self, candidate: &RetentionCandidate) -> bool {
+        let matches = |criterion: &Option<String>, value: Option<&str>| match criterion {
+            Some(expected) => value.is_some_and(|v| v.trim().eq_ignore_ascii_case(expected)),
+            None => true,
+        };
+
+        matches(&self.source_type, Some(&candidate.source_type))
+            && matches(&self.modality, candidate.modality.as_deref())
+            && matches(&self.status, Some(&candidate.status))
+    }
+
+    /// Description lisible des critères (rapport de purge)
+    pub fn describe(&self) -> String {
+        let mut criteria: Vec<String> = [
+            ("source_type", &self.source_type),
+            ("modality", &self.modality),
+            ("status", &self.status),
+        ]
+        .iter()
+        .filter_map(|(name, value)| value.as_ref().map(|v| format!("{}={}", name, v)))
+        .collect();
+
+        if criteria.is_empty() {
+            criteria.push("tous les rapports".to_string());
+        }
+        format!("{}, créé il y a plus de {} h", criteria.join(", "), self.max_age_hours)
+    }
+}
+
+/// Politique de rétention compilée depuis la configuration
+#[derive(Debug, Clone)]
+pub struct RetentionPolicy {
+    pub rules: Vec<RetentionRule>,
+    pub secure_delete: bool,
+    pub dry_run: bool,
+    /// 0 = logs d'accès conservés indéfiniment
+    pub access_log_days: u32,
+    /// Fenêtre pendant laquelle les backups conservent les données purgées
+    pub backup_retention_days: u32,
+}
+
+impl RetentionPolicy {
+    /// Compile la politique ; une règle invalide est ignorée (données conservées)
+    pub fn from_config(config: &RetentionConfig, access_log_days: u32, backup_retention_days: u32) -> Self {
+        let rules = config
+            .rules
+            .iter()
+            .filter_map(|rule| match RetentionRule::from_config(rule) {
+                Ok(rule) => Some(rule),
+                Err(e) => {
+                    warn!("⚠️ [Retention] Règle ignorée: {}", e);
+                    None
+                }
+            })
+            .collect();
+
+        Self {
+            rules,
+            secure_delete: config.secure_delete,
+            dry_run: config.dry_run,
+            access_log_days,
+            backup_retention_days,
+        }
+    }
+
+    /// Politique de la configuration courante
+    pub fn current() -> Self {
+        let config = get_config();
+        Self::from_config(&config.retention, config.log_retention_days, config.backup_retention_days)
+    }
+
+    /// Règle qui purge ce rapport à l'instant `now` (None = rapport conservé)
+    pub fn rule_for(&self, candidate: &RetentionCandidate, now: DateTime<Utc>) -> Option<&str> {
+        let expired = candidate.status == "expired"
+            || parse_timestamp(&candidate.expires_at).is_some_and(|expires_at| expires_at <= now);
+        if expired {
+            return Some(EXPIRY_RULE);
+        }
+
+        // Première règle correspondante : elle seule décide de la conservation
+        let rule = self.rules.iter().find(|rule| rule.matches(candidate))?;
+        let created_at = parse_timestamp(&candidate.created_at)?;
+        if now - created_at >= Duration::hours(rule.max_age_hours as i64) {
+            Some(&rule.id)
+        } else {
+            None
+        }
+    }
+}
+
+fn parse_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value.trim())
+        .map(|dt| dt.with_timezone(&Utc))
+        .ok()
+}
+
+// ============================================================================
+// Rapport de purge
+// ============================================================================
+
+/// Rapport purgé (aucun identifiant patient : technical_id uniquement)
+#[derive(Debug, Clone, Serialize)]
+pub struct PurgedReport {
+    pub technical_id: String,
+    pub source_type: String,
+    pub modality: Option<String>,
+    pub status: String,
+    pub created_at: String,
+}
+
+/// Éléments supprimés par une règle
+#[derive(Debug, Clone, Serialize)]
+pub struct RulePurge {
+    pub rule: String,
+    /// Table concernée : pending_reports ou access_logs
+    pub target: &'static str,
+    pub criteria: String,
+    pub count: usize,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub reports: Vec<PurgedReport>,
+}
+
+/// Rapport d'un cycle de purge (ou de sa simulation)
+#[derive(Debug, Clone, Serialize)]
+pub struct PurgeReport {
+    pub generated_at: String,
+    pub dry_run: bool,
+    pub secure_delete: bool,
+    pub rules: Vec<RulePurge>,
+    pub total_reports: usize,
+    pub total_access_logs: usize,
+    /// Les backups conservent les données purgées jusqu'à leur propre expiration
+    pub backup_retention_days: u32,
+}
+
+impl PurgeReport {
+    fn technical_ids(purge: &RulePurge) -> Vec<String> {
+        purge.reports.iter().map(|r| r.technical_id.clone()).collect()
+    }
+}
+
+/// Calcule ce que la politique supprimerait à l'instant `now`
+pub fn plan(db: &Database, policy: &RetentionPolicy, now: DateTime<Utc>) -> Result<PurgeReport, String> {
+    let candidates = db
+        .list_retention_candidates()
+        .map_err(|e| format!("Database error: {}", e))?;
+
+    // Une entrée par règle, dans l'ordre d'évaluation
+    let mut rules: Vec<RulePurge> = std::iter::once(RulePurge {
+        rule: EXPIRY_RULE.to_string(),
+        target: "pending_reports",
+        criteria: "expires_at dépassé ou statut expired".to_string(),
+        count: 0,
+        reports: Vec::new(),
+    })
+    .chain(policy.rules.iter().map(|rule| RulePurge {
+        rule: rule.id.clone(),
+        target: "pending_reports",
+        criteria: rule.describe(),
+        count: 0,
+        reports: Vec::new(),
+    }))
+    .collect();
+
+    for candidate in candidates {
+        let Some(rule_id) = policy.rule_for(&candidate, now) else {
+            continue;
+        };
+        if let Some(purge) = rules.iter_mut().find(|p| p.rule == rule_id) {
+            purge.count += 1;
+            purge.reports.push(PurgedReport {
+                technical_id: candidate.technical_id,
+                source_type: candidate.source_type,
+                modality: candidate.modality,
+                status: candidate.status,
+                created_at: candidate.created_at,
+            });
+        }
+    }
+
+    let total_reports = rules.iter().map(|p| p.count).sum();
+    let mut total_access_logs = 0;
+
+    if policy.access_log_days > 0 {
+        let cutoff = (now - Duration::days(policy.access_log_days as i64)).to_rfc3339();
+        let count = db
+            .count_access_logs_before(&cutoff)
+            .map_err(|e| format!("Database error: {}", e))? as usize;
+        total_access_logs = count;
+        rules.push(RulePurge {
+            rule: ACCESS_LOG_RULE.to_string(),
+            target: "access_logs",
+            criteria: format!("timestamp antérieur à {} jours", policy.access_log_days),
+            count,
+            reports: Vec::new(),
+        });
+    }
+
+    Ok(PurgeReport {
+        generated_at: now.to_rfc3339(),
+        dry_run: true,
+        secure_delete: policy.secure_delete,
+        rules,
+        total_reports,
+        total_access_logs,
+        backup_retention_days: policy.backup_retention_days,
+    })
+}
+
+/// Simulation : ce que le prochain cycle de nettoyage supprimerait
+pub fn preview(db: &Database) -> Result<PurgeReport, String> {
+    plan(db, &RetentionPolicy::current(), Utc::now())
+}
+
+/// Applique la politique : purge, journal retention_purges et événements d'audit
+pub fn execute(db: &Database, policy: &RetentionPolicy, now: DateTime<Utc>) -> Result<PurgeReport, String> {
+    let mut report = plan(db, policy, now)?;
+    if policy.dry_run {
+        if report.total_reports + report.total_access_logs > 0 {
+            info!(
+                "🔎 [Retention] Simulation: {} rapport(s) et {} log(s) d'accès seraient supprimés",
+                report.total_reports, report.total_access_logs
+            );
+        }
+        return Ok(report);
+    }
+
+    report.dry_run = false;
+    for purge in report.rules.iter_mut().filter(|p| p.count > 0) {
+        let technical_ids = PurgeReport::technical_ids(purge);
+        let deleted = if purge.target == "access_logs" {
+            let cutoff = (now - Duration::days(policy.access_log_days as i64)).to_rfc3339();
+            db.purge_access_logs_before(&cutoff, policy.secure_delete)
+        } else {
+            db.purge_pending_reports(&technical_ids, policy.secure_delete)
+        }
+        .map_err(|e| format!("Database error: {}", e))?;
+
+        purge.count = deleted;
+        if deleted == 0 {
+            continue;
+        }
+
+        if let Err(e) = db.insert_retention_purge(
+            &report.generated_at,
+            &purge.rule,
+            purge.target,
+            deleted as i64,
+            &technical_ids,
+            policy.secure_delete,
+        ) {
+            warn!("⚠️ [Retention] Échec journalisation de la purge '{}': {}", purge.rule, e);
+        }
+
+        info!("🧹 [Retention] Règle '{}': {} élément(s) supprimé(s) de {}", purge.rule, deleted, purge.target);
+        emit_purge_event(&report.generated_at, purge, &technical_ids, policy.secure_delete);
+    }
+
+    report.total_reports = report.rules.iter().filter(|p| p.target == "pending_reports").map(|p| p.count).sum();
+    report.total_access_logs = report.rules.iter().filter(|p| p.target == "access_logs").map(|p| p.count).sum();
+
+    if let Err(e) = db.cleanup_stale_report_locks() {
+        warn!("⚠️ [Retention] Échec nettoyage des verrous: {}", e);
+    }
+
+    Ok(report)
+}
+
+/// Cycle de nettoyage périodique selon la configuration courante
+pub fn run_cycle(db: &Database) -> Result<PurgeReport, String> {
+    execute(db, &RetentionPolicy::current(), Utc::now())
+}
+
+/// Transmet une purge aux sinks d'audit
+fn emit_purge_event(timestamp: &str, purge: &RulePurge, technical_ids: &[String], secure_delete: bool) {
+    let items = if technical_ids.is_empty() {
+        String::new()
+    } else {
+        format!(" [{}]", technical_ids.join(", "))
+    };
+
+    audit::emit(AuditEvent {
+        timestamp: timestamp.to_string(),
+        kind: AuditEventKind::Retention,
+        severity: AuditSeverity::Notice,
+        event_type: "retention_purge".to_string(),
+        request_id: crate::telemetry::generate_request_id(),
+        ip_address: "local".to_string(),
+        method: "-".to_string(),
+        endpoint: purge.target.to_string(),
+        status_code: None,
+        result: None,
+        api_key_prefix: None,
+        user_agent: None,
+        duration_ms: None,
+        message: Some(format!(
+            "rule '{}' ({}): {} item(s) deleted, secure_delete={}{}",
+            purge.rule, purge.criteria, purge.count, secure_delete, items
+        )),
+        user_id: None,
+    });
+}
+
+// ============================================================================
+// Tests unitaires
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(source_type: &str, modality: &str, status: &str, age_hours: i64, now: DateTime<Utc>) -> RetentionCandidate {
+        RetentionCandidate {
+            technical_id: format!("{}-{}-{}", source_type, modality, status),
+            source_type: source_type.to_string(),
+            modality: Some(modality.to_string()),
+            status: status.to_string(),
+            created_at: (now - Duration::hours(age_hours)).to_rfc3339(),
+            expires_at: (now + Duration::hours(24)).to_rfc3339(),
+        }
+    }
+
+    fn rule(id: &str, source_type: Option<&str>, modality: Option<&str>, status: Option<&str>, hours: u32) -> RetentionRuleConfig {
+        RetentionRuleConfig {
+            id: id.to_string(),
+            source_type: source_type.map(str::to_string),
+            modality: modality.map(str::to_string),
+            status: status.map(str::to_string),
+            max_age_hours: hours,
+        }
+    }
+
+    #[test]
+    fn test_first_matching_rule_decides() {
+        let now = Utc::now();
+        let config = RetentionConfig {
+            secure_delete: true,
+            dry_run: false,
+            rules: vec![
+                rule("ct_retrieved", None, Some("ct"), Some("retrieved"), 2),
+                rule("teo", Some("teo_hub"), None, None, 72),
+                rule("default", None, None, None, 168),
+                rule("bad", None, None, Some("archived"), 1),
+            ],
+        };
+        let policy = RetentionPolicy::from_config(&config, 30, 7);
+        assert_eq!(policy.rules.len(), 3);
+
+        let ct = candidate("teo_hub", "CT", "retrieved", 3, now);
+        assert_eq!(policy.rule_for(&ct, now), Some("ct_retrieved"));
+
+        // La règle "teo" correspond d'abord : conservé malgré "default"
+        let mr = candidate("teo_hub", "MR", "pending", 48, now);
+        assert_eq!(policy.rule_for(&mr, now), None);
+
+        let old = candidate("ris_local", "MR", "pending", 200, now);
+        assert_eq!(policy.rule_for(&old, now), Some("default"));
+
+        let mut expired = candidate("ris_local", "US", "pending", 1, now);
+        expired.expires_at = (now - Duration::minutes(1)).to_rfc3339();
+        assert_eq!(policy.rule_for(&expired, now), Some(EXPIRY_RULE));
+    }
+}
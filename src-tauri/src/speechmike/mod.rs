@@ -12,6 +12,7 @@ use std::thread;
 use std::time::Duration;
 use serde::Serialize;
 use tauri::Manager;
+use crate::http_server::metrics::Metrics;
 
 /// Status of the SpeechMike connection
 #[derive(Debug, Clone, Serialize)]
@@ -46,7 +47,8 @@ pub enum SpeechMikeAction {
     TogglePause,
     InjectRaw,
     InjectStructured,
-    FinalizeAndInject,
+    /// Passe le rapport en cours en urgent (liste de travail)
+    FlagUrgent,
 }
 
 /// Shared state for the SpeechMike module
@@ -76,7 +78,7 @@ fn button_to_action(button: ButtonEvent) -> Option<SpeechMikeAction> {
         ButtonEvent::Play     => Some(SpeechMikeAction::TogglePause),
         ButtonEvent::Instr    => Some(SpeechMikeAction::InjectRaw),
         ButtonEvent::F1A      => Some(SpeechMikeAction::InjectStructured),
-        ButtonEvent::EolPrio  => Some(SpeechMikeAction::FinalizeAndInject),
+        ButtonEvent::EolPrio  => Some(SpeechMikeAction::FlagUrgent),
         // PowerMic IV extra: TabBackward/TabForward currently unmapped
         _ => None,
     }
@@ -89,7 +91,7 @@ pub fn action_to_channel_str(action: SpeechMikeAction) -> &'static str {
         SpeechMikeAction::TogglePause       => "toggle_pause",
         SpeechMikeAction::InjectRaw         => "inject_raw",
         SpeechMikeAction::InjectStructured  => "inject_structured",
-        SpeechMikeAction::FinalizeAndInject  => "inject_structured",
+        SpeechMikeAction::FlagUrgent        => "flag_urgent",
     }
 }
 
@@ -346,6 +348,7 @@ pub fn start_speechmike_thread(
                             s.device_code = None;
                             s.has_slider = false;
                             s.event_mode = None;
+                            Metrics::set_speechmike_connected(false);
                             let _ = app_handle.emit_all("airadcr:speechmike_disconnected", ());
                             info!("[SpeechMike] Périphérique déconnecté");
                         }
@@ -426,6 +429,7 @@ pub fn start_speechmike_thread(
                 has_slider,
                 event_mode: Some(mode_str.to_string()),
             };
+            Metrics::set_speechmike_connected(true);
             let _ = app_handle.emit_all("airadcr:speechmike_connected", &connect_status);
             info!("[SpeechMike] 🎤 Connecté: {} (natif HID) code={:?} slider={}", desc, device_code, has_slider);
             
@@ -481,6 +485,8 @@ pub fn start_speechmike_thread(
                                         continue;
                                     }
                                     
+                                    Metrics::record_speechmike_button(&format!("{:?}", button));
+                                    
                                     if let Some(action) = button_to_action(button) {
                                         let action_str = action_to_channel_str(action);
                                         debug!("[SpeechMike] 🎯 Bouton {:?} → action: {}", button, action_str);
@@ -508,6 +514,7 @@ pub fn start_speechmike_thread(
                         if let Ok(mut lt) = led_tx_store.lock() {
                             *lt = None;
                         }
+                        Metrics::set_speechmike_connected(false);
                         let _ = app_handle.emit_all("airadcr:speechmike_disconnected", ());
                         
                         break;
@@ -0,0 +1,346 @@
+// ============================================================================
+// AIRADCR Desktop - Corrélation des requêtes et traçage distribué
+// ============================================================================
+// - Identifiant de requête : X-Request-Id entrant (si valide) ou généré,
+//   renvoyé dans la réponse et transmis à TÉO Hub
+// - Contexte W3C Trace Context (traceparent) : honoré en entrée, propagé en
+//   sortie sur chaque appel TÉO Hub
+// - Spans `tracing` (requêtes HTTP, base de données, appels TÉO) exportés en
+//   OTLP/gRPC vers un collecteur local lorsque [tracing] est activé
+// ============================================================================
+
+use log::{info, error};
+use opentelemetry::propagation::TextMapPropagator;
+use opentelemetry::trace::{TraceContextExt, TracerProvider as _};
+use opentelemetry::KeyValue;
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use opentelemetry_sdk::trace::{Sampler, TracerProvider};
+use opentelemetry_sdk::Resource;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::OnceLock;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+use tracing_subscriber::layer::SubscriberExt;
+use uuid::Uuid;
+
+use crate::config::TracingConfig;
+
+/// Header d'identifiant de requête (entrée et sortie)
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Header W3C Trace Context
+pub const TRACEPARENT_HEADER: &str = "traceparent";
+
+/// Longueur maximale acceptée pour un X-Request-Id entrant
+const MAX_REQUEST_ID_LEN: usize = 64;
+
+/// Fournisseur OTLP (conservé pour le flush à l'arrêt)
+static TRACER_PROVIDER: OnceLock<TracerProvider> = OnceLock::new();
+
+tokio::task_local! {
+    /// Contexte de la requête HTTP en cours de traitement
+    static CURRENT_CONTEXT: RequestContext;
+}
+
+// ============================================================================
+// Contexte de requête
+// ============================================================================
+
+/// En-tête traceparent décodé (version 00)
+#[derive(Debug, Clone, PartialEq)]
+pub struct TraceParent {
+    pub trace_id: String,
+    pub parent_id: String,
+    pub sampled: bool,
+}
+
+impl TraceParent {
+    /// Décode "00-<trace_id 32 hex>-<parent_id 16 hex>-<flags 2 hex>"
+    pub fn parse(value: &str) -> Option<Self> {
+        let parts: Vec<&str> = value.trim().split('-').collect();
+        if parts.len() < 4 {
+            return None;
+        }
+        let (version, trace_id, parent_id, flags) = (parts[0], parts[1], parts[2], parts[3]);
+
+        // Version ff interdite ; version 00 : exactement 4 champs
+        if !is_lower_hex(version, 2) || version == "ff" || (version == "00" && parts.len() != 4) {
+            return None;
+        }
+        if !is_lower_hex(trace_id, 32) || trace_id.chars().all(|c| c == '0') {
+            return None;
+        }
+        if !is_lower_hex(parent_id, 16) || parent_id.chars().all(|c| c == '0') {
+            return None;
+        }
+        if !is_lower_hex(flags, 2) {
+            return None;
+        }
+        let flags = u8::from_str_radix(flags, 16).ok()?;
+
+        Some(Self {
+            trace_id: trace_id.to_string(),
+            parent_id: parent_id.to_string(),
+            sampled: flags & 0x01 == 0x01,
+        })
+    }
+
+    /// Encode au format traceparent version 00
+    pub fn to_header(&self) -> String {
+        format!("00-{}-{}-{}", self.trace_id, self.parent_id, if self.sampled { "01" } else { "00" })
+    }
+}
+
+fn is_lower_hex(value: &str, len: usize) -> bool {
+    value.len() == len && value.chars().all(|c| matches!(c, '0'..='9' | 'a'..='f'))
+}
+
+/// Contexte de corrélation d'une requête HTTP entrante
+#[derive(Debug, Clone)]
+pub struct RequestContext {
+    /// Identifiant de requête (X-Request-Id entrant ou généré)
+    pub request_id: String,
+    /// Trace ID W3C (hérité du traceparent entrant ou généré)
+    pub trace_id: String,
+    /// traceparent entrant, s'il était valide
+    pub incoming: Option<TraceParent>,
+}
+
+impl RequestContext {
+    /// Construit le contexte à partir des headers X-Request-Id et traceparent
+    pub fn from_incoming(request_id: Option<&str>, traceparent: Option<&str>) -> Self {
+        let incoming = traceparent.and_then(TraceParent::parse);
+        let trace_id = incoming
+            .as_ref()
+            .map(|tp| tp.trace_id.clone())
+            .unwrap_or_else(|| Uuid::new_v4().simple().to_string());
+
+        // Priorité : X-Request-Id valide > trace ID entrant > identifiant court généré
+        let request_id = request_id
+            .map(str::trim)
+            .filter(|id| is_valid_request_id(id))
+            .map(str::to_string)
+            .or_else(|| incoming.as_ref().map(|tp| tp.trace_id.clone()))
+            .unwrap_or_else(generate_request_id);
+
+        Self {
+            request_id,
+            trace_id,
+            incoming,
+        }
+    }
+}
+
+/// Identifiant court généré localement (8 caractères)
+pub fn generate_request_id() -> String {
+    Uuid::new_v4().to_string()[..8].to_string()
+}
+
+/// X-Request-Id accepté : 1 à 64 caractères [A-Za-z0-9._:-]
+/// (évite l'injection dans les logs, le syslog et les headers sortants)
+pub fn is_valid_request_id(value: &str) -> bool {
+    !value.is_empty()
+        && value.len() <= MAX_REQUEST_ID_LEN
+        && value.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | ':' | '-'))
+}
+
+/// Exécute un future avec le contexte de requête accessible via `current()`
+pub async fn scope<F: Future>(context: RequestContext, future: F) -> F::Output {
+    CURRENT_CONTEXT.scope(context, future).await
+}
+
+/// Contexte de la requête HTTP en cours (None hors requête, ex: commande Tauri)
+pub fn current() -> Option<RequestContext> {
+    CURRENT_CONTEXT.try_with(|context| context.clone()).ok()
+}
+
+// ============================================================================
+// Spans et propagation
+// ============================================================================
+
+/// Crée le span racine d'une requête HTTP, rattaché au traceparent entrant
+pub fn http_request_span(method: &str, route: &str, context: &RequestContext) -> tracing::Span {
+    let span = tracing::info_span!(
+        "http.request",
+        otel.name = %format!("{} {}", method, route),
+        otel.kind = "server",
+        http.method = %method,
+        http.route = %route,
+        http.status_code = tracing::field::Empty,
+        request_id = %context.request_id,
+    );
+
+    if let Some(incoming) = &context.incoming {
+        let carrier = HashMap::from([(TRACEPARENT_HEADER.to_string(), incoming.to_header())]);
+        let parent = TraceContextPropagator::new().extract(&carrier);
+        span.set_parent(parent);
+    }
+
+    span
+}
+
+/// Headers de corrélation à ajouter sur un appel sortant (TÉO Hub)
+pub fn outbound_headers() -> Vec<(&'static str, String)> {
+    let mut headers = Vec::new();
+    let context = current();
+
+    // 1. traceparent issu du span courant (si l'export OTLP est actif)
+    let mut carrier: HashMap<String, String> = HashMap::new();
+    let otel_context = tracing::Span::current().context();
+    if otel_context.span().span_context().is_valid() {
+        TraceContextPropagator::new().inject_context(&otel_context, &mut carrier);
+    }
+
+    match carrier.remove(TRACEPARENT_HEADER) {
+        Some(traceparent) => headers.push((TRACEPARENT_HEADER, traceparent)),
+        None => {
+            // 2. Sinon, traceparent construit depuis le contexte de requête
+            if let Some(context) = &context {
+                let traceparent = TraceParent {
+                    trace_id: context.trace_id.clone(),
+                    parent_id: Uuid::new_v4().simple().to_string()[..16].to_string(),
+                    sampled: context.incoming.as_ref().map(|tp| tp.sampled).unwrap_or(true),
+                };
+                headers.push((TRACEPARENT_HEADER, traceparent.to_header()));
+            }
+        }
+    }
+
+    let request_id = context
+        .map(|c| c.request_id)
+        .unwrap_or_else(generate_request_id);
+    headers.push((REQUEST_ID_HEADER, request_id));
+
+    headers
+}
+
+// ============================================================================
+// Initialisation de l'export OTLP
+// ============================================================================
+
+/// Installe le subscriber tracing avec export OTLP (doit être appelé dans un runtime Tokio)
+pub fn init(config: &TracingConfig) {
+    if !config.enabled {
+        info!("[Tracing] Export OTLP désactivé");
+        return;
+    }
+    if TRACER_PROVIDER.get().is_some() {
+        return;
+    }
+
+    let exporter = match opentelemetry_otlp::SpanExporter::builder()
+        .with_tonic()
+        .with_endpoint(config.otlp_endpoint.clone())
+        .build()
+    {
+        Ok(exporter) => exporter,
+        Err(e) => {
+            error!("[Tracing] Impossible de créer l'exporteur OTLP ({}): {}", config.otlp_endpoint, e);
+            return;
+        }
+    };
+
+    let ratio = config.sample_ratio.clamp(0.0, 1.0);
+    let provider = TracerProvider::builder()
+        .with_batch_exporter(exporter, opentelemetry_sdk::runtime::Tokio)
+        .with_sampler(Sampler::ParentBased(Box::new(Sampler::TraceIdRatioBased(ratio))))
+        .with_resource(Resource::new(vec![
+            KeyValue::new("service.name", config.service_name.clone()),
+            KeyValue::new("service.version", env!("CARGO_PKG_VERSION")),
+        ]))
+        .build();
+
+    let tracer = provider.tracer("airadcr-desktop");
+    let subscriber = tracing_subscriber::registry()
+        .with(tracing_opentelemetry::layer().with_tracer(tracer));
+
+    // Les logs restent gérés par env_logger : seul le subscriber de spans est installé
+    if let Err(e) = tracing::subscriber::set_global_default(subscriber) {
+        error!("[Tracing] Subscriber déjà installé: {}", e);
+        return;
+    }
+
+    opentelemetry::global::set_text_map_propagator(TraceContextPropagator::new());
+    let _ = TRACER_PROVIDER.set(provider);
+
+    info!("[Tracing] Export OTLP activé → {} (échantillonnage {:.0}%)", config.otlp_endpoint, ratio * 100.0);
+}
+
+/// Envoie les spans en attente au collecteur (à appeler avant la sortie)
+pub fn shutdown() {
+    if let Some(provider) = TRACER_PROVIDER.get() {
+        for result in provider.force_flush() {
+            if let Err(e) = result {
+                error!("[Tracing] Erreur flush OTLP: {}", e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TRACE_ID: &str = "4bf92f3577b34da6a3ce929d0e0e4736";
+    const PARENT_ID: &str = "00f067aa0ba902b7";
+
+    #[test]
+    fn test_traceparent_parse() {
+        let header = format!("00-{}-{}-01", TRACE_ID, PARENT_ID);
+        let parsed = TraceParent::parse(&header).unwrap();
+        assert_eq!(parsed.trace_id, TRACE_ID);
+        assert_eq!(parsed.parent_id, PARENT_ID);
+        assert!(parsed.sampled);
+        assert_eq!(parsed.to_header(), header);
+        assert!(!TraceParent::parse(&format!("00-{}-{}-00", TRACE_ID, PARENT_ID)).unwrap().sampled);
+
+        // Version : ff interdite, hex minuscule sur 2 caractères, 00 = exactement 4 champs
+        assert!(TraceParent::parse(&format!("ff-{}-{}-01", TRACE_ID, PARENT_ID)).is_none());
+        assert!(TraceParent::parse(&format!("0g-{}-{}-01", TRACE_ID, PARENT_ID)).is_none());
+        assert!(TraceParent::parse(&format!("000-{}-{}-01", TRACE_ID, PARENT_ID)).is_none());
+        assert!(TraceParent::parse(&format!("00-{}-{}-01-extra", TRACE_ID, PARENT_ID)).is_none());
+        // Version future : champs supplémentaires tolérés
+        assert!(TraceParent::parse(&format!("01-{}-{}-01-extra", TRACE_ID, PARENT_ID)).is_some());
+
+        // Identifiants nuls
+        assert!(TraceParent::parse(&format!("00-{}-{}-01", "0".repeat(32), PARENT_ID)).is_none());
+        assert!(TraceParent::parse(&format!("00-{}-{}-01", TRACE_ID, "0".repeat(16))).is_none());
+
+        // Majuscules
+        assert!(TraceParent::parse(&format!("00-{}-{}-01", TRACE_ID.to_uppercase(), PARENT_ID)).is_none());
+        assert!(TraceParent::parse(&format!("00-{}-{}-0A", TRACE_ID, PARENT_ID)).is_none());
+
+        // Longueurs
+        assert!(TraceParent::parse(&format!("00-{}-{}-01", &TRACE_ID[1..], PARENT_ID)).is_none());
+        assert!(TraceParent::parse(&format!("00-{}0-{}-01", TRACE_ID, PARENT_ID)).is_none());
+        assert!(TraceParent::parse(&format!("00-{}-{}-01", TRACE_ID, &PARENT_ID[1..])).is_none());
+        assert!(TraceParent::parse(&format!("00-{}-{}-1", TRACE_ID, PARENT_ID)).is_none());
+        assert!(TraceParent::parse(&format!("00-{}-{}", TRACE_ID, PARENT_ID)).is_none());
+        assert!(TraceParent::parse("").is_none());
+    }
+
+    #[test]
+    fn test_request_id_validation() {
+        assert!(is_valid_request_id("req-42"));
+        assert!(is_valid_request_id("ris:ACC.2024_001"));
+        assert!(is_valid_request_id(&"a".repeat(MAX_REQUEST_ID_LEN)));
+
+        assert!(!is_valid_request_id(""));
+        assert!(!is_valid_request_id(&"a".repeat(MAX_REQUEST_ID_LEN + 1)));
+        assert!(!is_valid_request_id("req\n42"));
+        assert!(!is_valid_request_id("req\r\nX-Injected: 1"));
+        assert!(!is_valid_request_id("req\u{0}42"));
+        assert!(!is_valid_request_id("req\u{7f}"));
+        assert!(!is_valid_request_id("req 42"));
+        assert!(!is_valid_request_id("réq-42"));
+
+        // Identifiant refusé : remplacé par le trace ID entrant, sinon généré
+        let header = format!("00-{}-{}-01", TRACE_ID, PARENT_ID);
+        let context = RequestContext::from_incoming(Some("bad\nid"), Some(&header));
+        assert_eq!(context.request_id, TRACE_ID);
+        assert_eq!(context.trace_id, TRACE_ID);
+        let context = RequestContext::from_incoming(Some(&"x".repeat(65)), None);
+        assert_eq!(context.request_id.len(), 8);
+    }
+}
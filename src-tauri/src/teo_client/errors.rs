@@ -30,6 +30,9 @@
      
      /// Erreur de création du client HTTP
      ClientError(String),
+     
+     /// Envoi refusé : données nominatives détectées dans le rapport
+     PiiRejected(String),
  }
  
  impl fmt::Display for TeoClientError {
@@ -43,6 +46,24 @@
              TeoClientError::ParseError(msg) => write!(f, "Erreur parsing: {}", msg),
              TeoClientError::TlsError(msg) => write!(f, "Erreur TLS: {}", msg),
              TeoClientError::ClientError(msg) => write!(f, "Erreur client: {}", msg),
+             TeoClientError::PiiRejected(msg) => write!(f, "Données nominatives détectées: {}", msg),
+         }
+     }
+ }
+ 
+ impl TeoClientError {
+     /// Libellé court de l'issue (label Prometheus "outcome")
+     pub fn outcome(&self) -> &'static str {
+         match self {
+             TeoClientError::Disabled => "disabled",
+             TeoClientError::NetworkError(_) => "network_error",
+             TeoClientError::HttpError(_, _) => "http_error",
+             TeoClientError::Unauthorized(_) => "unauthorized",
+             TeoClientError::NotFound(_) => "not_found",
+             TeoClientError::ParseError(_) => "parse_error",
+             TeoClientError::TlsError(_) => "tls_error",
+             TeoClientError::ClientError(_) => "client_error",
+             TeoClientError::PiiRejected(_) => "pii_rejected",
          }
      }
  }
@@ -11,10 +11,14 @@ pub mod models;
 pub mod errors;
 
 use crate::config::get_config;
+use crate::pii::pseudonym::{pseudonymize, IdentifierKind};
+use crate::http_server::metrics::Metrics;
+use crate::telemetry;
+use tracing::Instrument;
 use errors::TeoClientError;
 use models::{TeoHealthResponse, TeoAiReportResponse, TeoApprovedReport, TeoApprovalResponse};
 use log::{info, warn, debug};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use std::sync::Mutex;
 use once_cell::sync::Lazy;
 
@@ -92,19 +96,25 @@ fn build_base_url() -> String {
     format!("{}://{}:{}", protocol, config.teo_hub.host, config.teo_hub.port)
 }
 
-/// Masque les PII dans les logs (patient_id: 1234**** )
+/// Pseudonyme d'un identifiant patient pour les logs (HMAC à clé, stable)
 fn mask_pii(patient_id: &str) -> String {
-    if patient_id.len() <= 4 {
-        "****".to_string()
-    } else {
-        format!("{}****", &patient_id[..4])
+    pseudonymize(IdentifierKind::PatientId, patient_id)
+}
+
+/// Ajoute les headers de corrélation (X-Request-Id, traceparent) de la requête en cours
+fn add_trace_headers(mut request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+    for (name, value) in telemetry::outbound_headers() {
+        request = request.header(name, value);
     }
+    request
 }
 
-/// Ajoute le header d'authentification API_TOKEN
+/// Ajoute le header d'authentification API_TOKEN (et les headers de corrélation)
 /// 🔐 Phase 4 : Le token est lu depuis le keychain OS en priorité,
 /// avec fallback sur le config.toml pour rétrocompatibilité
-fn add_auth_headers(mut request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+fn add_auth_headers(request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+    let mut request = add_trace_headers(request);
+    
     // 1. Essayer le keychain OS d'abord (sécurisé)
     if let Ok(Some(token)) = crate::database::keychain::get_teo_token() {
         request = request.header("API_TOKEN", &token);
@@ -128,6 +138,53 @@ fn add_auth_headers(mut request: reqwest::RequestBuilder) -> reqwest::RequestBui
 
 /// Vérifie la disponibilité du serveur TÉO Hub
 pub async fn check_health() -> Result<TeoHealthResponse, TeoClientError> {
+    let start = Instant::now();
+    let span = teo_span("check_health");
+    let result = check_health_inner().instrument(span.clone()).await;
+    record_call(&span, "check_health", &result, start);
+    result
+}
+
+/// Récupère un rapport IA depuis TÉO Hub par patient_id + study_uid
+pub async fn fetch_ai_report(patient_id: &str, study_uid: &str) -> Result<TeoAiReportResponse, TeoClientError> {
+    let start = Instant::now();
+    let span = teo_span("fetch_ai_report");
+    let result = fetch_ai_report_inner(patient_id, study_uid).instrument(span.clone()).await;
+    record_call(&span, "fetch_ai_report", &result, start);
+    result
+}
+
+/// Envoie un rapport validé à TÉO Hub
+pub async fn submit_approved_report(report: TeoApprovedReport) -> Result<TeoApprovalResponse, TeoClientError> {
+    let start = Instant::now();
+    let span = teo_span("submit_approved_report");
+    let result = submit_approved_report_inner(report).instrument(span.clone()).await;
+    record_call(&span, "submit_approved_report", &result, start);
+    result
+}
+
+/// Span client d'un appel TÉO Hub (retries inclus)
+fn teo_span(operation: &str) -> tracing::Span {
+    tracing::info_span!(
+        "teo.request",
+        otel.name = %format!("TEO {}", operation),
+        otel.kind = "client",
+        teo.operation = %operation,
+        teo.outcome = tracing::field::Empty,
+    )
+}
+
+/// Enregistre la latence et l'issue d'un appel TÉO Hub (retries inclus)
+fn record_call<T>(span: &tracing::Span, operation: &str, result: &Result<T, TeoClientError>, start: Instant) {
+    let outcome = match result {
+        Ok(_) => "success",
+        Err(e) => e.outcome(),
+    };
+    span.record("teo.outcome", outcome);
+    Metrics::record_teo_call(operation, outcome, start.elapsed());
+}
+
+async fn check_health_inner() -> Result<TeoHealthResponse, TeoClientError> {
     let config = get_config();
     
     if !config.teo_hub.enabled {
@@ -157,8 +214,7 @@ pub async fn check_health() -> Result<TeoHealthResponse, TeoClientError> {
     Ok(health)
 }
 
-/// Récupère un rapport IA depuis TÉO Hub par patient_id + study_uid
-pub async fn fetch_ai_report(patient_id: &str, study_uid: &str) -> Result<TeoAiReportResponse, TeoClientError> {
+async fn fetch_ai_report_inner(patient_id: &str, study_uid: &str) -> Result<TeoAiReportResponse, TeoClientError> {
     let config = get_config();
     
     if !config.teo_hub.enabled {
@@ -173,8 +229,8 @@ pub async fn fetch_ai_report(patient_id: &str, study_uid: &str) -> Result<TeoAiR
         urlencoding::encode(study_uid)
     );
     
-    debug!("[TÉO Client] Fetch AI report: patient_id={}, study_uid={}...", 
-        mask_pii(patient_id), &study_uid[..study_uid.len().min(20)]);
+    debug!("[TÉO Client] Fetch AI report: patient_id={}, study_uid={}", 
+        mask_pii(patient_id), pseudonymize(IdentifierKind::StudyUid, study_uid));
     
     // Retry logic avec backoff exponentiel
     let mut last_error = None;
@@ -197,7 +253,11 @@ pub async fn fetch_ai_report(patient_id: &str, study_uid: &str) -> Result<TeoAiR
                         mask_pii(patient_id), report.result.structured_report.title);
                     return Ok(report);
                 } else if response.status().as_u16() == 404 {
-                    return Err(TeoClientError::NotFound(format!("patient_id={}, study_uid={}", patient_id, study_uid)));
+                    return Err(TeoClientError::NotFound(format!(
+                        "patient_id={}, study_uid={}",
+                        mask_pii(patient_id),
+                        pseudonymize(IdentifierKind::StudyUid, study_uid)
+                    )));
                 } else if response.status().as_u16() == 401 || response.status().as_u16() == 403 {
                     let body = response.text().await.unwrap_or_default();
                     return Err(TeoClientError::Unauthorized(body));
@@ -216,8 +276,7 @@ pub async fn fetch_ai_report(patient_id: &str, study_uid: &str) -> Result<TeoAiR
     Err(last_error.unwrap_or_else(|| TeoClientError::NetworkError("Erreur inconnue".to_string())))
 }
 
-/// Envoie un rapport validé à TÉO Hub
-pub async fn submit_approved_report(report: TeoApprovedReport) -> Result<TeoApprovalResponse, TeoClientError> {
+async fn submit_approved_report_inner(mut report: TeoApprovedReport) -> Result<TeoApprovalResponse, TeoClientError> {
     let config = get_config();
     
     if !config.teo_hub.enabled {
@@ -229,6 +288,10 @@ pub async fn submit_approved_report(report: TeoApprovedReport) -> Result<TeoAppr
     
     debug!("[TÉO Client] Submit approved report: patient_id={}", mask_pii(&report.patient_id));
     
+    // Données nominatives dans le texte du rapport (patient_id / study_uid sont
+    // les clés d'appariement attendues par TÉO Hub)
+    report.approved_report = sanitize_approved_report(&report.approved_report)?;
+    
     // Retry logic avec backoff exponentiel
     let mut last_error = None;
     for attempt in 0..config.teo_hub.retry_count {
@@ -269,6 +332,26 @@ pub async fn submit_approved_report(report: TeoApprovedReport) -> Result<TeoAppr
     Err(last_error.unwrap_or_else(|| TeoClientError::NetworkError("Erreur inconnue".to_string())))
 }
 
+/// Applique la politique PII au rapport approuvé (texte brut ou JSON sérialisé)
+fn sanitize_approved_report(approved_report: &str) -> Result<String, TeoClientError> {
+    let parsed = serde_json::from_str::<serde_json::Value>(approved_report)
+        .ok()
+        .filter(|v| v.is_object() || v.is_array());
+    let payload = match &parsed {
+        Some(value) => serde_json::json!({ "approved_report": value }),
+        None => serde_json::json!({ "approved_report": approved_report }),
+    };
+    
+    let report = crate::pii::policy::check_outbound(crate::pii::policy::Destination::Teo, &payload)
+        .map_err(|report| TeoClientError::PiiRejected(report.summary()))?;
+    
+    Ok(match (parsed, &report.sanitized["approved_report"]) {
+        (Some(_), value) => value.to_string(),
+        (None, serde_json::Value::String(text)) => text.clone(),
+        (None, _) => approved_report.to_string(),
+    })
+}
+
 /// Récupère le statut de connexion actuel
 pub fn get_connection_status() -> TeoConnectionStatus {
     let config = get_config();
@@ -0,0 +1,168 @@
+// ============================================================================
+// AIRADCR Desktop - Liste de travail (worklist)
+// ============================================================================
+// File de lecture construite sur pending_reports :
+//   - priorité (routine / urgent / STAT) et échéance de lecture par rapport
+//   - tri : priorité, puis échéance, puis ancienneté
+//   - "rapport suivant" : verrouillage puis navigation vers le premier rapport
+//     de la file (hors rapport en cours et rapports verrouillés par un autre
+//     client) via l'événement airadcr:navigate_to_report
+//   - bouton SpeechMike EolPrio : passe le rapport en cours en urgent
+// ============================================================================
+
+use log::{info, warn};
+use serde::Serialize;
+use std::sync::Mutex;
+use tauri::Manager;
+
+use crate::database::queries::{WorklistFilter, WorklistItem, REPORT_PRIORITIES};
+use crate::database::Database;
+use crate::report_locks::{self, LockHolder};
+use crate::APP_HANDLE;
+
+/// Priorité par défaut d'un rapport
+pub const DEFAULT_PRIORITY: &str = "routine";
+
+/// Rapport actuellement affiché dans AIRADCR
+static CURRENT_REPORT: Mutex<Option<String>> = Mutex::new(None);
+
+/// Payload de l'événement airadcr:report_priority_changed
+#[derive(Debug, Clone, Serialize)]
+pub struct PriorityChange {
+    pub technical_id: String,
+    pub priority: String,
+}
+
+// ============================================================================
+// Validation
+// ============================================================================
+
+/// Normalise une priorité ("STAT" → "stat") et vérifie qu'elle est connue
+pub fn parse_priority(value: &str) -> Result<String, String> {
+    let priority = value.trim().to_lowercase();
+    if REPORT_PRIORITIES.contains(&priority.as_str()) {
+        Ok(priority)
+    } else {
+        Err(format!("priority must be one of: {}", REPORT_PRIORITIES.join(", ")))
+    }
+}
+
+/// Valide une échéance RFC 3339 et la convertit en UTC (comparable en SQL)
+pub fn normalize_due_at(value: &str) -> Result<String, String> {
+    chrono::DateTime::parse_from_rfc3339(value.trim())
+        .map(|dt| dt.with_timezone(&chrono::Utc).to_rfc3339())
+        .map_err(|_| "due_at must be an RFC 3339 timestamp (e.g. 2025-01-15T14:30:00Z)".to_string())
+}
+
+// ============================================================================
+// Rapport en cours
+// ============================================================================
+
+/// Enregistre le rapport affiché (None = aucun rapport)
+pub fn set_current_report(technical_id: Option<&str>) {
+    if let Ok(mut current) = CURRENT_REPORT.lock() {
+        *current = technical_id.filter(|tid| !tid.is_empty()).map(str::to_string);
+    }
+}
+
+/// Rapport actuellement affiché
+pub fn current_report() -> Option<String> {
+    CURRENT_REPORT.lock().ok().and_then(|current| current.clone())
+}
+
+/// Émet la navigation vers un rapport et le marque comme rapport en cours
+pub fn navigate_to_report(technical_id: &str) -> Result<(), String> {
+    let app_handle = APP_HANDLE.get().ok_or_else(|| "Application not yet ready".to_string())?;
+    let window = app_handle
+        .get_window("main")
+        .ok_or_else(|| "Main window not found".to_string())?;
+
+    window
+        .emit("airadcr:navigate_to_report", technical_id)
+        .map_err(|e| format!("Failed to trigger navigation event: {}", e))?;
+    set_current_report(Some(technical_id));
+    Ok(())
+}
+
+// ============================================================================
+// Navigation et priorité
+// ============================================================================
+
+/// Nombre de candidats examinés pour trouver un rapport non verrouillé
+const NEXT_REPORT_CANDIDATES: i64 = 20;
+
+/// Premier rapport de la file du radiologue, hors rapport en cours
+/// et hors rapports verrouillés par un autre client
+pub fn next_report(db: &Database, user_id: Option<&str>, holder: &LockHolder) -> Result<Option<WorklistItem>, String> {
+    let filter = WorklistFilter {
+        user_id: user_id.map(str::to_string),
+        exclude_tid: current_report(),
+        limit: NEXT_REPORT_CANDIDATES,
+        ..Default::default()
+    };
+
+    db.list_worklist(&filter)
+        .map(|items| {
+            items.into_iter().find(|item| {
+                !report_locks::is_locked_by_other(
+                    item.locked_by.as_deref(),
+                    item.locked_workstation.as_deref(),
+                    holder,
+                )
+            })
+        })
+        .map_err(|e| format!("Database error: {}", e))
+}
+
+/// Verrouille puis ouvre le rapport suivant du radiologue actif (None si la file est vide)
+pub fn open_next_report(db: &Database, holder: &LockHolder) -> Result<Option<WorklistItem>, String> {
+    let user_id = db.get_active_user_id().ok().flatten();
+
+    match next_report(db, user_id.as_deref(), holder)? {
+        Some(item) => {
+            report_locks::acquire(db, &item.technical_id, holder).map_err(|e| e.message())?;
+            navigate_to_report(&item.technical_id)?;
+            info!("[Worklist] Rapport suivant: tid={} priorité={}", item.technical_id, item.priority);
+            Ok(Some(item))
+        }
+        None => {
+            info!("[Worklist] File de lecture vide");
+            Ok(None)
+        }
+    }
+}
+
+/// Passe le rapport en cours en urgent (un rapport STAT reste STAT)
+pub fn flag_current_report_urgent(db: &Database) -> Result<Option<PriorityChange>, String> {
+    let technical_id = match current_report() {
+        Some(tid) => tid,
+        None => {
+            warn!("[Worklist] Aucun rapport en cours à marquer urgent");
+            return Ok(None);
+        }
+    };
+
+    let report = db
+        .get_pending_report(&technical_id)
+        .map_err(|e| format!("Database error: {}", e))?
+        .ok_or_else(|| format!("Report {} not found or expired", technical_id))?;
+
+    if report.priority == "stat" {
+        return Ok(Some(PriorityChange { technical_id, priority: report.priority }));
+    }
+
+    db.set_report_priority(&technical_id, "urgent")
+        .map_err(|e| format!("Database error: {}", e))?;
+
+    let change = PriorityChange {
+        technical_id,
+        priority: "urgent".to_string(),
+    };
+    info!("[Worklist] Rapport marqué urgent: tid={}", change.technical_id);
+
+    if let Some(app_handle) = APP_HANDLE.get() {
+        let _ = app_handle.emit_all("airadcr:report_priority_changed", &change);
+    }
+
+    Ok(Some(change))
+}
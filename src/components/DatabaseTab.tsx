@@ -24,7 +24,8 @@ import {
   DialogTitle,
 } from '@/components/ui/dialog';
 import { invoke } from '@tauri-apps/api/tauri';
-import { RefreshCw, Trash2, Database, Key, FileText, X, Plus, Copy, Check, Ban, Search, Activity, Clock, AlertTriangle } from 'lucide-react';
+import { listen } from '@tauri-apps/api/event';
+import { RefreshCw, Trash2, Database, Key, FileText, X, Plus, Copy, Check, Ban, Search, Activity, Clock, AlertTriangle, History, RotateCcw, KeyRound, ShieldCheck, LifeBuoy } from 'lucide-react';
 
 interface DatabaseStats {
   total_reports: number;
@@ -82,12 +83,92 @@ interface AccessLogsStats {
   last_24h_requests: number;
 }
 
+interface MigrationStatus {
+  current_version: number;
+  latest_version: number;
+  up_to_date: boolean;
+  applied: { version: number; name: string; applied_at: string; duration_ms: number }[];
+  pending: { version: number; name: string }[];
+  pre_migration_backups: string[];
+}
+
+interface BackupInfo {
+  filename: string;
+  path: string;
+  size_bytes: number;
+  created_at: string | null;
+  manifest: { schema_version: number; encrypted: boolean } | null;
+}
+
+type RestoreStep =
+  | 'verifying'
+  | 'pausing'
+  | 'snapshot'
+  | 'restoring'
+  | 'checking'
+  | 'reopening'
+  | 'rolling_back'
+  | 'completed'
+  | 'rolled_back'
+  | 'failed';
+
+interface RestoreProgress {
+  step: RestoreStep;
+  backup: string;
+  message: string;
+}
+
+interface KeyStatus {
+  current_version: number;
+  in_use_since: string | null;
+  scheduled: boolean;
+  interval_days: number;
+  next_rotation_at: string | null;
+  history: { rotated_at: string; from_version: number; to_version: number; trigger: string }[];
+}
+
+interface KeystoreStatus {
+  configured: string;
+  active: 'os' | 'file' | 'unavailable';
+  error: string | null;
+  os_available: boolean;
+  file_path: string;
+  file_exists: boolean;
+  file_secret_source: 'passphrase' | 'machine' | null;
+  db_key_backends: string[];
+  teo_token_backends: string[];
+  migration: string | null;
+}
+
+interface EscrowStatus {
+  enabled: boolean;
+  recipients: number;
+  key_fingerprint: string | null;
+  recipients_escrow: boolean;
+  passphrase_escrow: boolean;
+  files: { path: string; method: string; key_fingerprint: string; current: boolean; modified_at: string | null }[];
+}
+
+interface RecoveryReport {
+  key_fingerprint: string;
+  escrowed_version: number;
+  escrowed_at: string;
+  already_active: boolean;
+}
+
+const KEYSTORE_LABELS: Record<string, string> = {
+  os: 'Keychain OS',
+  file: 'Fichier chiffré',
+  unavailable: 'Indisponible',
+};
+
 interface DatabaseTabProps {
   isTauriApp: boolean;
 }
 
 export const DatabaseTab = ({ isTauriApp }: DatabaseTabProps) => {
   const [stats, setStats] = useState<DatabaseStats | null>(null);
+  const [migrations, setMigrations] = useState<MigrationStatus | null>(null);
   const [reports, setReports] = useState<PendingReportSummary[]>([]);
   const [apiKeys, setApiKeys] = useState<ApiKeySummary[]>([]);
   const [isLoading, setIsLoading] = useState(false);
@@ -112,6 +193,32 @@ export const DatabaseTab = ({ isTauriApp }: DatabaseTabProps) => {
   const [accessLogs, setAccessLogs] = useState<AccessLogSummary[]>([]);
   const [accessLogsStats, setAccessLogsStats] = useState<AccessLogsStats | null>(null);
   const [showAccessLogs, setShowAccessLogs] = useState(false);
+  
+  // États pour la restauration de backup
+  const [backups, setBackups] = useState<BackupInfo[]>([]);
+  const [backupToRestore, setBackupToRestore] = useState<BackupInfo | null>(null);
+  const [identityFile, setIdentityFile] = useState('');
+  // Clé admin des opérations sensibles (restauration, clé de chiffrement), effacée après usage
+  const [adminKey, setAdminKey] = useState('');
+  const [isRestoring, setIsRestoring] = useState(false);
+  const [restoreProgress, setRestoreProgress] = useState<RestoreProgress | null>(null);
+  
+  // États pour la rotation de la clé SQLCipher
+  const [keyStatus, setKeyStatus] = useState<KeyStatus | null>(null);
+  const [showRotateDialog, setShowRotateDialog] = useState(false);
+  const [isRotating, setIsRotating] = useState(false);
+  const [keystoreStatus, setKeystoreStatus] = useState<KeystoreStatus | null>(null);
+  
+  // États pour le séquestre de la clé (reprise après sinistre)
+  const [escrowStatus, setEscrowStatus] = useState<EscrowStatus | null>(null);
+  const [showEscrowDialog, setShowEscrowDialog] = useState(false);
+  const [escrowPassphrase, setEscrowPassphrase] = useState('');
+  const [escrowConfirm, setEscrowConfirm] = useState('');
+  const [isEscrowing, setIsEscrowing] = useState(false);
+  const [showRecoverDialog, setShowRecoverDialog] = useState(false);
+  const [recoverForm, setRecoverForm] = useState({ escrowPath: '', identityFile: '', passphrase: '', backupPath: '' });
+  const [isRecovering, setIsRecovering] = useState(false);
+  const [recoveryResult, setRecoveryResult] = useState<RecoveryReport | null>(null);
 
   const fetchData = useCallback(async () => {
     if (!isTauriApp) return;
@@ -120,12 +227,17 @@ export const DatabaseTab = ({ isTauriApp }: DatabaseTabProps) => {
     setError(null);
     
     try {
-      const [statsResult, reportsResult, keysResult, logsResult, logsStatsResult] = await Promise.all([
+      const [statsResult, reportsResult, keysResult, logsResult, logsStatsResult, migrationsResult, backupsResult, keyStatusResult, keystoreResult, escrowResult] = await Promise.all([
         invoke<DatabaseStats>('get_database_stats'),
         invoke<PendingReportSummary[]>('get_all_pending_reports'),
         invoke<ApiKeySummary[]>('get_api_keys_list'),
         invoke<AccessLogSummary[]>('get_access_logs', { limit: 50, offset: 0 }),
         invoke<AccessLogsStats>('get_access_logs_stats'),
+        invoke<MigrationStatus>('get_migrations_status'),
+        invoke<BackupInfo[]>('list_backups_cmd'),
+        invoke<KeyStatus>('get_key_rotation_status'),
+        invoke<KeystoreStatus>('get_keystore_status'),
+        invoke<EscrowStatus>('get_key_escrow_status'),
       ]);
       
       setStats(statsResult);
@@ -133,6 +245,11 @@ export const DatabaseTab = ({ isTauriApp }: DatabaseTabProps) => {
       setApiKeys(keysResult);
       setAccessLogs(logsResult);
       setAccessLogsStats(logsStatsResult);
+      setMigrations(migrationsResult);
+      setBackups(backupsResult);
+      setKeyStatus(keyStatusResult);
+      setKeystoreStatus(keystoreResult);
+      setEscrowStatus(escrowResult);
       setLastUpdate(new Date());
     } catch (err) {
       console.error('Erreur chargement données DB:', err);
@@ -146,6 +263,18 @@ export const DatabaseTab = ({ isTauriApp }: DatabaseTabProps) => {
     fetchData();
   }, [fetchData]);
 
+  // Progression de la restauration (émise par le backend à chaque étape)
+  useEffect(() => {
+    if (!isTauriApp) return;
+    
+    const unlisten = listen<RestoreProgress>('airadcr:restore_progress', (event) => {
+      setRestoreProgress(event.payload);
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [isTauriApp]);
+
   const handleCleanup = async () => {
     if (!isTauriApp) return;
     
@@ -227,6 +356,123 @@ export const DatabaseTab = ({ isTauriApp }: DatabaseTabProps) => {
     }
   };
 
+  // Rotation de la clé SQLCipher (base et backups locaux re-chiffrés)
+  const handleRotateKey = async () => {
+    if (!isTauriApp) return;
+    
+    setShowRotateDialog(false);
+    setIsRotating(true);
+    setError(null);
+    try {
+      await invoke('rotate_db_key_cmd', { adminKey });
+      await fetchData();
+    } catch (err) {
+      console.error('Erreur rotation clé:', err);
+      setError(String(err));
+    } finally {
+      setIsRotating(false);
+      setAdminKey('');
+    }
+  };
+
+  // Séquestre de la clé active avec une passphrase de reprise
+  const handleCloseEscrowDialog = () => {
+    setShowEscrowDialog(false);
+    setEscrowPassphrase('');
+    setEscrowConfirm('');
+    setAdminKey('');
+  };
+
+  const handleEscrowKey = async () => {
+    if (!isTauriApp) return;
+    
+    setIsEscrowing(true);
+    setError(null);
+    try {
+      await invoke('escrow_db_key_cmd', { adminKey, passphrase: escrowPassphrase });
+      handleCloseEscrowDialog();
+      await fetchData();
+    } catch (err) {
+      console.error('Erreur séquestre clé:', err);
+      setError(String(err));
+    } finally {
+      setIsEscrowing(false);
+    }
+  };
+
+  // Reprise : clé d'un séquestre installée dans le keychain du poste
+  const handleCloseRecoverDialog = () => {
+    setShowRecoverDialog(false);
+    setRecoverForm({ escrowPath: '', identityFile: '', passphrase: '', backupPath: '' });
+    setRecoveryResult(null);
+    setAdminKey('');
+  };
+
+  const handleRecoverKey = async () => {
+    if (!isTauriApp) return;
+    
+    setIsRecovering(true);
+    setError(null);
+    try {
+      const result = await invoke<RecoveryReport>('recover_db_key_cmd', {
+        adminKey,
+        escrowPath: recoverForm.escrowPath.trim(),
+        identityFile: recoverForm.identityFile.trim() || null,
+        passphrase: recoverForm.passphrase || null,
+        backupPath: recoverForm.backupPath.trim(),
+      });
+      setRecoveryResult(result);
+      await fetchData();
+    } catch (err) {
+      console.error('Erreur reprise clé:', err);
+      setError(String(err));
+    } finally {
+      setIsRecovering(false);
+    }
+  };
+
+  // Archive exportée : la clé privée age de la DSI est requise
+  const isArchive = (backup: BackupInfo) => backup.filename.endsWith('.tar.zst.age');
+
+  // Restauration d'un backup (serveur HTTP en pause, retour arrière automatique)
+  const handleRestoreBackup = async () => {
+    if (!isTauriApp || !backupToRestore) return;
+    
+    const backup = backupToRestore;
+    setBackupToRestore(null);
+    setIsRestoring(true);
+    setRestoreProgress(null);
+    setError(null);
+    try {
+      await invoke('restore_backup_cmd', {
+        adminKey,
+        filename: backup.filename,
+        identityFile: isArchive(backup) ? identityFile.trim() || null : null,
+      });
+      await fetchData();
+    } catch (err) {
+      console.error('Erreur restauration backup:', err);
+      setError(String(err));
+    } finally {
+      setIsRestoring(false);
+      setIdentityFile('');
+      setAdminKey('');
+    }
+  };
+
+  const getRestoreBadge = (step: RestoreStep) => {
+    switch (step) {
+      case 'completed':
+        return <Badge variant="outline" className="bg-green-500/10 text-green-600 border-green-500/30 text-[10px]">✅ Restauré</Badge>;
+      case 'rolled_back':
+        return <Badge variant="outline" className="bg-yellow-500/10 text-yellow-600 border-yellow-500/30 text-[10px]">↩️ Annulé</Badge>;
+      case 'failed':
+        return <Badge variant="destructive" className="text-[10px]">❌ Échec</Badge>;
+      default:
+        return <Badge variant="secondary" className="text-[10px]">⏳ {step}</Badge>;
+    }
+  };
+
   // Nettoyage des logs d'accès
   const handleCleanupLogs = async () => {
     if (!isTauriApp) return;
@@ -366,6 +612,189 @@ export const DatabaseTab = ({ isTauriApp }: DatabaseTabProps) => {
         </div>
       )}
 
+      {/* Migrations du schéma */}
+      {migrations && (
+        <div className="p-2 bg-background/50 rounded text-xs space-y-1">
+          <div className="flex items-center justify-between">
+            <span className="text-muted-foreground">Schéma</span>
+            <Badge variant={migrations.up_to_date ? 'outline' : 'destructive'} className="text-[10px]">
+              v{migrations.current_version}/v{migrations.latest_version}
+            </Badge>
+          </div>
+          {migrations.pending.length > 0 && (
+            <div className="text-[10px] text-red-500">
+              En attente : {migrations.pending.map((m) => `v${m.version} ${m.name}`).join(', ')}
+            </div>
+          )}
+          {migrations.applied.length > 0 && (
+            <div className="text-[10px] text-muted-foreground">
+              Dernière : v{migrations.applied[migrations.applied.length - 1].version}{' '}
+              {migrations.applied[migrations.applied.length - 1].name} (
+              {formatDate(migrations.applied[migrations.applied.length - 1].applied_at)})
+            </div>
+          )}
+          {migrations.pre_migration_backups.length > 0 && (
+            <div className="text-[10px] text-muted-foreground truncate" title={migrations.pre_migration_backups[0]}>
+              Backup pré-migration : {migrations.pre_migration_backups[0]}
+            </div>
+          )}
+        </div>
+      )}
+
+      {/* Clé de chiffrement SQLCipher */}
+      {keyStatus && (
+        <div className="p-2 bg-background/50 rounded text-xs space-y-1">
+          <div className="flex items-center justify-between">
+            <div className="flex items-center gap-2">
+              <KeyRound className="w-3 h-3" />
+              <span className="text-muted-foreground">Clé de chiffrement</span>
+            </div>
+            <div className="flex items-center gap-1">
+              <Badge variant="outline" className="text-[10px]">v{keyStatus.current_version}</Badge>
+              <Button
+                size="sm"
+                variant="ghost"
+                onClick={() => setShowRotateDialog(true)}
+                disabled={isRotating || isRestoring}
+                className="h-5 w-5 p-0"
+                title="Rotation de la clé"
+              >
+                <RefreshCw className={`w-3 h-3 ${isRotating ? 'animate-spin' : ''}`} />
+              </Button>
+            </div>
+          </div>
+          {keyStatus.in_use_since && (
+            <div className="text-[10px] text-muted-foreground">
+              En service depuis : {formatDate(keyStatus.in_use_since)}
+            </div>
+          )}
+          <div className="text-[10px] text-muted-foreground">
+            {keyStatus.scheduled && keyStatus.next_rotation_at
+              ? `Prochaine rotation : ${formatDate(keyStatus.next_rotation_at)}`
+              : 'Rotation planifiée désactivée'}
+          </div>
+          {keystoreStatus && (
+            <div className="text-[10px] text-muted-foreground space-y-0.5">
+              <div className="flex items-center gap-1">
+                Stockage :
+                <Badge
+                  variant={keystoreStatus.active === 'unavailable' ? 'destructive' : 'outline'}
+                  className="text-[10px]"
+                >
+                  {KEYSTORE_LABELS[keystoreStatus.active]}
+                  {keystoreStatus.active === 'file' && keystoreStatus.file_secret_source === 'machine' && ' (lié au poste)'}
+                  {keystoreStatus.active === 'file' && keystoreStatus.file_secret_source === 'passphrase' && ' (passphrase)'}
+                </Badge>
+                {keystoreStatus.configured !== 'auto' && <span>({keystoreStatus.configured} imposé)</span>}
+              </div>
+              <div>
+                Clé SQLCipher : {keystoreStatus.db_key_backends.map((b) => KEYSTORE_LABELS[b] ?? b).join(', ') || '—'}
+                {' · '}Token TÉO : {keystoreStatus.teo_token_backends.map((b) => KEYSTORE_LABELS[b] ?? b).join(', ') || '—'}
+              </div>
+              {keystoreStatus.active === 'file' && (
+                <div className="font-mono truncate" title={keystoreStatus.file_path}>{keystoreStatus.file_path}</div>
+              )}
+              {keystoreStatus.migration && <div>Migration : {keystoreStatus.migration}</div>}
+              {keystoreStatus.error && <div className="text-destructive">{keystoreStatus.error}</div>}
+            </div>
+          )}
+          {escrowStatus && (
+            <div className="flex items-center justify-between text-[10px] text-muted-foreground">
+              <div className="flex items-center gap-1">
+                Séquestre :
+                {escrowStatus.enabled && (
+                  <Badge variant={escrowStatus.recipients_escrow ? 'outline' : 'destructive'} className="text-[10px]">
+                    {escrowStatus.recipients_escrow ? '✓' : '✗'} {escrowStatus.recipients} clé(s) publique(s)
+                  </Badge>
+                )}
+                <Badge variant="outline" className="text-[10px]">
+                  {escrowStatus.passphrase_escrow
+                    ? '✓ passphrase'
+                    : escrowStatus.files.some((f) => f.method === 'passphrase')
+                      ? '⚠️ passphrase à refaire'
+                      : 'passphrase —'}
+                </Badge>
+              </div>
+              <div className="flex items-center gap-1">
+                <Button
+                  size="sm"
+                  variant="ghost"
+                  onClick={() => setShowEscrowDialog(true)}
+                  disabled={isEscrowing || isRotating || isRestoring}
+                  className="h-5 w-5 p-0"
+                  title="Séquestrer la clé (passphrase de reprise)"
+                >
+                  <ShieldCheck className="w-3 h-3" />
+                </Button>
+                <Button
+                  size="sm"
+                  variant="ghost"
+                  onClick={() => setShowRecoverDialog(true)}
+                  disabled={isRecovering || isRotating || isRestoring}
+                  className="h-5 w-5 p-0"
+                  title="Reprise depuis un séquestre"
+                >
+                  <LifeBuoy className="w-3 h-3" />
+                </Button>
+              </div>
+            </div>
+          )}
+        </div>
+      )}
+
+      {/* Backups et restauration */}
+      <div className="space-y-2">
+        <div className="flex items-center gap-2">
+          <History className="w-3 h-3" />
+          <span className="text-xs font-medium">Backups ({backups.length})</span>
+        </div>
+        
+        {restoreProgress && (
+          <div className="p-2 bg-background/50 rounded text-[10px] space-y-1">
+            <div className="flex items-center justify-between">
+              <span className="font-mono truncate max-w-[160px]">{restoreProgress.backup}</span>
+              {getRestoreBadge(restoreProgress.step)}
+            </div>
+            <div className="text-muted-foreground">{restoreProgress.message}</div>
+          </div>
+        )}
+        
+        {backups.length === 0 ? (
+          <div className="text-xs text-muted-foreground text-center py-2">
+            Aucun backup local
+          </div>
+        ) : (
+          <ScrollArea className="h-24">
+            <div className="space-y-1">
+              {backups.map((backup) => (
+                <div
+                  key={backup.filename}
+                  className="p-1.5 bg-background/50 rounded text-[10px] flex items-center justify-between group"
+                >
+                  <div className="min-w-0">
+                    <div className="font-mono truncate max-w-[170px]" title={backup.filename}>{backup.filename}</div>
+                    <div className="text-muted-foreground">
+                      {backup.created_at ? formatDate(backup.created_at) : '—'} · {(backup.size_bytes / 1024).toFixed(0)} Ko
+                      {backup.manifest && ` · v${backup.manifest.schema_version}`}
+                    </div>
+                  </div>
+                  <Button
+                    size="sm"
+                    variant="ghost"
+                    onClick={() => setBackupToRestore(backup)}
+                    disabled={isRestoring}
+                    className="h-5 w-5 p-0 opacity-0 group-hover:opacity-100 transition-opacity"
+                    title="Restaurer ce backup"
+                  >
+                    <RotateCcw className="w-3 h-3" />
+                  </Button>
+                </div>
+              ))}
+            </div>
+          </ScrollArea>
+        )}
+      </div>
+
       <Separator />
 
       {/* Liste des rapports */}
@@ -704,6 +1133,253 @@ export const DatabaseTab = ({ isTauriApp }: DatabaseTabProps) => {
         </DialogContent>
       </Dialog>
 
+      {/* Dialog de confirmation de restauration */}
+      <AlertDialog
+        open={!!backupToRestore}
+        onOpenChange={(open) => {
+          if (!open) {
+            setBackupToRestore(null);
+            setAdminKey('');
+          }
+        }}
+      >
+        <AlertDialogContent>
+          <AlertDialogHeader>
+            <AlertDialogTitle>Restaurer le backup</AlertDialogTitle>
+            <AlertDialogDescription>
+              La base actuelle sera remplacée par ce backup.
+              {backupToRestore && (
+                <div className="mt-2 p-2 bg-muted rounded text-xs space-y-1">
+                  <div><strong>Fichier:</strong> {backupToRestore.filename}</div>
+                  {backupToRestore.created_at && (
+                    <div><strong>Date:</strong> {formatDate(backupToRestore.created_at)}</div>
+                  )}
+                </div>
+              )}
+              <p className="mt-2 text-destructive font-medium">
+                Le serveur HTTP est indisponible pendant la restauration. La base actuelle est conservée
+                et remise en service automatiquement en cas d'échec.
+              </p>
+            </AlertDialogDescription>
+          </AlertDialogHeader>
+          {backupToRestore && isArchive(backupToRestore) && (
+            <div className="space-y-2">
+              <Label htmlFor="identityFile">Clé privée age de la DSI</Label>
+              <Input
+                id="identityFile"
+                placeholder="Chemin du fichier AGE-SECRET-KEY"
+                value={identityFile}
+                onChange={(e) => setIdentityFile(e.target.value)}
+              />
+            </div>
+          )}
+          <div className="space-y-2">
+            <Label htmlFor="restoreAdminKey">Clé admin</Label>
+            <Input
+              id="restoreAdminKey"
+              type="password"
+              value={adminKey}
+              onChange={(e) => setAdminKey(e.target.value)}
+            />
+          </div>
+          <AlertDialogFooter>
+            <AlertDialogCancel>Annuler</AlertDialogCancel>
+            <AlertDialogAction
+              onClick={handleRestoreBackup}
+              disabled={!adminKey || (!!backupToRestore && isArchive(backupToRestore) && !identityFile.trim())}
+              className="bg-destructive text-destructive-foreground hover:bg-destructive/90"
+            >
+              Restaurer
+            </AlertDialogAction>
+          </AlertDialogFooter>
+        </AlertDialogContent>
+      </AlertDialog>
+
+      {/* Dialog de confirmation de rotation de clé */}
+      <AlertDialog
+        open={showRotateDialog}
+        onOpenChange={(open) => {
+          setShowRotateDialog(open);
+          if (!open) setAdminKey('');
+        }}
+      >
+        <AlertDialogContent>
+          <AlertDialogHeader>
+            <AlertDialogTitle>Rotation de la clé de chiffrement</AlertDialogTitle>
+            <AlertDialogDescription>
+              Une nouvelle clé SQLCipher est générée. La base et les backups locaux sont re-chiffrés,
+              puis l'ancienne clé est supprimée du keychain si elle est séquestrée. Les backups illisibles
+              sont mis en quarantaine.
+              <p className="mt-2 text-destructive font-medium">
+                Le serveur HTTP est indisponible pendant le re-chiffrement de la base. Les backups `.db`
+                déjà copiés sur les destinations distantes ne seront plus lisibles qu'avec le séquestre
+                de l'ancienne clé.
+              </p>
+            </AlertDialogDescription>
+          </AlertDialogHeader>
+          <div className="space-y-2">
+            <Label htmlFor="rotateAdminKey">Clé admin</Label>
+            <Input
+              id="rotateAdminKey"
+              type="password"
+              value={adminKey}
+              onChange={(e) => setAdminKey(e.target.value)}
+            />
+          </div>
+          <AlertDialogFooter>
+            <AlertDialogCancel>Annuler</AlertDialogCancel>
+            <AlertDialogAction
+              onClick={handleRotateKey}
+              disabled={!adminKey}
+              className="bg-destructive text-destructive-foreground hover:bg-destructive/90"
+            >
+              Lancer la rotation
+            </AlertDialogAction>
+          </AlertDialogFooter>
+        </AlertDialogContent>
+      </AlertDialog>
+
+      {/* Dialog de séquestre par passphrase */}
+      <Dialog open={showEscrowDialog} onOpenChange={(open) => !open && handleCloseEscrowDialog()}>
+        <DialogContent className="sm:max-w-md">
+          <DialogHeader>
+            <DialogTitle>Séquestrer la clé de chiffrement</DialogTitle>
+            <DialogDescription>
+              La clé active est chiffrée avec cette passphrase et déposée avec les backups. Elle permet
+              de relire la base et les backups sur un autre poste. À refaire après chaque rotation.
+            </DialogDescription>
+          </DialogHeader>
+          <div className="space-y-4">
+            <div className="space-y-2">
+              <Label htmlFor="escrowPassphrase">Passphrase de reprise (12 caractères minimum)</Label>
+              <Input
+                id="escrowPassphrase"
+                type="password"
+                value={escrowPassphrase}
+                onChange={(e) => setEscrowPassphrase(e.target.value)}
+              />
+            </div>
+            <div className="space-y-2">
+              <Label htmlFor="escrowConfirm">Confirmation</Label>
+              <Input
+                id="escrowConfirm"
+                type="password"
+                value={escrowConfirm}
+                onChange={(e) => setEscrowConfirm(e.target.value)}
+              />
+            </div>
+            <div className="space-y-2">
+              <Label htmlFor="escrowAdminKey">Clé admin</Label>
+              <Input
+                id="escrowAdminKey"
+                type="password"
+                value={adminKey}
+                onChange={(e) => setAdminKey(e.target.value)}
+              />
+            </div>
+          </div>
+          <DialogFooter>
+            <Button variant="outline" onClick={handleCloseEscrowDialog}>
+              Annuler
+            </Button>
+            <Button
+              onClick={handleEscrowKey}
+              disabled={isEscrowing || !adminKey || escrowPassphrase.length < 12 || escrowPassphrase !== escrowConfirm}
+            >
+              {isEscrowing ? 'Séquestre...' : 'Séquestrer'}
+            </Button>
+          </DialogFooter>
+        </DialogContent>
+      </Dialog>
+
+      {/* Dialog de reprise depuis un séquestre */}
+      <Dialog open={showRecoverDialog} onOpenChange={(open) => !open && handleCloseRecoverDialog()}>
+        <DialogContent className="sm:max-w-md">
+          <DialogHeader>
+            <DialogTitle>Reprise depuis un séquestre</DialogTitle>
+            <DialogDescription>
+              La clé du séquestre remplace la clé du poste, après vérification sur le backup à restaurer.
+              Restaurez ensuite ce backup depuis la section "Backups".
+            </DialogDescription>
+          </DialogHeader>
+          {!recoveryResult ? (
+            <div className="space-y-4">
+              <div className="space-y-2">
+                <Label htmlFor="recoverEscrow">Fichier de séquestre</Label>
+                <Input
+                  id="recoverEscrow"
+                  placeholder="airadcr_key_escrow_....age"
+                  value={recoverForm.escrowPath}
+                  onChange={(e) => setRecoverForm({ ...recoverForm, escrowPath: e.target.value })}
+                />
+              </div>
+              <div className="space-y-2">
+                <Label htmlFor="recoverIdentity">Clé privée age (séquestre .age)</Label>
+                <Input
+                  id="recoverIdentity"
+                  placeholder="Chemin du fichier AGE-SECRET-KEY"
+                  value={recoverForm.identityFile}
+                  onChange={(e) => setRecoverForm({ ...recoverForm, identityFile: e.target.value })}
+                />
+              </div>
+              <div className="space-y-2">
+                <Label htmlFor="recoverPassphrase">Passphrase de reprise (séquestre .passphrase.age)</Label>
+                <Input
+                  id="recoverPassphrase"
+                  type="password"
+                  value={recoverForm.passphrase}
+                  onChange={(e) => setRecoverForm({ ...recoverForm, passphrase: e.target.value })}
+                />
+              </div>
+              <div className="space-y-2">
+                <Label htmlFor="recoverBackup">Backup à restaurer (vérification de la clé)</Label>
+                <Input
+                  id="recoverBackup"
+                  placeholder="Chemin du fichier airadcr_backup_....db"
+                  value={recoverForm.backupPath}
+                  onChange={(e) => setRecoverForm({ ...recoverForm, backupPath: e.target.value })}
+                />
+              </div>
+              <div className="space-y-2">
+                <Label htmlFor="recoverAdminKey">Clé admin</Label>
+                <Input
+                  id="recoverAdminKey"
+                  type="password"
+                  value={adminKey}
+                  onChange={(e) => setAdminKey(e.target.value)}
+                />
+              </div>
+            </div>
+          ) : (
+            <div className="p-2 bg-green-500/10 border border-green-500/30 rounded text-xs space-y-1">
+              <div>✅ Clé {recoveryResult.key_fingerprint} installée dans le keychain du poste</div>
+              <div>Séquestrée en v{recoveryResult.escrowed_version} le {formatDate(recoveryResult.escrowed_at)}</div>
+              {recoveryResult.already_active && <div>La clé était déjà la clé active.</div>}
+            </div>
+          )}
+          <DialogFooter>
+            {!recoveryResult ? (
+              <>
+                <Button variant="outline" onClick={handleCloseRecoverDialog}>
+                  Annuler
+                </Button>
+                <Button
+                  onClick={handleRecoverKey}
+                  disabled={isRecovering || !adminKey || !recoverForm.escrowPath.trim() || !recoverForm.backupPath.trim()}
+                  className="bg-destructive text-destructive-foreground hover:bg-destructive/90"
+                >
+                  {isRecovering ? 'Reprise...' : 'Installer la clé'}
+                </Button>
+              </>
+            ) : (
+              <Button onClick={handleCloseRecoverDialog}>
+                Fermer
+              </Button>
+            )}
+          </DialogFooter>
+        </DialogContent>
+      </Dialog>
+
       {/* Dialog de confirmation de révocation */}
       <AlertDialog open={!!keyToRevoke} onOpenChange={(open) => !open && setKeyToRevoke(null)}>
         <AlertDialogContent>